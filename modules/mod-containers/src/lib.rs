@@ -2,17 +2,16 @@ use anyhow::Result;
 use serde::Serialize;
 use serde_json::json;
 use std::process::Command;
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    CollectionContext, Collector, CollectorMetadata, CostHint, CostLevel, Section,
+    collector_version, register_collector,
+};
 
 struct ContainersCollector;
 
 impl Collector for ContainersCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "containers",
-            title: "Alternative Containers",
-            description: "Podman and containerd runtimes",
-        }
+        metadata()
     }
 
     fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
@@ -21,11 +20,28 @@ impl Collector for ContainersCollector {
     }
 }
 
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "containers",
+        title: "Alternative Containers",
+        description: "Podman and containerd runtimes",
+        tags: &["containers", "spawns-subprocess"],
+        requires_root: false,
+        order: 70,
+        version: collector_version!(),
+        cost: CostHint {
+            cpu: CostLevel::Low,
+            io: CostLevel::Low,
+            spawns_processes: true,
+        },
+    }
+}
+
 fn create_collector() -> Box<dyn Collector> {
     Box::new(ContainersCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 struct RuntimeInfo {
@@ -66,7 +82,15 @@ fn build_snapshot() -> Result<ContainersSnapshot> {
 }
 
 fn detect_runtime(command: &str, args: &[&str]) -> Option<RuntimeInfo> {
-    let output = Command::new(command).args(args).output().ok()?;
+    log::debug!("running {} {}", command, args.join(" "));
+    let output = match Command::new(command).args(args).output() {
+        Ok(output) => output,
+        Err(err) => {
+            log::debug!("{} unavailable: {}", command, err);
+            return None;
+        }
+    };
+    log::debug!("{} returned {}", command, output.status);
     if !output.status.success() {
         return None;
     }
@@ -3,21 +3,20 @@ use serde::Serialize;
 use serde_json::json;
 use std::fs;
 use std::path::{Path, PathBuf};
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    Availability, CollectionContext, Collector, CollectorMetadata, CostHint, Section,
+    SensitiveCategory, Table, collector_version, register_collector,
+};
 
 struct CronCollector;
 
 impl Collector for CronCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "cron",
-            title: "Scheduled Jobs",
-            description: "System cron configuration",
-        }
+        metadata()
     }
 
-    fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
-        match build_snapshot() {
+    fn collect(&self, ctx: &CollectionContext) -> Result<Section> {
+        match build_snapshot(ctx) {
             Ok(snapshot) => Ok(section_from_snapshot(&snapshot)),
             Err(error) => Ok(Section::degraded(
                 "cron",
@@ -30,13 +29,33 @@ impl Collector for CronCollector {
             )),
         }
     }
+
+    fn probe(&self, ctx: &CollectionContext) -> Availability {
+        match build_snapshot(ctx) {
+            Ok(_) => Availability::Ready,
+            Err(err) => Availability::Unavailable(format!("{:#}", err)),
+        }
+    }
+}
+
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "cron",
+        title: "Scheduled Jobs",
+        description: "System cron configuration",
+        tags: &["scheduling"],
+        requires_root: false,
+        order: 90,
+        version: collector_version!(),
+        cost: CostHint::LOW,
+    }
 }
 
 fn create_collector() -> Box<dyn Collector> {
     Box::new(CronCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 struct CronEntry {
@@ -69,9 +88,9 @@ impl CronSnapshot {
     }
 }
 
-fn build_snapshot() -> Result<CronSnapshot> {
-    let system_entries = read_crontab(Path::new("/etc/crontab"))?;
-    let cron_d = read_cron_directory(Path::new("/etc/cron.d"))?;
+fn build_snapshot(ctx: &CollectionContext) -> Result<CronSnapshot> {
+    let system_entries = read_crontab(&ctx.path("/etc/crontab"))?;
+    let cron_d = read_cron_directory(&ctx.path("/etc/cron.d"), Path::new("/etc/cron.d"))?;
 
     Ok(CronSnapshot {
         system_entries,
@@ -87,7 +106,9 @@ fn read_crontab(path: &Path) -> Result<Vec<CronEntry>> {
     }
 }
 
-fn read_cron_directory(path: &Path) -> Result<Vec<CronFileSummary>> {
+/// Lists `path`; files are reported under `shown_as`, the directory as seen
+/// on the host, so an alternate root does not leak into the report.
+fn read_cron_directory(path: &Path, shown_as: &Path) -> Result<Vec<CronFileSummary>> {
     match fs::read_dir(path) {
         Ok(entries) => {
             let mut result = Vec::new();
@@ -97,13 +118,13 @@ fn read_cron_directory(path: &Path) -> Result<Vec<CronFileSummary>> {
                 if !entry.file_type()?.is_file() {
                     continue;
                 }
-                let file_path = entry.path();
-                let entries = read_crontab(&file_path)?;
+                let entries = read_crontab(&entry.path())?;
                 result.push(CronFileSummary {
-                    path: file_path,
+                    path: shown_as.join(entry.file_name()),
                     entries,
                 });
             }
+            result.sort_by(|a, b| a.path.cmp(&b.path));
             Ok(result)
         }
         Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
@@ -183,9 +204,35 @@ fn section_from_snapshot(snapshot: &CronSnapshot) -> Section {
     });
     let mut section = Section::success("cron", "Scheduled Jobs", body);
     section.summary = Some(snapshot.summary());
+    section.add_table(cron_table(&snapshot.system_entries).titled("System crontab"));
+    for file in &snapshot.cron_d {
+        section.add_table(cron_table(&file.entries).titled(file.path.display().to_string()));
+    }
+    for index in 0..snapshot.system_entries.len() {
+        section.mark_sensitive(
+            format!("/system_crontab/{index}/command"),
+            SensitiveCategory::Command,
+        );
+    }
+    for (file, summary) in snapshot.cron_d.iter().enumerate() {
+        for index in 0..summary.entries.len() {
+            section.mark_sensitive(
+                format!("/cron_d/{file}/entries/{index}/command"),
+                SensitiveCategory::Command,
+            );
+        }
+    }
     section
 }
 
+fn cron_table(entries: &[CronEntry]) -> Table {
+    let mut table = Table::new(["Schedule", "User", "Command"]);
+    for entry in entries {
+        table.push_row([&entry.schedule, &entry.user, &entry.command]);
+    }
+    table
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,5 +281,24 @@ mod tests {
         };
 
         assert_eq!(snapshot.summary(), "2 cron entries");
+
+        let section = section_from_snapshot(&snapshot);
+        assert_eq!(section.body["cron_d"][0]["entries"][0]["user"], "alice");
+        let titles: Vec<Option<&str>> = section
+            .parts
+            .iter()
+            .map(|part| match part {
+                vmic_sdk::SectionPart::Table(table) => table.title.as_deref(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(titles, [Some("System crontab"), Some("/etc/cron.d/test")]);
+
+        let marked: Vec<&str> = section
+            .sensitive
+            .iter()
+            .filter_map(|field| section.body.pointer(&field.pointer)?.as_str())
+            .collect();
+        assert_eq!(marked, ["/bin/true", "/bin/echo"]);
     }
 }
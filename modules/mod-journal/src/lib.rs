@@ -1,76 +1,129 @@
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::process::Command;
 use std::time::{Duration, UNIX_EPOCH};
 
-use anyhow::{Context as _, Result, anyhow};
+use anyhow::{Context as _, Result};
 use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_json::json;
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    Availability, CollectionContext, Collector, CollectorError, CollectorMetadata, CostHint,
+    CostLevel, DetailLevel, ErrorKind, Section, SensitiveCategory, collector_version, find_command,
+    register_collector,
+};
 
-const JOURNAL_LINES: &str = "50";
+/// Entries requested from journalctl unless `--set journal.lines=N` says otherwise.
+const DEFAULT_JOURNAL_LINES: usize = 50;
+
+/// Default line count at [`DetailLevel::Full`].
+const FULL_JOURNAL_LINES: usize = 500;
 
 struct JournalCollector;
 
 impl Collector for JournalCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "journal",
-            title: "systemd journal",
-            description: "Recent events from journald",
-        }
+        metadata()
     }
 
     fn collect(&self, ctx: &CollectionContext) -> Result<Section> {
-        match gather_entries(ctx) {
-            Ok(entries) => {
-                let ssh_summary = summarize_ssh_activity(&entries);
-                let body = json!({
-                    "source": "journalctl --output=json",
-                    "entries": entries,
-                    "ssh_summary": ssh_summary,
-                });
-
-                let mut section = Section::success("journal", "systemd journal", body);
-                if let Some(summary) = section.body.get("ssh_summary").and_then(Value::as_object) {
-                    let invalid = summary
-                        .get("invalid_user_count")
-                        .and_then(Value::as_u64)
-                        .unwrap_or(0);
-                    let failures = summary
-                        .get("auth_failure_count")
-                        .and_then(Value::as_u64)
-                        .unwrap_or(0);
-                    section.summary = Some(format!(
-                        "Captured {} entries (SSH invalid users: {}, auth failures: {})",
-                        entries.len(),
-                        invalid,
-                        failures
-                    ));
-                } else {
-                    section.summary = Some(format!("Captured {} entries", entries.len()));
-                }
-                Ok(section)
-            }
-            Err(err) => Ok(Section::degraded(
+        let (lines, lines_note) = journal_lines(ctx);
+        let mut section = match gather_entries(ctx, lines) {
+            Ok(entries) => entries_section(&entries),
+            Err(err) => Section::degraded_from_error(
                 "journal",
                 "systemd journal",
-                err.to_string(),
+                &err,
                 json!({
                     "source": "journalctl --output=json",
                     "entries": Vec::<serde_json::Value>::new(),
                 }),
-            )),
+            ),
+        };
+        section.notes.extend(lines_note);
+        Ok(section)
+    }
+
+    fn probe(&self, _ctx: &CollectionContext) -> Availability {
+        match find_command("journalctl") {
+            Some(_) => Availability::Ready,
+            None => Availability::Unavailable("journalctl not found in PATH".to_string()),
         }
     }
 }
 
+fn entries_section(entries: &[JournalEntry]) -> Section {
+    let ssh_summary = summarize_ssh_activity(entries);
+    let body = json!({
+        "source": "journalctl --output=json",
+        "entries": entries,
+        "ssh_summary": ssh_summary,
+    });
+
+    let mut section = Section::success("journal", "systemd journal", body);
+    if let Some(summary) = section.body.get("ssh_summary").and_then(Value::as_object) {
+        let invalid = summary
+            .get("invalid_user_count")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let failures = summary
+            .get("auth_failure_count")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        section.summary = Some(format!(
+            "Captured {} entries (SSH invalid users: {}, auth failures: {})",
+            entries.len(),
+            invalid,
+            failures
+        ));
+    } else {
+        section.summary = Some(format!("Captured {} entries", entries.len()));
+    }
+    for index in 0..entries.len() {
+        section.mark_sensitive(
+            format!("/entries/{index}/message"),
+            SensitiveCategory::LogMessage,
+        );
+    }
+    section
+}
+
+/// Line count from `journal.lines`, falling back to the detail level's default
+/// with a note when the setting is not a positive integer.
+fn journal_lines(ctx: &CollectionContext) -> (usize, Option<String>) {
+    let default = match ctx.detail_level() {
+        DetailLevel::Full => FULL_JOURNAL_LINES,
+        DetailLevel::Minimal | DetailLevel::Standard => DEFAULT_JOURNAL_LINES,
+    };
+    match ctx.setting_as::<NonZeroUsize>("journal", "lines") {
+        Ok(lines) => (lines.map_or(default, NonZeroUsize::get), None),
+        Err(err) => (default, Some(format!("{:#}; using {}", err, default))),
+    }
+}
+
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "journal",
+        title: "systemd journal",
+        description: "Recent events from journald",
+        tags: &["logs", "spawns-subprocess"],
+        requires_root: true,
+        order: 80,
+        version: collector_version!(),
+        cost: CostHint {
+            cpu: CostLevel::Medium,
+            io: CostLevel::Medium,
+            spawns_processes: true,
+        },
+    }
+}
+
 fn create_collector() -> Box<dyn Collector> {
     Box::new(JournalCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Deserialize)]
 struct RawJournalEntry {
@@ -105,28 +158,40 @@ struct CountEntry {
     count: u64,
 }
 
-fn gather_entries(ctx: &CollectionContext) -> Result<Vec<JournalEntry>> {
+fn gather_entries(ctx: &CollectionContext, lines: usize) -> Result<Vec<JournalEntry>> {
     let mut command = Command::new("journalctl");
     command
         .arg("--output=json")
         .arg("--no-pager")
         .arg("-n")
-        .arg(JOURNAL_LINES);
+        .arg(lines.to_string());
 
     if let Some(since) = ctx.since() {
         command.arg("--since").arg(since);
     }
 
-    let output = command.output().context("failed to execute journalctl")?;
+    log::debug!("running {:?}", command);
+    let output = command
+        .output()
+        .map_err(|err| CollectorError::spawn("journalctl", err))?;
+    log::debug!("journalctl returned {}", output.status);
 
     if !output.status.success() {
-        return Err(anyhow!(
-            "journalctl exited with status {}",
-            output.status.code().unwrap_or_default()
-        ));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(CollectorError::new(
+            ErrorKind::from_stderr(&stderr),
+            format!(
+                "journalctl exited with status {}",
+                output.status.code().unwrap_or_default()
+            ),
+        )
+        .into());
     }
 
-    let stdout = String::from_utf8(output.stdout).context("journalctl returned invalid UTF-8")?;
+    let stdout = String::from_utf8(output.stdout).map_err(|err| {
+        CollectorError::new(ErrorKind::ParseError, "journalctl returned invalid UTF-8")
+            .with_source(err)
+    })?;
     parse_journal_stream(&stdout)
 }
 
@@ -212,7 +277,7 @@ fn summarize_ssh_activity(entries: &[JournalEntry]) -> Option<SshSummary> {
 
 fn top_counts(map: HashMap<String, u64>) -> Vec<CountEntry> {
     let mut counts: Vec<_> = map.into_iter().collect();
-    counts.sort_by(|a, b| b.1.cmp(&a.1));
+    counts.sort_by_key(|entry| std::cmp::Reverse(entry.1));
     counts
         .into_iter()
         .take(5)
@@ -261,6 +326,13 @@ fn format_timestamp(value: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vmic_sdk::testing::assert_section_matches_schema;
+
+    #[test]
+    fn malformed_lines_are_parse_errors() {
+        let error = parse_journal_stream("not json").unwrap_err();
+        assert_eq!(ErrorKind::of(&error), ErrorKind::ParseError);
+    }
 
     #[test]
     fn parse_line_extracts_fields() {
@@ -271,6 +343,26 @@ mod tests {
         assert!(entry.timestamp.starts_with("2023-"));
     }
 
+    #[test]
+    fn section_body_matches_the_published_schema() {
+        let stream = [
+            r#"{"__REALTIME_TIMESTAMP":"1700000000000000","MESSAGE":"Service started","_SYSTEMD_UNIT":"demo.service","PRIORITY":"6"}"#,
+            r#"{"__REALTIME_TIMESTAMP":"1700000001000000","MESSAGE":"Invalid user admin from 203.0.113.9 port 52144","SYSLOG_IDENTIFIER":"sshd","PRIORITY":"4"}"#,
+            r#"{"__REALTIME_TIMESTAMP":"1700000002000000","MESSAGE":"Failed password for root from 203.0.113.9 port 52150 ssh2","_SYSTEMD_UNIT":"ssh.service","PRIORITY":"4"}"#,
+        ]
+        .join("\n");
+        let entries = parse_journal_stream(&stream).expect("parse");
+        let section = entries_section(&entries);
+        assert!(section.body["ssh_summary"].is_object());
+
+        let schema: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../vmic-core/src/section_schemas/journal.json"
+        ))
+        .unwrap();
+        assert_section_matches_schema(&section, &schema);
+        assert_section_matches_schema(&entries_section(&[]), &schema);
+    }
+
     #[test]
     fn parse_stream_skips_empty_lines() {
         let sample = "\n\n";
@@ -278,6 +370,33 @@ mod tests {
         assert!(entries.is_empty());
     }
 
+    #[test]
+    fn line_count_comes_from_the_journal_lines_setting() {
+        let mut ctx = CollectionContext::new();
+        assert_eq!(journal_lines(&ctx), (DEFAULT_JOURNAL_LINES, None));
+
+        ctx.set_setting("journal", "lines", "200");
+        assert_eq!(journal_lines(&ctx), (200, None));
+
+        ctx.set_setting("journal", "lines", "0");
+        let (lines, note) = journal_lines(&ctx);
+        assert_eq!(lines, DEFAULT_JOURNAL_LINES);
+        assert!(
+            note.unwrap()
+                .starts_with("invalid value '0' for journal.lines")
+        );
+    }
+
+    #[test]
+    fn full_detail_reads_more_lines_unless_a_setting_says_otherwise() {
+        let mut ctx = CollectionContext::new();
+        ctx.set_detail_level(DetailLevel::Full);
+        assert_eq!(journal_lines(&ctx), (FULL_JOURNAL_LINES, None));
+
+        ctx.set_setting("journal", "lines", "20");
+        assert_eq!(journal_lines(&ctx), (20, None));
+    }
+
     #[test]
     fn parse_line_handles_missing_fields() {
         let sample = r#"{"MESSAGE":"","_COMM":"bash"}"#;
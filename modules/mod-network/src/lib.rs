@@ -5,46 +5,29 @@ use procfs::process;
 use serde::Serialize;
 use serde_json::json;
 use std::collections::{BTreeMap, HashMap, HashSet};
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::NonZeroUsize;
+use vmic_sdk::{
+    Availability, CancellationToken, CollectionContext, Collector, CollectorMetadata, CostHint,
+    CostLevel, FindingSeverity, Section, SensitiveCategory, collector_version, register_collector,
+};
 
-const MAX_SOCKET_SAMPLES: usize = 20;
+/// Listening sockets kept as samples unless `--set network.socket_samples=N`
+/// says otherwise.
+const DEFAULT_SOCKET_SAMPLES: usize = 20;
 
 struct NetworkCollector;
 
 impl Collector for NetworkCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "network",
-            title: "Network Overview",
-            description: "Interfaces and listening sockets",
-        }
+        metadata()
     }
 
-    fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
-        match build_snapshot() {
-            Ok((snapshot, notes)) => {
-                let summary = format!(
-                    "{} interfaces, {} listening sockets",
-                    snapshot.interfaces.len(),
-                    snapshot.listeners.counts.total()
-                );
-
-                let body = json!({
-                    "interfaces": snapshot.interfaces,
-                    "listeners": {
-                        "counts": snapshot.listeners.counts,
-                        "samples": snapshot.listeners.samples,
-                        "groups": snapshot.listeners.groups,
-                        "insights": snapshot.listeners.insights,
-                    }
-                });
-
-                let mut section = Section::success("network", "Network Overview", body);
-                section.summary = Some(summary);
-                section.notes.extend(notes);
-                Ok(section)
-            }
-            Err(err) => Ok(Section::degraded(
+    fn collect(&self, ctx: &CollectionContext) -> Result<Section> {
+        let (max_samples, samples_note) = socket_samples(ctx);
+        let mut section = match build_snapshot(max_samples, ctx.cancellation()) {
+            Ok((snapshot, notes)) => snapshot_section(&snapshot, notes),
+            Err(err) => Section::degraded(
                 "network",
                 "Network Overview",
                 err.to_string(),
@@ -57,8 +40,91 @@ impl Collector for NetworkCollector {
                         "insights": Vec::<serde_json::Value>::new(),
                     }
                 }),
-            )),
+            ),
+        };
+        section.notes.extend(samples_note);
+        Ok(section)
+    }
+
+    fn probe(&self, _ctx: &CollectionContext) -> Availability {
+        if let Err(err) = std::fs::File::open("/proc/net/tcp") {
+            return Availability::Unavailable(format!("cannot read /proc/net/tcp: {}", err));
+        }
+        // Sockets are mapped to processes through /proc/<pid>/fd, which only
+        // the owner (or root) may list.
+        match std::fs::read_dir("/proc/1/fd") {
+            Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => {
+                Availability::Degraded(
+                    "cannot list other users' /proc/<pid>/fd; listeners may lack their process"
+                        .to_string(),
+                )
+            }
+            _ => Availability::Ready,
+        }
+    }
+}
+
+fn snapshot_section(snapshot: &NetworkSnapshot, notes: Vec<String>) -> Section {
+    let summary = format!(
+        "{} interfaces, {} listening sockets",
+        snapshot.interfaces.len(),
+        snapshot.listeners.counts.total()
+    );
+
+    let body = json!({
+        "interfaces": snapshot.interfaces,
+        "listeners": {
+            "counts": snapshot.listeners.counts,
+            "samples": snapshot.listeners.samples,
+            "groups": snapshot.listeners.groups,
+            "insights": snapshot.listeners.insights,
         }
+    });
+
+    let mut section = Section::success("network", "Network Overview", body);
+    section.summary = Some(summary);
+    section.notes.extend(notes);
+    mark_interface_addresses(&mut section, &snapshot.interfaces);
+    mark_local_addresses(&mut section, &snapshot.listeners);
+    for insight in &snapshot.listeners.insights {
+        section.add_finding(
+            insight.severity,
+            format!("network.{}", insight.rule),
+            insight.finding_message(),
+        );
+    }
+    section
+}
+
+/// Sample cap from `network.socket_samples`, falling back to the default with a
+/// note when the setting is not a positive integer.
+fn socket_samples(ctx: &CollectionContext) -> (usize, Option<String>) {
+    match ctx.setting_as::<NonZeroUsize>("network", "socket_samples") {
+        Ok(samples) => (
+            samples.map_or(DEFAULT_SOCKET_SAMPLES, NonZeroUsize::get),
+            None,
+        ),
+        Err(err) => (
+            DEFAULT_SOCKET_SAMPLES,
+            Some(format!("{:#}; using {}", err, DEFAULT_SOCKET_SAMPLES)),
+        ),
+    }
+}
+
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "network",
+        title: "Network Overview",
+        description: "Interfaces and listening sockets",
+        tags: &["network"],
+        requires_root: true,
+        order: 50,
+        version: collector_version!(),
+        cost: CostHint {
+            cpu: CostLevel::Medium,
+            io: CostLevel::Low,
+            spawns_processes: false,
+        },
     }
 }
 
@@ -66,17 +132,31 @@ fn create_collector() -> Box<dyn Collector> {
     Box::new(NetworkCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 struct InterfaceInfo {
     name: String,
+    /// Set from `IFF_LOOPBACK`; loopback stays in the list but is marked.
+    loopback: bool,
+    /// `operstate` from sysfs: `up`, `down`, `unknown` (usual for `lo`), ...
+    state: Option<String>,
+    mtu: Option<u32>,
+    /// `None` when the device has no hardware address (all zeros).
+    mac: Option<String>,
+    /// `address/prefix` for each IPv4 address, read over rtnetlink.
+    ipv4_addresses: Vec<String>,
+    /// `address/prefix` for each IPv6 address, read over rtnetlink.
+    ipv6_addresses: Vec<String>,
     rx_bytes: u64,
     tx_bytes: u64,
     rx_packets: u64,
     tx_packets: u64,
 }
 
+const SYS_CLASS_NET: &str = "/sys/class/net";
+const IFF_LOOPBACK: u32 = 0x8;
+
 #[derive(Debug, Serialize, Clone, PartialEq, Eq, Default)]
 struct ListenerCounts {
     tcp: usize,
@@ -143,11 +223,29 @@ struct ListenerProcessGroup {
 #[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 struct ListenerInsight {
     rule: String,
-    severity: String,
+    severity: FindingSeverity,
     message: String,
     sockets: Vec<SocketReference>,
 }
 
+/// Addresses named in an insight's digest message before the rest are counted.
+const FINDING_ADDRESSES: usize = 3;
+
+impl ListenerInsight {
+    fn finding_message(&self) -> String {
+        let mut addresses: Vec<String> = self
+            .sockets
+            .iter()
+            .take(FINDING_ADDRESSES)
+            .map(|socket| format!("{} {}", socket.protocol, socket.local_address))
+            .collect();
+        if self.sockets.len() > FINDING_ADDRESSES {
+            addresses.push(format!("{} more", self.sockets.len() - FINDING_ADDRESSES));
+        }
+        format!("{}: {}", self.message, addresses.join(", "))
+    }
+}
+
 #[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 struct SocketReference {
     protocol: String,
@@ -157,14 +255,57 @@ struct SocketReference {
     pid: Option<i32>,
 }
 
-fn build_snapshot() -> Result<(NetworkSnapshot, Vec<String>)> {
-    let interfaces = gather_interfaces().context("failed to read network interfaces")?;
+/// Marks both address lists of every interface.
+fn mark_interface_addresses(section: &mut Section, interfaces: &[InterfaceInfo]) {
+    for index in 0..interfaces.len() {
+        for family in ["ipv4_addresses", "ipv6_addresses"] {
+            section.mark_sensitive(
+                format!("/interfaces/{index}/{family}"),
+                SensitiveCategory::IpAddress,
+            );
+        }
+    }
+}
+
+/// Marks every listener address in the body built from `listeners`.
+fn mark_local_addresses(section: &mut Section, listeners: &ListenerSnapshot) {
+    for index in 0..listeners.samples.len() {
+        section.mark_sensitive(
+            format!("/listeners/samples/{index}/local_address"),
+            SensitiveCategory::IpAddress,
+        );
+    }
+    for (group, entry) in listeners.groups.iter().enumerate() {
+        for process in 0..entry.processes.len() {
+            section.mark_sensitive(
+                format!("/listeners/groups/{group}/processes/{process}/local_addresses"),
+                SensitiveCategory::IpAddress,
+            );
+        }
+    }
+    for (insight, entry) in listeners.insights.iter().enumerate() {
+        for socket in 0..entry.sockets.len() {
+            section.mark_sensitive(
+                format!("/listeners/insights/{insight}/sockets/{socket}/local_address"),
+                SensitiveCategory::IpAddress,
+            );
+        }
+    }
+}
+
+fn build_snapshot(
+    max_samples: usize,
+    cancel: &CancellationToken,
+) -> Result<(NetworkSnapshot, Vec<String>)> {
+    let (interfaces, mut notes) =
+        gather_interfaces().context("failed to read network interfaces")?;
 
     if interfaces.is_empty() {
         anyhow::bail!("no network interface data available")
     }
 
-    let (listeners, notes) = gather_listeners();
+    let (listeners, mut listener_notes) = gather_listeners(max_samples, cancel);
+    notes.append(&mut listener_notes);
 
     Ok((
         NetworkSnapshot {
@@ -175,34 +316,217 @@ fn build_snapshot() -> Result<(NetworkSnapshot, Vec<String>)> {
     ))
 }
 
-fn gather_interfaces() -> Result<Vec<InterfaceInfo>> {
+/// Counters from `/proc/net/dev`, link details from sysfs, and addresses
+/// from an rtnetlink dump. A failed dump only costs a note.
+fn gather_interfaces() -> Result<(Vec<InterfaceInfo>, Vec<String>)> {
     let stats = net::dev_status()?;
+    let mut notes = Vec::new();
+    let mut addresses = match dump_addresses() {
+        Ok(addresses) => addresses,
+        Err(err) => {
+            notes.push(format!("Interface addresses unavailable: {:#}", err));
+            HashMap::new()
+        }
+    };
     let mut interfaces: Vec<_> = stats
         .into_iter()
-        .map(|(name, device)| InterfaceInfo {
-            name,
-            rx_bytes: device.recv_bytes,
-            tx_bytes: device.sent_bytes,
-            rx_packets: device.recv_packets,
-            tx_packets: device.sent_packets,
+        .map(|(name, device)| {
+            let sys = |attr: &str| read_sys_attr(&name, attr);
+            let flags = sys("flags").and_then(|flags| parse_hex_u32(&flags));
+            let assigned = sys("ifindex")
+                .and_then(|index| index.parse::<u32>().ok())
+                .and_then(|index| addresses.remove(&index))
+                .unwrap_or_default();
+            InterfaceInfo {
+                loopback: flags.map_or(name == "lo", |flags| flags & IFF_LOOPBACK != 0),
+                state: sys("operstate"),
+                mtu: sys("mtu").and_then(|mtu| mtu.parse().ok()),
+                mac: sys("address").filter(|mac| mac.chars().any(|c| c != '0' && c != ':')),
+                ipv4_addresses: assigned.ipv4,
+                ipv6_addresses: assigned.ipv6,
+                name,
+                rx_bytes: device.recv_bytes,
+                tx_bytes: device.sent_bytes,
+                rx_packets: device.recv_packets,
+                tx_packets: device.sent_packets,
+            }
         })
         .collect();
 
+    // Names are unique, so the order is the same on every run.
     interfaces.sort_by(|a, b| a.name.cmp(&b.name));
-    Ok(interfaces)
+    Ok((interfaces, notes))
+}
+
+fn read_sys_attr(interface: &str, attr: &str) -> Option<String> {
+    let path = format!("{SYS_CLASS_NET}/{interface}/{attr}");
+    let value = std::fs::read_to_string(path).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn parse_hex_u32(value: &str) -> Option<u32> {
+    u32::from_str_radix(value.trim_start_matches("0x"), 16).ok()
+}
+
+/// Addresses one interface holds, as `address/prefix`.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct InterfaceAddresses {
+    ipv4: Vec<String>,
+    ipv6: Vec<String>,
+}
+
+const NLMSG_HEADER_LEN: usize = 16;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const RTM_NEWADDR: u16 = 20;
+const RTM_GETADDR: u16 = 22;
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_DUMP: u16 = 0x300;
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+const IFA_ADDRESS: u16 = 1;
+const IFA_LOCAL: u16 = 2;
+
+/// Asks the kernel for every address of every interface (`RTM_GETADDR`
+/// dump, the query behind `ip addr`), keyed by interface index.
+fn dump_addresses() -> Result<HashMap<u32, InterfaceAddresses>> {
+    use rustix::net::netlink::SocketAddrNetlink;
+    use rustix::net::{AddressFamily, RecvFlags, SendFlags, SocketType};
+
+    let socket = rustix::net::socket(AddressFamily::NETLINK, SocketType::RAW, None)
+        .context("failed to open a netlink socket")?;
+    let mut request = Vec::with_capacity(NLMSG_HEADER_LEN + 8);
+    request.extend_from_slice(&((NLMSG_HEADER_LEN + 8) as u32).to_ne_bytes());
+    request.extend_from_slice(&RTM_GETADDR.to_ne_bytes());
+    request.extend_from_slice(&(NLM_F_REQUEST | NLM_F_DUMP).to_ne_bytes());
+    request.extend_from_slice(&1u32.to_ne_bytes());
+    request.extend_from_slice(&0u32.to_ne_bytes());
+    // ifaddrmsg with family AF_UNSPEC: both address families.
+    request.extend_from_slice(&[0; 8]);
+    rustix::net::sendto(
+        &socket,
+        &request,
+        SendFlags::empty(),
+        &SocketAddrNetlink::new(0, 0),
+    )
+    .context("failed to request interface addresses")?;
+
+    let mut addresses = HashMap::new();
+    let mut buffer = vec![0u8; 32 * 1024];
+    loop {
+        let (_, received) = rustix::net::recv(&socket, &mut buffer[..], RecvFlags::empty())
+            .context("failed to read interface addresses")?;
+        let received = received.min(buffer.len());
+        if parse_address_dump(&buffer[..received], &mut addresses)? {
+            return Ok(addresses);
+        }
+    }
+}
+
+/// Records the `RTM_NEWADDR` messages in one datagram of the dump. Returns
+/// true once `NLMSG_DONE` arrives.
+fn parse_address_dump(
+    data: &[u8],
+    addresses: &mut HashMap<u32, InterfaceAddresses>,
+) -> Result<bool> {
+    let mut offset = 0;
+    while offset + NLMSG_HEADER_LEN <= data.len() {
+        let length = read_u32(data, offset) as usize;
+        let kind = read_u16(data, offset + 4);
+        if length < NLMSG_HEADER_LEN || offset + length > data.len() {
+            anyhow::bail!("truncated netlink message");
+        }
+        let payload = &data[offset + NLMSG_HEADER_LEN..offset + length];
+        match kind {
+            NLMSG_DONE => return Ok(true),
+            NLMSG_ERROR if payload.len() >= 4 => {
+                let code = i32::from_ne_bytes([payload[0], payload[1], payload[2], payload[3]]);
+                if code != 0 {
+                    return Err(std::io::Error::from_raw_os_error(-code))
+                        .context("netlink address dump failed");
+                }
+            }
+            RTM_NEWADDR => record_address(payload, addresses),
+            _ => {}
+        }
+        offset += align4(length);
+    }
+    Ok(false)
+}
+
+/// `ifaddrmsg` (family, prefix length, flags, scope, index) followed by
+/// route attributes. `IFA_LOCAL` wins over `IFA_ADDRESS`, which is the peer
+/// on point-to-point links.
+fn record_address(payload: &[u8], addresses: &mut HashMap<u32, InterfaceAddresses>) {
+    if payload.len() < 8 {
+        return;
+    }
+    let (family, prefix, index) = (payload[0], payload[1], read_u32(payload, 4));
+    let (mut local, mut address) = (None, None);
+    let mut offset = 8;
+    while offset + 4 <= payload.len() {
+        let length = usize::from(read_u16(payload, offset));
+        if length < 4 || offset + length > payload.len() {
+            break;
+        }
+        let value = &payload[offset + 4..offset + length];
+        match read_u16(payload, offset + 2) {
+            IFA_LOCAL => local = Some(value),
+            IFA_ADDRESS => address = Some(value),
+            _ => {}
+        }
+        offset += align4(length);
+    }
+    let Some(raw) = local.or(address) else {
+        return;
+    };
+    let entry = addresses.entry(index).or_default();
+    match (family, <[u8; 4]>::try_from(raw), <[u8; 16]>::try_from(raw)) {
+        (AF_INET, Ok(octets), _) => entry
+            .ipv4
+            .push(format!("{}/{prefix}", Ipv4Addr::from(octets))),
+        (AF_INET6, _, Ok(octets)) => entry
+            .ipv6
+            .push(format!("{}/{prefix}", Ipv6Addr::from(octets))),
+        _ => {}
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+fn align4(length: usize) -> usize {
+    (length + 3) & !3
 }
 
-fn gather_listeners() -> (ListenerSnapshot, Vec<String>) {
+fn gather_listeners(
+    max_samples: usize,
+    cancel: &CancellationToken,
+) -> (ListenerSnapshot, Vec<String>) {
     let mut samples = Vec::new();
     let mut counts = ListenerCounts::default();
     let mut notes = Vec::new();
-    let process_map = collect_socket_process_map().unwrap_or_default();
+    let process_map = collect_socket_process_map(cancel).unwrap_or_default();
+    if cancel.is_cancelled() {
+        notes.push("Socket owner lookup cancelled; process lists are partial".to_string());
+    }
 
     match net::tcp() {
         Ok(entries) => {
             for entry in entries.into_iter().filter(|e| e.state == TcpState::Listen) {
                 counts.tcp += 1;
-                if samples.len() < MAX_SOCKET_SAMPLES {
+                if samples.len() < max_samples {
                     let processes = process_map.get(&entry.inode).cloned().unwrap_or_default();
                     let protocol = "tcp".to_string();
                     let local_address = format!("{}", entry.local_address);
@@ -223,7 +547,7 @@ fn gather_listeners() -> (ListenerSnapshot, Vec<String>) {
         Ok(entries) => {
             for entry in entries.into_iter().filter(|e| e.state == TcpState::Listen) {
                 counts.tcp6 += 1;
-                if samples.len() < MAX_SOCKET_SAMPLES {
+                if samples.len() < max_samples {
                     let processes = process_map.get(&entry.inode).cloned().unwrap_or_default();
                     let protocol = "tcp6".to_string();
                     let local_address = format!("{}", entry.local_address);
@@ -245,7 +569,7 @@ fn gather_listeners() -> (ListenerSnapshot, Vec<String>) {
             counts.udp = entries.len();
             for entry in entries
                 .into_iter()
-                .take(MAX_SOCKET_SAMPLES.saturating_sub(samples.len()))
+                .take(max_samples.saturating_sub(samples.len()))
             {
                 let processes = process_map.get(&entry.inode).cloned().unwrap_or_default();
                 let protocol = "udp".to_string();
@@ -267,7 +591,7 @@ fn gather_listeners() -> (ListenerSnapshot, Vec<String>) {
             counts.udp6 = entries.len();
             for entry in entries
                 .into_iter()
-                .take(MAX_SOCKET_SAMPLES.saturating_sub(samples.len()))
+                .take(max_samples.saturating_sub(samples.len()))
             {
                 let processes = process_map.get(&entry.inode).cloned().unwrap_or_default();
                 let protocol = "udp6".to_string();
@@ -298,11 +622,18 @@ fn gather_listeners() -> (ListenerSnapshot, Vec<String>) {
     )
 }
 
-fn collect_socket_process_map() -> Result<HashMap<u64, Vec<SocketProcessInfo>>> {
+/// Socket inode to owning processes. Stops early when `cancel` is tripped and
+/// returns what it has mapped so far.
+fn collect_socket_process_map(
+    cancel: &CancellationToken,
+) -> Result<HashMap<u64, Vec<SocketProcessInfo>>> {
     let mut map: HashMap<u64, Vec<SocketProcessInfo>> = HashMap::new();
     let processes = process::all_processes()?;
 
     for proc in processes {
+        if cancel.is_cancelled() {
+            break;
+        }
         let proc = match proc {
             Ok(proc) => proc,
             Err(_) => continue,
@@ -337,15 +668,15 @@ fn collect_socket_process_map() -> Result<HashMap<u64, Vec<SocketProcessInfo>>>
 fn extract_container_from_cgroups(groups: &procfs::ProcessCGroups) -> Option<String> {
     for group in &groups.0 {
         let path = group.pathname.trim_matches('/');
-        if path.contains("docker/") {
-            if let Some(id) = path.split("docker/").nth(1) {
-                return Some(id.split('/').next().unwrap_or(id).to_string());
-            }
+        if path.contains("docker/")
+            && let Some(id) = path.split("docker/").nth(1)
+        {
+            return Some(id.split('/').next().unwrap_or(id).to_string());
         }
-        if path.contains("kubepods/") {
-            if let Some(id) = path.rsplit('/').next() {
-                return Some(id.to_string());
-            }
+        if path.contains("kubepods/")
+            && let Some(id) = path.rsplit('/').next()
+        {
+            return Some(id.to_string());
         }
     }
     None
@@ -384,7 +715,7 @@ fn build_listener_groups(samples: &[SocketSample]) -> Vec<ListenerContainerGroup
         .into_values()
         .map(|builder| builder.finish())
         .collect();
-    groups.sort_by(|a, b| b.socket_count.cmp(&a.socket_count));
+    groups.sort_by_key(|group| std::cmp::Reverse(group.socket_count));
     groups
 }
 
@@ -396,7 +727,7 @@ fn derive_listener_insights(samples: &[SocketSample]) -> Vec<ListenerInsight> {
             rules
                 .entry("wildcard_listener")
                 .or_insert_with(|| {
-                    InsightBucket::new("warning", "Listener bound to all interfaces")
+                    InsightBucket::new(FindingSeverity::Warning, "Listener bound to all interfaces")
                 })
                 .push(sample);
         }
@@ -410,7 +741,10 @@ fn derive_listener_insights(samples: &[SocketSample]) -> Vec<ListenerInsight> {
             rules
                 .entry("legacy_protocol")
                 .or_insert_with(|| {
-                    InsightBucket::new("warning", "Legacy or insecure protocol exposed")
+                    InsightBucket::new(
+                        FindingSeverity::Warning,
+                        "Legacy or insecure protocol exposed",
+                    )
                 })
                 .push(sample);
         }
@@ -428,15 +762,15 @@ fn derive_listener_insights(samples: &[SocketSample]) -> Vec<ListenerInsight> {
 }
 
 struct InsightBucket {
-    severity: String,
+    severity: FindingSeverity,
     message: String,
     sockets: Vec<SocketReference>,
 }
 
 impl InsightBucket {
-    fn new(severity: &str, message: &str) -> Self {
+    fn new(severity: FindingSeverity, message: &str) -> Self {
         InsightBucket {
-            severity: severity.to_string(),
+            severity,
             message: message.to_string(),
             sockets: Vec::new(),
         }
@@ -597,7 +931,7 @@ impl ListenerContainerGroupBuilder {
 
     fn finish(mut self) -> ListenerContainerGroup {
         self.processes
-            .sort_by(|a, b| b.socket_count.cmp(&a.socket_count));
+            .sort_by_key(|process| std::cmp::Reverse(process.socket_count));
         ListenerContainerGroup {
             container: self.container,
             socket_count: self.socket_count,
@@ -610,6 +944,41 @@ impl ListenerContainerGroupBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vmic_sdk::testing::assert_section_matches_schema;
+
+    #[test]
+    fn socket_samples_come_from_the_network_setting() {
+        let mut ctx = CollectionContext::new();
+        assert_eq!(socket_samples(&ctx), (DEFAULT_SOCKET_SAMPLES, None));
+
+        ctx.set_setting("network", "socket_samples", "50");
+        assert_eq!(socket_samples(&ctx), (50, None));
+
+        ctx.set_setting("network", "socket_samples", "0");
+        let (samples, note) = socket_samples(&ctx);
+        assert_eq!(samples, DEFAULT_SOCKET_SAMPLES);
+        assert!(
+            note.unwrap()
+                .starts_with("invalid value '0' for network.socket_samples")
+        );
+    }
+
+    #[test]
+    fn cancelled_listener_scan_skips_socket_owners_with_a_note() {
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert!(collect_socket_process_map(&cancel).unwrap().is_empty());
+        let (listeners, notes) = gather_listeners(5, &cancel);
+        assert!(
+            listeners
+                .samples
+                .iter()
+                .all(|sample| sample.processes.is_empty())
+        );
+        assert!(
+            notes.contains(&"Socket owner lookup cancelled; process lists are partial".to_string())
+        );
+    }
 
     #[test]
     fn listener_counts_total() {
@@ -683,9 +1052,9 @@ mod tests {
         assert_eq!(host_group.processes[0].local_addresses, vec!["0.0.0.0:22"]);
     }
 
-    #[test]
-    fn derive_listener_insights_flags_wildcard_and_legacy() {
-        let samples = vec![
+    /// A wildcard telnet listener on the host and a loopback one in a container.
+    fn telnet_and_app_samples() -> Vec<SocketSample> {
+        vec![
             SocketSample {
                 protocol: "tcp".into(),
                 local_address: "0.0.0.0:23".into(),
@@ -710,7 +1079,12 @@ mod tests {
                 }],
                 service: Some("http-alt".into()),
             },
-        ];
+        ]
+    }
+
+    #[test]
+    fn derive_listener_insights_flags_wildcard_and_legacy() {
+        let samples = telnet_and_app_samples();
 
         let insights = derive_listener_insights(&samples);
         assert_eq!(insights.len(), 2);
@@ -727,5 +1101,156 @@ mod tests {
             .find(|insight| insight.rule == "legacy_protocol")
             .expect("legacy rule");
         assert_eq!(legacy.sockets[0].service.as_deref(), Some("telnet"));
+        assert_eq!(
+            serde_json::to_value(legacy).unwrap()["severity"],
+            json!("warning")
+        );
+        assert!(
+            legacy
+                .finding_message()
+                .starts_with("Legacy or insecure protocol exposed: tcp ")
+        );
+
+        let listeners = ListenerSnapshot {
+            counts: ListenerCounts::default(),
+            groups: build_listener_groups(&samples),
+            insights,
+            samples,
+        };
+        let mut section = Section::success(
+            "network",
+            "Network Overview",
+            json!({ "listeners": listeners }),
+        );
+        mark_local_addresses(&mut section, &listeners);
+        assert_eq!(section.sensitive.len(), 2 + 2 + 2);
+        for field in &section.sensitive {
+            let value = section.body.pointer(&field.pointer).expect(&field.pointer);
+            assert!(value.to_string().contains(":"), "{}", field.pointer);
+        }
+    }
+
+    #[test]
+    fn section_body_matches_the_published_schema() {
+        let samples = telnet_and_app_samples();
+        let interface = |name: &str, loopback: bool| InterfaceInfo {
+            name: name.to_string(),
+            loopback,
+            state: Some(if loopback { "unknown" } else { "up" }.to_string()),
+            mtu: Some(if loopback { 65536 } else { 1500 }),
+            mac: (!loopback).then(|| "52:54:00:12:34:56".to_string()),
+            ipv4_addresses: vec![
+                if loopback {
+                    "127.0.0.1/8"
+                } else {
+                    "10.0.0.5/24"
+                }
+                .to_string(),
+            ],
+            ipv6_addresses: vec![if loopback { "::1/128" } else { "fe80::1/64" }.to_string()],
+            rx_bytes: 4096,
+            tx_bytes: 2048,
+            rx_packets: 32,
+            tx_packets: 16,
+        };
+        let snapshot = NetworkSnapshot {
+            interfaces: vec![interface("lo", true), interface("eth0", false)],
+            listeners: ListenerSnapshot {
+                counts: ListenerCounts {
+                    tcp: samples.len(),
+                    ..ListenerCounts::default()
+                },
+                groups: build_listener_groups(&samples),
+                insights: derive_listener_insights(&samples),
+                samples,
+            },
+        };
+
+        let schema: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../vmic-core/src/section_schemas/network.json"
+        ))
+        .unwrap();
+        assert_section_matches_schema(&snapshot_section(&snapshot, Vec::new()), &schema);
+    }
+
+    fn netlink_message(kind: u16, payload: &[u8]) -> Vec<u8> {
+        let length = NLMSG_HEADER_LEN + payload.len();
+        let mut message = Vec::new();
+        message.extend_from_slice(&(length as u32).to_ne_bytes());
+        message.extend_from_slice(&kind.to_ne_bytes());
+        message.extend_from_slice(&[0; 10]);
+        message.extend_from_slice(payload);
+        message.resize(align4(length), 0);
+        message
+    }
+
+    fn new_address(family: u8, prefix: u8, index: u32, attributes: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut payload = vec![family, prefix, 0, 0];
+        payload.extend_from_slice(&index.to_ne_bytes());
+        for (kind, value) in attributes {
+            let length = 4 + value.len();
+            payload.extend_from_slice(&(length as u16).to_ne_bytes());
+            payload.extend_from_slice(&kind.to_ne_bytes());
+            payload.extend_from_slice(value);
+            payload.resize(align4(payload.len()), 0);
+        }
+        netlink_message(RTM_NEWADDR, &payload)
+    }
+
+    #[test]
+    fn address_dump_files_addresses_under_their_interface() {
+        let link_local: [u8; 16] = "fe80::fc:ff:fe00:1".parse::<Ipv6Addr>().unwrap().octets();
+        let mut dump = Vec::new();
+        dump.extend(new_address(AF_INET, 8, 1, &[(IFA_LOCAL, &[127, 0, 0, 1])]));
+        dump.extend(new_address(
+            AF_INET,
+            24,
+            2,
+            &[(IFA_ADDRESS, &[192, 0, 2, 2]), (IFA_LOCAL, &[192, 0, 2, 2])],
+        ));
+        // A WireGuard-style /32 with no connected route, and a
+        // point-to-point link whose IFA_ADDRESS is the peer.
+        dump.extend(new_address(
+            AF_INET,
+            32,
+            3,
+            &[(IFA_LOCAL, &[198, 51, 100, 7])],
+        ));
+        dump.extend(new_address(
+            AF_INET,
+            32,
+            4,
+            &[(IFA_ADDRESS, &[10, 9, 0, 1]), (IFA_LOCAL, &[10, 9, 0, 2])],
+        ));
+        dump.extend(new_address(AF_INET6, 64, 2, &[(IFA_ADDRESS, &link_local)]));
+
+        let mut addresses = HashMap::new();
+        assert!(!parse_address_dump(&dump, &mut addresses).unwrap());
+        assert!(parse_address_dump(&netlink_message(NLMSG_DONE, &[0; 4]), &mut addresses).unwrap());
+
+        assert_eq!(addresses[&1].ipv4, ["127.0.0.1/8"]);
+        assert_eq!(
+            addresses[&2],
+            InterfaceAddresses {
+                ipv4: vec!["192.0.2.2/24".to_string()],
+                ipv6: vec!["fe80::fc:ff:fe00:1/64".to_string()],
+            }
+        );
+        assert_eq!(addresses[&3].ipv4, ["198.51.100.7/32"]);
+        assert_eq!(addresses[&4].ipv4, ["10.9.0.2/32"]);
+    }
+
+    #[test]
+    fn address_dump_errors_and_truncation_are_reported() {
+        let mut addresses = HashMap::new();
+        let denied = netlink_message(NLMSG_ERROR, &(-13i32).to_ne_bytes());
+        let err = parse_address_dump(&denied, &mut addresses).unwrap_err();
+        assert!(format!("{:#}", err).contains("netlink address dump failed"));
+
+        let mut truncated = new_address(AF_INET, 24, 2, &[(IFA_LOCAL, &[192, 0, 2, 2])]);
+        truncated.truncate(20);
+        assert!(parse_address_dump(&truncated, &mut addresses).is_err());
+        assert!(addresses.is_empty());
+        assert_eq!(parse_hex_u32("0x1009"), Some(0x1009));
     }
 }
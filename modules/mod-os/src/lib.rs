@@ -4,17 +4,16 @@ use anyhow::{Context as _, Result};
 use etc_os_release::OsRelease;
 use rustix::system::uname;
 use serde_json::{Value, json};
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    CollectionContext, Collector, CollectorMetadata, CostHint, Section, collector_version,
+    register_collector,
+};
 
 struct OsCollector;
 
 impl Collector for OsCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "os",
-            title: "Operating System",
-            description: "Information from /etc/os-release and uname",
-        }
+        metadata()
     }
 
     fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
@@ -23,11 +22,24 @@ impl Collector for OsCollector {
     }
 }
 
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "os",
+        title: "Operating System",
+        description: "Information from /etc/os-release and uname",
+        tags: &["system"],
+        requires_root: false,
+        order: 10,
+        version: collector_version!(),
+        cost: CostHint::LOW,
+    }
+}
+
 fn create_collector() -> Box<dyn Collector> {
     Box::new(OsCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct OsSnapshot {
@@ -3,32 +3,44 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context as _, Result};
-use procfs::{Current, LoadAverage, Meminfo, Uptime, process::Process};
+use procfs::{Current, FromRead, LoadAverage, Meminfo, Uptime, process::Process};
 use serde_json::json;
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    CollectionContext, Collector, CollectorMetadata, CostHint, Section, collector_version,
+    register_collector,
+};
 
 struct ProcCollector;
 
 impl Collector for ProcCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "proc",
-            title: "Processes and Resources",
-            description: "Overview of /proc: load and memory",
-        }
+        metadata()
     }
 
-    fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
-        let snapshot = build_snapshot().context("failed to read /proc metrics")?;
+    fn collect(&self, ctx: &CollectionContext) -> Result<Section> {
+        let snapshot = build_snapshot(ctx).context("failed to read /proc metrics")?;
         Ok(section_from_snapshot(&snapshot))
     }
 }
 
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "proc",
+        title: "Processes and Resources",
+        description: "Overview of /proc: load and memory",
+        tags: &["system", "performance"],
+        requires_root: false,
+        order: 20,
+        version: collector_version!(),
+        cost: CostHint::LOW,
+    }
+}
+
 fn create_collector() -> Box<dyn Collector> {
     Box::new(ProcCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Clone, PartialEq)]
 struct ProcSnapshot {
@@ -125,14 +137,14 @@ struct TopProcesses {
     by_memory: Vec<ProcessUsage>,
 }
 
-fn build_snapshot() -> Result<ProcSnapshot> {
-    let loadavg = LoadAverage::current()
+fn build_snapshot(ctx: &CollectionContext) -> Result<ProcSnapshot> {
+    let loadavg = LoadAverage::from_file(ctx.path(LoadAverage::PATH))
         .ok()
         .map(|l| (l.one, l.five, l.fifteen));
 
-    let (memory, mut notes) = collect_memory_snapshot()?;
-    let psi = collect_psi_snapshot();
-    let (top_processes, mut process_notes) = collect_top_processes();
+    let (memory, mut notes) = collect_memory_snapshot(ctx)?;
+    let psi = collect_psi_snapshot(ctx);
+    let (top_processes, mut process_notes) = collect_top_processes(ctx);
     notes.append(&mut process_notes);
 
     Ok(ProcSnapshot {
@@ -144,9 +156,9 @@ fn build_snapshot() -> Result<ProcSnapshot> {
     })
 }
 
-fn collect_memory_snapshot() -> Result<(MemorySnapshot, Vec<String>)> {
+fn collect_memory_snapshot(ctx: &CollectionContext) -> Result<(MemorySnapshot, Vec<String>)> {
     let mut notes = Vec::new();
-    let meminfo = Meminfo::current().ok();
+    let meminfo = Meminfo::from_file(ctx.path(Meminfo::PATH)).ok();
 
     let host = meminfo
         .as_ref()
@@ -167,7 +179,7 @@ fn collect_memory_snapshot() -> Result<(MemorySnapshot, Vec<String>)> {
         .map(|info| info.swap_free)
         .map(|kb| kb.saturating_mul(1024));
 
-    let devices = match collect_swap_devices() {
+    let devices = match collect_swap_devices(ctx) {
         Ok(devices) => devices,
         Err(err) => {
             notes.push(format!("Failed to read /proc/swaps: {err}"));
@@ -177,7 +189,7 @@ fn collect_memory_snapshot() -> Result<(MemorySnapshot, Vec<String>)> {
 
     let active_swaps: HashSet<String> = devices.iter().map(|device| device.name.clone()).collect();
 
-    let zram_devices = match collect_zram_devices(&active_swaps) {
+    let zram_devices = match collect_zram_devices(ctx, &active_swaps) {
         Ok(devices) => devices,
         Err(err) => {
             notes.push(format!("Failed to inspect zram devices: {err}"));
@@ -192,7 +204,7 @@ fn collect_memory_snapshot() -> Result<(MemorySnapshot, Vec<String>)> {
         );
     }
 
-    let cgroup = match collect_cgroup_memory() {
+    let cgroup = match collect_cgroup_memory(ctx) {
         Ok(value) => value,
         Err(err) => {
             notes.push(format!("Failed to collect cgroup memory stats: {err}"));
@@ -210,10 +222,10 @@ fn collect_memory_snapshot() -> Result<(MemorySnapshot, Vec<String>)> {
     Ok((MemorySnapshot { host, cgroup, swap }, notes))
 }
 
-fn collect_psi_snapshot() -> Option<PsiSnapshot> {
-    let cpu = read_psi_resource("/proc/pressure/cpu");
-    let memory = read_psi_resource("/proc/pressure/memory");
-    let io = read_psi_resource("/proc/pressure/io");
+fn collect_psi_snapshot(ctx: &CollectionContext) -> Option<PsiSnapshot> {
+    let cpu = read_psi_resource(&ctx.path("/proc/pressure/cpu"));
+    let memory = read_psi_resource(&ctx.path("/proc/pressure/memory"));
+    let io = read_psi_resource(&ctx.path("/proc/pressure/io"));
 
     if cpu.is_none() && memory.is_none() && io.is_none() {
         None
@@ -222,8 +234,8 @@ fn collect_psi_snapshot() -> Option<PsiSnapshot> {
     }
 }
 
-fn collect_top_processes() -> (Option<TopProcesses>, Vec<String>) {
-    match gather_process_usage() {
+fn collect_top_processes(ctx: &CollectionContext) -> (Option<TopProcesses>, Vec<String>) {
+    match gather_process_usage(ctx) {
         Ok(usages) => {
             if usages.is_empty() {
                 (None, Vec::new())
@@ -243,8 +255,8 @@ fn collect_top_processes() -> (Option<TopProcesses>, Vec<String>) {
     }
 }
 
-fn gather_process_usage() -> Result<Vec<ProcessUsage>> {
-    let uptime = match Uptime::current() {
+fn gather_process_usage(ctx: &CollectionContext) -> Result<Vec<ProcessUsage>> {
+    let uptime = match Uptime::from_file(ctx.path(Uptime::PATH)) {
         Ok(value) if value.uptime > 0.0 => value.uptime,
         Ok(_) => return Ok(Vec::new()),
         Err(error) => return Err(error.into()),
@@ -254,7 +266,7 @@ fn gather_process_usage() -> Result<Vec<ProcessUsage>> {
     let page_size = procfs::page_size();
     let mut usages = Vec::new();
 
-    let processes = procfs::process::all_processes()?;
+    let processes = procfs::process::all_processes_with_root(ctx.path("/proc"))?;
     for entry in processes {
         let proc = match entry {
             Ok(proc) => proc,
@@ -370,8 +382,9 @@ fn host_memory_from_meminfo(meminfo: &Meminfo) -> HostMemory {
     }
 }
 
-fn collect_swap_devices() -> Result<Vec<SwapDevice>> {
-    let content = fs::read_to_string("/proc/swaps").context("failed to read /proc/swaps")?;
+fn collect_swap_devices(ctx: &CollectionContext) -> Result<Vec<SwapDevice>> {
+    let content =
+        fs::read_to_string(ctx.path("/proc/swaps")).context("failed to read /proc/swaps")?;
     let mut devices = Vec::new();
 
     for line in content.lines().skip(1) {
@@ -395,8 +408,11 @@ fn collect_swap_devices() -> Result<Vec<SwapDevice>> {
     Ok(devices)
 }
 
-fn collect_zram_devices(active_swaps: &HashSet<String>) -> Result<Vec<ZramDevice>> {
-    let sys_block = match fs::read_dir("/sys/block") {
+fn collect_zram_devices(
+    ctx: &CollectionContext,
+    active_swaps: &HashSet<String>,
+) -> Result<Vec<ZramDevice>> {
+    let sys_block = match fs::read_dir(ctx.path("/sys/block")) {
         Ok(entries) => entries,
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
         Err(err) => return Err(err.into()),
@@ -434,8 +450,8 @@ fn collect_zram_devices(active_swaps: &HashSet<String>) -> Result<Vec<ZramDevice
     Ok(devices)
 }
 
-fn collect_cgroup_memory() -> Result<Option<CgroupMemorySnapshot>> {
-    let process = match Process::myself() {
+fn collect_cgroup_memory(ctx: &CollectionContext) -> Result<Option<CgroupMemorySnapshot>> {
+    let process = match Process::new_with_root(ctx.path("/proc/self")) {
         Ok(process) => process,
         Err(_) => return Ok(None),
     };
@@ -467,13 +483,13 @@ fn collect_cgroup_memory() -> Result<Option<CgroupMemorySnapshot>> {
     for (version, relative) in candidates {
         match version {
             CgroupVersion::Unified => {
-                let dir = join_cgroup_path(Path::new("/sys/fs/cgroup"), &relative);
+                let dir = join_cgroup_path(&ctx.path("/sys/fs/cgroup"), &relative);
                 if let Some(snapshot) = read_cgroup_v2_memory(&dir, &relative)? {
                     return Ok(Some(snapshot));
                 }
             }
             CgroupVersion::Legacy => {
-                let dir = join_cgroup_path(Path::new("/sys/fs/cgroup/memory"), &relative);
+                let dir = join_cgroup_path(&ctx.path("/sys/fs/cgroup/memory"), &relative);
                 if let Some(snapshot) = read_cgroup_v1_memory(&dir, &relative)? {
                     return Ok(Some(snapshot));
                 }
@@ -550,7 +566,7 @@ fn read_cgroup_v1_memory(dir: &Path, relative: &str) -> Result<Option<CgroupMemo
     }))
 }
 
-fn read_psi_resource(path: &str) -> Option<PsiResource> {
+fn read_psi_resource(path: &Path) -> Option<PsiResource> {
     let content = fs::read_to_string(path).ok()?;
     let mut resource = PsiResource {
         some: None,
@@ -767,22 +783,21 @@ impl ProcSnapshot {
 
         if let (Some(used), Some(total)) =
             (self.memory.host.used_bytes, self.memory.host.total_bytes)
+            && total > 0
         {
-            if total > 0 {
-                let ratio = used as f64 / total as f64;
-                let available = self
-                    .memory
-                    .host
-                    .available_bytes
-                    .unwrap_or(total.saturating_sub(used));
-                let available_gib = bytes_to_gib(available);
-                return format!(
-                    "{}, Mem used {:.1}% ({:.1} GiB free)",
-                    load,
-                    ratio * 100.0,
-                    available_gib
-                );
-            }
+            let ratio = used as f64 / total as f64;
+            let available = self
+                .memory
+                .host
+                .available_bytes
+                .unwrap_or(total.saturating_sub(used));
+            let available_gib = bytes_to_gib(available);
+            return format!(
+                "{}, Mem used {:.1}% ({:.1} GiB free)",
+                load,
+                ratio * 100.0,
+                available_gib
+            );
         }
 
         load
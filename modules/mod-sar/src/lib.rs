@@ -2,39 +2,62 @@ use anyhow::{Context as _, Result};
 use serde::Serialize;
 use serde_json::json;
 use std::process::Command;
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    Availability, CollectionContext, Collector, CollectorError, CollectorMetadata, CostHint,
+    CostLevel, ErrorKind, Section, collector_version, find_command, register_collector,
+};
 
 struct SarCollector;
 
 impl Collector for SarCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "sar",
-            title: "Sysstat Metrics",
-            description: "CPU averages from sar",
-        }
+        metadata()
     }
 
     fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
         match gather_snapshot() {
             Ok(snapshot) => Ok(section_from_snapshot(&snapshot)),
-            Err(error) => Ok(Section::degraded(
+            Err(error) => Ok(Section::degraded_from_error(
                 "sar",
                 "Sysstat Metrics",
-                error.to_string(),
+                &error,
                 json!({
                     "cpu": serde_json::Value::Null,
                 }),
             )),
         }
     }
+
+    fn probe(&self, _ctx: &CollectionContext) -> Availability {
+        match find_command("sar") {
+            Some(_) => Availability::Ready,
+            None => Availability::Unavailable("sar not found in PATH; install sysstat".to_string()),
+        }
+    }
+}
+
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "sar",
+        title: "Sysstat Metrics",
+        description: "CPU averages from sar",
+        tags: &["performance", "spawns-subprocess"],
+        requires_root: false,
+        order: 120,
+        version: collector_version!(),
+        cost: CostHint {
+            cpu: CostLevel::Low,
+            io: CostLevel::Medium,
+            spawns_processes: true,
+        },
+    }
 }
 
 fn create_collector() -> Box<dyn Collector> {
     Box::new(SarCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
 struct CpuAverages {
@@ -62,21 +85,32 @@ impl SarSnapshot {
 
 fn gather_snapshot() -> Result<SarSnapshot> {
     let output = run_sar_command()?;
-    let averages = parse_sar_cpu(&output).context("failed to parse sar output")?;
+    snapshot_from_output(&output)
+}
+
+fn snapshot_from_output(output: &str) -> Result<SarSnapshot> {
+    let averages = parse_sar_cpu(output)
+        .ok_or_else(|| CollectorError::new(ErrorKind::ParseError, "failed to parse sar output"))?;
     Ok(SarSnapshot { cpu: averages })
 }
 
 fn run_sar_command() -> Result<String> {
+    log::debug!("running sar -u 1 1");
     let output = Command::new("sar")
         .args(["-u", "1", "1"])
         .output()
-        .with_context(|| "failed to execute sar -u 1 1")?;
+        .map_err(|err| CollectorError::spawn("sar -u 1 1", err))?;
+    log::debug!("sar returned {}", output.status);
 
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("sar command failed: {}", stderr.trim())
+        Err(CollectorError::new(
+            ErrorKind::from_stderr(&stderr),
+            format!("sar command failed: {}", stderr.trim()),
+        )
+        .into())
     }
 }
 
@@ -140,6 +174,12 @@ mod tests {
         assert_eq!(averages.idle, 97.6);
     }
 
+    #[test]
+    fn unparsable_output_is_a_parse_error() {
+        let error = snapshot_from_output("garbage").unwrap_err();
+        assert_eq!(ErrorKind::of(&error), ErrorKind::ParseError);
+    }
+
     #[test]
     fn snapshot_summary_formats_values() {
         let snapshot = SarSnapshot {
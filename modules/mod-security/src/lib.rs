@@ -3,23 +3,25 @@ use serde::Serialize;
 use serde_json::json;
 use std::fs;
 use std::path::Path;
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    Availability, CollectionContext, Collector, CollectorMetadata, CostHint,
+    FindingSeverity as Severity, Section, collector_version, register_collector,
+};
+
+const SSHD_CONFIG: &str = "/etc/ssh/sshd_config";
+const SUDOERS: &str = "/etc/sudoers";
 
 struct SecurityCollector;
 
 impl Collector for SecurityCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "security",
-            title: "Security Posture",
-            description: "Key host hardening checks",
-        }
+        metadata()
     }
 
-    fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
+    fn collect(&self, ctx: &CollectionContext) -> Result<Section> {
         let mut notes = Vec::new();
 
-        let sshd = match analyze_sshd_config(Path::new("/etc/ssh/sshd_config")) {
+        let sshd = match analyze_sshd_config(&ctx.path(SSHD_CONFIG)) {
             Ok(analysis) => analysis,
             Err(error) => {
                 notes.push(format!("sshd_config check failed: {error}"));
@@ -27,7 +29,7 @@ impl Collector for SecurityCollector {
             }
         };
 
-        let sudoers = match analyze_sudoers(Path::new("/etc/sudoers")) {
+        let sudoers = match analyze_sudoers(&ctx.path(SUDOERS)) {
             Ok(analysis) => analysis,
             Err(error) => {
                 notes.push(format!("sudoers check failed: {error}"));
@@ -35,9 +37,15 @@ impl Collector for SecurityCollector {
             }
         };
 
-        let cgroups = analyze_cgroups();
+        let cgroups = analyze_cgroups(ctx);
 
-        let findings = sshd.findings.len() + sudoers.findings.len() + cgroups.findings.len();
+        let findings: Vec<Finding> = sshd
+            .findings
+            .iter()
+            .chain(&sudoers.findings)
+            .chain(&cgroups.findings)
+            .cloned()
+            .collect();
 
         let body = json!({
             "sshd": sshd,
@@ -45,7 +53,7 @@ impl Collector for SecurityCollector {
             "cgroups": cgroups,
         });
 
-        let mut section = if findings == 0 {
+        let mut section = if findings.is_empty() {
             let mut section = Section::success("security", "Security Posture", body);
             section.summary = Some("No high-risk findings detected".to_string());
             section
@@ -53,37 +61,63 @@ impl Collector for SecurityCollector {
             Section::degraded(
                 "security",
                 "Security Posture",
-                format!("{} potential security issues", findings),
+                format!("{} potential security issues", findings.len()),
                 body,
             )
         };
 
         section.notes = notes;
+        for finding in findings {
+            section.add_finding(finding.severity, finding.code, finding.message);
+        }
         Ok(section)
     }
+
+    fn probe(&self, ctx: &CollectionContext) -> Availability {
+        let unreadable: Vec<String> = [SSHD_CONFIG, SUDOERS]
+            .into_iter()
+            .filter_map(|path| {
+                fs::File::open(ctx.path(path))
+                    .err()
+                    .map(|err| format!("cannot read {}: {}", path, err))
+            })
+            .collect();
+        if unreadable.is_empty() {
+            Availability::Ready
+        } else {
+            Availability::Degraded(unreadable.join("; "))
+        }
+    }
+}
+
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "security",
+        title: "Security Posture",
+        description: "Key host hardening checks",
+        tags: &["security"],
+        requires_root: true,
+        order: 110,
+        version: collector_version!(),
+        cost: CostHint::LOW,
+    }
 }
 
 fn create_collector() -> Box<dyn Collector> {
     Box::new(SecurityCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
-#[derive(Debug, Default, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 struct Finding {
+    /// Check identifier for the section finding; the body keeps its old shape.
+    #[serde(skip)]
+    code: &'static str,
     message: String,
     severity: Severity,
 }
 
-#[derive(Debug, Default, Clone, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-enum Severity {
-    #[default]
-    Info,
-    Warning,
-    Critical,
-}
-
 #[derive(Debug, Default, Clone, Serialize, PartialEq, Eq)]
 struct SshdConfigAnalysis {
     hardening_present: bool,
@@ -139,6 +173,7 @@ fn analyze_sshd_config_from_str(contents: &str) -> SshdConfigAnalysis {
 
     if password_auth.as_deref() == Some("yes") {
         analysis.findings.push(Finding {
+            code: "sshd.password_authentication",
             message: "PasswordAuthentication is enabled".to_string(),
             severity: Severity::Warning,
         });
@@ -150,6 +185,7 @@ fn analyze_sshd_config_from_str(contents: &str) -> SshdConfigAnalysis {
         .unwrap_or(false)
     {
         analysis.findings.push(Finding {
+            code: "sshd.permit_root_login",
             message: "PermitRootLogin allows direct root access".to_string(),
             severity: Severity::Critical,
         });
@@ -157,6 +193,7 @@ fn analyze_sshd_config_from_str(contents: &str) -> SshdConfigAnalysis {
 
     if challenge_response.as_deref() == Some("yes") {
         analysis.findings.push(Finding {
+            code: "sshd.challenge_response",
             message: "ChallengeResponseAuthentication is enabled".to_string(),
             severity: Severity::Warning,
         });
@@ -168,6 +205,7 @@ fn analyze_sshd_config_from_str(contents: &str) -> SshdConfigAnalysis {
         .unwrap_or(false)
     {
         analysis.findings.push(Finding {
+            code: "sshd.protocol_v1",
             message: "SSH protocol version 1 is allowed".to_string(),
             severity: Severity::Critical,
         });
@@ -195,6 +233,7 @@ fn analyze_sudoers_from_str(contents: &str) -> SudoersAnalysis {
 
         if line.contains("NOPASSWD:") && line.contains("ALL") {
             analysis.findings.push(Finding {
+                code: "sudoers.nopasswd",
                 message: format!("Potential password-less sudo entry: {}", line),
                 severity: Severity::Warning,
             });
@@ -202,6 +241,7 @@ fn analyze_sudoers_from_str(contents: &str) -> SudoersAnalysis {
 
         if line.contains("ALL=(ALL) ALL") && line.split_whitespace().next() == Some("ALL") {
             analysis.findings.push(Finding {
+                code: "sudoers.wildcard",
                 message: "Wildcard sudo entry grants full access".to_string(),
                 severity: Severity::Critical,
             });
@@ -211,8 +251,8 @@ fn analyze_sudoers_from_str(contents: &str) -> SudoersAnalysis {
     analysis
 }
 
-fn analyze_cgroups() -> CgroupAnalysis {
-    let unified_path = Path::new("/sys/fs/cgroup");
+fn analyze_cgroups(ctx: &CollectionContext) -> CgroupAnalysis {
+    let unified_path = ctx.path("/sys/fs/cgroup");
     let controllers_path = unified_path.join("cgroup.controllers");
     let mut analysis = CgroupAnalysis {
         unified_hierarchy: controllers_path.exists(),
@@ -226,6 +266,7 @@ fn analyze_cgroups() -> CgroupAnalysis {
         }
     } else {
         analysis.findings.push(Finding {
+            code: "cgroups.legacy_hierarchy",
             message: "Host is not running with cgroup v2 unified hierarchy".to_string(),
             severity: Severity::Warning,
         });
@@ -250,6 +291,21 @@ Protocol 2,1
 
         let analysis = analyze_sshd_config_from_str(config);
         assert_eq!(analysis.findings.len(), 4);
+        let codes: Vec<&str> = analysis.findings.iter().map(|f| f.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "sshd.password_authentication",
+                "sshd.permit_root_login",
+                "sshd.challenge_response",
+                "sshd.protocol_v1"
+            ]
+        );
+        let body = serde_json::to_value(&analysis.findings[0]).unwrap();
+        assert_eq!(
+            body,
+            json!({ "message": "PasswordAuthentication is enabled", "severity": "warning" })
+        );
         assert!(
             analysis
                 .findings
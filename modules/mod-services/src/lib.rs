@@ -1,27 +1,26 @@
-use anyhow::{Context as _, Result};
+use anyhow::Result;
 use serde::Serialize;
 use serde_json::json;
 use std::process::Command;
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    Availability, CollectionContext, Collector, CollectorError, CollectorMetadata, CostHint,
+    CostLevel, ErrorKind, Section, Table, collector_version, find_command, register_collector,
+};
 
 struct ServicesCollector;
 
 impl Collector for ServicesCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "services",
-            title: "System Services",
-            description: "systemd services status",
-        }
+        metadata()
     }
 
     fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
         match gather_snapshot() {
             Ok(snapshot) => Ok(section_from_snapshot(&snapshot)),
-            Err(error) => Ok(Section::degraded(
+            Err(error) => Ok(Section::degraded_from_error(
                 "services",
                 "System Services",
-                error.to_string(),
+                &error,
                 json!({
                     "running": Vec::<serde_json::Value>::new(),
                     "failed": Vec::<serde_json::Value>::new(),
@@ -29,13 +28,37 @@ impl Collector for ServicesCollector {
             )),
         }
     }
+
+    fn probe(&self, _ctx: &CollectionContext) -> Availability {
+        match find_command("systemctl") {
+            Some(_) => Availability::Ready,
+            None => Availability::Unavailable("systemctl not found in PATH".to_string()),
+        }
+    }
+}
+
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "services",
+        title: "System Services",
+        description: "systemd services status",
+        tags: &["system", "spawns-subprocess"],
+        requires_root: false,
+        order: 40,
+        version: collector_version!(),
+        cost: CostHint {
+            cpu: CostLevel::Low,
+            io: CostLevel::Low,
+            spawns_processes: true,
+        },
+    }
 }
 
 fn create_collector() -> Box<dyn Collector> {
     Box::new(ServicesCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 struct ServiceInfo {
@@ -85,16 +108,34 @@ fn gather_snapshot() -> Result<ServicesSnapshot> {
 }
 
 fn run_systemctl(args: &[&str]) -> Result<String> {
+    log::debug!("running systemctl {}", args.join(" "));
     let output = Command::new("systemctl")
         .args(args)
         .output()
-        .with_context(|| format!("failed to execute systemctl {}", args.join(" ")))?;
+        .map_err(|err| CollectorError::spawn(&format!("systemctl {}", args.join(" ")), err))?;
+    log::debug!("systemctl {} returned {}", args.join(" "), output.status);
 
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("systemctl {}: {}", args.join(" "), stderr.trim())
+        Err(CollectorError::new(
+            systemctl_error_kind(&stderr),
+            format!("systemctl {}: {}", args.join(" "), stderr.trim()),
+        )
+        .into())
+    }
+}
+
+/// Hosts and containers without systemd as init have `systemctl` installed but
+/// nothing to talk to; that counts as systemd not being there.
+fn systemctl_error_kind(stderr: &str) -> ErrorKind {
+    if stderr.contains("not been booted with systemd")
+        || stderr.contains("Failed to connect to bus")
+    {
+        ErrorKind::NotInstalled
+    } else {
+        ErrorKind::from_stderr(stderr)
     }
 }
 
@@ -145,12 +186,44 @@ fn section_from_snapshot(snapshot: &ServicesSnapshot) -> Section {
     });
     let mut section = Section::success("services", "System Services", body);
     section.summary = Some(snapshot.summary());
+    section.add_table(
+        service_table(&snapshot.running)
+            .titled("Running Services")
+            .with_total(),
+    );
+    section.add_table(service_table(&snapshot.failed).titled("Failed Services"));
     section
 }
 
+fn service_table(services: &[ServiceInfo]) -> Table {
+    let mut table = Table::new(["Unit", "Description", "State"]);
+    for service in services {
+        table.push_row([
+            service.unit.clone(),
+            service.description.clone(),
+            format!("{} / {}", service.active, service.sub),
+        ]);
+    }
+    table
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vmic_sdk::testing::assert_section_matches_schema;
+
+    #[test]
+    fn systemctl_errors_are_classified() {
+        assert_eq!(
+            systemctl_error_kind("System has not been booted with systemd as init system (PID 1)."),
+            ErrorKind::NotInstalled
+        );
+        assert_eq!(
+            systemctl_error_kind("Failed to list units: Access denied"),
+            ErrorKind::PermissionDenied
+        );
+        assert_eq!(systemctl_error_kind("Unknown operation"), ErrorKind::Other);
+    }
 
     #[test]
     fn parse_systemctl_line_extracts_description() {
@@ -172,6 +245,25 @@ mod tests {
         assert_eq!(info.sub, "failed");
     }
 
+    #[test]
+    fn section_body_matches_the_published_schema() {
+        let snapshot = ServicesSnapshot {
+            running: parse_systemctl_units(
+                "cron.service loaded active running Regular background program processing daemon\n\
+                 ssh.service loaded active running OpenBSD Secure Shell server\n",
+            ),
+            failed: parse_systemctl_units(
+                "● fail2ban.service loaded failed failed Fail2Ban Service\n",
+            ),
+        };
+
+        let schema: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../vmic-core/src/section_schemas/services.json"
+        ))
+        .unwrap();
+        assert_section_matches_schema(&section_from_snapshot(&snapshot), &schema);
+    }
+
     #[test]
     fn snapshot_summary_counts_services() {
         let snapshot = ServicesSnapshot {
@@ -192,5 +284,14 @@ mod tests {
         };
 
         assert_eq!(snapshot.summary(), "1 running, 1 failed services");
+
+        let section = section_from_snapshot(&snapshot);
+        assert_eq!(section.body["failed"][0]["unit"], "failed.service");
+        let vmic_sdk::SectionPart::Table(running) = &section.parts[0] else {
+            panic!("expected a table part");
+        };
+        assert_eq!(running.title.as_deref(), Some("Running Services"));
+        assert!(running.show_total);
+        assert_eq!(running.rows, [["cron.service", "Cron", "active / running"]]);
     }
 }
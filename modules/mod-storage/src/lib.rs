@@ -1,70 +1,145 @@
 use std::fs;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context as _, Result};
 use rustix::fs::{StatVfs, statvfs};
 use serde::Serialize;
 use serde_json::json;
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    CancellationToken, CollectionContext, Collector, CollectorMetadata, CostHint, CostLevel,
+    DetailLevel, Section, SubSection, collector_version, register_collector,
+};
 use walkdir::WalkDir;
 
+/// Directory levels scanned below each mount for hotspots unless
+/// `--set storage.hotspot_depth=N` says otherwise.
+const DEFAULT_HOTSPOT_DEPTH: usize = 3;
+
+/// Scan depth at [`DetailLevel::Full`] when no setting overrides it.
+const FULL_HOTSPOT_DEPTH: usize = 5;
+
+/// How far the hotspot scan reaches and how many entries it keeps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HotspotScan {
+    depth: usize,
+    directories: usize,
+    logs: usize,
+}
+
 struct StorageCollector;
 
 impl Collector for StorageCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "storage",
-            title: "Storage Overview",
-            description: "Filesystem usage across mounted volumes",
-        }
+        metadata()
     }
 
-    fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
-        match build_snapshot() {
-            Ok((snapshot, notes)) => {
-                let (worst_path, worst_ratio) = snapshot
-                    .operating
-                    .iter()
-                    .filter(|mount| !mount.read_only)
-                    .map(|mount| (mount.mount_point.as_str(), mount.usage_ratio))
-                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
-                    .unwrap_or(("", 0.0));
-
-                let summary = if worst_path.is_empty() {
-                    format!(
-                        "{} operating mounts, {:.1}% average usage",
-                        snapshot.operating.len(),
-                        snapshot.average_usage() * 100.0
-                    )
-                } else {
-                    format!(
-                        "{} operating mounts, worst {:.1}% at {}",
-                        snapshot.operating.len(),
-                        worst_ratio * 100.0,
-                        worst_path
-                    )
-                };
-
-                let body = json!({
-                    "operating_mounts": snapshot.operating,
-                    "pseudo_mounts": snapshot.pseudo,
-                    "totals": snapshot.aggregate,
-                    "docker": snapshot.docker,
-                    "hotspots": snapshot.hotspots,
-                });
-
-                let mut section = Section::success("storage", "Storage Overview", body);
-                section.summary = Some(summary);
-                section.notes = notes;
-                Ok(section)
-            }
-            Err(error) => Ok(Section::degraded(
+    fn collect(&self, ctx: &CollectionContext) -> Result<Section> {
+        let (scan, scan_note) = hotspot_scan(ctx);
+        let mut section = match build_snapshot(scan, ctx.cancellation()) {
+            Ok((snapshot, notes)) => snapshot_section(&snapshot, notes),
+            Err(error) => Section::degraded(
                 "storage",
                 "Storage Overview",
                 error.to_string(),
                 json!({ "mounts": [], "totals": {} }),
-            )),
+            ),
+        };
+        section.notes.extend(scan_note);
+        Ok(section)
+    }
+}
+
+/// Storage section for a snapshot: mounts and totals in the body, the Docker
+/// breakdown and the hotspots as subsections.
+fn snapshot_section(snapshot: &StorageSnapshot, notes: Vec<String>) -> Section {
+    let (worst_path, worst_ratio) = snapshot
+        .operating
+        .iter()
+        .filter(|mount| !mount.read_only)
+        .map(|mount| (mount.mount_point.as_str(), mount.usage_ratio))
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .unwrap_or(("", 0.0));
+
+    let summary = if worst_path.is_empty() {
+        format!(
+            "{} operating mounts, {:.1}% average usage",
+            snapshot.operating.len(),
+            snapshot.average_usage() * 100.0
+        )
+    } else {
+        format!(
+            "{} operating mounts, worst {:.1}% at {}",
+            snapshot.operating.len(),
+            worst_ratio * 100.0,
+            worst_path
+        )
+    };
+
+    let body = json!({
+        "operating_mounts": snapshot.operating,
+        "pseudo_mounts": snapshot.pseudo,
+        "totals": snapshot.aggregate,
+    });
+
+    let mut section = Section::success("storage", "Storage Overview", body);
+    section.summary = Some(summary);
+    section.notes = notes;
+    if let Some(docker) = &snapshot.docker {
+        section.add_subsection(SubSection::new("docker", "Docker storage", json!(docker)));
+    }
+    if let Some(hotspots) = &snapshot.hotspots {
+        section.add_subsection(SubSection::new("hotspots", "Hotspots", json!(hotspots)));
+    }
+    section
+}
+
+/// Hotspot scan for the context's detail level: none at minimal, deeper and
+/// longer lists at full. An explicit `storage.hotspot_depth` wins over the
+/// level's depth.
+fn hotspot_scan(ctx: &CollectionContext) -> (Option<HotspotScan>, Option<String>) {
+    let (default_depth, entries) = match ctx.detail_level() {
+        DetailLevel::Minimal => {
+            return (
+                None,
+                Some("Hotspot scan skipped at minimal detail level".to_string()),
+            );
         }
+        DetailLevel::Standard => (DEFAULT_HOTSPOT_DEPTH, 5),
+        DetailLevel::Full => (FULL_HOTSPOT_DEPTH, 10),
+    };
+    let (depth, note) = hotspot_depth(ctx, default_depth);
+    let scan = HotspotScan {
+        depth,
+        directories: entries,
+        logs: entries,
+    };
+    (Some(scan), note)
+}
+
+/// Scan depth from `storage.hotspot_depth`, falling back to `default` with a
+/// note when the setting is not a positive integer.
+fn hotspot_depth(ctx: &CollectionContext, default: usize) -> (usize, Option<String>) {
+    match ctx.setting_as::<NonZeroUsize>("storage", "hotspot_depth") {
+        Ok(depth) => (depth.map_or(default, NonZeroUsize::get), None),
+        Err(err) => (default, Some(format!("{:#}; using {}", err, default))),
+    }
+}
+
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "storage",
+        title: "Storage Overview",
+        description: "Filesystem usage across mounted volumes",
+        tags: &["storage"],
+        requires_root: false,
+        order: 30,
+        version: collector_version!(),
+        cost: CostHint {
+            cpu: CostLevel::Medium,
+            io: CostLevel::High,
+            spawns_processes: false,
+        },
     }
 }
 
@@ -72,7 +147,7 @@ fn create_collector() -> Box<dyn Collector> {
     Box::new(StorageCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -121,7 +196,7 @@ struct StorageSnapshot {
     pseudo: Vec<MountUsage>,
     aggregate: AggregateUsage,
     docker: Option<DockerStorageBreakdown>,
-    hotspots: HotspotSummary,
+    hotspots: Option<HotspotSummary>,
 }
 
 impl StorageSnapshot {
@@ -152,7 +227,10 @@ struct LogHotspot {
     size_bytes: u64,
 }
 
-fn build_snapshot() -> Result<(StorageSnapshot, Vec<String>)> {
+fn build_snapshot(
+    hotspot_scan: Option<HotspotScan>,
+    cancel: &CancellationToken,
+) -> Result<(StorageSnapshot, Vec<String>)> {
     let mounts = parse_proc_mounts(fs::read_to_string("/proc/mounts")?)
         .context("failed to parse /proc/mounts")?;
 
@@ -204,7 +282,7 @@ fn build_snapshot() -> Result<(StorageSnapshot, Vec<String>)> {
 
     let aggregate = aggregate_usage(&operating);
 
-    let docker_usage = match docker_storage_breakdown() {
+    let docker_usage = match docker_storage_breakdown(cancel) {
         Some(Ok(usage)) => Some(usage),
         Some(Err(error)) => {
             notes.push(format!("Failed to summarize Docker storage: {error}"));
@@ -213,8 +291,11 @@ fn build_snapshot() -> Result<(StorageSnapshot, Vec<String>)> {
         None => None,
     };
 
-    let (hotspots, mut hotspot_notes) = collect_hotspots(&operating);
-    notes.append(&mut hotspot_notes);
+    let hotspots = hotspot_scan.map(|scan| {
+        let (hotspots, mut hotspot_notes) = collect_hotspots(&operating, scan, cancel);
+        notes.append(&mut hotspot_notes);
+        hotspots
+    });
 
     Ok((
         StorageSnapshot {
@@ -361,13 +442,12 @@ fn decode_mount_field(raw: &str) -> String {
                 }
             }
 
-            if octal.len() == 3 {
-                if let Ok(value) = u32::from_str_radix(&octal, 8) {
-                    if let Some(ch) = char::from_u32(value) {
-                        result.push(ch);
-                        continue;
-                    }
-                }
+            if octal.len() == 3
+                && let Ok(value) = u32::from_str_radix(&octal, 8)
+                && let Some(ch) = char::from_u32(value)
+            {
+                result.push(ch);
+                continue;
             }
 
             result.push('\\');
@@ -398,7 +478,7 @@ fn is_operational_mount(mount_point: &str) -> bool {
     })
 }
 
-fn docker_storage_breakdown() -> Option<Result<DockerStorageBreakdown>> {
+fn docker_storage_breakdown(cancel: &CancellationToken) -> Option<Result<DockerStorageBreakdown>> {
     const DOCKER_ROOT: &str = "/var/lib/docker";
     let root = Path::new(DOCKER_ROOT);
     if !root.exists() {
@@ -406,7 +486,7 @@ fn docker_storage_breakdown() -> Option<Result<DockerStorageBreakdown>> {
     }
 
     Some(
-        calculate_docker_storage(root).map(|(overlay, logs, volumes, total)| {
+        calculate_docker_storage(root, cancel).map(|(overlay, logs, volumes, total)| {
             DockerStorageBreakdown {
                 data_root: root.to_path_buf(),
                 total_bytes: total,
@@ -418,20 +498,23 @@ fn docker_storage_breakdown() -> Option<Result<DockerStorageBreakdown>> {
     )
 }
 
-fn calculate_docker_storage(root: &Path) -> Result<(u64, u64, u64, u64)> {
+fn calculate_docker_storage(
+    root: &Path,
+    cancel: &CancellationToken,
+) -> Result<(u64, u64, u64, u64)> {
     let overlay_path = root.join("overlay2");
     let containers_path = root.join("containers");
     let volumes_path = root.join("volumes");
 
-    let overlay_bytes = directory_size(&overlay_path, None)?;
+    let overlay_bytes = directory_size(&overlay_path, None, cancel)?;
     let logs_bytes = containers_path
         .exists()
         .then(|| collect_container_logs_size(&containers_path))
         .transpose()?
         .unwrap_or(0);
-    let volumes_bytes = directory_size(&volumes_path, None)?;
+    let volumes_bytes = directory_size(&volumes_path, None, cancel)?;
 
-    let total_bytes = directory_size(root, None)?;
+    let total_bytes = directory_size(root, None, cancel)?;
 
     Ok((overlay_bytes, logs_bytes, volumes_bytes, total_bytes))
 }
@@ -456,7 +539,12 @@ fn collect_container_logs_size(path: &Path) -> Result<u64> {
     Ok(total)
 }
 
-fn directory_size(path: &Path, max_depth: Option<usize>) -> Result<u64> {
+/// Bytes in regular files below `path`; fails as soon as `cancel` is tripped.
+fn directory_size(
+    path: &Path,
+    max_depth: Option<usize>,
+    cancel: &CancellationToken,
+) -> Result<u64> {
     if !path.exists() {
         return Ok(0);
     }
@@ -465,15 +553,16 @@ fn directory_size(path: &Path, max_depth: Option<usize>) -> Result<u64> {
     let mut walker = WalkDir::new(path).follow_links(false).into_iter();
 
     while let Some(entry) = walker.next() {
+        cancel.check()?;
         match entry {
             Ok(entry) => {
-                if let Some(depth) = max_depth {
-                    if entry.depth() > depth {
-                        if entry.file_type().is_dir() {
-                            walker.skip_current_dir();
-                        }
-                        continue;
+                if let Some(depth) = max_depth
+                    && entry.depth() > depth
+                {
+                    if entry.file_type().is_dir() {
+                        walker.skip_current_dir();
                     }
+                    continue;
                 }
 
                 if entry.file_type().is_file() {
@@ -490,12 +579,13 @@ fn directory_size(path: &Path, max_depth: Option<usize>) -> Result<u64> {
     Ok(total)
 }
 
-fn collect_hotspots(operating: &[MountUsage]) -> (HotspotSummary, Vec<String>) {
-    const DIRECTORY_SCAN_DEPTH: usize = 3;
+fn collect_hotspots(
+    operating: &[MountUsage],
+    scan: HotspotScan,
+    cancel: &CancellationToken,
+) -> (HotspotSummary, Vec<String>) {
     const DIRECTORY_SAMPLE_PER_MOUNT: usize = 20;
-    const DIRECTORY_LIMIT: usize = 5;
     const LOG_SCAN_DEPTH: usize = 2;
-    const LOG_LIMIT: usize = 5;
 
     let mut notes = Vec::new();
     let mut directory_candidates = Vec::new();
@@ -505,8 +595,9 @@ fn collect_hotspots(operating: &[MountUsage]) -> (HotspotSummary, Vec<String>) {
         .filter(|mount| mount.operational && !mount.read_only)
     {
         let path = Path::new(&mount.mount_point);
-        match collect_directory_hotspots(path, DIRECTORY_SCAN_DEPTH, DIRECTORY_SAMPLE_PER_MOUNT) {
+        match collect_directory_hotspots(path, scan.depth, DIRECTORY_SAMPLE_PER_MOUNT, cancel) {
             Ok(mut hotspots) => directory_candidates.append(&mut hotspots),
+            Err(_) if cancel.is_cancelled() => break,
             Err(error) => notes.push(format!(
                 "Failed to inspect {}: {}",
                 mount.mount_point, error
@@ -514,13 +605,18 @@ fn collect_hotspots(operating: &[MountUsage]) -> (HotspotSummary, Vec<String>) {
         }
     }
 
-    directory_candidates.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
-    directory_candidates.truncate(DIRECTORY_LIMIT);
-
-    let (log_hotspots, mut log_notes) = collect_log_hotspots(Path::new("/var/log"), LOG_SCAN_DEPTH);
-    notes.append(&mut log_notes);
+    directory_candidates.sort_by_key(|entry| std::cmp::Reverse(entry.size_bytes));
+    directory_candidates.truncate(scan.directories);
 
-    let logs = log_hotspots.into_iter().take(LOG_LIMIT).collect();
+    let logs = if cancel.is_cancelled() {
+        notes.push("Hotspot scan cancelled; results are partial".to_string());
+        Vec::new()
+    } else {
+        let (log_hotspots, mut log_notes) =
+            collect_log_hotspots(Path::new("/var/log"), LOG_SCAN_DEPTH);
+        notes.append(&mut log_notes);
+        log_hotspots.into_iter().take(scan.logs).collect()
+    };
 
     (
         HotspotSummary {
@@ -535,6 +631,7 @@ fn collect_directory_hotspots(
     root: &Path,
     max_depth: usize,
     limit: usize,
+    cancel: &CancellationToken,
 ) -> Result<Vec<DirectoryHotspot>> {
     if !root.is_dir() {
         return Ok(Vec::new());
@@ -553,7 +650,7 @@ fn collect_directory_hotspots(
             continue;
         }
 
-        let size = directory_size(&entry.path(), Some(max_depth))?;
+        let size = directory_size(&entry.path(), Some(max_depth), cancel)?;
         hotspots.push(DirectoryHotspot {
             path: entry.path().display().to_string(),
             size_bytes: size,
@@ -561,7 +658,7 @@ fn collect_directory_hotspots(
         processed += 1;
     }
 
-    hotspots.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+    hotspots.sort_by_key(|entry| std::cmp::Reverse(entry.size_bytes));
     Ok(hotspots)
 }
 
@@ -607,7 +704,7 @@ fn collect_log_hotspots(root: &Path, max_depth: usize) -> (Vec<LogHotspot>, Vec<
         }
     }
 
-    files.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+    files.sort_by_key(|entry| std::cmp::Reverse(entry.size_bytes));
     (files, notes)
 }
 
@@ -642,6 +739,7 @@ mod tests {
     use super::*;
     use std::fs;
     use tempfile::tempdir;
+    use vmic_sdk::testing::assert_section_matches_schema;
 
     #[test]
     fn decode_mount_field_unescapes_space() {
@@ -698,6 +796,149 @@ mod tests {
         assert_eq!(aggregate.available_bytes, 100);
     }
 
+    #[test]
+    fn section_body_matches_the_published_schema() {
+        let root = MountUsage {
+            mount_point: "/".into(),
+            source: "/dev/sda1".into(),
+            fs_type: "ext4".into(),
+            read_only: false,
+            category: MountCategory::Operating,
+            operational: true,
+            total_bytes: 100,
+            used_bytes: 40,
+            available_bytes: 60,
+            usage_ratio: 0.4,
+            inodes_total: Some(1000),
+            inodes_used: Some(400),
+            inodes_available: Some(600),
+            inodes_usage_ratio: Some(0.4),
+        };
+        let run = MountUsage {
+            mount_point: "/run".into(),
+            source: "tmpfs".into(),
+            fs_type: "tmpfs".into(),
+            category: MountCategory::Pseudo,
+            operational: false,
+            inodes_total: None,
+            inodes_used: None,
+            inodes_available: None,
+            inodes_usage_ratio: None,
+            ..root.clone()
+        };
+        let snapshot = StorageSnapshot {
+            aggregate: aggregate_usage(std::slice::from_ref(&root)),
+            operating: vec![root],
+            pseudo: vec![run],
+            docker: None,
+            hotspots: Some(HotspotSummary::default()),
+        };
+
+        let schema: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../vmic-core/src/section_schemas/storage.json"
+        ))
+        .unwrap();
+        assert_section_matches_schema(&snapshot_section(&snapshot, Vec::new()), &schema);
+    }
+
+    #[test]
+    fn docker_breakdown_and_hotspots_become_subsections() {
+        let snapshot = StorageSnapshot {
+            operating: Vec::new(),
+            pseudo: Vec::new(),
+            aggregate: aggregate_usage(&[]),
+            docker: Some(DockerStorageBreakdown {
+                data_root: PathBuf::from("/var/lib/docker"),
+                total_bytes: 30,
+                overlay_bytes: 10,
+                container_logs_bytes: 10,
+                volumes_bytes: 10,
+            }),
+            hotspots: Some(HotspotSummary {
+                directories: vec![DirectoryHotspot {
+                    path: "/srv".into(),
+                    size_bytes: 4096,
+                }],
+                logs: Vec::new(),
+            }),
+        };
+
+        let section = snapshot_section(&snapshot, Vec::new());
+        assert!(section.body.get("docker").is_none());
+        assert!(section.body.get("hotspots").is_none());
+        let ids: Vec<&str> = section
+            .subsections
+            .iter()
+            .map(|subsection| subsection.id.as_str())
+            .collect();
+        assert_eq!(ids, ["docker", "hotspots"]);
+        assert_eq!(section.subsections[0].body["total_bytes"], 30);
+        assert_eq!(
+            section.subsections[1].body["directories"][0]["path"],
+            "/srv"
+        );
+
+        let without_docker = StorageSnapshot {
+            docker: None,
+            ..snapshot.clone()
+        };
+        let section = snapshot_section(&without_docker, Vec::new());
+        assert!(section.subsection("docker").is_none());
+        assert!(section.subsection("hotspots").is_some());
+
+        let without_hotspots = StorageSnapshot {
+            hotspots: None,
+            ..snapshot
+        };
+        let section = snapshot_section(&without_hotspots, Vec::new());
+        assert!(section.subsection("hotspots").is_none());
+    }
+
+    #[test]
+    fn hotspot_scan_follows_the_detail_level() {
+        let mut ctx = CollectionContext::new();
+        let standard = HotspotScan {
+            depth: DEFAULT_HOTSPOT_DEPTH,
+            directories: 5,
+            logs: 5,
+        };
+        assert_eq!(hotspot_scan(&ctx), (Some(standard), None));
+
+        ctx.set_detail_level(DetailLevel::Full);
+        let (full, _) = hotspot_scan(&ctx);
+        let full = full.unwrap();
+        assert_eq!(full.depth, FULL_HOTSPOT_DEPTH);
+        assert!(full.directories > standard.directories && full.logs > standard.logs);
+
+        ctx.set_setting("storage", "hotspot_depth", "2");
+        assert_eq!(hotspot_scan(&ctx).0.unwrap().depth, 2);
+
+        ctx.set_detail_level(DetailLevel::Minimal);
+        let (scan, note) = hotspot_scan(&ctx);
+        assert!(scan.is_none());
+        assert!(note.unwrap().contains("minimal detail level"));
+    }
+
+    #[test]
+    fn hotspot_depth_comes_from_the_storage_setting() {
+        let mut ctx = CollectionContext::new();
+        assert_eq!(
+            hotspot_depth(&ctx, DEFAULT_HOTSPOT_DEPTH),
+            (DEFAULT_HOTSPOT_DEPTH, None)
+        );
+
+        ctx.set_setting("storage", "hotspot_depth", "5");
+        assert_eq!(hotspot_depth(&ctx, DEFAULT_HOTSPOT_DEPTH), (5, None));
+
+        ctx.set_setting("storage", "hotspot_depth", "deep");
+        let (depth, note) = hotspot_depth(&ctx, DEFAULT_HOTSPOT_DEPTH);
+        assert_eq!(depth, DEFAULT_HOTSPOT_DEPTH);
+        assert!(
+            note.unwrap()
+                .starts_with("invalid value 'deep' for storage.hotspot_depth")
+        );
+    }
+
     #[test]
     fn collect_directory_hotspots_prioritizes_larger() {
         let temp = tempdir().expect("tempdir");
@@ -708,12 +949,56 @@ mod tests {
         fs::write(large_dir.join("big.log"), vec![0u8; 2048]).expect("write big");
         fs::write(small_dir.join("tiny.log"), vec![0u8; 16]).expect("write tiny");
 
-        let hotspots = collect_directory_hotspots(temp.path(), 1, 10).expect("hotspots");
+        let hotspots = collect_directory_hotspots(temp.path(), 1, 10, &CancellationToken::new())
+            .expect("hotspots");
         assert!(hotspots.len() >= 2);
         assert!(hotspots[0].path.ends_with("large"));
         assert!(hotspots[0].size_bytes >= hotspots[1].size_bytes);
     }
 
+    #[test]
+    fn cancelled_hotspot_scan_returns_promptly_with_a_note() {
+        let temp = tempdir().expect("tempdir");
+        for index in 0..50 {
+            let dir = temp.path().join(format!("dir{index}/nested"));
+            fs::create_dir_all(&dir).expect("create dir");
+            fs::write(dir.join("data.bin"), vec![0u8; 512]).expect("write data");
+        }
+        let mount = MountUsage {
+            mount_point: temp.path().display().to_string(),
+            source: "/dev/sda1".into(),
+            fs_type: "ext4".into(),
+            read_only: false,
+            category: MountCategory::Operating,
+            operational: true,
+            total_bytes: 100,
+            used_bytes: 40,
+            available_bytes: 60,
+            usage_ratio: 0.4,
+            inodes_total: None,
+            inodes_used: None,
+            inodes_available: None,
+            inodes_usage_ratio: None,
+        };
+        let scan = HotspotScan {
+            depth: 10,
+            directories: 5,
+            logs: 5,
+        };
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let started = std::time::Instant::now();
+        let (hotspots, notes) = collect_hotspots(&[mount], scan, &cancel);
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        assert!(hotspots.directories.is_empty());
+        assert!(hotspots.logs.is_empty());
+        assert_eq!(notes, ["Hotspot scan cancelled; results are partial"]);
+
+        let error = directory_size(temp.path(), None, &cancel).unwrap_err();
+        assert_eq!(error.to_string(), "collection cancelled");
+    }
+
     #[test]
     fn collect_log_hotspots_limits_results() {
         let temp = tempdir().expect("tempdir");
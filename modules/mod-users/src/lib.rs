@@ -4,21 +4,20 @@ use serde_json::json;
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
-use vmic_sdk::{CollectionContext, Collector, CollectorMetadata, Section, register_collector};
+use vmic_sdk::{
+    Availability, CollectionContext, Collector, CollectorMetadata, CostHint, Section,
+    collector_version, register_collector,
+};
 
 struct UsersCollector;
 
 impl Collector for UsersCollector {
     fn metadata(&self) -> CollectorMetadata {
-        CollectorMetadata {
-            id: "users",
-            title: "Local Users",
-            description: "Accounts defined in /etc/passwd",
-        }
+        metadata()
     }
 
-    fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
-        match build_snapshot() {
+    fn collect(&self, ctx: &CollectionContext) -> Result<Section> {
+        match build_snapshot(ctx) {
             Ok(snapshot) => Ok(section_from_snapshot(&snapshot)),
             Err(error) => Ok(Section::degraded(
                 "users",
@@ -30,13 +29,33 @@ impl Collector for UsersCollector {
             )),
         }
     }
+
+    fn probe(&self, ctx: &CollectionContext) -> Availability {
+        match read_passwd(&ctx.path("/etc/passwd")) {
+            Ok(_) => Availability::Ready,
+            Err(err) => Availability::Unavailable(format!("{:#}", err)),
+        }
+    }
+}
+
+fn metadata() -> CollectorMetadata {
+    CollectorMetadata {
+        id: "users",
+        title: "Local Users",
+        description: "Accounts defined in /etc/passwd",
+        tags: &["accounts", "security"],
+        requires_root: false,
+        order: 100,
+        version: collector_version!(),
+        cost: CostHint::LOW,
+    }
 }
 
 fn create_collector() -> Box<dyn Collector> {
     Box::new(UsersCollector)
 }
 
-register_collector!(create_collector);
+register_collector!(create_collector, metadata);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct UsersSnapshot {
@@ -68,9 +87,9 @@ struct UserRecord {
     sudo: bool,
 }
 
-fn build_snapshot() -> Result<UsersSnapshot> {
-    let mut users = read_passwd(Path::new("/etc/passwd"))?;
-    let groups = read_groups(Path::new("/etc/group")).unwrap_or_default();
+fn build_snapshot(ctx: &CollectionContext) -> Result<UsersSnapshot> {
+    let mut users = read_passwd(&ctx.path("/etc/passwd"))?;
+    let groups = read_groups(&ctx.path("/etc/group")).unwrap_or_default();
     let privileged_groups = ["sudo", "wheel", "admin"];
 
     let mut privileged_members: HashSet<String> = HashSet::new();
@@ -0,0 +1,204 @@
+//! `--archive` / `--bundle`: every requested format in one gzip'd tarball.
+//!
+//! The archive holds the rendered reports under their timestamped names, the
+//! JSON report even when it was not requested, and a `manifest.json` that lists
+//! the files with the vmic version and hostname.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use clap::ValueEnum;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde_json::json;
+use vmic_core::{RenderOptions, Report};
+
+use crate::{OutputFormat, render};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ArchiveFormat {
+    #[value(name = "tar.gz")]
+    TarGz,
+}
+
+impl ArchiveFormat {
+    pub(crate) fn file_extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// Renders `formats` plus JSON into a tarball at `path` and returns the
+/// resolved path. Entries are named `<base_name>.<ext>` and the manifest
+/// records `host`. The tarball is built under a temporary name and renamed
+/// into place.
+pub(crate) fn write_archive(
+    report: &Report,
+    formats: &[OutputFormat],
+    options: &RenderOptions,
+    base_name: &str,
+    host: &str,
+    path: &Path,
+) -> Result<PathBuf> {
+    let mut contents = Vec::new();
+    for format in formats.iter().chain([&OutputFormat::Json]) {
+        if contents.iter().any(|(known, _, _)| known == format) {
+            continue;
+        }
+        let name = format!("{}.{}", base_name, format.file_extension());
+        contents.push((format.clone(), name, render(report, format, options)?));
+    }
+    let manifest = json!({
+        "vmic_version": env!("CARGO_PKG_VERSION"),
+        "hostname": host,
+        "generated_at": report.metadata.generated_at,
+        "files": contents
+            .iter()
+            .map(|(format, name, rendered)| json!({
+                "name": name,
+                "format": format,
+                "bytes": rendered.len(),
+            }))
+            .collect::<Vec<_>>(),
+    });
+
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let staging = path.with_file_name(format!(".{}.tmp", file_name));
+    let file = File::create(&staging)
+        .with_context(|| format!("failed to create {}", staging.display()))?;
+    let mtime = report
+        .metadata
+        .generated_at_utc()
+        .map_or(0, |timestamp| timestamp.timestamp().max(0) as u64);
+    let mut tarball = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let manifest = serde_json::to_string_pretty(&manifest)?;
+    let entries = contents
+        .iter()
+        .map(|(_, name, rendered)| (name.as_str(), rendered.as_bytes()))
+        .chain([("manifest.json", manifest.as_bytes())]);
+    for (name, data) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        tarball
+            .append_data(&mut header, name, data)
+            .with_context(|| format!("failed to add {} to {}", name, path.display()))?;
+    }
+    tarball
+        .into_inner()
+        .and_then(GzEncoder::finish)
+        .with_context(|| format!("failed to write {}", staging.display()))?;
+    fs::rename(&staging, path).with_context(|| format!("failed to replace {}", path.display()))?;
+    Ok(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify;
+    use std::io::Read;
+    use vmic_core::{RedactionPolicy, Section};
+
+    /// Name and text of every entry in the tarball at `path`.
+    fn entries(path: &Path) -> Vec<(String, String)> {
+        let mut archive =
+            tar::Archive::new(flate2::read::GzDecoder::new(File::open(path).unwrap()));
+        let mut files = Vec::new();
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let name = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut text = String::new();
+            entry.read_to_string(&mut text).unwrap();
+            files.push((name, text));
+        }
+        files
+    }
+
+    #[test]
+    fn archive_holds_formats_json_and_manifest() {
+        let report = Report::new(vec![Section::success(
+            "os",
+            "Operating System",
+            json!({ "kernel": "6.1" }),
+        )]);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/vmic-report-x.tar.gz");
+        let formats = [OutputFormat::Markdown, OutputFormat::Html];
+        write_archive(
+            &report,
+            &formats,
+            &RenderOptions::default(),
+            "vmic-report-x",
+            "web-01",
+            &path,
+        )
+        .unwrap();
+
+        let files = entries(&path);
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "vmic-report-x.md",
+                "vmic-report-x.html",
+                "vmic-report-x.json",
+                "manifest.json"
+            ]
+        );
+
+        let manifest: serde_json::Value = serde_json::from_str(&files[3].1).unwrap();
+        assert_eq!(manifest["vmic_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest["hostname"], "web-01");
+        assert_eq!(manifest["files"][2]["name"], "vmic-report-x.json");
+        assert_eq!(manifest["files"][2]["format"], "json");
+        assert_eq!(manifest["files"][0]["bytes"], files[0].1.len());
+        let json: serde_json::Value = serde_json::from_str(&files[2].1).unwrap();
+        assert_eq!(json["sections"][0]["id"], "os");
+    }
+
+    #[test]
+    fn redacted_archives_do_not_name_the_host() {
+        let raw = notify::hostname();
+        let policy = RedactionPolicy {
+            hostnames: true,
+            ..RedactionPolicy::default()
+        };
+        let mut report = Report::new(vec![Section::success(
+            "os",
+            "Operating System",
+            json!({ "kernel": "6.1" }),
+        )]);
+        report.redact(&policy);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vmic-report-x.tar.gz");
+        write_archive(
+            &report,
+            &[OutputFormat::Json],
+            &RenderOptions::default(),
+            "vmic-report-x",
+            &notify::report_hostname(Some(&policy)),
+            &path,
+        )
+        .unwrap();
+
+        let files = entries(&path);
+        let (_, manifest) = files
+            .iter()
+            .find(|(name, _)| name == "manifest.json")
+            .unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(manifest).unwrap();
+        let hostname = manifest["hostname"].as_str().unwrap();
+        assert_ne!(hostname, raw);
+        assert_eq!(hostname, policy.redact_hostname(&raw));
+        assert!(hostname.starts_with("host-"));
+    }
+}
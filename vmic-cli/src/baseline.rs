@@ -0,0 +1,142 @@
+//! `--baseline`: compare each fresh report with a stored known-good one.
+//!
+//! The comparison is the `vmic diff` one with the digest's disk thresholds, so
+//! mounts that newly crossed a threshold count as regressions. A missing or
+//! unreadable baseline only produces a warning.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use vmic_core::diff::{self, DiffOptions, ReportDiff};
+use vmic_core::{DigestThresholds, Report};
+
+use crate::write_report;
+
+/// Exit status of `--fail-on-change` when the report regressed.
+pub(crate) const REGRESSION_EXIT_CODE: i32 = 3;
+
+/// Changes from the baseline at `path` to `report`, or `None` with a warning
+/// when the baseline cannot be used.
+pub(crate) fn compare(
+    path: &Path,
+    report: &Report,
+    thresholds: DigestThresholds,
+) -> Option<ReportDiff> {
+    if !path.exists() {
+        log::warn!(
+            "baseline {} does not exist; skipping the comparison",
+            path.display()
+        );
+        return None;
+    }
+    match diff::load_report(path) {
+        Ok(baseline) => {
+            let options = DiffOptions {
+                thresholds: Some(thresholds),
+                ..DiffOptions::default()
+            };
+            Some(diff::diff_reports(
+                &baseline,
+                &report.to_json_value(),
+                &options,
+            ))
+        }
+        Err(err) => {
+            log::warn!("ignoring baseline: {:#}", err);
+            None
+        }
+    }
+}
+
+/// `--update-baseline`: replaces the baseline with `report` after a comparison,
+/// or creates it when it does not exist yet. A baseline that failed to load is
+/// left alone so a typo in the path cannot overwrite an unrelated file.
+pub(crate) fn update(path: &Path, report: &Report, compared: bool) -> Result<Option<PathBuf>> {
+    if !compared && path.exists() {
+        return Ok(None);
+    }
+    let rendered = serde_json::to_string_pretty(&report.to_json_value())?;
+    write_report(path, rendered, true).map(Some)
+}
+
+/// `baseline: N regression(s) since <time> — exiting 3`, or `None` when the
+/// report did not regress.
+pub(crate) fn regression_exit(diff: &ReportDiff) -> Option<(i32, String)> {
+    if !diff.is_regression() {
+        return None;
+    }
+    let critical = diff
+        .new_findings
+        .iter()
+        .filter(|finding| finding.severity == vmic_core::Severity::Critical)
+        .count();
+    Some((
+        REGRESSION_EXIT_CODE,
+        format!(
+            "baseline: {} new critical finding(s), {} threshold crossing(s) since {} — exiting {}",
+            critical,
+            diff.threshold_crossings.len(),
+            diff.old_generated_at.as_deref().unwrap_or("the baseline"),
+            REGRESSION_EXIT_CODE
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vmic_core::Section;
+
+    fn report(root_usage: f64) -> Report {
+        Report::new(vec![Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    { "mount_point": "/", "fs_type": "ext4", "operational": true, "usage_ratio": root_usage, "available_bytes": 100u64 << 30 }
+                ]
+            }),
+        )])
+    }
+
+    #[test]
+    fn missing_and_invalid_baselines_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let thresholds = DigestThresholds::default();
+        let missing = dir.path().join("missing.json");
+        assert!(compare(&missing, &report(0.5), thresholds).is_none());
+
+        let invalid = dir.path().join("invalid.json");
+        std::fs::write(&invalid, "{ nope").unwrap();
+        assert!(compare(&invalid, &report(0.5), thresholds).is_none());
+        // Not overwritten without a successful comparison.
+        assert_eq!(update(&invalid, &report(0.5), false).unwrap(), None);
+        assert_eq!(std::fs::read_to_string(&invalid).unwrap(), "{ nope");
+
+        // A missing baseline is created.
+        assert!(update(&missing, &report(0.5), false).unwrap().is_some());
+        assert!(compare(&missing, &report(0.5), thresholds).is_some());
+    }
+
+    #[test]
+    fn crossing_a_threshold_fails_the_comparison() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        update(&path, &report(0.80), false).unwrap();
+        let thresholds = DigestThresholds::default();
+
+        let same = compare(&path, &report(0.80), thresholds).unwrap();
+        assert!(!same.is_material());
+        assert_eq!(regression_exit(&same), None);
+
+        let fuller = compare(&path, &report(0.91), thresholds).unwrap();
+        let (code, message) = regression_exit(&fuller).unwrap();
+        assert_eq!(code, REGRESSION_EXIT_CODE);
+        assert!(message.contains("0 new critical finding(s), 1 threshold crossing(s)"));
+
+        update(&path, &report(0.91), true).unwrap();
+        let settled = compare(&path, &report(0.91), thresholds).unwrap();
+        assert_eq!(regression_exit(&settled), None);
+    }
+}
@@ -0,0 +1,457 @@
+//! CLI settings merged from flags, environment variables, and a TOML file.
+//!
+//! The file comes from `--config PATH` or `$XDG_CONFIG_HOME/vmic/config.toml`
+//! (falling back to `~/.config/vmic/config.toml`). A flag beats an environment
+//! variable, which beats the file, which beats the built-in default. Output
+//! paths from any source expand a leading `~` or `$HOME`.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use vmic_core::DigestThresholds;
+use vmic_core::health::percent_to_ratio;
+
+use crate::{Cli, OutputFormat, parse_label};
+
+/// Config file layout; every key is optional and unknown keys are errors.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct FileConfig {
+    formats: Option<Vec<OutputFormat>>,
+    output_dir: Option<PathBuf>,
+    since: Option<String>,
+    jobs: Option<NonZeroUsize>,
+    only: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    /// Same keys as `--digest-config` files.
+    digest: Option<toml::Table>,
+    labels: Option<BTreeMap<String, String>>,
+}
+
+impl FileConfig {
+    fn from_toml_str(source: &str) -> Result<Self> {
+        toml::from_str(source).map_err(|error| anyhow!("invalid config: {}", error.message()))
+    }
+
+    fn from_path(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_toml_str(&source).with_context(|| format!("in {}", path.display()))
+    }
+}
+
+/// Effective settings after merging every source.
+#[derive(Debug, Serialize)]
+pub(crate) struct Config {
+    /// File the settings were loaded from, if any.
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+    pub formats: Vec<OutputFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<PathBuf>,
+    /// `--output` with `~` expanded.
+    #[serde(skip)]
+    pub output: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Collector worker limit; unset means one per available CPU.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jobs: Option<NonZeroUsize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub only: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    pub digest: DigestThresholds,
+    /// Report labels; a flag replaces a `VMIC_LABELS` entry with the same key,
+    /// which replaces the file's.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// Loads the config file (an explicit `--config` must exist; the default
+    /// location is optional) and merges it with `cli`.
+    pub fn resolve(cli: &Cli) -> Result<Self> {
+        let source = match &cli.config {
+            Some(path) => Some(path.clone()),
+            None => default_path().filter(|path| path.is_file()),
+        };
+        let file = match &source {
+            Some(path) => FileConfig::from_path(path)?,
+            None => FileConfig::default(),
+        };
+        let mut config = Self::merge(cli, file)?;
+        config.source = source;
+        Ok(config)
+    }
+
+    fn merge(cli: &Cli, file: FileConfig) -> Result<Self> {
+        let digest = load_thresholds(cli, &file)?;
+        let formats = match (cli.formats.is_empty(), file.formats) {
+            (false, _) => cli.formats.clone(),
+            (true, Some(formats)) if !formats.is_empty() => formats,
+            _ => vec![OutputFormat::Markdown],
+        };
+        // --only and --exclude replace the file's selection as a pair so the two
+        // never end up combined.
+        let (only, exclude) = if cli.only.is_empty() && cli.exclude.is_empty() {
+            let only = file.only.unwrap_or_default();
+            let exclude = file.exclude.unwrap_or_default();
+            if !only.is_empty() && !exclude.is_empty() {
+                return Err(anyhow!("config sets both 'only' and 'exclude'; keep one"));
+            }
+            (only, exclude)
+        } else {
+            (cli.only.clone(), cli.exclude.clone())
+        };
+
+        let mut labels = BTreeMap::new();
+        for (key, value) in file.labels.unwrap_or_default() {
+            parse_label(&format!("{}={}", key, value))
+                .map_err(|err| anyhow!("invalid label in config: {}", err))?;
+            labels.insert(key, value);
+        }
+        labels.extend(env_labels()?);
+        labels.extend(unique_labels(cli.labels.iter().cloned()).context("in --label")?);
+
+        let home = env::var_os("HOME").filter(|home| !home.is_empty());
+        let home = home.as_deref().map(Path::new);
+        let output_dir = match (&cli.output_dir, env_output_dir(), file.output_dir) {
+            (Some(dir), _, _) => Some((dir.clone(), "--output-dir")),
+            (None, Some(dir), _) => Some((dir, "VMIC_OUTPUT_DIR")),
+            (None, None, Some(dir)) => Some((dir, "output_dir in the config")),
+            (None, None, None) => None,
+        };
+        let output_dir = output_dir
+            .map(|(dir, source)| expand_home(&dir, home).with_context(|| format!("in {}", source)))
+            .transpose()?;
+        let output = cli
+            .output
+            .as_deref()
+            .map(|path| expand_home(path, home).context("in --output"))
+            .transpose()?;
+
+        Ok(Self {
+            source: None,
+            formats,
+            output_dir,
+            output,
+            since: cli.since.clone().or(file.since),
+            jobs: match cli.jobs {
+                Some(jobs) => Some(jobs),
+                None => env_jobs()?.or(file.jobs),
+            },
+            only,
+            exclude,
+            digest,
+            labels,
+        })
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(self).expect("config serializes to TOML")
+    }
+}
+
+fn env_jobs() -> Result<Option<NonZeroUsize>> {
+    match env::var("VMIC_JOBS") {
+        Ok(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| anyhow!("invalid value for VMIC_JOBS: expected a positive integer")),
+        _ => Ok(None),
+    }
+}
+
+fn env_output_dir() -> Option<PathBuf> {
+    env::var_os("VMIC_OUTPUT_DIR")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Replaces a leading `~`, `$HOME`, or `${HOME}` component of `path` with
+/// `home`. Other paths, including `~user`, are returned unchanged.
+fn expand_home(path: &Path, home: Option<&Path>) -> Result<PathBuf> {
+    let Some(text) = path.to_str() else {
+        return Ok(path.to_path_buf());
+    };
+    let rest = ["~", "$HOME", "${HOME}"].iter().find_map(|prefix| {
+        text.strip_prefix(prefix)
+            .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+    });
+    let Some(rest) = rest else {
+        return Ok(path.to_path_buf());
+    };
+    let home = home.ok_or_else(|| {
+        anyhow!(
+            "cannot expand '{}': HOME is not set; use an absolute path",
+            text
+        )
+    })?;
+    Ok(home.join(rest.trim_start_matches('/')))
+}
+
+/// `VMIC_LABELS="env=prod,role=db"`.
+fn env_labels() -> Result<BTreeMap<String, String>> {
+    let Ok(value) = env::var("VMIC_LABELS") else {
+        return Ok(BTreeMap::new());
+    };
+    let labels = value
+        .split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(parse_label)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| anyhow!(err))
+        .and_then(unique_labels);
+    labels.context("invalid value for VMIC_LABELS")
+}
+
+/// Collects labels into a map, rejecting a key given twice.
+fn unique_labels(
+    labels: impl IntoIterator<Item = (String, String)>,
+) -> Result<BTreeMap<String, String>> {
+    let mut map = BTreeMap::new();
+    for (key, value) in labels {
+        if map.contains_key(&key) {
+            return Err(anyhow!("label '{}' is given more than once", key));
+        }
+        map.insert(key, value);
+    }
+    Ok(map)
+}
+
+fn default_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("vmic").join("config.toml"))
+}
+
+/// Starts from `--digest-config`, else the file's `[digest]` table, else the
+/// defaults; environment variables and then flags override individual values.
+fn load_thresholds(cli: &Cli, file: &FileConfig) -> Result<DigestThresholds> {
+    let mut thresholds = match (&cli.digest_config, &file.digest) {
+        (Some(path), _) => DigestThresholds::from_path(path)?,
+        (None, Some(table)) => {
+            let source = toml::to_string(table).context("failed to encode [digest]")?;
+            DigestThresholds::from_toml_str(&source).context("in [digest]")?
+        }
+        (None, None) => DigestThresholds::default(),
+    };
+
+    apply_env_override("VMIC_DIGEST_DISK_WARNING", |ratio| {
+        thresholds.disk_warning = ratio;
+        Ok(())
+    })?;
+    apply_env_override("VMIC_DIGEST_DISK_CRITICAL", |ratio| {
+        thresholds.disk_critical = ratio;
+        Ok(())
+    })?;
+    apply_env_override("VMIC_DIGEST_MEMORY_WARNING", |ratio| {
+        thresholds.memory_warning = ratio;
+        Ok(())
+    })?;
+    apply_env_override("VMIC_DIGEST_MEMORY_CRITICAL", |ratio| {
+        thresholds.memory_critical = ratio;
+        Ok(())
+    })?;
+
+    if let Some(value) = cli.digest_disk_warning {
+        thresholds.disk_warning = percent_to_ratio(value)?;
+    }
+    if let Some(value) = cli.digest_disk_critical {
+        thresholds.disk_critical = percent_to_ratio(value)?;
+    }
+    if let Some(value) = cli.digest_memory_warning {
+        thresholds.memory_warning = percent_to_ratio(value)?;
+    }
+    if let Some(value) = cli.digest_memory_critical {
+        thresholds.memory_critical = percent_to_ratio(value)?;
+    }
+    if let Some(value) = cli.digest_free_space_min_fs_gib {
+        thresholds.free_space_min_fs_gib = value;
+    }
+
+    thresholds.validate()?;
+    Ok(thresholds)
+}
+
+fn apply_env_override<F>(key: &str, mut assign: F) -> Result<()>
+where
+    F: FnMut(f64) -> Result<()>,
+{
+    if let Ok(value) = env::var(key)
+        && !value.trim().is_empty()
+    {
+        let ratio =
+            percent_str_to_ratio(&value).with_context(|| format!("invalid value for {}", key))?;
+        assign(ratio)?;
+    }
+    Ok(())
+}
+
+fn percent_str_to_ratio(value: &str) -> Result<f64> {
+    let parsed: f64 = value.trim().parse()?;
+    percent_to_ratio(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn cli(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("vmic").chain(args.iter().copied())).unwrap()
+    }
+
+    const FILE: &str = r#"
+formats = ["json", "html"]
+output_dir = "/var/lib/vmic"
+since = "2h"
+jobs = 3
+exclude = ["docker"]
+
+[digest]
+disk_warning = 80
+disk_critical = 85
+
+[labels]
+env = "staging"
+role = "db"
+"#;
+
+    #[test]
+    fn file_values_fill_in_for_missing_flags() {
+        let file = FileConfig::from_toml_str(FILE).unwrap();
+        let config = Config::merge(&cli(&[]), file).unwrap();
+        assert_eq!(config.formats, [OutputFormat::Json, OutputFormat::Html]);
+        assert_eq!(config.output_dir, Some(PathBuf::from("/var/lib/vmic")));
+        assert_eq!(config.since.as_deref(), Some("2h"));
+        assert_eq!(config.jobs, NonZeroUsize::new(3));
+        assert_eq!(config.exclude, ["docker"]);
+        assert_eq!(config.digest.disk_warning, 0.80);
+        assert_eq!(config.digest.disk_critical, 0.85);
+        assert_eq!(
+            config.digest.memory_warning,
+            DigestThresholds::default().memory_warning
+        );
+    }
+
+    #[test]
+    fn flags_override_the_file() {
+        let file = FileConfig::from_toml_str(FILE).unwrap();
+        let flags = cli(&[
+            "--format",
+            "markdown",
+            "--output-dir",
+            "./reports",
+            "--only",
+            "storage",
+            "--digest-disk-warning",
+            "70",
+            "--jobs",
+            "1",
+        ]);
+        let config = Config::merge(&flags, file).unwrap();
+        assert_eq!(config.formats, [OutputFormat::Markdown]);
+        assert_eq!(config.output_dir, Some(PathBuf::from("./reports")));
+        assert_eq!(config.only, ["storage"]);
+        assert_eq!(config.jobs, NonZeroUsize::new(1));
+        assert!(config.exclude.is_empty());
+        assert_eq!(config.digest.disk_warning, 0.70);
+        assert_eq!(config.digest.disk_critical, 0.85);
+    }
+
+    #[test]
+    fn labels_are_validated_and_flags_win() {
+        let file = FileConfig::from_toml_str(FILE).unwrap();
+        let flags = cli(&["--label", "env=prod", "--label", "region=eu"]);
+        let config = Config::merge(&flags, file).unwrap();
+        let labels: Vec<(&str, &str)> = config
+            .labels
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(labels, [("env", "prod"), ("region", "eu"), ("role", "db")]);
+
+        let twice = cli(&["--label", "env=prod", "--label", "env=dev"]);
+        let error = Config::merge(&twice, FileConfig::default()).unwrap_err();
+        assert!(format!("{:#}", error).contains("label 'env' is given more than once"));
+        assert!(Cli::try_parse_from(["vmic", "--label", "9lives=x"]).is_err());
+        assert!(Cli::try_parse_from(["vmic", "--label", "role"]).is_err());
+        assert!(unique_labels([("a".to_string(), "1".to_string())]).is_ok());
+    }
+
+    #[test]
+    fn home_is_expanded_in_output_paths() {
+        let home = Some(Path::new("/home/ops"));
+        let expand = |path: &str| expand_home(Path::new(path), home).unwrap();
+        assert_eq!(expand("~"), PathBuf::from("/home/ops"));
+        assert_eq!(expand("~/reports"), PathBuf::from("/home/ops/reports"));
+        assert_eq!(expand("$HOME/reports"), PathBuf::from("/home/ops/reports"));
+        assert_eq!(expand("${HOME}/a/b"), PathBuf::from("/home/ops/a/b"));
+        // Only a whole leading component is expanded.
+        assert_eq!(expand("~ops/reports"), PathBuf::from("~ops/reports"));
+        assert_eq!(expand("$HOMEDIR/x"), PathBuf::from("$HOMEDIR/x"));
+        assert_eq!(expand("./~/x"), PathBuf::from("./~/x"));
+        assert_eq!(expand("/var/lib/vmic"), PathBuf::from("/var/lib/vmic"));
+        assert_eq!(expand("-"), PathBuf::from("-"));
+
+        let error = expand_home(Path::new("~/reports"), None).unwrap_err();
+        assert!(error.to_string().contains("HOME is not set"));
+        assert_eq!(
+            expand_home(Path::new("reports"), None).unwrap(),
+            PathBuf::from("reports")
+        );
+    }
+
+    #[test]
+    fn defaults_apply_without_file_or_flags() {
+        let config = Config::merge(&cli(&[]), FileConfig::default()).unwrap();
+        assert_eq!(config.formats, [OutputFormat::Markdown]);
+        assert_eq!(config.output_dir, None);
+        assert_eq!(config.digest, DigestThresholds::default());
+    }
+
+    #[test]
+    fn unknown_keys_are_named_in_the_error() {
+        let error = FileConfig::from_toml_str("fromats = [\"json\"]\n").unwrap_err();
+        assert!(error.to_string().contains("unknown field `fromats`"));
+
+        let file = FileConfig::from_toml_str("[digest]\ndisk_warnign = 80\n").unwrap();
+        let error = Config::merge(&cli(&[]), file).unwrap_err();
+        assert!(format!("{:#}", error).contains("unknown field `disk_warnign`"));
+
+        let file = FileConfig::from_toml_str("only = [\"os\"]\nexclude = [\"sar\"]\n").unwrap();
+        assert!(Config::merge(&cli(&[]), file).is_err());
+    }
+
+    #[test]
+    fn shown_config_loads_back_as_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, FILE).unwrap();
+
+        let path_arg = path.to_str().unwrap();
+        let config = Config::resolve(&cli(&["--config", path_arg])).unwrap();
+        assert_eq!(config.source.as_deref(), Some(path.as_path()));
+
+        let shown = config.to_toml_string();
+        let reloaded =
+            Config::merge(&cli(&[]), FileConfig::from_toml_str(&shown).unwrap()).unwrap();
+        assert_eq!(reloaded.formats, config.formats);
+        assert_eq!(reloaded.exclude, config.exclude);
+        assert_eq!(reloaded.digest, config.digest);
+        assert_eq!(reloaded.labels, config.labels);
+
+        let missing = dir.path().join("missing.toml");
+        assert!(Config::resolve(&cli(&["--config", missing.to_str().unwrap()])).is_err());
+    }
+}
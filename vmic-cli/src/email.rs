@@ -0,0 +1,403 @@
+//! `--email-to`: mail the health digest through an SMTP relay.
+//!
+//! The digest text is the plain-text body and the HTML and JSON reports are
+//! attached. The client speaks plain SMTP (no TLS) with optional `AUTH PLAIN`,
+//! which is what internal relays in closed networks usually offer. It lives
+//! behind the `email` feature. Passwords come from an environment variable or
+//! a file, never from the command line.
+
+use std::fs;
+#[cfg(feature = "email")]
+use std::io::{BufRead, BufReader, Write};
+#[cfg(feature = "email")]
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(feature = "email")]
+use std::time::Duration;
+
+use anyhow::{Context as _, Result, anyhow};
+use vmic_core::Report;
+
+use crate::Cli;
+
+#[cfg(feature = "email")]
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+#[cfg(feature = "email")]
+const DEFAULT_SMTP_PORT: u16 = 25;
+
+/// A rendered report attached to the message.
+#[cfg_attr(not(feature = "email"), allow(dead_code))]
+pub(crate) struct Attachment {
+    pub(crate) name: String,
+    pub(crate) content_type: &'static str,
+    pub(crate) data: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "email"), allow(dead_code))]
+pub(crate) struct Email {
+    pub(crate) to: Vec<String>,
+    pub(crate) from: String,
+    pub(crate) server: String,
+    pub(crate) credentials: Option<(String, String)>,
+}
+
+impl Email {
+    /// Mail settings from the command line, or `None` without `--email-to`.
+    /// Fails before anything is collected when this build cannot send mail or
+    /// the password cannot be read.
+    pub(crate) fn from_cli(cli: &Cli) -> Result<Option<Self>> {
+        if cli.email_to.is_empty() {
+            return Ok(None);
+        }
+        if !cfg!(feature = "email") {
+            return Err(anyhow!(
+                "--email-to needs a vmic build with the `email` feature"
+            ));
+        }
+        let (Some(from), Some(server)) = (&cli.email_from, &cli.smtp_server) else {
+            return Err(anyhow!("--email-to needs --email-from and --smtp-server"));
+        };
+        let credentials = match &cli.smtp_user {
+            Some(user) => Some((user.clone(), smtp_password(cli)?)),
+            None => None,
+        };
+        Ok(Some(Self {
+            to: cli.email_to.clone(),
+            from: from.clone(),
+            server: server.clone(),
+            credentials,
+        }))
+    }
+
+    /// Sends the digest of `report` from `host` with `attachments`.
+    pub(crate) fn send(
+        &self,
+        report: &Report,
+        host: &str,
+        attachments: &[Attachment],
+    ) -> Result<()> {
+        #[cfg(feature = "email")]
+        {
+            let message = self.message(report, host, attachments);
+            self.deliver(&message, host)
+                .with_context(|| format!("email via {} failed", self.server))
+        }
+        #[cfg(not(feature = "email"))]
+        {
+            let _ = (report, host, attachments);
+            Err(anyhow!("this vmic build has no email support"))
+        }
+    }
+
+    /// The RFC 5322 message: headers, then a multipart body with the digest
+    /// text and one base64 part per attachment. Lines end with CRLF.
+    #[cfg(feature = "email")]
+    fn message(&self, report: &Report, host: &str, attachments: &[Attachment]) -> String {
+        let digest = &report.health_digest;
+        let subject = format!(
+            "vmic report for {}: {} ({} finding(s))",
+            host,
+            digest.overall.as_str(),
+            digest.findings.len()
+        );
+        let id = report.metadata.checksum.trim_start_matches("sha256:");
+        let boundary = format!("vmic-{}", id);
+        let date = report
+            .metadata
+            .generated_at_utc()
+            .unwrap_or_else(chrono::Utc::now);
+
+        let mut message = String::new();
+        let mut line = |text: &str| {
+            message.push_str(text);
+            message.push_str("\r\n");
+        };
+        line(&format!("From: {}", self.from));
+        line(&format!("To: {}", self.to.join(", ")));
+        line(&format!("Subject: {}", encode_header(&subject)));
+        line(&format!("Date: {}", date.to_rfc2822()));
+        line(&format!(
+            "Message-ID: <{}.{}@{}>",
+            date.timestamp(),
+            id.get(..16).unwrap_or(id),
+            host
+        ));
+        line("MIME-Version: 1.0");
+        line(&format!(
+            "Content-Type: multipart/mixed; boundary=\"{}\"",
+            boundary
+        ));
+        line("");
+        line(&format!("--{}", boundary));
+        line("Content-Type: text/plain; charset=utf-8");
+        line("Content-Transfer-Encoding: base64");
+        line("");
+        line(&base64_lines(digest.to_text(false).as_bytes()));
+        for attachment in attachments {
+            line(&format!("--{}", boundary));
+            line(&format!(
+                "Content-Type: {}; name=\"{}\"",
+                attachment.content_type, attachment.name
+            ));
+            line("Content-Transfer-Encoding: base64");
+            line(&format!(
+                "Content-Disposition: attachment; filename=\"{}\"",
+                attachment.name
+            ));
+            line("");
+            line(&base64_lines(&attachment.data));
+        }
+        line(&format!("--{}--", boundary));
+        message
+    }
+
+    #[cfg(feature = "email")]
+    fn deliver(&self, message: &str, host: &str) -> Result<()> {
+        let address = if self.server.contains(':') {
+            self.server.clone()
+        } else {
+            format!("{}:{}", self.server, DEFAULT_SMTP_PORT)
+        };
+        let socket = address
+            .to_socket_addrs()
+            .with_context(|| format!("cannot resolve {}", address))?
+            .next()
+            .ok_or_else(|| anyhow!("{} has no address", address))?;
+        let stream = TcpStream::connect_timeout(&socket, SMTP_TIMEOUT)?;
+        stream.set_read_timeout(Some(SMTP_TIMEOUT))?;
+        stream.set_write_timeout(Some(SMTP_TIMEOUT))?;
+        let mut session = Session {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        };
+
+        session.expect(220)?;
+        let extensions = session.command(&format!("EHLO {}", host), 250)?;
+        if let Some((user, password)) = &self.credentials {
+            if !extensions
+                .iter()
+                .any(|line| line.to_ascii_uppercase().starts_with("AUTH"))
+            {
+                return Err(anyhow!("the server does not offer AUTH"));
+            }
+            let token = base64_encode(format!("\0{}\0{}", user, password).as_bytes());
+            session.command(&format!("AUTH PLAIN {}", token), 235)?;
+        }
+        session.command(&format!("MAIL FROM:<{}>", self.from), 250)?;
+        for recipient in &self.to {
+            session.command(&format!("RCPT TO:<{}>", recipient), 250)?;
+        }
+        session.command("DATA", 354)?;
+        for line in message.split_terminator("\r\n") {
+            // Dot-stuffing keeps a leading '.' from ending the data early.
+            let stuffed = if line.starts_with('.') { "." } else { "" };
+            write!(session.writer, "{}{}\r\n", stuffed, line)?;
+        }
+        session.command(".", 250)?;
+        let _ = session.command("QUIT", 221);
+        Ok(())
+    }
+}
+
+/// `--smtp-password-env` or `--smtp-password-file`, trimmed.
+fn smtp_password(cli: &Cli) -> Result<String> {
+    let password = match (&cli.smtp_password_env, &cli.smtp_password_file) {
+        (Some(name), _) => std::env::var(name)
+            .with_context(|| format!("--smtp-password-env: {} is not set", name))?,
+        (None, Some(path)) => fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?,
+        (None, None) => {
+            return Err(anyhow!(
+                "--smtp-user needs --smtp-password-env or --smtp-password-file"
+            ));
+        }
+    };
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Checks an `--email-to`/`--email-from` address: `local@domain` without
+/// spaces, angle brackets, or line breaks that could rewrite the headers.
+pub(crate) fn parse_address(value: &str) -> Result<String, String> {
+    let address = value.trim();
+    let valid = address
+        .split_once('@')
+        .is_some_and(|(local, domain)| !local.is_empty() && !domain.is_empty())
+        && !address
+            .chars()
+            .any(|ch| ch.is_whitespace() || ch.is_control() || "<>,;\"".contains(ch));
+    if valid {
+        Ok(address.to_string())
+    } else {
+        Err(format!("invalid email address '{}'", value))
+    }
+}
+
+#[cfg(feature = "email")]
+struct Session {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+#[cfg(feature = "email")]
+impl Session {
+    /// Sends `line` and checks the reply code; returns the reply lines.
+    fn command(&mut self, line: &str, code: u16) -> Result<Vec<String>> {
+        write!(self.writer, "{}\r\n", line)?;
+        self.writer.flush()?;
+        // Only the verb, so AUTH credentials never reach an error message.
+        let verb = line.split(' ').next().unwrap_or(line).to_string();
+        self.expect(code).with_context(|| format!("after {}", verb))
+    }
+
+    /// Reads one (possibly multi-line) reply and checks its code.
+    fn expect(&mut self, code: u16) -> Result<Vec<String>> {
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(anyhow!("connection closed by the server"));
+            }
+            let line = line.trim_end().to_string();
+            let last = line.as_bytes().get(3) != Some(&b'-');
+            let reply: Option<u16> = line.get(..3).and_then(|digits| digits.parse().ok());
+            lines.push(line.get(4..).unwrap_or_default().to_string());
+            if last {
+                return match reply {
+                    Some(reply) if reply == code => Ok(lines),
+                    _ => Err(anyhow!("server answered '{}'", line)),
+                };
+            }
+        }
+    }
+}
+
+/// `text` as an RFC 2047 encoded word when it is not plain ASCII.
+#[cfg(feature = "email")]
+fn encode_header(text: &str) -> String {
+    if text.is_ascii() {
+        text.to_string()
+    } else {
+        format!("=?utf-8?B?{}?=", base64_encode(text.as_bytes()))
+    }
+}
+
+#[cfg(feature = "email")]
+fn base64_encode(data: &[u8]) -> String {
+    use base64::Engine as _;
+    base64::engine::general_purpose::STANDARD.encode(data)
+}
+
+/// Base64 of `data` in lines of 76 characters, as MIME requires.
+#[cfg(feature = "email")]
+fn base64_lines(data: &[u8]) -> String {
+    let encoded = base64_encode(data);
+    encoded
+        .as_bytes()
+        .chunks(76)
+        .map(|chunk| std::str::from_utf8(chunk).expect("base64 is ASCII"))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_are_checked_for_header_injection() {
+        assert_eq!(
+            parse_address(" ops@example.com "),
+            Ok("ops@example.com".to_string())
+        );
+        assert!(parse_address("ops").is_err());
+        assert!(parse_address("@example.com").is_err());
+        assert!(parse_address("ops@example.com\r\nBcc: x@y").is_err());
+        assert!(parse_address("Ops <ops@example.com>").is_err());
+    }
+
+    #[cfg(feature = "email")]
+    #[test]
+    fn digest_is_sent_with_attachments() {
+        use std::net::TcpListener;
+        use std::thread;
+        use vmic_core::Section;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = listener.local_addr().unwrap().to_string();
+        let relay = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut transcript = Vec::new();
+            writer.write_all(b"220 relay ready\r\n").unwrap();
+            let mut data = false;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                let reply: &[u8] = if data {
+                    if line == "." {
+                        data = false;
+                        b"250 queued\r\n"
+                    } else {
+                        b""
+                    }
+                } else if line.starts_with("EHLO") {
+                    b"250-relay\r\n250 AUTH PLAIN\r\n"
+                } else if line.starts_with("AUTH") {
+                    b"235 ok\r\n"
+                } else if line == "DATA" {
+                    data = true;
+                    b"354 go ahead\r\n"
+                } else if line == "QUIT" {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    transcript.push(line);
+                    break;
+                } else {
+                    b"250 ok\r\n"
+                };
+                writer.write_all(reply).unwrap();
+                transcript.push(line);
+            }
+            transcript
+        });
+
+        let email = Email {
+            to: vec!["ops@example.com".to_string(), "dba@example.com".to_string()],
+            from: "vmic@example.com".to_string(),
+            server,
+            credentials: Some(("vmic".to_string(), "s3cret".to_string())),
+        };
+        let report = Report::new(vec![Section::degraded(
+            "docker",
+            "Docker Containers",
+            "engine unavailable".to_string(),
+            serde_json::json!({}),
+        )]);
+        let attachment = Attachment {
+            name: "vmic-report.json".to_string(),
+            content_type: "application/json",
+            data: b"{\"ok\":true}".to_vec(),
+        };
+        email.send(&report, "web-1", &[attachment]).unwrap();
+
+        let transcript = relay.join().unwrap();
+        assert_eq!(
+            transcript[1],
+            format!("AUTH PLAIN {}", base64_encode(b"\0vmic\0s3cret"))
+        );
+        assert_eq!(transcript[2], "MAIL FROM:<vmic@example.com>");
+        assert_eq!(transcript[4], "RCPT TO:<dba@example.com>");
+        assert!(
+            transcript
+                .contains(&"Subject: vmic report for web-1: warning (1 finding(s))".to_string())
+        );
+        assert!(transcript.contains(
+            &"Content-Disposition: attachment; filename=\"vmic-report.json\"".to_string()
+        ));
+        assert!(transcript.contains(&base64_encode(b"{\"ok\":true}")));
+        assert_eq!(transcript.last().unwrap(), "QUIT");
+    }
+}
@@ -1,11 +1,44 @@
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{IsTerminal as _, Read as _, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use anyhow::{Context as _, Result};
+use anyhow::{Context as _, Result, anyhow};
 use chrono::Utc;
-use clap::{Parser, ValueEnum};
-use vmic_core::{Context, DigestThresholds, collect_report_with_digest};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use vmic_core::diff::{self, DiffOptions, ReportDiff};
+use vmic_core::schema;
+use vmic_core::{
+    Availability, Context, DegradedAging, DetailLevel, Locale, RedactionPolicy, RenderOptions,
+    Report, Severity, collect_report_filtered, collect_report_with_progress, probe_collectors,
+    registered_collector_ids, registered_collectors,
+};
+
+mod archive;
+mod baseline;
+mod config;
+mod email;
+mod naming;
+mod notify;
+mod progress;
+mod since;
+mod summary;
+mod syslog;
+mod upload;
+mod watch;
+
+use archive::ArchiveFormat;
+use config::Config;
+use email::{Attachment, Email};
+use naming::NameTemplate;
+use notify::Notifier;
+use progress::Progress;
+use upload::Upload;
 
 // Ensure mandatory modules are linked so their collectors register.
 use mod_os as _;
@@ -29,49 +62,537 @@ use mod_users as _;
     name = "vmic",
     version,
     about = "VMIC system report",
-    author = "VMIC Team"
+    author = "VMIC Team",
+    after_long_help = SERVICE_HELP
 )]
 struct Cli {
-    /// Output formats to generate (repeat or use comma-separated values)
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Log progress to stderr: -v for collector start/finish, -vv for external commands
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Do not print the "report written to" lines
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Settings file (defaults to $XDG_CONFIG_HOME/vmic/config.toml when present)
+    #[arg(long, value_name = "PATH", global = true)]
+    config: Option<PathBuf>,
+
+    /// Output formats to generate (repeat or use comma-separated values; default markdown)
     #[arg(
         long = "format",
         visible_alias = "formats",
         value_enum,
-        value_delimiter = ',',
-        default_value = "markdown"
+        value_delimiter = ','
     )]
     formats: Vec<OutputFormat>,
 
-    /// Directory to write generated artifacts (defaults to current directory for file outputs)
+    /// Directory to write generated artifacts (defaults to VMIC_OUTPUT_DIR, then the current directory)
     #[arg(long, value_name = "PATH")]
     output_dir: Option<PathBuf>,
 
+    /// Write the single requested format to exactly this file (`-` for stdout)
+    #[arg(long, value_name = "PATH", conflicts_with = "output_dir")]
+    output: Option<PathBuf>,
+
+    /// Bundle every requested format plus JSON and a manifest into one archive in --output-dir
+    #[arg(long, value_enum, value_name = "KIND", conflicts_with_all = ["output", "watch"])]
+    archive: Option<ArchiveFormat>,
+
+    /// Write the archive to exactly this file (implies --archive tar.gz)
+    #[arg(
+        long,
+        value_name = "PATH.tar.gz",
+        conflicts_with_all = ["output", "output_dir", "watch", "archive"]
+    )]
+    bundle: Option<PathBuf>,
+
+    /// Base name for output files instead of vmic-report-<timestamp>; files are replaced atomically
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_parser = parse_output_name,
+        conflicts_with_all = ["output", "bundle"]
+    )]
+    output_name: Option<String>,
+
+    /// Output path under --output-dir with {hostname}, {timestamp}, {date}, and {format}, such as {hostname}/{date}/report.{format}
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        value_parser = NameTemplate::parse,
+        conflicts_with_all = ["output", "output_name", "archive", "bundle", "keep", "watch"]
+    )]
+    name_template: Option<NameTemplate>,
+
+    /// Gzip JSON output into .json.gz files; JSON on stdout is gzip'd as well
+    #[arg(long, conflicts_with_all = ["compress_over", "archive", "bundle", "digest_only"])]
+    compress: bool,
+
+    /// Gzip JSON output files only when the report is larger than SIZE, such as 512K or 5M
+    #[arg(
+        long,
+        value_name = "SIZE",
+        value_parser = parse_size,
+        conflicts_with_all = ["archive", "bundle", "digest_only"]
+    )]
+    compress_over: Option<u64>,
+
+    /// Keep only the newest N vmic-report-* files per format in the output directory
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with_all = ["output", "output_name", "bundle", "watch"]
+    )]
+    keep: Option<NonZeroUsize>,
+
     /// Limit collections to data since the given timestamp or duration (passed to collectors)
     #[arg(long, value_name = "SINCE")]
     since: Option<String>,
 
+    /// TOML file with digest thresholds; environment variables and the flags below override it
+    #[arg(long, value_name = "PATH", global = true)]
+    digest_config: Option<PathBuf>,
+
     /// Warn when any disk usage exceeds this percentage (default 90)
-    #[arg(long, value_name = "PERCENT")]
+    #[arg(long, value_name = "PERCENT", global = true)]
     digest_disk_warning: Option<f64>,
 
     /// Mark as critical when any disk usage exceeds this percentage (default 95)
-    #[arg(long, value_name = "PERCENT")]
+    #[arg(long, value_name = "PERCENT", global = true)]
     digest_disk_critical: Option<f64>,
 
     /// Warn when available memory falls below this percentage of total (default 10)
-    #[arg(long, value_name = "PERCENT")]
+    #[arg(long, value_name = "PERCENT", global = true)]
     digest_memory_warning: Option<f64>,
 
     /// Mark as critical when available memory falls below this percentage of total (default 5)
-    #[arg(long, value_name = "PERCENT")]
+    #[arg(long, value_name = "PERCENT", global = true)]
     digest_memory_critical: Option<f64>,
+
+    /// Apply the absolute free-space rules only to filesystems larger than this many GiB (default 16)
+    #[arg(long, value_name = "GIB", global = true)]
+    digest_free_space_min_fs_gib: Option<f64>,
+
+    /// Language for section titles and report labels (en or ru)
+    #[arg(long, value_name = "LOCALE", default_value = "en", global = true)]
+    locale: Locale,
+
+    /// Run only these collectors (comma-separated ids, tag:<name>, or cost:<key> selectors)
+    #[arg(
+        long,
+        value_name = "IDS",
+        value_delimiter = ',',
+        conflicts_with = "exclude"
+    )]
+    only: Vec<String>,
+
+    /// Skip these collectors (comma-separated ids, tag:<name>, or cost:<key> selectors)
+    #[arg(long, value_name = "IDS", value_delimiter = ',')]
+    exclude: Vec<String>,
+
+    /// Check section bodies against their embedded schemas and note any mismatch
+    #[arg(long)]
+    validate_schemas: bool,
+
+    /// How much collectors gather: minimal skips per-container stats and the
+    /// hotspot scan, full scans deeper and reads more log lines
+    #[arg(long, value_name = "LEVEL", default_value = "standard")]
+    detail_level: DetailLevel,
+
+    /// Probe collectors first and record a degraded stub instead of running those that are unavailable
+    #[arg(long)]
+    skip_unavailable: bool,
+
+    /// Read host files (/etc, /proc, /sys) from this directory instead of /, e.g. an extracted sosreport
+    #[arg(long, value_name = "DIR")]
+    root: Option<PathBuf>,
+
+    /// Flag collectors slower than this many seconds (default 5)
+    #[arg(long, value_name = "SECONDS")]
+    slow_collector_secs: Option<f64>,
+
+    /// Run at most this many collectors at once (default: available CPUs; 1 runs them in sequence)
+    #[arg(long, value_name = "N")]
+    jobs: Option<NonZeroUsize>,
+
+    /// Abandon any collector still running after this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+
+    /// Per-collector timeout overriding --timeout, as ID=SECONDS (repeatable)
+    #[arg(long, value_name = "ID=SECONDS", value_parser = parse_collector_timeout)]
+    collector_timeout: Vec<(String, Duration)>,
+
+    /// Collector option as ID.KEY=VALUE, such as journal.lines=200 (repeatable)
+    #[arg(long = "set", value_name = "ID.KEY=VALUE", value_parser = parse_setting)]
+    settings: Vec<(String, String, String)>,
+
+    /// Label for the report metadata as KEY=VALUE, such as env=prod (repeatable; adds to VMIC_LABELS)
+    #[arg(long = "label", value_name = "KEY=VALUE", value_parser = parse_label)]
+    labels: Vec<(String, String)>,
+
+    /// Salt for an anonymous host fingerprint in the report metadata (omitted when unset)
+    #[arg(long, value_name = "SALT")]
+    fingerprint_salt: Option<String>,
+
+    /// Replace IP addresses, usernames, and host names with stable hashes before writing any output
+    #[arg(long)]
+    redact: bool,
+
+    /// Redact the default categories except these (comma-separated; implies --redact)
+    #[arg(
+        long,
+        value_enum,
+        value_name = "CATEGORIES",
+        value_delimiter = ',',
+        conflicts_with = "redact_only"
+    )]
+    redact_keep: Vec<RedactCategory>,
+
+    /// Redact exactly these categories (comma-separated; implies --redact)
+    #[arg(long, value_enum, value_name = "CATEGORIES", value_delimiter = ',')]
+    redact_only: Vec<RedactCategory>,
+
+    /// Print only the digest (overall severity and one line per finding) and write nothing
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "output",
+            "output_dir",
+            "output_name",
+            "name_template",
+            "keep",
+            "archive",
+            "bundle",
+            "watch",
+            "formats"
+        ]
+    )]
+    digest_only: bool,
+
+    /// Print a one-line JSON summary of the run (files written, timing, digest, failed collectors) to stderr
+    #[arg(long, conflicts_with_all = ["watch", "digest_only"])]
+    summary_json: bool,
+
+    /// Write the --summary-json object to this file instead of stderr
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["watch", "digest_only"])]
+    summary_json_path: Option<PathBuf>,
+
+    /// Keep collecting into --output-dir every --interval until SIGINT or SIGTERM
+    #[arg(long, conflicts_with = "output")]
+    watch: bool,
+
+    /// Time between --watch runs, such as 30s, 15m, or 1h (default 15m)
+    #[arg(long, value_name = "DURATION", value_parser = watch::parse_interval, requires = "watch")]
+    interval: Option<Duration>,
+
+    /// Keep only this many reports per format in --watch mode, deleting the oldest
+    #[arg(long, value_name = "COUNT", requires = "watch")]
+    retention: Option<NonZeroUsize>,
+
+    /// POST the report, gzip-compressed, to this URL after local outputs are written
+    #[arg(long, value_name = "URL")]
+    upload_url: Option<String>,
+
+    /// Extra request header for --upload-url as 'Name: value' (repeatable)
+    #[arg(long, value_name = "HEADER", value_parser = upload::parse_header, requires = "upload_url")]
+    upload_header: Vec<(String, String)>,
+
+    /// Rendered format to upload
+    #[arg(long, value_enum, value_name = "FORMAT", default_value = "json")]
+    upload_format: OutputFormat,
+
+    /// Retries after a failed upload, with exponential backoff from 1s
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    upload_retries: u32,
+
+    /// Fail the run when the upload fails instead of only warning
+    #[arg(long, requires = "upload_url")]
+    upload_required: bool,
+
+    /// Post a short digest message ({"text": ...}) to this Slack/Mattermost webhook
+    #[arg(long, value_name = "URL")]
+    notify_webhook: Option<String>,
+
+    /// Only notify when the digest reaches this severity (info, warning, critical)
+    #[arg(
+        long,
+        value_name = "SEVERITY",
+        default_value = "warning",
+        requires = "notify_webhook"
+    )]
+    notify_min_severity: Severity,
+
+    /// Finding messages to include in the notification
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 5,
+        requires = "notify_webhook"
+    )]
+    notify_findings: usize,
+
+    /// Email the digest with the HTML/JSON reports attached to this address (repeatable)
+    #[arg(
+        long,
+        value_name = "ADDR",
+        value_parser = email::parse_address,
+        requires = "email_from",
+        requires = "smtp_server"
+    )]
+    email_to: Vec<String>,
+
+    /// Sender address for --email-to
+    #[arg(long, value_name = "ADDR", value_parser = email::parse_address, requires = "email_to")]
+    email_from: Option<String>,
+
+    /// SMTP relay for --email-to as HOST:PORT (plain SMTP without TLS; port 25 when omitted)
+    #[arg(long, value_name = "HOST:PORT", requires = "email_to")]
+    smtp_server: Option<String>,
+
+    /// SMTP user for AUTH PLAIN; the password comes from --smtp-password-env or --smtp-password-file
+    #[arg(long, value_name = "USER", requires = "smtp_server")]
+    smtp_user: Option<String>,
+
+    /// Environment variable holding the SMTP password
+    #[arg(
+        long,
+        value_name = "VAR",
+        requires = "smtp_user",
+        conflicts_with = "smtp_password_file"
+    )]
+    smtp_password_env: Option<String>,
+
+    /// File holding the SMTP password
+    #[arg(long, value_name = "PATH", requires = "smtp_user")]
+    smtp_password_file: Option<PathBuf>,
+
+    /// Fail the run when the email cannot be sent instead of only warning
+    #[arg(long, requires = "email_to")]
+    email_required: bool,
+
+    /// Also log every digest finding to the local syslog (/dev/log), tagged vmic
+    #[arg(long)]
+    syslog: bool,
+
+    /// Only log findings of at least this severity to syslog (info, warning, critical)
+    #[arg(
+        long,
+        value_name = "SEVERITY",
+        default_value = "warning",
+        requires = "syslog"
+    )]
+    syslog_min_severity: Severity,
+
+    /// Compare the fresh report with this stored JSON report and add "Changes since baseline" to Markdown/HTML
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<PathBuf>,
+
+    /// Exit 3 when new critical findings appeared or a mount crossed a disk threshold since the baseline
+    #[arg(long, requires = "baseline")]
+    fail_on_change: bool,
+
+    /// Replace the baseline with the fresh report after comparing (creates it when missing)
+    #[arg(long, requires = "baseline")]
+    update_baseline: bool,
+
+    /// Append this run to a JSONL history file and include trends from earlier runs
+    #[arg(long, value_name = "PATH")]
+    history: Option<PathBuf>,
+
+    /// Escalate a section to critical once degraded for more than this many consecutive runs (needs --history)
+    #[arg(long, value_name = "RUNS", default_value_t = vmic_core::health::DEFAULT_DEGRADED_ESCALATION_RUNS)]
+    degraded_escalation_runs: usize,
+
+    /// Render Markdown as the digest plus one line per section (for chat notifications)
+    #[arg(long, global = true)]
+    summary_only: bool,
+
+    /// Show at most N rows per Markdown/HTML table instead of 12
+    #[arg(long, value_name = "N", conflicts_with = "full_detail", global = true)]
+    max_table_rows: Option<NonZeroUsize>,
+
+    /// Show every table row and list item, plus each section's raw JSON body
+    #[arg(long, global = true)]
+    full_detail: bool,
+
+    /// Exit non-zero when the digest reaches this severity: 1 for warning, 2 for critical
+    #[arg(long, value_enum, value_name = "SEVERITY", default_value = "never")]
+    fail_on: FailOn,
+
+    /// Hide digest findings below this severity in Markdown/HTML (info, warning, critical)
+    #[arg(long, value_name = "SEVERITY", default_value = "info", global = true)]
+    min_severity: Severity,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the collectors compiled into this binary without running them
+    ListCollectors {
+        /// Print a text table or JSON
+        #[arg(long, value_enum, default_value = "table")]
+        format: ListFormat,
+    },
+    /// Check each collector's prerequisites without collecting; exits 1 unless all are ready
+    Doctor {
+        /// Print a text table or JSON
+        #[arg(long, value_enum, default_value = "table")]
+        format: ListFormat,
+    },
+    /// Compare two saved JSON reports; exits 1 when anything material changed
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        /// Print Markdown or JSON
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: DiffFormat,
+        /// Report mounts whose usage moved by at least this many percentage points
+        #[arg(long, value_name = "PERCENT", default_value_t = 5.0)]
+        usage_delta: f64,
+    },
+    /// Render a saved JSON report again, with the current render flags, without collecting
+    Render {
+        /// Saved report (`.json` or `.json.gz`), or `-` for stdin
+        #[arg(long, value_name = "PATH")]
+        input: PathBuf,
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: OutputFormat,
+        /// Write to this file instead of stdout
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Recompute the health digest of a saved JSON report with the current thresholds
+    Digest {
+        /// Saved report (`.json` or `.json.gz`), or `-` for stdin
+        #[arg(long, value_name = "PATH")]
+        input: PathBuf,
+        /// Print the digest as text or JSON
+        #[arg(long, value_enum, default_value = "text")]
+        format: DigestFormat,
+    },
+    /// Check saved JSON reports against the embedded schemas; exits 1 if any file fails
+    Validate {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Only print files that fail
+        #[arg(short, long)]
+        quiet: bool,
+    },
+    /// Print the embedded JSON schema for reports or for one section body
+    Schema {
+        /// Print the body schema of this section instead of the report schema
+        #[arg(long, value_name = "ID", conflicts_with = "list")]
+        section: Option<String>,
+        /// List the available schema ids
+        #[arg(long)]
+        list: bool,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Inspect the merged configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Print the effective configuration as TOML
+    Show,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+enum DiffFormat {
+    Markdown,
+    Json,
 }
 
 #[derive(Clone, Debug, ValueEnum)]
+enum DigestFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+enum ListFormat {
+    Table,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum RedactCategory {
+    Ips,
+    Usernames,
+    Hostnames,
+    /// Usernames and peers from the journal SSH summary
+    Ssh,
+    /// Storage mount sources
+    Mounts,
+    /// Docker container names
+    Containers,
+    /// Cron commands and other command lines collectors mark as sensitive
+    Commands,
+    /// Journal message text
+    Messages,
+}
+
+/// File name for `--format prometheus` inside `--output-dir`.
+const PROMETHEUS_FILE: &str = "vmic.prom";
+
+const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// `vmic --help` epilogue describing a systemd timer deployment.
+const SERVICE_HELP: &str = "\
+Running from a systemd timer:
+  --output-name current writes current.html, current.json, ... into --output-dir and replaces
+  them atomically on every run, so web servers and scrapers always read a complete file.
+  With timestamped names instead, --keep N deletes all but the newest N files per format.
+
+  # /etc/systemd/system/vmic.service
+  [Service]
+  Type=oneshot
+  ExecStart=/usr/local/bin/vmic --quiet --format html,json --output-dir /var/lib/vmic --output-name current
+
+  # /etc/systemd/system/vmic.timer
+  [Timer]
+  OnCalendar=hourly
+  Persistent=true
+  [Install]
+  WantedBy=timers.target
+
+  systemctl enable --now vmic.timer";
+
+/// Categories `--redact` enables on its own.
+const DEFAULT_REDACTION: [RedactCategory; 3] = [
+    RedactCategory::Ips,
+    RedactCategory::Usernames,
+    RedactCategory::Hostnames,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum FailOn {
+    Warning,
+    Critical,
+    Never,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 enum OutputFormat {
     Markdown,
     Json,
     Html,
+    /// Prometheus text exposition for node_exporter's textfile collector
+    Prometheus,
 }
 
 impl OutputFormat {
@@ -80,6 +601,7 @@ impl OutputFormat {
             OutputFormat::Markdown => "md",
             OutputFormat::Json => "json",
             OutputFormat::Html => "html",
+            OutputFormat::Prometheus => "prom",
         }
     }
 
@@ -88,163 +610,1908 @@ impl OutputFormat {
             OutputFormat::Markdown => "Markdown",
             OutputFormat::Json => "JSON",
             OutputFormat::Html => "HTML",
+            OutputFormat::Prometheus => "Prometheus",
         }
     }
 }
 
+/// One run's report, the files and archive written for it, and the baseline
+/// comparison.
+type RunOutcome = (
+    Report,
+    Vec<(OutputFormat, PathBuf)>,
+    Option<PathBuf>,
+    Option<ReportDiff>,
+);
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let thresholds = load_thresholds(&cli)?;
+    init_logging(cli.verbose);
+    if let Some(Command::ListCollectors { format }) = &cli.command {
+        list_collectors(format);
+        return Ok(());
+    }
+    if let Some(Command::Doctor { format }) = &cli.command {
+        let (output, ready) = doctor(format);
+        print!("{}", output);
+        std::process::exit(i32::from(!ready));
+    }
+    if let Some(Command::Schema { section, list }) = &cli.command {
+        println!("{}", schema_command(section.as_deref(), *list)?);
+        return Ok(());
+    }
+    if let Some(Command::Completions { shell }) = &cli.command {
+        print!("{}", completions(*shell));
+        return Ok(());
+    }
+    if let Some(Command::Diff {
+        old,
+        new,
+        format,
+        usage_delta,
+    }) = &cli.command
+    {
+        let changed = diff_command(old, new, format, *usage_delta, cli.locale)?;
+        std::process::exit(i32::from(changed));
+    }
+    if let Some(Command::Validate { files, quiet }) = &cli.command {
+        let valid = validate_files(files, *quiet, &mut std::io::stdout().lock())?;
+        std::process::exit(i32::from(!valid));
+    }
+    let config = Config::resolve(&cli)?;
+    if let Some(Command::Config {
+        action: ConfigCommand::Show,
+    }) = &cli.command
+    {
+        match &config.source {
+            Some(path) => println!("# config file: {}", path.display()),
+            None => println!("# no config file"),
+        }
+        print!("{}", config.to_toml_string());
+        return Ok(());
+    }
+    if let Some(Command::Render {
+        input,
+        format,
+        output,
+    }) = &cli.command
+    {
+        let report = Report::from_json_value(&load_input(input)?)?;
+        let options = render_options(&cli);
+        match output {
+            Some(path) => {
+                if let Some((_, path)) =
+                    write_single_output(&report, format, &options, path, Compress::Never)?
+                    && !cli.quiet
+                {
+                    eprintln!(
+                        "{} report written to {}",
+                        format.display_name(),
+                        path.display()
+                    );
+                }
+            }
+            None => print_output(&render(&report, format, &options)?, false)?,
+        }
+        return Ok(());
+    }
+    if let Some(Command::Digest { input, format }) = &cli.command {
+        let digest = vmic_core::health::digest_from_json(&load_input(input)?, &config.digest)?;
+        match format {
+            DigestFormat::Text => print!(
+                "{}",
+                digest.filtered(cli.min_severity).to_text(stdout_color())
+            ),
+            DigestFormat::Json => println!("{}", serde_json::to_string_pretty(&digest)?),
+        }
+        return Ok(());
+    }
+    if let Some(template) = &cli.name_template
+        && config.formats.len() > 1
+        && !template.has_format()
+    {
+        return Err(anyhow!(
+            "--name-template needs {{format}} when {} formats are requested",
+            config.formats.len()
+        ));
+    }
+    if config.output.is_some() && config.formats.len() > 1 {
+        return Err(anyhow!(
+            "--output writes a single format but {} were requested; use --output-dir for several",
+            config.formats.len()
+        ));
+    }
+    let since = config
+        .since
+        .as_deref()
+        .map(|value| since::normalize(value, &chrono::Local::now()))
+        .transpose()
+        .map_err(|err| anyhow!(err))?;
+    let thresholds = config.digest;
+    let only = collector_ids(&config.only, "only")?;
+    let exclude = collector_ids(&config.exclude, "exclude")?;
     let mut context = Context::new();
-    context.set_since(cli.since.clone());
-    let report = collect_report_with_digest(&context, thresholds);
+    context.set_since(since);
+    context.set_locale(cli.locale);
+    context.set_validate_schemas(cli.validate_schemas);
+    context.set_detail_level(cli.detail_level);
+    context.set_skip_unavailable(cli.skip_unavailable);
+    context.set_root(cli.root.clone());
+    context.set_fingerprint_salt(cli.fingerprint_salt.clone());
+    context.set_max_jobs(config.jobs);
+    context.set_collector_timeout(cli.timeout);
+    for (id, timeout) in &cli.collector_timeout {
+        context.set_collector_timeout_for(id.clone(), *timeout);
+    }
+    for (id, key, value) in &cli.settings {
+        context.set_setting(id.as_str(), key.as_str(), value.as_str());
+    }
+    if let Some(seconds) = cli.slow_collector_secs {
+        let threshold = Duration::try_from_secs_f64(seconds)
+            .context("--slow-collector-secs must be a non-negative number of seconds")?;
+        context.set_slow_collector_threshold(threshold);
+    }
+    let upload = Upload::from_cli(&cli)?;
+    let notifier = Notifier::from_cli(&cli)?;
+    let email = Email::from_cli(&cli)?;
+    let render_options = render_options(&cli);
+
+    // -v logging already reports every collector.
+    let progress = Progress::for_stderr(cli.quiet || cli.verbose > 0);
+
+    // Collection with history and redaction applied; `record` appends the run
+    // to `--history`.
+    let collect = |record: bool| -> (Report, Option<RedactionPolicy>) {
+        let aging = cli
+            .history
+            .as_deref()
+            .map(|path| load_degraded_aging(path, cli.degraded_escalation_runs));
+        let include = |metadata: &vmic_core::CollectorMetadata| {
+            let listed = |ids: &[String]| ids.iter().any(|id| id == metadata.id);
+            (only.is_empty() || listed(&only)) && !listed(&exclude)
+        };
+        let mut report = match progress {
+            Some(progress) => collect_report_with_progress(
+                &context,
+                thresholds,
+                aging.as_ref(),
+                include,
+                &|event| progress.report(event),
+            ),
+            None => collect_report_filtered(&context, thresholds, aging.as_ref(), include),
+        };
+        report.metadata.labels = config.labels.clone();
+        if record && let Some(path) = &cli.history {
+            record_history(&mut report, path);
+        }
+        let redaction = redaction_policy(&cli);
+        if let Some(policy) = &redaction {
+            report.redact(policy);
+        }
+        (report, redaction)
+    };
+
+    if cli.digest_only {
+        let (report, _) = collect(false);
+        let digest = report.health_digest.filtered(cli.min_severity);
+        print!("{}", digest.to_text(stdout_color()));
+        if let Some((code, message)) = health_exit(&report, cli.fail_on) {
+            eprintln!("{}", message);
+            std::process::exit(code);
+        }
+        return Ok(());
+    }
+
+    // One collection, redaction, and write; `--watch` repeats it.
+    let run_once = || -> Result<RunOutcome> {
+        let (report, redaction) = collect(true);
+        let hostname = notify::report_hostname(redaction.as_ref());
+        let comparison = cli
+            .baseline
+            .as_deref()
+            .and_then(|path| baseline::compare(path, &report, thresholds));
+        let render_options = RenderOptions {
+            baseline: comparison.clone(),
+            ..render_options.clone()
+        };
+
+        let output_dir = match config.output_dir.as_deref() {
+            Some(dir) => dir.to_path_buf(),
+            None => env::current_dir()?,
+        };
+        let stem = cli
+            .output_name
+            .clone()
+            .unwrap_or_else(|| base_name(&report));
+        let names = match (&cli.output_name, &cli.name_template) {
+            (Some(name), _) => Some(NameTemplate::fixed(name)),
+            (None, template) => template.clone(),
+        };
+        let archive = match (&cli.bundle, cli.archive) {
+            (Some(path), _) => Some(path.clone()),
+            (None, Some(kind)) => {
+                Some(output_dir.join(format!("{}.{}", stem, kind.file_extension())))
+            }
+            (None, None) => None,
+        };
+        let archive = archive
+            .map(|path| {
+                archive::write_archive(
+                    &report,
+                    &config.formats,
+                    &render_options,
+                    &stem,
+                    &hostname,
+                    &path,
+                )
+            })
+            .transpose()?;
+        let written: Vec<(OutputFormat, PathBuf)> = match &config.output {
+            _ if archive.is_some() => Vec::new(),
+            Some(target) => write_single_output(
+                &report,
+                &config.formats[0],
+                &render_options,
+                target,
+                Compress::from_cli(&cli),
+            )?
+            .into_iter()
+            .collect(),
+            None => write_outputs(
+                &report,
+                &config.formats,
+                config.output_dir.as_deref(),
+                &render_options,
+                names.as_ref(),
+                &hostname,
+                Compress::from_cli(&cli),
+            )?,
+        };
+        if let Some(keep) = cli.keep {
+            let extensions = written
+                .iter()
+                .filter(|(format, _)| *format != OutputFormat::Prometheus)
+                .map(|(format, path)| written_extension(format, path))
+                .chain(cli.archive.map(|kind| kind.file_extension().to_string()));
+            for extension in extensions {
+                for path in watch::prune(&output_dir, &extension, keep.get())? {
+                    log::info!("removed old report {}", path.display());
+                }
+            }
+        }
+        if !cli.quiet {
+            let redacted = if redaction.is_some() {
+                " (redacted)"
+            } else {
+                ""
+            };
+            for (format, path) in &written {
+                eprintln!(
+                    "{} report written to {}{}",
+                    format.display_name(),
+                    path.display(),
+                    redacted
+                );
+            }
+            if let Some(path) = &archive {
+                eprintln!("Report archive written to {}{}", path.display(), redacted);
+            }
+        }
+        if let Some(upload) = &upload {
+            let sent = render(&report, &upload.format, &render_options)
+                .and_then(|body| upload.send(&body));
+            match sent {
+                Ok(()) if !cli.quiet => eprintln!(
+                    "{} report uploaded to {}",
+                    upload.format.display_name(),
+                    upload.url
+                ),
+                Ok(()) => {}
+                Err(err) if cli.upload_required => return Err(err),
+                Err(err) => log::warn!("{:#}", err),
+            }
+        }
+        if let Some(email) = &email {
+            let sent = email_attachments(&report, &config.formats, &render_options, &stem)
+                .and_then(|attachments| email.send(&report, &hostname, &attachments));
+            match sent {
+                Ok(()) if !cli.quiet => {
+                    eprintln!("health digest emailed to {}", email.to.join(", "))
+                }
+                Ok(()) => {}
+                Err(err) if cli.email_required => return Err(err),
+                Err(err) => log::warn!("{:#}", err),
+            }
+        }
+        if let Some(notifier) = &notifier {
+            let location = written
+                .iter()
+                .find(|(format, _)| *format == OutputFormat::Html)
+                .or(written.first())
+                .map(|(_, path)| path.as_path())
+                .or(archive.as_deref());
+            match notifier.notify(&report, &hostname, location, upload::post_json) {
+                Ok(true) if !cli.quiet => {
+                    eprintln!("health digest sent to the notification webhook")
+                }
+                Ok(_) => {}
+                Err(err) => log::warn!("notification failed: {:#}", err),
+            }
+        }
+        if cli.syslog {
+            let logged = syslog::emit(&report, cli.syslog_min_severity);
+            log::info!("{} finding(s) sent to syslog", logged);
+        }
+        if cli.update_baseline
+            && let Some(path) = &cli.baseline
+            && let Some(path) = baseline::update(path, &report, comparison.is_some())?
+            && !cli.quiet
+        {
+            eprintln!("baseline updated at {}", path.display());
+        }
+        Ok((report, written, archive, comparison))
+    };
+
+    if cli.watch {
+        let Some(dir) = config.output_dir.as_deref() else {
+            return Err(anyhow!(
+                "--watch needs --output-dir (or output_dir in the config file)"
+            ));
+        };
+        let interval = cli.interval.unwrap_or(DEFAULT_WATCH_INTERVAL);
+        return watch::watch(dir, interval, cli.retention, || {
+            run_once().map(|(report, written, _, _)| {
+                if !cli.quiet
+                    && let Some(warning) = timeout_warning(&report)
+                {
+                    eprintln!("{}", warning);
+                }
+                written
+            })
+        });
+    }
+
+    let (report, written, archive, comparison) = run_once()?;
+    if !cli.quiet
+        && let Some(warning) = timeout_warning(&report)
+    {
+        eprintln!("{}", warning);
+    }
+    let regression = comparison
+        .as_ref()
+        .filter(|_| cli.fail_on_change)
+        .and_then(baseline::regression_exit);
+    let exit = health_exit(&report, cli.fail_on).or(regression);
+    if cli.summary_json || cli.summary_json_path.is_some() {
+        let code = exit.as_ref().map_or(0, |(code, _)| *code);
+        let summary = summary::run_summary(&report, &written, archive.as_deref(), code);
+        let summary = serde_json::to_string(&summary)?;
+        match &cli.summary_json_path {
+            Some(path) => {
+                write_report(path, format!("{}\n", summary), true)?;
+            }
+            None => eprintln!("{}", summary),
+        }
+    }
+    if let Some((code, message)) = exit {
+        eprintln!("{}", message);
+        std::process::exit(code);
+    }
+
+    Ok(())
+}
 
-    let formats = if cli.formats.is_empty() {
-        vec![OutputFormat::Markdown]
+/// Render options shared by every format written in one run.
+fn render_options(cli: &Cli) -> RenderOptions {
+    let base = if cli.full_detail {
+        RenderOptions {
+            include_raw_body: true,
+            raw_body_max_bytes: usize::MAX,
+            ..RenderOptions::full_detail()
+        }
     } else {
-        cli.formats.clone()
+        RenderOptions::default()
     };
+    RenderOptions {
+        max_table_rows: match cli.max_table_rows {
+            Some(rows) => Some(rows.get()),
+            None => base.max_table_rows,
+        },
+        compact_tables: base.compact_tables && cli.max_table_rows.is_none(),
+        locale: cli.locale,
+        summary_only: cli.summary_only,
+        min_finding_severity: cli.min_severity,
+        ..base
+    }
+}
 
-    let multi_output = formats.len() > 1;
-    let explicit_dir = cli.output_dir.is_some();
-    let needs_dir = formats
-        .iter()
-        .any(|format| format_requires_file(format, multi_output, explicit_dir));
+fn render(report: &Report, format: &OutputFormat, options: &RenderOptions) -> Result<String> {
+    match format {
+        OutputFormat::Markdown => report.to_markdown_with(options),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(&report.to_json_value())?),
+        OutputFormat::Html => report.to_html_with(options),
+        OutputFormat::Prometheus => Ok(report.to_prometheus()),
+    }
+}
+
+/// Writes `rendered` to `path`, creating missing parent directories, and
+/// returns the resolved path. With `atomic` the file is replaced through a
+/// temporary file so a reader such as a scraper never sees a partial file.
+fn write_report(path: &Path, rendered: impl AsRef<[u8]>, atomic: bool) -> Result<PathBuf> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    if atomic {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let staging = path.with_file_name(format!(".{}.tmp", file_name));
+        fs::write(&staging, rendered)
+            .with_context(|| format!("failed to write {}", staging.display()))?;
+        fs::rename(&staging, path)
+            .with_context(|| format!("failed to replace {}", path.display()))?;
+    } else {
+        fs::write(path, rendered).with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()))
+}
+
+/// `--compress` / `--compress-over`: which JSON outputs are gzip'd.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Compress {
+    #[default]
+    Never,
+    Always,
+    /// Files whose JSON is larger than this many bytes.
+    Over(u64),
+}
+
+impl Compress {
+    fn from_cli(cli: &Cli) -> Self {
+        match (cli.compress, cli.compress_over) {
+            (true, _) => Compress::Always,
+            (false, Some(limit)) => Compress::Over(limit),
+            (false, None) => Compress::Never,
+        }
+    }
+
+    /// Whether `rendered` is gzip'd; only an explicit `--compress` applies to
+    /// stdout.
+    fn applies(self, format: &OutputFormat, rendered: &str, stdout: bool) -> bool {
+        *format == OutputFormat::Json
+            && match self {
+                Compress::Never => false,
+                Compress::Always => true,
+                Compress::Over(limit) => !stdout && rendered.len() as u64 > limit,
+            }
+    }
+}
+
+/// `path` with `.gz` appended to its file name unless it already ends with it.
+fn gz_path(path: &Path) -> PathBuf {
+    if path.extension().is_some_and(|extension| extension == "gz") {
+        return path.to_path_buf();
+    }
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".gz");
+    path.with_file_name(name)
+}
+
+/// Extension of a written report file, such as `json` or `json.gz`.
+pub(crate) fn written_extension(format: &OutputFormat, path: &Path) -> String {
+    if path.extension().is_some_and(|extension| extension == "gz") {
+        format!("{}.gz", format.file_extension())
+    } else {
+        format.file_extension().to_string()
+    }
+}
+
+pub(crate) fn gzip(data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
 
-    let output_dir = if needs_dir {
-        let dir = match &cli.output_dir {
-            Some(path) => path.clone(),
+/// Prints `rendered` to stdout, gzip'd when `compressed`; compressed data is
+/// never sent to a terminal.
+fn print_output(rendered: &str, compressed: bool) -> Result<()> {
+    if !compressed {
+        println!("{}", rendered);
+        return Ok(());
+    }
+    let mut stdout = std::io::stdout().lock();
+    if stdout.is_terminal() {
+        return Err(anyhow!(
+            "refusing to write gzip'd JSON to a terminal; redirect stdout or drop --compress"
+        ));
+    }
+    stdout.write_all(&gzip(format!("{}\n", rendered).as_bytes())?)?;
+    Ok(stdout.flush()?)
+}
+
+/// Writes `rendered` to `path`, gzip'd to `<path>.gz` when `compressed`.
+fn write_rendered(
+    path: &Path,
+    rendered: String,
+    compressed: bool,
+    atomic: bool,
+) -> Result<PathBuf> {
+    if compressed {
+        write_report(&gz_path(path), gzip(rendered.as_bytes())?, atomic)
+    } else {
+        write_report(path, rendered, atomic)
+    }
+}
+
+/// `--output`: one format to an exact path, or stdout for `-`. Returns the
+/// written file, if any; a gzip'd file gets `.gz` appended unless the path
+/// already ends with it.
+fn write_single_output(
+    report: &Report,
+    format: &OutputFormat,
+    options: &RenderOptions,
+    target: &Path,
+    compress: Compress,
+) -> Result<Option<(OutputFormat, PathBuf)>> {
+    let rendered = render(report, format, options)?;
+    let stdout = target == Path::new("-");
+    let compressed = compress.applies(format, &rendered, stdout);
+    if stdout {
+        print_output(&rendered, compressed)?;
+        return Ok(None);
+    }
+    let atomic = *format == OutputFormat::Prometheus;
+    Ok(Some((
+        format.clone(),
+        write_rendered(target, rendered, compressed, atomic)?,
+    )))
+}
+
+/// Renders `formats` into `output_dir` at the paths `names` gives, by default
+/// `vmic-report-<timestamp>.<ext>`, with `host` for `{hostname}`. Names that
+/// later runs write again are replaced atomically.
+fn write_outputs(
+    report: &Report,
+    formats: &[OutputFormat],
+    output_dir: Option<&Path>,
+    options: &RenderOptions,
+    names: Option<&NameTemplate>,
+    host: &str,
+    compress: Compress,
+) -> Result<Vec<(OutputFormat, PathBuf)>> {
+    // A lone format goes to stdout unless a directory or file name was asked for.
+    let output_dir = if formats.len() > 1 || output_dir.is_some() || names.is_some() {
+        let dir = match output_dir {
+            Some(path) => path.to_path_buf(),
             None => env::current_dir()?,
         };
-        fs::create_dir_all(&dir)?;
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create output directory {}", dir.display()))?;
         Some(dir)
     } else {
         None
     };
 
-    let timestamp = report.metadata.generated_at_utc().unwrap_or_else(Utc::now);
-    let base_name = format!("vmic-report-{}", timestamp.format("%Y-%m-%dT%H-%M-%SZ"));
+    let names = names.cloned().unwrap_or_default();
 
+    let mut written = Vec::new();
     for format in formats {
-        match format {
-            OutputFormat::Markdown => {
-                let rendered = report.to_markdown()?;
-                if format_requires_file(&format, multi_output, explicit_dir) {
-                    let dir = output_dir
-                        .as_ref()
-                        .expect("output directory available for markdown");
-                    let path = dir.join(format!("{}.{}", base_name, format.file_extension()));
-                    fs::write(&path, rendered)?;
-                    println!(
-                        "{} report written to {}",
-                        format.display_name(),
-                        path.display()
-                    );
-                } else {
-                    println!("{}", rendered);
-                }
+        let rendered = render(report, format, options)?;
+        match &output_dir {
+            Some(dir) => {
+                // node_exporter reads one fixed file, so it is overwritten each run.
+                let path = match format {
+                    OutputFormat::Prometheus => dir.join(PROMETHEUS_FILE),
+                    _ => dir.join(names.render(report, format, host)?),
+                };
+                let atomic = names.is_reused() || *format == OutputFormat::Prometheus;
+                let compressed = compress.applies(format, &rendered, false);
+                let path = write_rendered(&path, rendered, compressed, atomic)?;
+                written.push((format.clone(), path));
             }
-            OutputFormat::Json => {
-                let payload = report.to_json_value();
-                let rendered = serde_json::to_string_pretty(&payload)?;
-                if format_requires_file(&format, multi_output, explicit_dir) {
-                    let dir = output_dir
-                        .as_ref()
-                        .expect("output directory available for json");
-                    let path = dir.join(format!("{}.{}", base_name, format.file_extension()));
-                    fs::write(&path, rendered)?;
-                    println!(
-                        "{} report written to {}",
-                        format.display_name(),
-                        path.display()
-                    );
-                } else {
-                    println!("{}", rendered);
-                }
+            None => print_output(&rendered, compress.applies(format, &rendered, true))?,
+        }
+    }
+    Ok(written)
+}
+
+/// The requested HTML and JSON reports for `--email-to`, or the JSON report
+/// when neither format was requested.
+fn email_attachments(
+    report: &Report,
+    formats: &[OutputFormat],
+    options: &RenderOptions,
+    base_name: &str,
+) -> Result<Vec<Attachment>> {
+    let mut kinds: Vec<OutputFormat> = [OutputFormat::Html, OutputFormat::Json]
+        .into_iter()
+        .filter(|format| formats.contains(format))
+        .collect();
+    if kinds.is_empty() {
+        kinds.push(OutputFormat::Json);
+    }
+    kinds
+        .iter()
+        .map(|format| {
+            Ok(Attachment {
+                name: format!("{}.{}", base_name, format.file_extension()),
+                content_type: match format {
+                    OutputFormat::Html => "text/html; charset=utf-8",
+                    _ => "application/json",
+                },
+                data: render(report, format, options)?.into_bytes(),
+            })
+        })
+        .collect()
+}
+
+/// Whether stdout is a terminal and `NO_COLOR` is unset or empty.
+fn stdout_color() -> bool {
+    std::io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+/// `vmic-report-<timestamp>`, the stem shared by the files of one run.
+fn base_name(report: &Report) -> String {
+    let timestamp = report.metadata.generated_at_utc().unwrap_or_else(Utc::now);
+    format!("vmic-report-{}", timestamp.format("%Y-%m-%dT%H-%M-%SZ"))
+}
+
+/// Policy selected by `--redact`, `--redact-keep`, and `--redact-only`, or `None`
+/// when nothing should be redacted. Tokens are salted with `--fingerprint-salt`
+/// when given, otherwise with a per-run value so they cannot be matched across runs.
+fn redaction_policy(cli: &Cli) -> Option<RedactionPolicy> {
+    let categories: Vec<RedactCategory> = if !cli.redact_only.is_empty() {
+        cli.redact_only.clone()
+    } else if cli.redact || !cli.redact_keep.is_empty() {
+        DEFAULT_REDACTION
+            .into_iter()
+            .filter(|category| !cli.redact_keep.contains(category))
+            .collect()
+    } else {
+        return None;
+    };
+    let salt = cli.fingerprint_salt.clone().unwrap_or_else(|| {
+        format!(
+            "{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        )
+    });
+    let mut policy = RedactionPolicy {
+        salt,
+        ..RedactionPolicy::default()
+    };
+    for category in categories {
+        let enabled = match category {
+            RedactCategory::Ips => &mut policy.ip_addresses,
+            RedactCategory::Usernames => &mut policy.usernames,
+            RedactCategory::Hostnames => &mut policy.hostnames,
+            RedactCategory::Ssh => &mut policy.ssh_activity,
+            RedactCategory::Mounts => &mut policy.mount_sources,
+            RedactCategory::Containers => &mut policy.container_names,
+            RedactCategory::Commands => &mut policy.commands,
+            RedactCategory::Messages => &mut policy.log_messages,
+        };
+        *enabled = true;
+    }
+    Some(policy)
+}
+
+/// Exit code and stderr line for `--fail-on`, or `None` when the run should
+/// exit successfully.
+fn health_exit(report: &Report, fail_on: FailOn) -> Option<(i32, String)> {
+    let overall = report.health_digest.overall;
+    let code = match overall {
+        Severity::Critical => 2,
+        Severity::Warning => 1,
+        Severity::Info => return None,
+    };
+    let failing = match fail_on {
+        FailOn::Warning => true,
+        FailOn::Critical => overall == Severity::Critical,
+        FailOn::Never => false,
+    };
+    failing.then(|| {
+        let message = format!(
+            "health: {} ({} findings) — exiting {}",
+            overall.as_str(),
+            report.health_digest.findings.len(),
+            code
+        );
+        (code, message)
+    })
+}
+
+/// End-of-run stderr line naming the collectors abandoned after their
+/// timeout, or `None` when every collector finished.
+fn timeout_warning(report: &Report) -> Option<String> {
+    let timed_out = &report.metadata.timing.as_ref()?.timed_out;
+    (!timed_out.is_empty()).then(|| {
+        format!(
+            "warning: {} collector(s) timed out: {}",
+            timed_out.len(),
+            timed_out.join(", ")
+        )
+    })
+}
+
+/// History problems are reported on stderr and never abort the run.
+fn load_degraded_aging(path: &Path, escalate_after: usize) -> DegradedAging {
+    let window = vmic_core::history::DEFAULT_TREND_WINDOW.max(escalate_after + 1);
+    match vmic_core::history::load(path, window) {
+        Ok(history) => DegradedAging::from_history(&history.entries, escalate_after),
+        Err(error) => {
+            eprintln!("warning: failed to load history: {:#}", error);
+            DegradedAging {
+                escalate_after,
+                ..DegradedAging::default()
             }
-            OutputFormat::Html => {
-                let rendered = report.to_html()?;
-                let dir = output_dir
-                    .as_ref()
-                    .expect("output directory available for html");
-                let path = dir.join(format!("{}.{}", base_name, format.file_extension()));
-                fs::write(&path, rendered)?;
-                println!(
-                    "{} report written to {}",
-                    format.display_name(),
-                    path.display()
-                );
+        }
+    }
+}
+
+fn record_history(report: &mut vmic_core::Report, path: &Path) {
+    if let Err(error) = report.attach_history(path) {
+        eprintln!("warning: failed to load history: {:#}", error);
+    }
+    if let Err(error) = vmic_core::history::append(path, report) {
+        eprintln!("warning: failed to record history: {:#}", error);
+    }
+}
+
+/// Sends log records to stderr: warnings by default, info with `-v`, debug
+/// with `-vv`. `RUST_LOG` takes precedence when set.
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => "warn",
+        1 => "info",
+        _ => "debug",
+    };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level))
+        .format_timestamp(None)
+        .format_target(verbose > 1)
+        .init();
+}
+
+/// Prints the diff of two saved reports and returns whether anything material changed.
+fn diff_command(
+    old: &Path,
+    new: &Path,
+    format: &DiffFormat,
+    usage_delta: f64,
+    locale: Locale,
+) -> Result<bool> {
+    if !(0.0..=100.0).contains(&usage_delta) {
+        return Err(anyhow!("--usage-delta must be between 0 and 100"));
+    }
+    let old_report = diff::load_report(old)?;
+    let new_report = diff::load_report(new)?;
+    let options = DiffOptions {
+        usage_delta: usage_delta / 100.0,
+        ..DiffOptions::default()
+    };
+    let changes = diff::diff_reports(&old_report, &new_report, &options);
+    match format {
+        DiffFormat::Markdown => {
+            let rendered = changes.to_markdown_with(&RenderOptions {
+                locale,
+                ..RenderOptions::default()
+            })?;
+            print!("{}", rendered);
+        }
+        DiffFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&changes.to_json_value())?
+        ),
+    }
+    Ok(changes.is_material())
+}
+
+/// Parses the saved report at `path` (`-` for stdin), listing every schema
+/// violation when it does not match.
+fn load_input(path: &Path) -> Result<serde_json::Value> {
+    let (name, source) = if path == Path::new("-") {
+        let mut source = String::new();
+        std::io::stdin()
+            .read_to_string(&mut source)
+            .context("failed to read the report from stdin")?;
+        ("stdin".to_string(), source)
+    } else {
+        let source = diff::read_report_source(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        (path.display().to_string(), source)
+    };
+    let document: serde_json::Value =
+        serde_json::from_str(&source).with_context(|| format!("{} is not valid JSON", name))?;
+    let violations = schema::report_violations(&document);
+    if !violations.is_empty() {
+        let lines: Vec<String> = violations
+            .iter()
+            .map(|violation| format!("  {}", violation))
+            .collect();
+        return Err(anyhow!(
+            "{} does not match the report schema:\n{}",
+            name,
+            lines.join("\n")
+        ));
+    }
+    Ok(document)
+}
+
+/// Writes one verdict per file (failures only when `quiet`) and returns whether
+/// every file is valid. Unreadable and non-JSON files are reported apart from
+/// schema violations.
+fn validate_files(files: &[PathBuf], quiet: bool, out: &mut impl Write) -> Result<bool> {
+    let mut all_valid = true;
+    for path in files {
+        let display = path.display();
+        let source = match diff::read_report_source(path) {
+            Ok(source) => source,
+            Err(err) => {
+                all_valid = false;
+                writeln!(out, "{}: cannot read file: {}", display, err)?;
+                continue;
+            }
+        };
+        let document: serde_json::Value = match serde_json::from_str(&source) {
+            Ok(document) => document,
+            Err(err) => {
+                all_valid = false;
+                writeln!(out, "{}: not valid JSON: {}", display, err)?;
+                continue;
             }
+        };
+        let violations = schema::report_violations(&document);
+        if violations.is_empty() {
+            if !quiet {
+                writeln!(out, "{}: OK", display)?;
+            }
+            continue;
+        }
+        all_valid = false;
+        let noun = if violations.len() == 1 {
+            "violation"
+        } else {
+            "violations"
+        };
+        writeln!(out, "{}: {} schema {}", display, violations.len(), noun)?;
+        for violation in &violations {
+            writeln!(out, "  {}", violation)?;
         }
     }
+    Ok(all_valid)
+}
 
-    Ok(())
+/// `vmic schema`: the pretty-printed report schema, one section's body schema,
+/// or with `list` the ids accepted by `--section` after `report`.
+fn schema_command(section: Option<&str>, list: bool) -> Result<String> {
+    if list {
+        let mut ids = vec!["report"];
+        ids.extend(schema::section_schema_ids());
+        return Ok(ids.join("\n"));
+    }
+    let document = match section {
+        None | Some("report") => schema::report_schema(),
+        Some(id) => schema::section_schema(id).ok_or_else(|| {
+            let available = schema::section_schema_ids().join(", ");
+            if registered_collector_ids().contains(&id) {
+                anyhow!(
+                    "section '{}' has no published schema; available: {}",
+                    id,
+                    available
+                )
+            } else {
+                anyhow!("unknown section id '{}'; available: {}", id, available)
+            }
+        })?,
+    };
+    Ok(serde_json::to_string_pretty(document)?)
 }
 
-fn format_requires_file(format: &OutputFormat, multi: bool, explicit_dir: bool) -> bool {
-    matches!(format, OutputFormat::Html) || explicit_dir || multi
+fn completions(shell: Shell) -> String {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "vmic", &mut script);
+    String::from_utf8(script).expect("completion scripts are UTF-8")
 }
 
-fn load_thresholds(cli: &Cli) -> Result<DigestThresholds> {
-    let mut thresholds = DigestThresholds::default();
+/// Cargo features that change what a collector can do, as
+/// (collector id, feature, enabled in this build).
+fn feature_gates() -> [(&'static str, &'static str, bool); 2] {
+    [
+        ("docker", "docker client", mod_docker::CLIENT_ENABLED),
+        ("journal", "journal", cfg!(feature = "journal")),
+    ]
+}
 
-    apply_env_override("VMIC_DIGEST_DISK_WARNING", |ratio| {
-        thresholds.disk_warning = ratio;
-        Ok(())
-    })?;
-    apply_env_override("VMIC_DIGEST_DISK_CRITICAL", |ratio| {
-        thresholds.disk_critical = ratio;
-        Ok(())
-    })?;
-    apply_env_override("VMIC_DIGEST_MEMORY_WARNING", |ratio| {
-        thresholds.memory_warning = ratio;
-        Ok(())
-    })?;
-    apply_env_override("VMIC_DIGEST_MEMORY_CRITICAL", |ratio| {
-        thresholds.memory_critical = ratio;
-        Ok(())
-    })?;
+fn list_collectors(format: &ListFormat) {
+    let collectors = registered_collectors();
+    let gates = feature_gates();
+    let gate_for = |id: &str| gates.iter().find(|(collector, _, _)| *collector == id);
 
-    if let Some(value) = cli.digest_disk_warning {
-        thresholds.disk_warning = percent_to_ratio(value)?;
+    match format {
+        ListFormat::Json => {
+            let entries: Vec<_> = collectors
+                .iter()
+                .map(|metadata| {
+                    json!({
+                        "id": metadata.id,
+                        "title": metadata.title,
+                        "description": metadata.description,
+                        "tags": metadata.all_tags(),
+                        "requires_root": metadata.requires_root,
+                        "version": metadata.version,
+                        "cost": metadata.cost,
+                        "feature": gate_for(metadata.id)
+                            .map(|(_, name, enabled)| json!({ "name": name, "enabled": enabled })),
+                    })
+                })
+                .collect();
+            let features: serde_json::Map<String, serde_json::Value> = gates
+                .iter()
+                .map(|(_, name, enabled)| (name.to_string(), json!(enabled)))
+                .collect();
+            let document = json!({ "collectors": entries, "features": features });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&document).expect("listing serializes to JSON")
+            );
+        }
+        ListFormat::Table => {
+            let rows: Vec<[String; 6]> = collectors
+                .iter()
+                .map(|metadata| {
+                    let feature = match gate_for(metadata.id) {
+                        Some((_, name, true)) => format!("{} (enabled)", name),
+                        Some((_, name, false)) => format!("{} (disabled)", name),
+                        None => "-".to_string(),
+                    };
+                    [
+                        metadata.id.to_string(),
+                        metadata.title.to_string(),
+                        feature,
+                        match metadata.all_tags() {
+                            tags if tags.is_empty() => "-".to_string(),
+                            tags => tags.join(","),
+                        },
+                        metadata.cost.to_string(),
+                        metadata.description.to_string(),
+                    ]
+                })
+                .collect();
+            let header =
+                ["ID", "TITLE", "FEATURE", "TAGS", "COST", "DESCRIPTION"].map(String::from);
+            let mut widths = header.clone().map(|cell| cell.len());
+            for row in &rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+            for row in std::iter::once(&header).chain(&rows) {
+                println!(
+                    "{:<w0$}  {:<w1$}  {:<w2$}  {:<w3$}  {:<w4$}  {}",
+                    row[0],
+                    row[1],
+                    row[2],
+                    row[3],
+                    row[4],
+                    row[5],
+                    w0 = widths[0],
+                    w1 = widths[1],
+                    w2 = widths[2],
+                    w3 = widths[3],
+                    w4 = widths[4],
+                );
+            }
+            for (id, name, enabled) in gates {
+                if !enabled && !collectors.iter().any(|metadata| metadata.id == id) {
+                    println!("{} collector not built (feature '{}' disabled)", id, name);
+                }
+            }
+        }
     }
-    if let Some(value) = cli.digest_disk_critical {
-        thresholds.disk_critical = percent_to_ratio(value)?;
+}
+
+/// `vmic doctor`: the probe result of every linked collector, plus collectors
+/// left out of this build, and whether all of them are ready.
+fn doctor(format: &ListFormat) -> (String, bool) {
+    let mut rows: Vec<(&str, Availability)> = probe_collectors(&Context::new())
+        .into_iter()
+        .map(|(metadata, availability)| (metadata.id, availability))
+        .collect();
+    for (id, name, enabled) in feature_gates() {
+        if !enabled && !rows.iter().any(|(known, _)| *known == id) {
+            let reason = format!("not built (feature '{}' disabled)", name);
+            rows.push((id, Availability::Unavailable(reason)));
+        }
     }
-    if let Some(value) = cli.digest_memory_warning {
-        thresholds.memory_warning = percent_to_ratio(value)?;
+    rows.sort_by_key(|(id, _)| *id);
+    let ready = rows.iter().all(|(_, availability)| availability.is_ready());
+
+    let output = match format {
+        ListFormat::Json => {
+            let collectors: Vec<_> = rows
+                .iter()
+                .map(|(id, availability)| {
+                    json!({
+                        "id": id,
+                        "status": availability.as_str(),
+                        "reason": availability.reason(),
+                    })
+                })
+                .collect();
+            let document = json!({ "ready": ready, "collectors": collectors });
+            format!(
+                "{}\n",
+                serde_json::to_string_pretty(&document).expect("doctor output serializes to JSON")
+            )
+        }
+        ListFormat::Table => {
+            let width = rows
+                .iter()
+                .map(|(id, _)| id.len())
+                .max()
+                .unwrap_or(0)
+                .max(2);
+            let mut output = format!("{:<width$}  {:<11}  REASON\n", "ID", "STATUS");
+            for (id, availability) in &rows {
+                let line = format!(
+                    "{:<width$}  {:<11}  {}",
+                    id,
+                    availability.as_str(),
+                    availability.reason().unwrap_or("-")
+                );
+                output.push_str(line.trim_end());
+                output.push('\n');
+            }
+            output
+        }
+    };
+    (output, ready)
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    let seconds: f64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a number of seconds", value))?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("'{}' must be a non-negative number of seconds", value))
+}
+
+fn parse_collector_timeout(value: &str) -> Result<(String, Duration), String> {
+    let (id, seconds) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected ID=SECONDS, got '{}'", value))?;
+    let id = id.trim();
+    let known = registered_collector_ids();
+    if !known.contains(&id) {
+        return Err(format!(
+            "unknown collector id '{}'; valid ids: {}",
+            id,
+            known.join(", ")
+        ));
     }
-    if let Some(value) = cli.digest_memory_critical {
-        thresholds.memory_critical = percent_to_ratio(value)?;
+    Ok((id.to_string(), parse_seconds(seconds)?))
+}
+
+/// Splits `--set ID.KEY=VALUE`. Only the collector id is checked; each
+/// collector interprets its own keys and values.
+fn parse_setting(value: &str) -> Result<(String, String, String), String> {
+    let (name, setting) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected ID.KEY=VALUE, got '{}'", value))?;
+    let (id, key) = name
+        .trim()
+        .split_once('.')
+        .ok_or_else(|| format!("expected ID.KEY=VALUE, got '{}'", value))?;
+    let known = registered_collector_ids();
+    if !known.contains(&id) {
+        return Err(format!(
+            "unknown collector id '{}'; valid ids: {}",
+            id,
+            known.join(", ")
+        ));
     }
+    let valid_key = !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+    if !valid_key {
+        return Err(format!(
+            "invalid setting key '{}': use letters, digits, '_' or '-'",
+            key
+        ));
+    }
+    Ok((id.to_string(), key.to_string(), setting.trim().to_string()))
+}
 
-    thresholds.validate()?;
-    Ok(thresholds)
+/// Splits `KEY=VALUE` for `--label`; keys match `[a-zA-Z_][a-zA-Z0-9_]*` so
+/// they can double as Prometheus label names.
+pub(crate) fn parse_label(value: &str) -> Result<(String, String), String> {
+    let (key, label) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", value))?;
+    let key = key.trim();
+    let valid_key = key
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    if !valid_key {
+        return Err(format!(
+            "invalid label key '{}': use letters, digits, and '_', not starting with a digit",
+            key
+        ));
+    }
+    Ok((key.to_string(), label.trim().to_string()))
 }
 
-fn apply_env_override<F>(key: &str, mut assign: F) -> Result<()>
-where
-    F: FnMut(f64) -> Result<()>,
-{
-    if let Ok(value) = env::var(key) {
-        if !value.trim().is_empty() {
-            let ratio = percent_str_to_ratio(&value)
-                .with_context(|| format!("invalid value for {}", key))?;
-            assign(ratio)?;
+/// Parses `--compress-over` sizes: bytes, or a number with a K, M, or G suffix
+/// (powers of 1024, optionally followed by `B` or `iB`).
+fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid size '{}': expected e.g. 512K or 5M", value))?;
+    let unit = unit.to_ascii_lowercase();
+    let unit = unit
+        .strip_suffix("ib")
+        .or_else(|| unit.strip_suffix('b'))
+        .unwrap_or(&unit);
+    let scale: u64 = match unit {
+        "" => 1,
+        "k" => 1 << 10,
+        "m" => 1 << 20,
+        "g" => 1 << 30,
+        _ => {
+            return Err(format!("invalid size unit in '{}': use K, M, or G", value));
         }
+    };
+    amount
+        .checked_mul(scale)
+        .ok_or_else(|| format!("size '{}' is too large", value))
+}
+
+/// Checks an `--output-name` base name: a plain file name that is not hidden,
+/// since staging files for atomic replacement start with a dot.
+fn parse_output_name(value: &str) -> Result<String, String> {
+    let name = value.trim();
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return Err(format!(
+            "invalid output name '{}': use a plain file name",
+            value
+        ));
     }
-    Ok(())
+    if name.starts_with('.') {
+        return Err(format!(
+            "invalid output name '{}': must not start with '.'",
+            value
+        ));
+    }
+    Ok(name.to_string())
 }
 
-fn percent_str_to_ratio(value: &str) -> Result<f64> {
-    let parsed: f64 = value.trim().parse()?;
-    percent_to_ratio(parsed)
+/// Trims the ids of the `list` selection, expands `tag:<name>` selectors to the
+/// collectors carrying that tag and `cost:<key>` selectors (`high-io`,
+/// `medium-cpu`, `spawns-processes`) to those declaring that cost, and rejects
+/// ids, tags, and cost keys no linked collector uses.
+fn collector_ids(values: &[String], list: &str) -> Result<Vec<String>> {
+    let collectors = registered_collectors();
+    let mut ids: Vec<String> = Vec::new();
+    for value in values
+        .iter()
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+    {
+        let matched: Vec<&str> = if let Some(tag) = value.strip_prefix("tag:") {
+            collectors
+                .iter()
+                .filter(|metadata| metadata.has_tag(tag.trim()))
+                .map(|metadata| metadata.id)
+                .collect()
+        } else if let Some(key) = value.strip_prefix("cost:") {
+            collectors
+                .iter()
+                .filter(|metadata| metadata.cost.matches(key.trim()))
+                .map(|metadata| metadata.id)
+                .collect()
+        } else {
+            collectors
+                .iter()
+                .filter(|metadata| metadata.id == value)
+                .map(|metadata| metadata.id)
+                .collect()
+        };
+        if matched.is_empty() && value.starts_with("tag:") {
+            let mut tags: Vec<&str> = collectors
+                .iter()
+                .flat_map(|metadata| metadata.all_tags())
+                .collect();
+            tags.sort_unstable();
+            tags.dedup();
+            return Err(anyhow!(
+                "unknown collector tag '{}' in {} list; valid tags: {}",
+                value,
+                list,
+                tags.join(", ")
+            ));
+        }
+        if matched.is_empty() && value.starts_with("cost:") {
+            return Err(anyhow!(
+                "no collector matches cost selector '{}' in {} list; valid keys: \
+                 low-cpu, medium-cpu, high-cpu, low-io, medium-io, high-io, spawns-processes",
+                value,
+                list
+            ));
+        }
+        if matched.is_empty() {
+            return Err(anyhow!(
+                "unknown collector id '{}' in {} list; valid ids: {}",
+                value,
+                list,
+                registered_collector_ids().join(", ")
+            ));
+        }
+        for id in matched {
+            if !ids.iter().any(|known| known == id) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+    Ok(ids)
 }
 
-fn percent_to_ratio(value: f64) -> Result<f64> {
-    let ratio = if value > 1.0 { value / 100.0 } else { value };
-    if !(0.0..=1.0).contains(&ratio) {
-        anyhow::bail!("threshold must be between 0 and 100 (or 0.0-1.0)");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vmic_core::Section;
+
+    fn report(severity: Severity) -> Report {
+        let sections = match severity {
+            Severity::Info => vec![Section::success("os", "Operating System", json!({}))],
+            Severity::Warning => vec![Section::degraded(
+                "docker",
+                "Docker Containers",
+                "engine unavailable".to_string(),
+                json!({}),
+            )],
+            Severity::Critical => vec![
+                Section::error("cron", "Scheduled Jobs", "permission denied".to_string()),
+                Section::error("users", "Local Users", "permission denied".to_string()),
+                Section::degraded("sar", "Sysstat Metrics", "no data".to_string(), json!({})),
+            ],
+        };
+        Report::new(sections)
+    }
+
+    #[test]
+    fn timed_out_collectors_are_named_in_a_warning() {
+        let mut report = report(Severity::Info);
+        assert_eq!(timeout_warning(&report), None);
+        report.metadata.timing = Some(vmic_core::CollectionTiming::default());
+        assert_eq!(timeout_warning(&report), None);
+        report.metadata.timing = Some(vmic_core::CollectionTiming {
+            timed_out: vec!["docker".to_string(), "sar".to_string()],
+            ..vmic_core::CollectionTiming::default()
+        });
+        assert_eq!(
+            timeout_warning(&report).as_deref(),
+            Some("warning: 2 collector(s) timed out: docker, sar")
+        );
+    }
+
+    #[test]
+    fn fail_on_never_always_succeeds() {
+        for severity in [Severity::Info, Severity::Warning, Severity::Critical] {
+            assert_eq!(health_exit(&report(severity), FailOn::Never), None);
+        }
+    }
+
+    #[test]
+    fn fail_on_warning_maps_severity_to_exit_code() {
+        assert_eq!(health_exit(&report(Severity::Info), FailOn::Warning), None);
+        let (code, _) = health_exit(&report(Severity::Warning), FailOn::Warning).unwrap();
+        assert_eq!(code, 1);
+        let (code, message) = health_exit(&report(Severity::Critical), FailOn::Warning).unwrap();
+        assert_eq!(code, 2);
+        assert_eq!(message, "health: critical (3 findings) — exiting 2");
+    }
+
+    #[test]
+    fn fail_on_critical_ignores_warnings() {
+        assert_eq!(
+            health_exit(&report(Severity::Warning), FailOn::Critical),
+            None
+        );
+        let (code, _) = health_exit(&report(Severity::Critical), FailOn::Critical).unwrap();
+        assert_eq!(code, 2);
+    }
+
+    #[test]
+    fn single_output_creates_parent_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("nested/latest.json");
+        let (format, path) = write_single_output(
+            &report(Severity::Info),
+            &OutputFormat::Json,
+            &RenderOptions::default(),
+            &target,
+            Compress::Never,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(format, OutputFormat::Json);
+        assert_eq!(path, fs::canonicalize(&target).unwrap());
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&target).unwrap()).unwrap();
+        assert_eq!(written["sections"][0]["id"], "os");
+    }
+
+    #[test]
+    fn html_goes_to_a_file_only_when_a_directory_is_requested() {
+        let dir = tempfile::tempdir().unwrap();
+        write_outputs(
+            &report(Severity::Info),
+            &[OutputFormat::Html],
+            Some(dir.path()),
+            &RenderOptions::default(),
+            None,
+            "web-01",
+            Compress::Never,
+        )
+        .unwrap();
+        let written: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(written.len(), 1);
+        assert!(written[0].starts_with("vmic-report-") && written[0].ends_with(".html"));
+    }
+
+    #[test]
+    fn collector_timeouts_are_validated_while_parsing() {
+        let cli = Cli::try_parse_from([
+            "vmic",
+            "--timeout",
+            "30",
+            "--collector-timeout",
+            "docker=10",
+            "--collector-timeout",
+            "os=2.5",
+        ])
+        .unwrap();
+        assert_eq!(cli.timeout, Some(Duration::from_secs(30)));
+        assert_eq!(
+            cli.collector_timeout,
+            [
+                ("docker".to_string(), Duration::from_secs(10)),
+                ("os".to_string(), Duration::from_millis(2500)),
+            ]
+        );
+
+        let error = parse_collector_timeout("dockr=10").unwrap_err();
+        assert!(error.contains("unknown collector id 'dockr'") && error.contains("docker"));
+        assert!(parse_collector_timeout("docker").is_err());
+        assert!(parse_collector_timeout("docker=soon").is_err());
+        assert!(Cli::try_parse_from(["vmic", "--timeout", "-1"]).is_err());
+    }
+
+    #[test]
+    fn table_limits_apply_to_every_format() {
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from(std::iter::once("vmic").chain(args.iter().copied()))
+                .map(|cli| render_options(&cli))
+        };
+        let options = parse(&["--max-table-rows", "40", "--summary-only"]).unwrap();
+        assert_eq!(options.max_table_rows, Some(40));
+        assert!(!options.compact_tables && options.compact_lists);
+        assert_eq!(
+            options.max_list_items,
+            RenderOptions::default().max_list_items
+        );
+        assert!(options.summary_only && !options.include_raw_body);
+
+        let options = parse(&["--full-detail", "--locale", "ru"]).unwrap();
+        assert_eq!(options.max_table_rows, None);
+        assert_eq!(options.max_list_items, None);
+        assert!(options.include_raw_body);
+        assert_eq!(options.locale, Locale::Ru);
+
+        let options = parse(&["--max-table-rows", "12"]).unwrap();
+        assert_eq!(
+            options.max_table_rows,
+            RenderOptions::default().max_table_rows
+        );
+        assert!(!options.compact_tables);
+
+        assert!(parse(&["--max-table-rows", "0"]).is_err());
+        assert!(parse(&["--max-table-rows", "-3"]).is_err());
+        assert!(parse(&["--max-table-rows", "5", "--full-detail"]).is_err());
+    }
+
+    #[test]
+    fn tag_selectors_expand_to_collector_ids() {
+        let selected = |values: &[&str]| {
+            let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+            collector_ids(&values, "only")
+        };
+        assert_eq!(
+            selected(&["tag:security", "security"]).unwrap(),
+            ["security", "users"]
+        );
+        let rooted = selected(&["tag:requires-root"]).unwrap();
+        assert!(rooted.contains(&"security".to_string()));
+        assert!(!rooted.contains(&"users".to_string()));
+        assert_eq!(selected(&[" os ", "os"]).unwrap(), ["os"]);
+
+        let error = selected(&["tag:nope"]).unwrap_err().to_string();
+        assert!(error.contains("unknown collector tag 'tag:nope'") && error.contains("security"));
+        assert!(selected(&["nope"]).is_err());
+    }
+
+    #[test]
+    fn cost_selectors_expand_to_collector_ids() {
+        let selected = |values: &[&str]| {
+            let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+            collector_ids(&values, "exclude")
+        };
+        let heavy_io = selected(&["cost:high-io"]).unwrap();
+        assert!(heavy_io.contains(&"storage".to_string()));
+        assert!(!heavy_io.contains(&"os".to_string()));
+        let spawning = selected(&["cost:spawns-processes"]).unwrap();
+        assert!(spawning.contains(&"services".to_string()));
+        assert!(!spawning.contains(&"storage".to_string()));
+
+        let error = selected(&["cost:huge"]).unwrap_err().to_string();
+        assert!(error.contains("cost selector 'cost:huge'") && error.contains("high-io"));
+    }
+
+    #[test]
+    fn settings_are_split_by_collector_and_key() {
+        let cli = Cli::try_parse_from([
+            "vmic",
+            "--set",
+            "journal.lines=200",
+            "--set",
+            "storage.hotspot_depth = 4",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.settings,
+            [
+                (
+                    "journal".to_string(),
+                    "lines".to_string(),
+                    "200".to_string()
+                ),
+                (
+                    "storage".to_string(),
+                    "hotspot_depth".to_string(),
+                    "4".to_string()
+                ),
+            ]
+        );
+
+        assert!(parse_setting("journal.lines").is_err());
+        assert!(parse_setting("lines=200").is_err());
+        assert!(
+            parse_setting("journl.lines=200")
+                .unwrap_err()
+                .contains("unknown collector id")
+        );
+        assert!(parse_setting("journal.=200").is_err());
+        assert!(parse_setting("journal.a b=1").is_err());
+        // Values are left for the collector to judge.
+        assert_eq!(
+            parse_setting("docker.mode=").unwrap(),
+            ("docker".to_string(), "mode".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn validate_separates_parse_errors_from_schema_violations() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.json");
+        let garbled = dir.path().join("garbled.json");
+        let mismatched = dir.path().join("mismatched.json");
+        fs::write(&good, report(Severity::Info).to_json_value().to_string()).unwrap();
+        fs::write(&garbled, "{ nope").unwrap();
+        fs::write(&mismatched, r#"{"schema_version": 1}"#).unwrap();
+
+        let mut out = Vec::new();
+        assert!(validate_files(std::slice::from_ref(&good), false, &mut out).unwrap());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("{}: OK\n", good.display())
+        );
+
+        let mut out = Vec::new();
+        let files = [good, garbled.clone(), mismatched.clone()];
+        assert!(!validate_files(&files, true, &mut out).unwrap());
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("OK"));
+        assert!(out.contains(&format!("{}: not valid JSON", garbled.display())));
+        assert!(out.contains(&format!("{}: ", mismatched.display())));
+        assert!(out.contains("schema violations\n  /schema_version: "));
+    }
+
+    #[test]
+    fn schema_command_prints_embedded_schemas() {
+        let report: serde_json::Value =
+            serde_json::from_str(&schema_command(None, false).unwrap()).unwrap();
+        assert_eq!(&report, schema::report_schema());
+
+        let storage = schema_command(Some("storage"), false).unwrap();
+        assert!(storage.contains("\n  "));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&storage).unwrap(),
+            *schema::section_schema("storage").unwrap()
+        );
+
+        let list = schema_command(None, true).unwrap();
+        let ids: Vec<&str> = list.lines().collect();
+        assert_eq!(ids[0], "report");
+        assert!(ids.contains(&"storage") && ids.contains(&"journal"));
+
+        let unknown = schema_command(Some("disks"), false)
+            .unwrap_err()
+            .to_string();
+        assert!(unknown.contains("unknown section id 'disks'") && unknown.contains("storage"));
+        let unpublished = schema_command(Some("os"), false).unwrap_err().to_string();
+        assert!(unpublished.contains("'os' has no published schema"));
+    }
+
+    #[test]
+    fn completions_cover_flags_for_every_shell() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let script = completions(shell);
+            assert!(script.contains("format"), "{shell} script lacks --format");
+            assert!(
+                script.contains("list-collectors"),
+                "{shell} script lacks subcommands"
+            );
+        }
+        assert!(completions(Shell::Bash).contains("--format"));
+        assert!(Cli::try_parse_from(["vmic", "completions", "zsh"]).is_ok());
+        assert!(Cli::try_parse_from(["vmic", "completions", "tcsh"]).is_err());
+    }
+
+    #[test]
+    fn redaction_categories_follow_the_flags() {
+        let policy = |args: &[&str]| {
+            let cli =
+                Cli::try_parse_from(std::iter::once("vmic").chain(args.iter().copied())).unwrap();
+            redaction_policy(&cli)
+        };
+        assert!(policy(&[]).is_none());
+
+        let default = policy(&["--redact"]).unwrap();
+        assert!(default.ip_addresses && default.usernames && default.hostnames);
+        assert!(!default.container_names && !default.mount_sources);
+
+        let keep = policy(&["--redact-keep", "ips"]).unwrap();
+        assert!(!keep.ip_addresses && keep.usernames && keep.hostnames);
+
+        let only = policy(&["--redact-only", "usernames,containers"]).unwrap();
+        assert!(only.usernames && only.container_names && !only.ip_addresses);
+
+        let salted = policy(&["--redact", "--fingerprint-salt", "fleet"]).unwrap();
+        assert_eq!(salted.salt, "fleet");
+        assert!(
+            Cli::try_parse_from(["vmic", "--redact-keep", "ips", "--redact-only", "ips"]).is_err()
+        );
+    }
+
+    #[test]
+    fn redacted_outputs_do_not_leak_addresses() {
+        let network = Section::success(
+            "network",
+            "Network Overview",
+            json!({
+                "interfaces": [{ "name": "eth0", "addresses": ["203.0.113.17/24"] }],
+                "listeners": { "samples": [{ "local_address": "203.0.113.17:22", "protocol": "tcp" }] }
+            }),
+        );
+        let mut report = Report::new(vec![network]);
+        let cli = Cli::try_parse_from(["vmic", "--redact"]).unwrap();
+        report.redact(&redaction_policy(&cli).unwrap());
+
+        let dir = tempfile::tempdir().unwrap();
+        write_outputs(
+            &report,
+            &[
+                OutputFormat::Markdown,
+                OutputFormat::Json,
+                OutputFormat::Html,
+            ],
+            Some(dir.path()),
+            &RenderOptions::default(),
+            None,
+            "web-01",
+            Compress::Never,
+        )
+        .unwrap();
+        let mut written = 0;
+        for entry in fs::read_dir(dir.path()).unwrap() {
+            let contents = fs::read_to_string(entry.unwrap().path()).unwrap();
+            assert!(!contents.contains("203.0.113.17"));
+            written += 1;
+        }
+        assert_eq!(written, 3);
+    }
+
+    #[test]
+    fn verbosity_counts_and_applies_to_subcommands() {
+        let cli = Cli::try_parse_from(["vmic", "-vv", "--quiet"]).unwrap();
+        assert_eq!(cli.verbose, 2);
+        assert!(cli.quiet);
+        let cli = Cli::try_parse_from(["vmic", "validate", "a.json", "-v"]).unwrap();
+        assert_eq!(cli.verbose, 1);
+    }
+
+    #[test]
+    fn prometheus_output_overwrites_one_file() {
+        let dir = tempfile::tempdir().unwrap();
+        for _ in 0..2 {
+            let written = write_outputs(
+                &report(Severity::Warning),
+                &[OutputFormat::Prometheus, OutputFormat::Json],
+                Some(dir.path()),
+                &RenderOptions::default(),
+                None,
+                "web-01",
+                Compress::Never,
+            )
+            .unwrap();
+            assert_eq!(
+                written[0].1,
+                fs::canonicalize(dir.path().join("vmic.prom")).unwrap()
+            );
+        }
+        let names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert!(names.iter().all(|name| !name.ends_with(".tmp")));
+        assert_eq!(
+            names.iter().filter(|name| name.ends_with(".prom")).count(),
+            1
+        );
+        let metrics = fs::read_to_string(dir.path().join("vmic.prom")).unwrap();
+        assert!(metrics.starts_with("# vmic_generated_at "));
+        assert!(metrics.contains("vmic_report_timestamp_seconds "));
+    }
+
+    #[test]
+    fn name_templates_create_nested_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = NameTemplate::parse("{hostname}/{date}/report.{format}").unwrap();
+        let written = write_outputs(
+            &report(Severity::Info),
+            &[OutputFormat::Json, OutputFormat::Markdown],
+            Some(dir.path()),
+            &RenderOptions::default(),
+            Some(&template),
+            "web-01",
+            Compress::Never,
+        )
+        .unwrap();
+        let nested = fs::canonicalize(dir.path()).unwrap().join("web-01");
+        assert!(written[0].1.starts_with(&nested));
+        assert!(written[0].1.ends_with("report.json"));
+        assert!(written[1].1.ends_with("report.md"));
+
+        assert!(Cli::try_parse_from(["vmic", "--name-template", "{host}.{format}"]).is_err());
+        assert!(Cli::try_parse_from(["vmic", "--name-template", "../{format}"]).is_err());
+        assert!(
+            Cli::try_parse_from(["vmic", "--name-template", "{date}.{format}", "--keep", "3"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn run_summary_lists_the_files_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = report(Severity::Warning);
+        let written = write_outputs(
+            &report,
+            &[OutputFormat::Json, OutputFormat::Html],
+            Some(dir.path()),
+            &RenderOptions::default(),
+            None,
+            "web-01",
+            Compress::Never,
+        )
+        .unwrap();
+        let line =
+            serde_json::to_string(&summary::run_summary(&report, &written, None, 1)).unwrap();
+        assert!(!line.contains('\n'));
+
+        let summary: serde_json::Value = serde_json::from_str(&line).unwrap();
+        let outputs = summary["outputs"].as_array().unwrap();
+        assert_eq!(outputs.len(), 2);
+        for ((format, path), output) in written.iter().zip(outputs) {
+            assert!(path.is_file());
+            assert_eq!(output["path"], path.to_str().unwrap());
+            assert_eq!(output["format"], json!(format));
+        }
+        assert_eq!(summary["overall"], "warning");
+        assert_eq!(summary["exit_code"], 1);
+        assert!(summary.get("archive").is_none());
+    }
+
+    #[test]
+    fn output_name_replaces_fixed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for severity in [Severity::Info, Severity::Warning] {
+            write_outputs(
+                &report(severity),
+                &[OutputFormat::Json, OutputFormat::Html],
+                Some(dir.path()),
+                &RenderOptions::default(),
+                Some(&NameTemplate::fixed("current")),
+                "web-01",
+                Compress::Never,
+            )
+            .unwrap();
+        }
+        let mut names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["current.html", "current.json"]);
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("current.json")).unwrap())
+                .unwrap();
+        assert_eq!(json["health_digest"]["overall"], "warning");
+
+        assert!(Cli::try_parse_from(["vmic", "--output-name", "../x"]).is_err());
+        assert!(Cli::try_parse_from(["vmic", "--output-name", ".hidden"]).is_err());
+        assert!(Cli::try_parse_from(["vmic", "--output-name", "a", "--keep", "3"]).is_err());
+        assert!(Cli::try_parse_from(["vmic", "--keep", "0"]).is_err());
+        assert!(Cli::try_parse_from(["vmic", "--output-dir", "out", "--keep", "3"]).is_ok());
+    }
+
+    #[test]
+    fn doctor_lists_every_collector() {
+        let (output, ready) = doctor(&ListFormat::Json);
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(document["ready"], ready);
+        let collectors = document["collectors"].as_array().unwrap();
+        for id in registered_collector_ids() {
+            let entry = collectors.iter().find(|entry| entry["id"] == id).unwrap();
+            let status = entry["status"].as_str().unwrap();
+            assert!(["ready", "degraded", "unavailable"].contains(&status));
+            assert_eq!(entry["reason"].is_null(), status == "ready");
+        }
+        assert!(collectors.iter().all(|entry| entry["status"] == "ready") == ready);
+
+        let (table, _) = doctor(&ListFormat::Table);
+        assert!(table.starts_with("ID "));
+        assert_eq!(table.lines().count(), collectors.len() + 1);
+    }
+
+    #[test]
+    fn compressed_json_reads_back_for_validate_and_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let formats = [OutputFormat::Json, OutputFormat::Markdown];
+        let options = RenderOptions::default();
+        let report = report(Severity::Info);
+        let written = write_outputs(
+            &report,
+            &formats,
+            Some(dir.path()),
+            &options,
+            None,
+            "web-01",
+            Compress::Always,
+        )
+        .unwrap();
+        let json = &written[0].1;
+        assert!(json.to_string_lossy().ends_with(".json.gz"));
+        assert_eq!(written_extension(&OutputFormat::Json, json), "json.gz");
+        assert!(written[1].1.to_string_lossy().ends_with(".md"));
+
+        let mut out = Vec::new();
+        assert!(validate_files(std::slice::from_ref(json), false, &mut out).unwrap());
+        assert!(String::from_utf8(out).unwrap().ends_with(": OK\n"));
+        assert_eq!(diff::load_report(json).unwrap()["sections"][0]["id"], "os");
+
+        let small = tempfile::tempdir().unwrap();
+        let written = write_outputs(
+            &report,
+            &[OutputFormat::Json],
+            Some(small.path()),
+            &options,
+            None,
+            "web-01",
+            Compress::Over(1 << 20),
+        )
+        .unwrap();
+        assert!(written[0].1.to_string_lossy().ends_with(".json"));
+        assert!(!Compress::Over(0).applies(&OutputFormat::Json, "{}", true));
+        assert!(!Compress::Always.applies(&OutputFormat::Html, "<p>", false));
+    }
+
+    #[test]
+    fn saved_reports_render_again_without_collecting() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        let report = report(Severity::Warning);
+        fs::write(
+            &path,
+            serde_json::to_string(&report.to_json_value()).unwrap(),
+        )
+        .unwrap();
+
+        let restored = Report::from_json_value(&load_input(&path).unwrap()).unwrap();
+        let options = RenderOptions::full_detail();
+        assert_eq!(
+            render(&restored, &OutputFormat::Html, &options).unwrap(),
+            render(&report, &OutputFormat::Html, &options).unwrap()
+        );
+
+        fs::write(
+            &path,
+            r#"{"schema_version": "1.2", "sections": [{"id": "os"}]}"#,
+        )
+        .unwrap();
+        let error = load_input(&path).unwrap_err().to_string();
+        assert!(error.contains("does not match the report schema:\n"));
+        assert!(error.contains("  /sections/0: \"status\" is a required property"));
+        assert!(
+            Cli::try_parse_from([
+                "vmic",
+                "digest",
+                "--input",
+                "-",
+                "--digest-disk-warning",
+                "70"
+            ])
+            .is_ok()
+        );
+        assert!(
+            Cli::try_parse_from(["vmic", "render", "--input", "r.json", "--full-detail"]).is_ok()
+        );
+    }
+
+    #[test]
+    fn sizes_accept_binary_suffixes() {
+        assert_eq!(parse_size("1500"), Ok(1500));
+        assert_eq!(parse_size("512K"), Ok(512 << 10));
+        assert_eq!(parse_size("5mb"), Ok(5 << 20));
+        assert_eq!(parse_size("1GiB"), Ok(1 << 30));
+        assert!(parse_size("5T").is_err());
+        assert!(parse_size("M").is_err());
+    }
+
+    #[test]
+    fn output_conflicts_with_output_dir() {
+        assert!(Cli::try_parse_from(["vmic", "--output", "a.json", "--output-dir", "b"]).is_err());
+    }
+
+    #[test]
+    fn digest_only_writes_nothing_else() {
+        assert!(Cli::try_parse_from(["vmic", "--digest-only", "--fail-on", "warning"]).is_ok());
+        assert!(Cli::try_parse_from(["vmic", "--digest-only", "--format", "json"]).is_err());
+        assert!(Cli::try_parse_from(["vmic", "--digest-only", "--output-dir", "out"]).is_err());
+    }
+
+    #[test]
+    fn fail_on_parses_from_the_command_line() {
+        let cli = Cli::try_parse_from(["vmic", "--fail-on", "critical"]).unwrap();
+        assert_eq!(cli.fail_on, FailOn::Critical);
+        let cli = Cli::try_parse_from(["vmic"]).unwrap();
+        assert_eq!(cli.fail_on, FailOn::Never);
+        assert!(Cli::try_parse_from(["vmic", "--fail-on", "info"]).is_err());
     }
-    Ok(ratio)
 }
@@ -0,0 +1,215 @@
+//! `--name-template`: where each output file goes inside the output directory.
+//!
+//! Templates are relative paths with `{hostname}`, `{timestamp}`, `{date}`, and
+//! `{format}` placeholders. The default, `vmic-report-{timestamp}.{format}`,
+//! gives the usual timestamped names. Placeholders are checked while parsing
+//! and every rendered path must stay inside the output directory.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use vmic_core::Report;
+
+use crate::OutputFormat;
+
+const DEFAULT_TEMPLATE: &str = "vmic-report-{timestamp}.{format}";
+const PLACEHOLDERS: [&str; 4] = ["hostname", "timestamp", "date", "format"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Hostname,
+    /// `%Y-%m-%dT%H-%M-%SZ` in UTC.
+    Timestamp,
+    /// `%Y-%m-%d` in UTC.
+    Date,
+    /// The format's file extension, such as `md`.
+    Format,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NameTemplate {
+    segments: Vec<Segment>,
+}
+
+impl Default for NameTemplate {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TEMPLATE).expect("default template is valid")
+    }
+}
+
+impl NameTemplate {
+    /// Parses a template, rejecting unknown placeholders, unbalanced braces,
+    /// absolute paths, and `..` components.
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        let invalid = |reason: String| format!("invalid name template '{}': {}", value, reason);
+        let mut segments = Vec::new();
+        let mut rest = value;
+        while !rest.is_empty() {
+            let Some(open) = rest.find(['{', '}']) else {
+                segments.push(Segment::Literal(rest.to_string()));
+                break;
+            };
+            if open > 0 {
+                segments.push(Segment::Literal(rest[..open].to_string()));
+            }
+            if rest[open..].starts_with('}') {
+                return Err(invalid("unmatched '}'".to_string()));
+            }
+            let close = rest[open..]
+                .find('}')
+                .ok_or_else(|| invalid("unclosed '{'".to_string()))?;
+            let name = &rest[open + 1..open + close];
+            segments.push(match name {
+                "hostname" => Segment::Hostname,
+                "timestamp" => Segment::Timestamp,
+                "date" => Segment::Date,
+                "format" => Segment::Format,
+                _ => {
+                    return Err(invalid(format!(
+                        "unknown placeholder '{{{}}}', use {}",
+                        name,
+                        PLACEHOLDERS
+                            .iter()
+                            .map(|known| format!("{{{}}}", known))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )));
+                }
+            });
+            rest = &rest[open + close + 1..];
+        }
+        check_relative(Path::new(value)).map_err(invalid)?;
+        Ok(Self { segments })
+    }
+
+    /// `<name>.{format}`, the files of `--output-name`.
+    pub(crate) fn fixed(name: &str) -> Self {
+        Self {
+            segments: vec![Segment::Literal(format!("{}.", name)), Segment::Format],
+        }
+    }
+
+    /// Whether every format gets its own path.
+    pub(crate) fn has_format(&self) -> bool {
+        self.segments.contains(&Segment::Format)
+    }
+
+    /// Whether the same path is written again by later runs, so it has to be
+    /// replaced atomically.
+    pub(crate) fn is_reused(&self) -> bool {
+        !self.segments.contains(&Segment::Timestamp)
+    }
+
+    /// The path of `format` relative to the output directory, with `host` for
+    /// `{hostname}`. Fails when a substituted value would leave the directory.
+    pub(crate) fn render(
+        &self,
+        report: &Report,
+        format: &OutputFormat,
+        host: &str,
+    ) -> Result<PathBuf> {
+        let timestamp = report.metadata.generated_at_utc().unwrap_or_else(Utc::now);
+        let mut rendered = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => rendered.push_str(text),
+                Segment::Hostname => rendered.push_str(host),
+                Segment::Timestamp => {
+                    rendered.push_str(&timestamp.format("%Y-%m-%dT%H-%M-%SZ").to_string())
+                }
+                Segment::Date => rendered.push_str(&timestamp.format("%Y-%m-%d").to_string()),
+                Segment::Format => rendered.push_str(format.file_extension()),
+            }
+        }
+        let path = PathBuf::from(&rendered);
+        check_relative(&path)
+            .map_err(|reason| anyhow!("refusing output path '{}': {}", rendered, reason))?;
+        Ok(path)
+    }
+}
+
+/// A relative path with a file name and no `..` components.
+fn check_relative(path: &Path) -> Result<(), String> {
+    if path.as_os_str().is_empty() || path.to_string_lossy().ends_with('/') {
+        return Err("must end with a file name".to_string());
+    }
+    for component in path.components() {
+        match component {
+            Component::Normal(_) | Component::CurDir => {}
+            Component::ParentDir => return Err("'..' is not allowed".to_string()),
+            Component::RootDir | Component::Prefix(_) => {
+                return Err("must be relative to the output directory".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vmic_core::Section;
+
+    fn report() -> Report {
+        let mut report = Report::new(vec![Section::success(
+            "os",
+            "Operating System",
+            serde_json::json!({}),
+        )]);
+        // 2026-03-01T08:15:30Z
+        report.metadata.set_generated_at(1_772_352_930);
+        report
+    }
+
+    #[test]
+    fn default_template_keeps_timestamped_names() {
+        let template = NameTemplate::default();
+        assert_eq!(
+            template
+                .render(&report(), &OutputFormat::Markdown, "web-01")
+                .unwrap(),
+            PathBuf::from("vmic-report-2026-03-01T08-15-30Z.md")
+        );
+        assert!(template.has_format() && !template.is_reused());
+        assert_eq!(
+            NameTemplate::fixed("current")
+                .render(&report(), &OutputFormat::Json, "web-01")
+                .unwrap(),
+            PathBuf::from("current.json")
+        );
+    }
+
+    #[test]
+    fn placeholders_are_substituted() {
+        let template = NameTemplate::parse("{hostname}/{date}/report.{format}").unwrap();
+        let path = template
+            .render(&report(), &OutputFormat::Html, "web-01")
+            .unwrap();
+        assert_eq!(path, PathBuf::from("web-01/2026-03-01/report.html"));
+        assert!(template.is_reused());
+        assert!(!NameTemplate::parse("{hostname}.json").unwrap().has_format());
+    }
+
+    #[test]
+    fn malformed_templates_are_rejected() {
+        let error = NameTemplate::parse("{host}.{format}").unwrap_err();
+        assert!(error.contains("unknown placeholder '{host}'") && error.contains("{hostname}"));
+        assert!(NameTemplate::parse("report-{date.{format}").is_err());
+        assert!(NameTemplate::parse("report}.md").is_err());
+        assert!(NameTemplate::parse("").is_err());
+        assert!(NameTemplate::parse("reports/").is_err());
+        assert!(
+            NameTemplate::parse("/etc/{format}")
+                .unwrap_err()
+                .contains("relative")
+        );
+        assert!(
+            NameTemplate::parse("{hostname}/../{format}")
+                .unwrap_err()
+                .contains("'..'")
+        );
+        assert!(NameTemplate::parse("./{date}/a..b.{format}").is_ok());
+    }
+}
@@ -0,0 +1,212 @@
+//! `--notify-webhook`: post the health digest to a Slack/Mattermost webhook.
+//!
+//! The message is the plain `{"text": ...}` payload both services accept and
+//! is only sent when the overall severity reaches `--notify-min-severity`.
+
+use std::path::Path;
+
+use anyhow::Result;
+use serde_json::{Value, json};
+use vmic_core::{RedactionPolicy, Report, Severity};
+
+use crate::Cli;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Notifier {
+    pub(crate) url: String,
+    pub(crate) min_severity: Severity,
+    /// Finding messages listed before the rest are summarised as a count.
+    pub(crate) findings: usize,
+}
+
+impl Notifier {
+    /// Webhook settings from the command line, or `None` without `--notify-webhook`.
+    pub(crate) fn from_cli(cli: &Cli) -> Result<Option<Self>> {
+        let Some(url) = &cli.notify_webhook else {
+            return Ok(None);
+        };
+        crate::upload::check_url("--notify-webhook", url)?;
+        Ok(Some(Self {
+            url: url.clone(),
+            min_severity: cli.notify_min_severity,
+            findings: cli.notify_findings,
+        }))
+    }
+
+    /// Message for `report`, or `None` when the digest is below the threshold.
+    /// `location` is where the full report was written, if anywhere.
+    pub(crate) fn payload(
+        &self,
+        report: &Report,
+        host: &str,
+        location: Option<&Path>,
+    ) -> Option<Value> {
+        let digest = &report.health_digest;
+        if digest.overall < self.min_severity {
+            return None;
+        }
+
+        let mut findings: Vec<_> = digest.findings.iter().collect();
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+
+        let mut text = format!(
+            "vmic on {}: {} ({} {})",
+            host,
+            digest.overall.as_str(),
+            findings.len(),
+            if findings.len() == 1 {
+                "finding"
+            } else {
+                "findings"
+            }
+        );
+        for finding in findings.iter().take(self.findings) {
+            text.push_str(&format!(
+                "\n• [{}] {}: {}",
+                finding.severity.as_str(),
+                finding.source_id,
+                finding.message
+            ));
+        }
+        if findings.len() > self.findings {
+            text.push_str(&format!("\n…and {} more", findings.len() - self.findings));
+        }
+        if let Some(path) = location {
+            text.push_str(&format!("\nFull report: {}", path.display()));
+        }
+        Some(json!({ "text": text }))
+    }
+
+    /// Builds the payload for `host` and hands it to `send` with the webhook
+    /// URL. Returns whether anything was sent.
+    pub(crate) fn notify<F>(
+        &self,
+        report: &Report,
+        host: &str,
+        location: Option<&Path>,
+        send: F,
+    ) -> Result<bool>
+    where
+        F: FnOnce(&str, &Value) -> Result<()>,
+    {
+        let Some(payload) = self.payload(report, host, location) else {
+            return Ok(false);
+        };
+        send(&self.url, &payload)?;
+        Ok(true)
+    }
+}
+
+/// Kernel hostname, or `unknown host` when it cannot be read.
+pub(crate) fn hostname() -> String {
+    vmic_core::local_hostname().unwrap_or_else(|| "unknown host".to_string())
+}
+
+/// Hostname to put next to a report: the token its body uses when `redaction`
+/// covers host names, so manifests, messages, and paths do not undo it.
+pub(crate) fn report_hostname(redaction: Option<&RedactionPolicy>) -> String {
+    let name = hostname();
+    match redaction {
+        Some(policy) => policy.redact_hostname(&name),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vmic_core::Section;
+
+    fn notifier(min_severity: Severity, findings: usize) -> Notifier {
+        Notifier {
+            url: "http://chat.internal/hooks/abc".to_string(),
+            min_severity,
+            findings,
+        }
+    }
+
+    fn report() -> Report {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    { "mount_point": "/", "fs_type": "ext4", "operational": true, "usage_ratio": 0.97, "available_bytes": 100u64 << 30 },
+                    { "mount_point": "/data", "fs_type": "ext4", "operational": true, "usage_ratio": 0.92, "available_bytes": 100u64 << 30 }
+                ]
+            }),
+        );
+        let docker = Section::degraded(
+            "docker",
+            "Docker Containers",
+            "engine unavailable".to_string(),
+            json!({}),
+        );
+        Report::new(vec![storage, docker])
+    }
+
+    #[test]
+    fn payload_lists_top_findings_worst_first() {
+        let report = report();
+        assert_eq!(report.health_digest.overall, Severity::Critical);
+        let payload = notifier(Severity::Warning, 1)
+            .payload(&report, "web-1", Some(Path::new("/srv/vmic/latest.html")))
+            .unwrap();
+        let text = payload["text"].as_str().unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            format!(
+                "vmic on web-1: critical ({} findings)",
+                report.health_digest.findings.len()
+            )
+        );
+        assert!(lines[1].starts_with("• [critical] storage: "));
+        assert_eq!(
+            lines[2],
+            format!("…and {} more", report.health_digest.findings.len() - 1)
+        );
+        assert_eq!(lines[3], "Full report: /srv/vmic/latest.html");
+        assert_eq!(payload.as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn nothing_is_sent_below_the_threshold() {
+        let quiet = Report::new(vec![Section::success("os", "Operating System", json!({}))]);
+        let mut calls = 0;
+        let sent = notifier(Severity::Warning, 5)
+            .notify(&quiet, "h", None, |_, _| {
+                calls += 1;
+                Ok(())
+            })
+            .unwrap();
+        assert!(!sent);
+        assert_eq!(calls, 0);
+
+        assert!(
+            notifier(Severity::Info, 5)
+                .payload(&quiet, "h", None)
+                .is_some()
+        );
+        assert!(
+            notifier(Severity::Critical, 5)
+                .payload(&report(), "h", None)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn notify_posts_to_the_webhook_url() {
+        let mut posted = None;
+        let sent = notifier(Severity::Critical, 5)
+            .notify(&report(), "h", None, |url, payload| {
+                posted = Some((url.to_string(), payload.clone()));
+                Ok(())
+            })
+            .unwrap();
+        assert!(sent);
+        let (url, payload) = posted.unwrap();
+        assert_eq!(url, "http://chat.internal/hooks/abc");
+        assert!(!payload["text"].as_str().unwrap().contains("…and"));
+    }
+}
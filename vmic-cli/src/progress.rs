@@ -0,0 +1,112 @@
+//! Per-collector progress on stderr while a report is collected.
+//!
+//! Only shown when stderr is a terminal. Capable terminals get a single line
+//! that is rewritten after each collector and cleared at the end; `TERM=dumb`
+//! gets one line per collector instead.
+
+use std::env;
+use std::io::{IsTerminal as _, Write as _};
+
+use vmic_core::{CollectorEvent, SectionStatus};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Progress {
+    updating: bool,
+}
+
+impl Progress {
+    /// Progress for this terminal, or `None` when stderr is not a terminal or
+    /// `quiet` asks for silence.
+    pub(crate) fn for_stderr(quiet: bool) -> Option<Self> {
+        if quiet || !std::io::stderr().is_terminal() {
+            return None;
+        }
+        let updating = env::var("TERM").map_or(true, |term| term != "dumb");
+        Some(Self { updating })
+    }
+
+    pub(crate) fn report(&self, event: CollectorEvent) {
+        let Some(line) = line(&event) else {
+            return;
+        };
+        let mut stderr = std::io::stderr().lock();
+        let _ = if !self.updating {
+            writeln!(stderr, "{}", line)
+        } else if matches!(event, CollectorEvent::Finished { completed, total, .. } if completed == total)
+        {
+            write!(stderr, "\r\x1b[2K")
+        } else {
+            write!(stderr, "\r\x1b[2K{}", line)
+        };
+        let _ = stderr.flush();
+    }
+}
+
+/// `[3/11] storage … done (1.2s)` for a finished collector.
+fn line(event: &CollectorEvent) -> Option<String> {
+    let CollectorEvent::Finished {
+        id,
+        status,
+        timed_out,
+        elapsed,
+        completed,
+        total,
+    } = event
+    else {
+        return None;
+    };
+    let outcome = match status {
+        _ if *timed_out => "timed out",
+        SectionStatus::Success => "done",
+        SectionStatus::Degraded => "degraded",
+        SectionStatus::Error => "failed",
+    };
+    Some(format!(
+        "[{}/{}] {} … {} ({:.1}s)",
+        completed,
+        total,
+        id,
+        outcome,
+        elapsed.as_secs_f64()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn finished(status: SectionStatus, timed_out: bool) -> CollectorEvent {
+        CollectorEvent::Finished {
+            id: "storage",
+            status,
+            timed_out,
+            elapsed: Duration::from_millis(1230),
+            completed: 3,
+            total: 11,
+        }
+    }
+
+    #[test]
+    fn finished_collectors_get_one_line() {
+        assert_eq!(
+            line(&finished(SectionStatus::Success, false)).unwrap(),
+            "[3/11] storage … done (1.2s)"
+        );
+        assert_eq!(
+            line(&finished(SectionStatus::Degraded, false)).unwrap(),
+            "[3/11] storage … degraded (1.2s)"
+        );
+        assert_eq!(
+            line(&finished(SectionStatus::Error, true)).unwrap(),
+            "[3/11] storage … timed out (1.2s)"
+        );
+        assert!(
+            line(&CollectorEvent::Started {
+                id: "storage",
+                total: 11
+            })
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! `--since`: validate the collection window before anything runs.
+//!
+//! Relative values (`2h`, `-45m`, `1d`) and timestamps with a zone become an
+//! absolute local `YYYY-MM-DD HH:MM:SS`, the form every `journalctl` accepts.
+//! Zoneless timestamps are already local and journalctl keywords pass through.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+
+const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const KEYWORDS: [&str; 4] = ["now", "today", "yesterday", "tomorrow"];
+
+/// Normalizes a `--since` value against `now`, whose time zone is the one
+/// journalctl will read the result in.
+pub(crate) fn normalize<Tz>(value: &str, now: &DateTime<Tz>) -> Result<String, String>
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    let value = value.trim();
+    if KEYWORDS.contains(&value) {
+        return Ok(value.to_string());
+    }
+    if let Some(ago) = relative(value)? {
+        return now
+            .clone()
+            .checked_sub_signed(ago)
+            .map(|since| since.format(FORMAT).to_string())
+            .ok_or_else(|| format!("invalid --since value '{}': reaches too far back", value));
+    }
+
+    let zone = now.timezone();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&zone).format(FORMAT).to_string());
+    }
+    if let Some(naive) = value.strip_suffix(" UTC").and_then(parse_naive) {
+        return Ok(Utc
+            .from_utc_datetime(&naive)
+            .with_timezone(&zone)
+            .format(FORMAT)
+            .to_string());
+    }
+    if let Some(naive) = parse_naive(value) {
+        return Ok(naive.format(FORMAT).to_string());
+    }
+    Err(format!(
+        "invalid --since value '{}': expected a duration such as 2h, 45m, or 1d, \
+         one of {}, or a timestamp such as 2025-01-01 08:00:00",
+        value,
+        KEYWORDS.join(", ")
+    ))
+}
+
+/// `Some(duration)` for `[-]<digits><unit>`, `None` when `value` is not
+/// shaped like a duration at all.
+fn relative(value: &str) -> Result<Option<TimeDelta>, String> {
+    let body = value.strip_prefix('-').unwrap_or(value);
+    let split = body
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(body.len());
+    let (digits, unit) = body.split_at(split);
+    if digits.is_empty() || !unit.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return Ok(None);
+    }
+    let scale: i64 = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        "" => {
+            return Err(format!(
+                "invalid --since value '{}': add a unit (s, m, h, d, or w)",
+                value
+            ));
+        }
+        _ => {
+            return Err(format!(
+                "invalid --since value '{}': unknown unit '{}', use s, m, h, d, or w",
+                value, unit
+            ));
+        }
+    };
+    digits
+        .parse::<i64>()
+        .ok()
+        .and_then(|amount| amount.checked_mul(scale))
+        .and_then(TimeDelta::try_seconds)
+        .map(Some)
+        .ok_or_else(|| format!("invalid --since value '{}': duration is too large", value))
+}
+
+fn parse_naive(value: &str) -> Option<NaiveDateTime> {
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn utc_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 0, 30, 0).unwrap()
+    }
+
+    fn moscow_now() -> DateTime<FixedOffset> {
+        utc_now().with_timezone(&FixedOffset::east_opt(3 * 3600).unwrap())
+    }
+
+    #[test]
+    fn relative_durations_become_absolute_times() {
+        let now = utc_now();
+        assert_eq!(normalize("2h", &now).unwrap(), "2026-02-28 22:30:00");
+        assert_eq!(normalize("-2h", &now).unwrap(), "2026-02-28 22:30:00");
+        assert_eq!(normalize("45m", &now).unwrap(), "2026-02-28 23:45:00");
+        assert_eq!(normalize("1d", &now).unwrap(), "2026-02-28 00:30:00");
+        assert_eq!(normalize("1w", &now).unwrap(), "2026-02-22 00:30:00");
+        assert_eq!(normalize("0s", &now).unwrap(), "2026-03-01 00:30:00");
+        assert_eq!(normalize(" 30s ", &now).unwrap(), "2026-03-01 00:29:30");
+    }
+
+    #[test]
+    fn results_use_the_local_time_zone() {
+        let now = moscow_now();
+        assert_eq!(normalize("2h", &now).unwrap(), "2026-03-01 01:30:00");
+        assert_eq!(
+            normalize("2026-01-01T00:00:00Z", &now).unwrap(),
+            "2026-01-01 03:00:00"
+        );
+        assert_eq!(
+            normalize("2026-01-01T05:00:00+05:00", &now).unwrap(),
+            "2026-01-01 03:00:00"
+        );
+        assert_eq!(
+            normalize("2026-01-01 00:00:00 UTC", &now).unwrap(),
+            "2026-01-01 03:00:00"
+        );
+        // Zoneless timestamps are local already.
+        assert_eq!(
+            normalize("2026-01-01 08:15", &now).unwrap(),
+            "2026-01-01 08:15:00"
+        );
+        assert_eq!(
+            normalize("2026-01-01", &now).unwrap(),
+            "2026-01-01 00:00:00"
+        );
+        assert_eq!(normalize("yesterday", &now).unwrap(), "yesterday");
+    }
+
+    #[test]
+    fn malformed_values_are_rejected() {
+        let now = utc_now();
+        assert!(
+            normalize("2hours", &now)
+                .unwrap_err()
+                .contains("unknown unit 'hours'")
+        );
+        assert!(normalize("90", &now).unwrap_err().contains("add a unit"));
+        assert!(
+            normalize("99999999999999d", &now)
+                .unwrap_err()
+                .contains("too large")
+        );
+        assert!(
+            normalize("9999999999w", &now)
+                .unwrap_err()
+                .contains("too far back")
+        );
+        assert!(normalize("", &now).is_err());
+        assert!(normalize("2026-13-01", &now).is_err());
+        assert!(normalize("last tuesday", &now).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! `--summary-json`: one compact JSON object describing the finished run.
+//!
+//! Meant for tooling that wraps `vmic`: the files written, collection time, the
+//! digest severity, and which collectors failed or timed out, without parsing
+//! the human-readable lines.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+use vmic_core::{Report, SectionStatus};
+
+use crate::OutputFormat;
+
+/// The summary of a run that wrote `written` and `archive` and is about to
+/// exit with `exit_code`.
+pub(crate) fn run_summary(
+    report: &Report,
+    written: &[(OutputFormat, PathBuf)],
+    archive: Option<&Path>,
+    exit_code: i32,
+) -> Value {
+    let count = |status: SectionStatus| {
+        report
+            .sections
+            .iter()
+            .filter(|section| section.status == status)
+            .count()
+    };
+    let errored: Vec<&str> = report
+        .sections
+        .iter()
+        .filter(|section| section.status == SectionStatus::Error)
+        .map(|section| section.id.as_ref())
+        .collect();
+    let timing = report.metadata.timing.as_ref();
+    let digest = &report.health_digest;
+    let mut summary = json!({
+        "vmic_version": env!("CARGO_PKG_VERSION"),
+        "generated_at": report.metadata.generated_at,
+        "exit_code": exit_code,
+        "overall": digest.overall,
+        "findings": {
+            "critical": digest.summary.critical,
+            "warning": digest.summary.warning,
+            "info": digest.summary.info,
+        },
+        "collection_ms": timing.map(|timing| timing.total_ms),
+        "sections": {
+            "total": report.sections.len(),
+            "success": count(SectionStatus::Success),
+            "degraded": count(SectionStatus::Degraded),
+            "error": errored.len(),
+        },
+        "errored": errored,
+        "timed_out": timing.map_or(&[][..], |timing| timing.timed_out.as_slice()),
+        "outputs": written
+            .iter()
+            .map(|(format, path)| json!({ "format": format, "path": path }))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(path) = archive {
+        summary["archive"] = json!(path);
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vmic_core::Section;
+
+    #[test]
+    fn failed_collectors_are_listed() {
+        let report = Report::new(vec![
+            Section::success("os", "Operating System", json!({})),
+            Section::error("docker", "Docker", "socket missing".to_string()),
+        ]);
+        let summary = run_summary(&report, &[], Some(Path::new("/tmp/r.tar.gz")), 2);
+        assert_eq!(summary["exit_code"], 2);
+        assert_eq!(summary["sections"]["error"], 1);
+        assert_eq!(summary["errored"], json!(["docker"]));
+        assert_eq!(summary["timed_out"], json!([]));
+        assert_eq!(summary["collection_ms"], Value::Null);
+        assert_eq!(summary["outputs"], json!([]));
+        assert_eq!(summary["archive"], "/tmp/r.tar.gz");
+    }
+}
@@ -0,0 +1,153 @@
+//! `--syslog`: one local syslog message per digest finding.
+//!
+//! Messages use the BSD format on the `user` facility, tagged `vmic`, and go to
+//! the `/dev/log` datagram socket. When the socket is missing the run carries on
+//! with a warning.
+
+use std::path::Path;
+
+use anyhow::Result;
+use vmic_core::{Report, Severity};
+
+const DEV_LOG: &str = "/dev/log";
+const FACILITY_USER: u8 = 1;
+
+/// Priority and text of the message for each finding at or above `min_severity`.
+pub(crate) fn messages(report: &Report, min_severity: Severity) -> Vec<(u8, String)> {
+    report
+        .health_digest
+        .findings
+        .iter()
+        .filter(|finding| finding.severity >= min_severity)
+        .map(|finding| {
+            // err, warning, and info in syslog terms.
+            let level = match finding.severity {
+                Severity::Critical => 3,
+                Severity::Warning => 4,
+                Severity::Info => 6,
+            };
+            (
+                FACILITY_USER * 8 + level,
+                format!(
+                    "{} finding in {}: {}",
+                    finding.severity.as_str(),
+                    finding.source_id,
+                    finding.message
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Sends the findings of `report` to the local syslog and returns how many were
+/// written. An unreachable socket is logged instead of failing the run.
+pub(crate) fn emit(report: &Report, min_severity: Severity) -> usize {
+    let messages = messages(report, min_severity);
+    if messages.is_empty() {
+        return 0;
+    }
+    match send(Path::new(DEV_LOG), &messages) {
+        Ok(()) => messages.len(),
+        Err(err) => {
+            log::warn!(
+                "syslog unavailable at {} ({:#}); {} finding(s) not logged",
+                DEV_LOG,
+                err,
+                messages.len()
+            );
+            0
+        }
+    }
+}
+
+#[cfg(unix)]
+fn send(socket: &Path, messages: &[(u8, String)]) -> Result<()> {
+    use anyhow::Context as _;
+    use chrono::Local;
+    use std::os::unix::net::UnixDatagram;
+
+    let datagram = UnixDatagram::unbound()?;
+    datagram
+        .connect(socket)
+        .with_context(|| format!("failed to connect to {}", socket.display()))?;
+    let timestamp = Local::now().format("%b %e %H:%M:%S");
+    let pid = std::process::id();
+    for (priority, text) in messages {
+        let line = format!("<{}>{} vmic[{}]: {}", priority, timestamp, pid, text);
+        datagram.send(line.as_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send(_socket: &Path, _messages: &[(u8, String)]) -> Result<()> {
+    Err(anyhow::anyhow!("syslog is only supported on Unix"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vmic_core::Section;
+
+    fn report() -> Report {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    { "mount_point": "/", "fs_type": "ext4", "operational": true, "usage_ratio": 0.97, "available_bytes": 100u64 << 30 }
+                ]
+            }),
+        );
+        let docker = Section::degraded(
+            "docker",
+            "Docker Containers",
+            "engine unavailable".to_string(),
+            json!({}),
+        );
+        Report::new(vec![storage, docker])
+    }
+
+    #[test]
+    fn priorities_follow_severity_and_the_filter() {
+        let report = report();
+        let all = messages(&report, Severity::Warning);
+        assert!(
+            all.iter().any(|(priority, text)| *priority == 11
+                && text.starts_with("critical finding in storage: "))
+        );
+        assert!(
+            all.iter().any(|(priority, text)| *priority == 12
+                && text.starts_with("warning finding in docker: "))
+        );
+
+        let critical = messages(&report, Severity::Critical);
+        assert!(critical.iter().all(|(priority, _)| *priority == 11));
+        assert!(critical.len() < all.len());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn messages_are_sent_as_tagged_datagrams() {
+        use std::os::unix::net::UnixDatagram;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log");
+        let receiver = UnixDatagram::bind(&path).unwrap();
+        send(
+            &path,
+            &[(11, "critical finding in storage: full".to_string())],
+        )
+        .unwrap();
+
+        let mut buffer = [0; 256];
+        let length = receiver.recv(&mut buffer).unwrap();
+        let line = std::str::from_utf8(&buffer[..length]).unwrap();
+        assert!(line.starts_with("<11>"));
+        assert!(line.contains(" vmic["));
+        assert!(line.ends_with("]: critical finding in storage: full"));
+
+        assert!(send(&dir.path().join("missing"), &[(11, "x".to_string())]).is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! `--upload-url`: POST a rendered report to an HTTP collector.
+//!
+//! The body is gzip-compressed and sent after local outputs are written.
+//! Connection errors and 5xx/408/429 answers are retried with exponential
+//! backoff; other answers fail at once. The client lives behind the `upload`
+//! feature, and HTTPS additionally needs `upload-tls`.
+
+#[cfg(feature = "upload")]
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+
+use crate::{Cli, OutputFormat};
+
+/// Delay before the first retry; each further retry doubles it.
+#[cfg(feature = "upload")]
+const BASE_DELAY: Duration = Duration::from_secs(1);
+#[cfg(feature = "upload")]
+const MAX_DELAY: Duration = Duration::from_secs(30);
+#[cfg(feature = "upload")]
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "upload"), allow(dead_code))]
+pub(crate) struct Upload {
+    pub(crate) url: String,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) format: OutputFormat,
+    pub(crate) retries: u32,
+}
+
+impl Upload {
+    /// Upload settings from the command line, or `None` without `--upload-url`.
+    /// Fails before anything is collected when this build cannot reach the URL.
+    pub(crate) fn from_cli(cli: &Cli) -> Result<Option<Self>> {
+        let Some(url) = &cli.upload_url else {
+            return Ok(None);
+        };
+        check_url("--upload-url", url)?;
+        Ok(Some(Self {
+            url: url.clone(),
+            headers: cli.upload_header.clone(),
+            format: cli.upload_format.clone(),
+            retries: cli.upload_retries,
+        }))
+    }
+
+    /// Sends `body`, retrying transient failures up to `retries` times.
+    pub(crate) fn send(&self, body: &str) -> Result<()> {
+        #[cfg(feature = "upload")]
+        return self.send_with_backoff(body, BASE_DELAY);
+        #[cfg(not(feature = "upload"))]
+        {
+            let _ = body;
+            Err(anyhow!("this vmic build has no upload support"))
+        }
+    }
+
+    #[cfg(feature = "upload")]
+    fn send_with_backoff(&self, body: &str, base_delay: Duration) -> Result<()> {
+        let payload = crate::gzip(body.as_bytes())?;
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(REQUEST_TIMEOUT))
+            .build()
+            .into();
+        let attempts = self.retries.saturating_add(1);
+        let mut attempt = 1;
+        loop {
+            let failure = match self.post(&agent, &payload) {
+                Ok(()) => return Ok(()),
+                Err(failure) => failure,
+            };
+            if !failure.retryable || attempt >= attempts {
+                return Err(anyhow!(
+                    "upload to {} failed after {} attempt(s): {}",
+                    self.url,
+                    attempt,
+                    failure.message
+                ));
+            }
+            let delay = backoff(base_delay, attempt);
+            log::warn!(
+                "upload attempt {}/{} failed: {}; retrying in {:.1}s",
+                attempt,
+                attempts,
+                failure.message,
+                delay.as_secs_f64()
+            );
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    #[cfg(feature = "upload")]
+    fn post(&self, agent: &ureq::Agent, payload: &[u8]) -> Result<(), Failure> {
+        let mut request = agent
+            .post(&self.url)
+            .header("Content-Type", content_type(&self.format))
+            .header("Content-Encoding", "gzip");
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        match request.send(payload) {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                if (200..300).contains(&status) {
+                    return Ok(());
+                }
+                Err(Failure {
+                    message: format!("server answered HTTP {}", status),
+                    retryable: status >= 500 || status == 408 || status == 429,
+                })
+            }
+            Err(err) => Err(Failure {
+                message: err.to_string(),
+                retryable: true,
+            }),
+        }
+    }
+}
+
+/// Checks that this build can send to `url`; `flag` names the option in errors.
+pub(crate) fn check_url(flag: &str, url: &str) -> Result<()> {
+    if !cfg!(feature = "upload") {
+        return Err(anyhow!(
+            "{} needs a vmic build with the `upload` feature",
+            flag
+        ));
+    }
+    match url.split_once("://").map(|(scheme, _)| scheme) {
+        Some("http") => Ok(()),
+        Some("https") if cfg!(feature = "upload-tls") => Ok(()),
+        Some("https") => Err(anyhow!(
+            "{} {} needs HTTPS support; rebuild with the `upload-tls` feature",
+            flag,
+            url
+        )),
+        _ => Err(anyhow!(
+            "{} must start with http:// or https://, got '{}'",
+            flag,
+            url
+        )),
+    }
+}
+
+/// POSTs a JSON document once, without compression or retries.
+#[cfg(feature = "upload")]
+pub(crate) fn post_json(url: &str, document: &serde_json::Value) -> Result<()> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .into();
+    let response = agent
+        .post(url)
+        .header("Content-Type", "application/json")
+        .send(document.to_string())
+        .map_err(|err| anyhow!("POST to {} failed: {}", url, err))?;
+    let status = response.status().as_u16();
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(anyhow!("POST to {} answered HTTP {}", url, status))
+    }
+}
+
+#[cfg(not(feature = "upload"))]
+pub(crate) fn post_json(_url: &str, _document: &serde_json::Value) -> Result<()> {
+    Err(anyhow!("this vmic build has no HTTP client"))
+}
+
+#[cfg(feature = "upload")]
+struct Failure {
+    message: String,
+    retryable: bool,
+}
+
+/// Parses an `--upload-header` value of the form `Name: value`.
+pub(crate) fn parse_header(value: &str) -> Result<(String, String), String> {
+    let Some((name, header_value)) = value.split_once(':') else {
+        return Err(format!("expected 'Name: value', got '{}'", value));
+    };
+    let name = name.trim();
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(ch));
+    if !valid_name {
+        return Err(format!("invalid header name '{}'", name));
+    }
+    Ok((name.to_string(), header_value.trim().to_string()))
+}
+
+#[cfg(feature = "upload")]
+fn content_type(format: &OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::Json => "application/json",
+        OutputFormat::Markdown => "text/markdown; charset=utf-8",
+        OutputFormat::Html => "text/html; charset=utf-8",
+        OutputFormat::Prometheus => "text/plain; version=0.0.4",
+    }
+}
+
+/// Wait before retry number `attempt` (1-based), capped at [`MAX_DELAY`].
+#[cfg(feature = "upload")]
+fn backoff(base: Duration, attempt: u32) -> Duration {
+    base.saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_are_split_and_validated() {
+        assert_eq!(
+            parse_header("Authorization: Bearer abc:def"),
+            Ok(("Authorization".to_string(), "Bearer abc:def".to_string()))
+        );
+        assert!(parse_header("no colon").is_err());
+        assert!(parse_header("Bad Name: x").is_err());
+        assert!(parse_header(": x").is_err());
+    }
+
+    #[cfg(feature = "upload")]
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let base = Duration::from_secs(1);
+        assert_eq!(backoff(base, 1), Duration::from_secs(1));
+        assert_eq!(backoff(base, 3), Duration::from_secs(4));
+        assert_eq!(backoff(base, 40), MAX_DELAY);
+    }
+
+    #[cfg(feature = "upload")]
+    mod http {
+        use super::super::*;
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+        use std::thread;
+
+        /// Lowercased request head and raw body of each request served.
+        type Requests = Vec<(String, Vec<u8>)>;
+
+        /// Answers one request per status on a local port.
+        fn serve(statuses: &'static [u16]) -> (String, thread::JoinHandle<Requests>) {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/reports", listener.local_addr().unwrap());
+            let handle = thread::spawn(move || {
+                let mut requests = Vec::new();
+                for status in statuses {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut head = String::new();
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if line == "\r\n" || line.is_empty() {
+                            break;
+                        }
+                        head.push_str(&line.to_ascii_lowercase());
+                    }
+                    let length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |value| value.trim().parse().unwrap());
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 {} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                        status
+                    )
+                    .unwrap();
+                    requests.push((head, body));
+                }
+                requests
+            });
+            (url, handle)
+        }
+
+        fn upload(url: String, retries: u32) -> Upload {
+            Upload {
+                url,
+                headers: vec![("Authorization".to_string(), "Bearer t0ken".to_string())],
+                format: OutputFormat::Json,
+                retries,
+            }
+        }
+
+        #[test]
+        fn retries_server_errors_and_sends_gzip() {
+            let (url, server) = serve(&[503, 200]);
+            upload(url, 2)
+                .send_with_backoff("{\"ok\":true}", Duration::from_millis(10))
+                .unwrap();
+
+            let requests = server.join().unwrap();
+            assert_eq!(requests.len(), 2);
+            let (head, body) = &requests[1];
+            assert!(head.starts_with("post /reports "));
+            assert!(head.contains("content-type: application/json"));
+            assert!(head.contains("content-encoding: gzip"));
+            assert!(head.contains("authorization: bearer t0ken"));
+            let mut decoded = String::new();
+            flate2::read::GzDecoder::new(&body[..])
+                .read_to_string(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, "{\"ok\":true}");
+        }
+
+        #[test]
+        fn client_errors_are_not_retried() {
+            let (url, server) = serve(&[401]);
+            let error = upload(url, 3)
+                .send_with_backoff("{}", Duration::from_millis(10))
+                .unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .contains("after 1 attempt(s): server answered HTTP 401")
+            );
+            assert_eq!(server.join().unwrap().len(), 1);
+        }
+    }
+}
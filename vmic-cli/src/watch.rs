@@ -0,0 +1,215 @@
+//! `--watch`: collect on a fixed interval into `--output-dir`.
+//!
+//! Every run writes the usual timestamped files, then prunes each format down
+//! to `--retention` files and points `latest.<ext>` at the newest one. SIGINT
+//! and SIGTERM stop the loop once the run in progress has been written.
+
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+
+use crate::{OutputFormat, written_extension};
+
+/// Parses `30s`, `15m`, `1h`, or `1d` (a bare number means seconds). Must be at
+/// least one second.
+pub(crate) fn parse_interval(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid interval '{}': expected e.g. 30s, 15m, 1h", value))?;
+    let scale = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "invalid interval unit '{}' in '{}': use s, m, h, or d",
+                unit, value
+            ));
+        }
+    };
+    match amount.checked_mul(scale) {
+        Some(0) => Err("interval must be at least 1s".to_string()),
+        Some(seconds) => Ok(Duration::from_secs(seconds)),
+        None => Err(format!("interval '{}' is too large", value)),
+    }
+}
+
+/// Calls `run` every `interval` until SIGINT or SIGTERM arrives. A failed run
+/// is logged and the loop carries on.
+pub(crate) fn watch<F>(
+    dir: &Path,
+    interval: Duration,
+    retention: Option<NonZeroUsize>,
+    mut run: F,
+) -> Result<()>
+where
+    F: FnMut() -> Result<Vec<(OutputFormat, PathBuf)>>,
+{
+    let (stop, stopped) = mpsc::channel();
+    ctrlc::set_handler(move || {
+        let _ = stop.send(());
+    })
+    .context("failed to install the signal handler")?;
+
+    loop {
+        match run() {
+            Ok(written) => {
+                for (format, path) in &written {
+                    if let Err(err) = rotate(dir, format, path, retention) {
+                        log::error!("{:#}", err);
+                    }
+                }
+            }
+            Err(err) => log::error!("report run failed: {:#}", err),
+        }
+        match stopped.recv_timeout(interval) {
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+                log::info!("signal received, stopping");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Prunes old reports of `format` and refreshes `latest.<ext>` to `newest`.
+/// The Prometheus file already has one fixed name and needs neither.
+fn rotate(
+    dir: &Path,
+    format: &OutputFormat,
+    newest: &Path,
+    retention: Option<NonZeroUsize>,
+) -> Result<()> {
+    if *format == OutputFormat::Prometheus {
+        return Ok(());
+    }
+    let extension = written_extension(format, newest);
+    if let Some(keep) = retention {
+        for path in prune(dir, &extension, keep.get())? {
+            log::info!("removed old report {}", path.display());
+        }
+    }
+    refresh_latest(dir, &extension, newest)
+}
+
+/// Deletes the oldest `vmic-report-*.<extension>` files beyond `keep` and
+/// returns the deleted paths. Timestamped names sort chronologically.
+pub(crate) fn prune(dir: &Path, extension: &str, keep: usize) -> Result<Vec<PathBuf>> {
+    let suffix = format!(".{}", extension);
+    let mut reports: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("failed to list {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("vmic-report-") && name.ends_with(&suffix))
+        })
+        .collect();
+    reports.sort();
+
+    let excess = reports.len().saturating_sub(keep);
+    let removed: Vec<PathBuf> = reports.drain(..excess).collect();
+    for path in &removed {
+        fs::remove_file(path).with_context(|| format!("failed to remove {}", path.display()))?;
+    }
+    Ok(removed)
+}
+
+/// Points `latest.<extension>` at `newest` with a relative symlink, or copies
+/// it where symlinks are unavailable. The swap goes through a temporary name
+/// so readers never see a missing file.
+fn refresh_latest(dir: &Path, extension: &str, newest: &Path) -> Result<()> {
+    let latest = dir.join(format!("latest.{}", extension));
+    let staging = dir.join(format!(".latest.{}.tmp", extension));
+    match fs::remove_file(&staging) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+            return Err(err).with_context(|| format!("failed to remove {}", staging.display()));
+        }
+        _ => {}
+    }
+    if !link(newest, &staging) {
+        fs::copy(newest, &staging)
+            .with_context(|| format!("failed to copy {}", newest.display()))?;
+    }
+    fs::rename(&staging, &latest).with_context(|| format!("failed to update {}", latest.display()))
+}
+
+#[cfg(unix)]
+fn link(target: &Path, link: &Path) -> bool {
+    let relative = target.file_name().map_or(target, Path::new);
+    std::os::unix::fs::symlink(relative, link).is_ok()
+}
+
+#[cfg(not(unix))]
+fn link(_target: &Path, _link: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_accept_common_units() {
+        assert_eq!(parse_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_interval("15m"), Ok(Duration::from_secs(900)));
+        assert_eq!(parse_interval("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_interval("90"), Ok(Duration::from_secs(90)));
+        assert!(parse_interval("0s").unwrap_err().contains("at least 1s"));
+        assert!(parse_interval("15 minutes").is_err());
+        assert!(parse_interval("m").is_err());
+        assert!(parse_interval("-5m").is_err());
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_reports_of_one_format() {
+        let dir = tempfile::tempdir().unwrap();
+        for stamp in ["01", "02", "03"] {
+            for extension in ["json", "html"] {
+                let name = format!("vmic-report-2026-01-{}T00-00-00Z.{}", stamp, extension);
+                fs::write(dir.path().join(name), "{}").unwrap();
+            }
+        }
+        fs::write(dir.path().join("notes.json"), "{}").unwrap();
+
+        let removed = prune(dir.path(), "json", 2).unwrap();
+        assert_eq!(
+            removed,
+            [dir.path().join("vmic-report-2026-01-01T00-00-00Z.json")]
+        );
+        let mut left: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        assert_eq!(left.len(), 6);
+        assert!(left.contains(&"notes.json".to_string()));
+        assert!(left.contains(&"vmic-report-2026-01-01T00-00-00Z.html".to_string()));
+    }
+
+    #[test]
+    fn latest_follows_the_newest_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("vmic-report-1.json");
+        let second = dir.path().join("vmic-report-2.json");
+        fs::write(&first, "first").unwrap();
+        fs::write(&second, "second").unwrap();
+
+        refresh_latest(dir.path(), "json", &first).unwrap();
+        refresh_latest(dir.path(), "json", &second).unwrap();
+        let latest = dir.path().join("latest.json");
+        assert_eq!(fs::read_to_string(&latest).unwrap(), "second");
+        assert!(!dir.path().join(".latest.json.tmp").exists());
+    }
+}
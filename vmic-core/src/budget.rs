@@ -0,0 +1,172 @@
+//! Per-section body size budget.
+//!
+//! Oversized bodies are trimmed by dropping trailing entries from their largest
+//! arrays until the serialized JSON fits. Status and summary are left alone; a
+//! note records how much was cut.
+
+use serde_json::Value;
+use vmic_sdk::Section;
+
+pub(crate) fn apply_body_budget(section: &mut Section, budget: usize) {
+    let original = serialized_len(&section.body);
+    if original <= budget {
+        return;
+    }
+
+    let mut current = original;
+    while current > budget {
+        let Some(pointer) = largest_array(&section.body, String::new()).map(|(path, _)| path)
+        else {
+            break;
+        };
+        let Some(array) = section
+            .body
+            .pointer_mut(&pointer)
+            .and_then(Value::as_array_mut)
+        else {
+            break;
+        };
+
+        let excess = current - budget;
+        let mut removed = 0;
+        while removed < excess {
+            let Some(item) = array.pop() else {
+                break;
+            };
+            // Account for the separating comma as well.
+            removed += serialized_len(&item) + 1;
+        }
+        current = serialized_len(&section.body);
+    }
+
+    if current < original {
+        let mut note = format!(
+            "body truncated from {} to {}",
+            format_size(original),
+            format_size(current)
+        );
+        if current > budget {
+            note.push_str(&format!(
+                " (budget of {} not reachable)",
+                format_size(budget)
+            ));
+        }
+        section.notes.push(note);
+        // Parts were built from the full body; render from the trimmed one instead.
+        section.parts.clear();
+    }
+}
+
+/// Returns the JSON pointer and serialized size of the largest non-empty array.
+fn largest_array(value: &Value, path: String) -> Option<(String, usize)> {
+    let mut best: Option<(String, usize)> = None;
+    let mut consider = |candidate: Option<(String, usize)>| {
+        if let Some(candidate) = candidate
+            && best.as_ref().is_none_or(|current| candidate.1 > current.1)
+        {
+            best = Some(candidate);
+        }
+    };
+
+    match value {
+        Value::Array(items) => {
+            if !items.is_empty() {
+                consider(Some((path.clone(), serialized_len(value))));
+            }
+            for (index, item) in items.iter().enumerate() {
+                consider(largest_array(item, format!("{}/{}", path, index)));
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let escaped = key.replace('~', "~0").replace('/', "~1");
+                consider(largest_array(item, format!("{}/{}", path, escaped)));
+            }
+        }
+        _ => {}
+    }
+
+    best
+}
+
+fn serialized_len(value: &Value) -> usize {
+    serde_json::to_vec(value)
+        .map(|bytes| bytes.len())
+        .unwrap_or(0)
+}
+
+fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn small_bodies_are_untouched() {
+        let mut section = Section::success("demo", "Demo", json!({ "items": [1, 2, 3] }));
+        section.add_list(vmic_sdk::ItemList::new());
+        apply_body_budget(&mut section, 1024);
+        assert_eq!(section.body, json!({ "items": [1, 2, 3] }));
+        assert!(section.notes.is_empty());
+        assert_eq!(section.parts.len(), 1);
+    }
+
+    #[test]
+    fn largest_array_is_trimmed_from_the_tail() {
+        let entries: Vec<Value> = (0..500)
+            .map(|index| json!({ "message": format!("entry {index:04}") }))
+            .collect();
+        let mut section = Section::degraded(
+            "journal",
+            "Journal",
+            "warnings".to_string(),
+            json!({ "entries": entries, "small": [1, 2] }),
+        );
+        section.add_list(vmic_sdk::ItemList::new());
+
+        apply_body_budget(&mut section, 2048);
+
+        assert!(serialized_len(&section.body) <= 2048);
+        let kept = section.body["entries"].as_array().unwrap();
+        assert!(!kept.is_empty());
+        assert_eq!(kept[0]["message"], "entry 0000");
+        assert_eq!(section.body["small"], json!([1, 2]));
+        assert!(matches!(section.status, vmic_sdk::SectionStatus::Degraded));
+        assert_eq!(section.notes.len(), 1);
+        assert!(section.notes[0].starts_with("body truncated from "));
+        assert!(section.parts.is_empty());
+    }
+
+    #[test]
+    fn unreachable_budget_is_reported() {
+        let mut section = Section::success(
+            "demo",
+            "Demo",
+            json!({ "text": "x".repeat(200), "items": [1, 2, 3] }),
+        );
+        apply_body_budget(&mut section, 64);
+        assert_eq!(section.body["items"], json!([]));
+        assert!(section.notes[0].contains("not reachable"));
+    }
+
+    #[test]
+    fn sizes_are_human_readable() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(512 * 1024), "512.0 KiB");
+        assert_eq!(format_size(4_404_019), "4.2 MiB");
+    }
+}
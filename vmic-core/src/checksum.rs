@@ -0,0 +1,106 @@
+//! Integrity checksum over the report content.
+//!
+//! The hash covers the sections array and the health digest, serialized as
+//! canonical JSON: object keys sorted, no insignificant whitespace. Metadata,
+//! including the checksum itself, is not part of the hashed content.
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+
+const PREFIX: &str = "sha256:";
+
+pub(crate) fn compute(sections: &Value, health_digest: &Value) -> String {
+    let mut canonical = String::new();
+    canonical.push_str("{\"health_digest\":");
+    write_canonical(health_digest, &mut canonical);
+    canonical.push_str(",\"sections\":");
+    write_canonical(sections, &mut canonical);
+    canonical.push('}');
+
+    let hash = Sha256::digest(canonical.as_bytes());
+    let mut output = String::with_capacity(PREFIX.len() + hash.len() * 2);
+    output.push_str(PREFIX);
+    for byte in hash {
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+pub(crate) fn verify(document: &Value) -> Result<bool> {
+    let stored = document
+        .pointer("/metadata/checksum")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("document has no metadata.checksum"))?;
+    if !stored.starts_with(PREFIX) {
+        return Err(anyhow!("unsupported checksum format '{}'", stored));
+    }
+    let sections = document
+        .get("sections")
+        .ok_or_else(|| anyhow!("document has no sections array"))?;
+    let health_digest = document
+        .get("health_digest")
+        .ok_or_else(|| anyhow!("document has no health_digest"))?;
+    Ok(compute(sections, health_digest) == stored)
+}
+
+fn write_canonical(value: &Value, output: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            output.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                output.push_str(&Value::String(key.clone()).to_string());
+                output.push(':');
+                write_canonical(item, output);
+            }
+            output.push('}');
+        }
+        Value::Array(items) => {
+            output.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_canonical(item, output);
+            }
+            output.push(']');
+        }
+        scalar => output.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn canonical_form_sorts_keys_without_whitespace() {
+        let mut output = String::new();
+        write_canonical(
+            &json!({ "b": [1, { "d": null, "c": "x" }], "a": true }),
+            &mut output,
+        );
+        assert_eq!(output, r#"{"a":true,"b":[1,{"c":"x","d":null}]}"#);
+    }
+
+    #[test]
+    fn key_order_does_not_change_the_hash() {
+        let first: Value = serde_json::from_str(r#"{"x": 1, "y": [2, 3]}"#).unwrap();
+        let second: Value = serde_json::from_str(r#"{ "y": [2,3],  "x": 1 }"#).unwrap();
+        assert_eq!(compute(&first, &json!({})), compute(&second, &json!({})));
+        assert!(compute(&first, &json!({})).starts_with("sha256:"));
+        assert_eq!(compute(&first, &json!({})).len(), PREFIX.len() + 64);
+    }
+
+    #[test]
+    fn missing_checksum_is_an_error() {
+        assert!(verify(&json!({ "sections": [], "health_digest": {} })).is_err());
+    }
+}
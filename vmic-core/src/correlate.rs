@@ -0,0 +1,187 @@
+//! Joins between sections collected independently.
+//!
+//! The network collector only sees the container ID in a listener's cgroup
+//! path. When the docker section was collected too, those IDs are replaced
+//! with the container name it lists for them, and the ID moves to
+//! `container_id`; without it they stay raw.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+use vmic_sdk::Section;
+
+/// Short IDs below this length are too ambiguous to match by prefix.
+const MIN_ID_PREFIX: usize = 12;
+
+/// Rewrites `container` values in the network section's listener samples,
+/// groups, and insights to docker container names.
+pub(crate) fn name_listener_containers(sections: &mut [Section]) {
+    let names = match sections.iter().find(|section| section.id == "docker") {
+        Some(docker) => container_names(&docker.body),
+        None => return,
+    };
+    if names.is_empty() {
+        return;
+    }
+    let Some(network) = sections.iter_mut().find(|section| section.id == "network") else {
+        return;
+    };
+    let Some(listeners) = network.body.get_mut("listeners") else {
+        return;
+    };
+    for (list, nested) in [
+        ("samples", Some("processes")),
+        ("groups", None),
+        ("insights", Some("sockets")),
+    ] {
+        let Some(entries) = listeners.get_mut(list).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for entry in entries {
+            match nested {
+                Some(key) => {
+                    if let Some(items) = entry.get_mut(key).and_then(Value::as_array_mut) {
+                        items.iter_mut().for_each(|item| rename(item, &names));
+                    }
+                }
+                None => rename(entry, &names),
+            }
+        }
+    }
+}
+
+/// Full container ID to the first listed name, for every container in the
+/// docker section body.
+fn container_names(body: &Value) -> HashMap<String, String> {
+    body.get("containers")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|container| {
+            let id = container.get("id").and_then(Value::as_str)?;
+            let name = container
+                .get("names")
+                .and_then(Value::as_array)
+                .and_then(|names| names.first())
+                .and_then(Value::as_str)?;
+            Some((id.to_string(), name.to_string()))
+        })
+        .collect()
+}
+
+fn rename(entry: &mut Value, names: &HashMap<String, String>) {
+    let Some(slot) = entry.get_mut("container") else {
+        return;
+    };
+    let Some(raw) = slot.as_str() else {
+        return;
+    };
+    let Some(name) = lookup(raw, names) else {
+        return;
+    };
+    let id = std::mem::replace(slot, Value::String(name.to_string()));
+    if let Some(fields) = entry.as_object_mut() {
+        fields.insert("container_id".to_string(), id);
+    }
+}
+
+/// Exact match first; otherwise an ID of at least [`MIN_ID_PREFIX`]
+/// characters matches the one full ID it starts, so short cgroup IDs still
+/// resolve.
+fn lookup<'a>(raw: &str, names: &'a HashMap<String, String>) -> Option<&'a str> {
+    if let Some(name) = names.get(raw) {
+        return Some(name);
+    }
+    if raw.len() < MIN_ID_PREFIX {
+        return None;
+    }
+    let mut matches = names
+        .iter()
+        .filter(|(id, _)| id.starts_with(raw) || raw.starts_with(id.as_str()));
+    match (matches.next(), matches.next()) {
+        (Some((_, name)), None) => Some(name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const WEB: &str = "4f1c2a9b8e7d6c5b4a39281706f5e4d3c2b1a09f8e7d6c5b4a3928170abcdef0";
+
+    fn network(container: &str) -> Section {
+        Section::success(
+            "network",
+            "Network",
+            json!({
+                "interfaces": [],
+                "listeners": {
+                    "samples": [{
+                        "protocol": "tcp",
+                        "local_address": "0.0.0.0:80",
+                        "processes": [{ "pid": 12, "command": "nginx", "uid": 0, "container": container }]
+                    }],
+                    "groups": [{ "container": container, "socket_count": 1, "process_count": 1, "processes": [] }],
+                    "insights": [{ "rule": "public", "sockets": [{ "protocol": "tcp", "container": container }] }]
+                }
+            }),
+        )
+    }
+
+    fn docker() -> Section {
+        Section::success(
+            "docker",
+            "Docker",
+            json!({ "containers": [{ "id": WEB, "names": ["web-1"], "mounts": [] }] }),
+        )
+    }
+
+    fn containers(section: &Section) -> Vec<&str> {
+        let listeners = &section.body["listeners"];
+        vec![
+            listeners["samples"][0]["processes"][0]["container"]
+                .as_str()
+                .unwrap(),
+            listeners["groups"][0]["container"].as_str().unwrap(),
+            listeners["insights"][0]["sockets"][0]["container"]
+                .as_str()
+                .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn cgroup_ids_become_container_names() {
+        let mut sections = vec![network(WEB), docker()];
+        name_listener_containers(&mut sections);
+        assert_eq!(containers(&sections[0]), ["web-1"; 3]);
+        let listeners = &sections[0].body["listeners"];
+        assert_eq!(listeners["groups"][0]["container_id"], WEB);
+        assert_eq!(listeners["samples"][0]["processes"][0]["container_id"], WEB);
+
+        let mut sections = vec![network(&WEB[..12]), docker()];
+        name_listener_containers(&mut sections);
+        assert_eq!(containers(&sections[0]), ["web-1"; 3]);
+    }
+
+    #[test]
+    fn raw_ids_stay_without_a_matching_docker_container() {
+        let mut sections = vec![network(WEB)];
+        name_listener_containers(&mut sections);
+        assert_eq!(containers(&sections[0]), [WEB; 3]);
+        assert!(
+            sections[0].body["listeners"]["groups"][0]
+                .get("container_id")
+                .is_none()
+        );
+
+        let mut sections = vec![network("kubepod-1234"), docker()];
+        name_listener_containers(&mut sections);
+        assert_eq!(containers(&sections[0]), ["kubepod-1234"; 3]);
+
+        let mut sections = vec![network(&WEB[..4]), docker()];
+        name_listener_containers(&mut sections);
+        assert_eq!(containers(&sections[0]), [&WEB[..4]; 3]);
+    }
+}
@@ -0,0 +1,512 @@
+//! Changes between two saved report documents.
+//!
+//! Findings are matched by section id, severity, and message with digits
+//! ignored, so a usage figure that drifts between runs does not count as a new
+//! finding while an escalation from warning to critical does. Mounts,
+//! containers, and failed services are read from the `storage`, `docker`, and
+//! `services` section bodies. With digest thresholds in the options, mounts
+//! whose usage newly reached the warning or critical level are listed too.
+
+use anyhow::{Context as _, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, Read as _};
+use std::path::Path;
+
+use crate::{DigestThresholds, RenderOptions, Severity, render, schema};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiffOptions {
+    /// Smallest change in a mount's usage ratio (0.0-1.0) that is reported.
+    pub usage_delta: f64,
+    /// Disk thresholds for [`ReportDiff::threshold_crossings`]; `None` skips them.
+    pub thresholds: Option<DigestThresholds>,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            usage_delta: 0.05,
+            thresholds: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct ReportDiff {
+    pub old_generated_at: Option<String>,
+    pub new_generated_at: Option<String>,
+    pub new_findings: Vec<DiffFinding>,
+    pub resolved_findings: Vec<DiffFinding>,
+    pub status_changes: Vec<StatusChange>,
+    pub mount_changes: Vec<MountChange>,
+    pub containers_added: Vec<String>,
+    pub containers_removed: Vec<String>,
+    pub newly_failed_services: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub threshold_crossings: Vec<ThresholdCrossing>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DiffFinding {
+    pub source_id: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Section whose status differs; `None` means the section is absent on that side.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct StatusChange {
+    pub id: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MountChange {
+    pub mount_point: String,
+    pub old_usage_ratio: f64,
+    pub new_usage_ratio: f64,
+}
+
+/// Mount whose usage was below a disk threshold before and is at or above it
+/// now; `severity` names the highest threshold reached.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ThresholdCrossing {
+    pub mount_point: String,
+    pub severity: Severity,
+    pub threshold: f64,
+    pub old_usage_ratio: f64,
+    pub new_usage_ratio: f64,
+}
+
+impl ReportDiff {
+    /// Whether anything that should gate a pipeline changed.
+    pub fn is_material(&self) -> bool {
+        !(self.new_findings.is_empty()
+            && self.threshold_crossings.is_empty()
+            && self.resolved_findings.is_empty()
+            && self.status_changes.is_empty()
+            && self.mount_changes.is_empty()
+            && self.containers_added.is_empty()
+            && self.containers_removed.is_empty()
+            && self.newly_failed_services.is_empty())
+    }
+
+    /// Whether the newer report got worse: a new critical finding or a mount
+    /// past a threshold it had not reached before.
+    pub fn is_regression(&self) -> bool {
+        !self.threshold_crossings.is_empty()
+            || self
+                .new_findings
+                .iter()
+                .any(|finding| finding.severity == Severity::Critical)
+    }
+
+    pub fn to_json_value(&self) -> Value {
+        serde_json::to_value(self).expect("report diff serializes to JSON")
+    }
+
+    pub fn to_markdown(&self) -> Result<String> {
+        self.to_markdown_with(&RenderOptions::default())
+    }
+
+    pub fn to_markdown_with(&self, options: &RenderOptions) -> Result<String> {
+        render::render_diff_markdown(self, options).map_err(Into::into)
+    }
+}
+
+/// Reads a saved JSON report and checks it against the report schema; errors
+/// name the file.
+pub fn load_report(path: &Path) -> Result<Value> {
+    let source =
+        read_report_source(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let document: Value = serde_json::from_str(&source)
+        .with_context(|| format!("{} is not valid JSON", path.display()))?;
+    schema::validate_report(&document).with_context(|| format!("in {}", path.display()))?;
+    Ok(document)
+}
+
+/// Text of a saved report, decompressed when the file starts with the gzip
+/// magic bytes so `.json.gz` reports read like plain ones.
+pub fn read_report_source(path: &Path) -> io::Result<String> {
+    let bytes = fs::read(path)?;
+    let mut source = String::new();
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        flate2::read::GzDecoder::new(bytes.as_slice()).read_to_string(&mut source)?;
+    } else {
+        source = String::from_utf8(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    }
+    Ok(source)
+}
+
+/// Compares two report documents, typically loaded with [`load_report`].
+/// Missing or malformed fields are treated as empty rather than as errors.
+pub fn diff_reports(old: &Value, new: &Value, options: &DiffOptions) -> ReportDiff {
+    let old_findings = findings(old);
+    let new_findings = findings(new);
+    let only_in = |side: &BTreeMap<FindingKey, DiffFinding>,
+                   other: &BTreeMap<FindingKey, DiffFinding>| {
+        side.iter()
+            .filter(|(key, _)| !other.contains_key(*key))
+            .map(|(_, finding)| finding.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let old_containers = containers(old);
+    let new_containers = containers(new);
+    let old_failed = failed_services(old);
+
+    ReportDiff {
+        old_generated_at: generated_at(old),
+        new_generated_at: generated_at(new),
+        new_findings: only_in(&new_findings, &old_findings),
+        resolved_findings: only_in(&old_findings, &new_findings),
+        status_changes: status_changes(old, new),
+        mount_changes: mount_changes(old, new, options.usage_delta),
+        containers_added: new_containers
+            .difference(&old_containers)
+            .cloned()
+            .collect(),
+        containers_removed: old_containers
+            .difference(&new_containers)
+            .cloned()
+            .collect(),
+        newly_failed_services: failed_services(new)
+            .difference(&old_failed)
+            .cloned()
+            .collect(),
+        threshold_crossings: options
+            .thresholds
+            .map(|thresholds| threshold_crossings(old, new, &thresholds))
+            .unwrap_or_default(),
+    }
+}
+
+type FindingKey = (String, Severity, String);
+
+fn generated_at(document: &Value) -> Option<String> {
+    crate::ReportMetadata::generated_at_from_json(document.get("metadata")?)
+        .map(|at| at.to_rfc3339())
+}
+
+fn findings(document: &Value) -> BTreeMap<FindingKey, DiffFinding> {
+    document
+        .pointer("/health_digest/findings")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|finding| {
+            let text = |name: &str| finding.get(name).and_then(Value::as_str);
+            let severity: Severity =
+                serde_json::from_value(finding.get("severity")?.clone()).ok()?;
+            let entry = DiffFinding {
+                source_id: text("source_id")?.to_string(),
+                severity,
+                message: text("message")?.to_string(),
+            };
+            let key = (
+                entry.source_id.clone(),
+                severity,
+                without_digits(&entry.message),
+            );
+            Some((key, entry))
+        })
+        .collect()
+}
+
+fn without_digits(message: &str) -> String {
+    let mut normalized = String::with_capacity(message.len());
+    let mut in_number = false;
+    for ch in message.chars() {
+        if ch.is_ascii_digit() || (in_number && ch == '.') {
+            if !in_number {
+                normalized.push('#');
+            }
+            in_number = true;
+        } else {
+            in_number = false;
+            normalized.push(ch);
+        }
+    }
+    normalized
+}
+
+fn sections(document: &Value) -> impl Iterator<Item = &Value> {
+    document
+        .get("sections")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn section_body<'a>(document: &'a Value, id: &str) -> Option<&'a Value> {
+    sections(document)
+        .find(|section| section.get("id").and_then(Value::as_str) == Some(id))?
+        .get("body")
+}
+
+fn status_changes(old: &Value, new: &Value) -> Vec<StatusChange> {
+    let statuses = |document| -> BTreeMap<String, String> {
+        sections(document)
+            .filter_map(|section| {
+                let id = section.get("id")?.as_str()?;
+                let status = section.get("status")?.as_str()?;
+                Some((id.to_string(), status.to_string()))
+            })
+            .collect()
+    };
+    let old = statuses(old);
+    let new = statuses(new);
+    let ids: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    ids.into_iter()
+        .filter_map(|id| {
+            let before = old.get(id);
+            let after = new.get(id);
+            (before != after).then(|| StatusChange {
+                id: id.clone(),
+                old: before.cloned(),
+                new: after.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn mount_usage(document: &Value) -> BTreeMap<String, f64> {
+    section_body(document, "storage")
+        .and_then(|body| body.get("operating_mounts"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|mount| {
+            let point = mount.get("mount_point")?.as_str()?;
+            let ratio = mount.get("usage_ratio")?.as_f64()?;
+            Some((point.to_string(), ratio))
+        })
+        .collect()
+}
+
+fn mount_changes(old: &Value, new: &Value, delta: f64) -> Vec<MountChange> {
+    let old = mount_usage(old);
+    mount_usage(new)
+        .into_iter()
+        .filter_map(|(mount_point, new_usage_ratio)| {
+            let old_usage_ratio = *old.get(&mount_point)?;
+            ((new_usage_ratio - old_usage_ratio).abs() >= delta).then_some(MountChange {
+                mount_point,
+                old_usage_ratio,
+                new_usage_ratio,
+            })
+        })
+        .collect()
+}
+
+fn threshold_crossings(
+    old: &Value,
+    new: &Value,
+    thresholds: &DigestThresholds,
+) -> Vec<ThresholdCrossing> {
+    let old = mount_usage(old);
+    mount_usage(new)
+        .into_iter()
+        .filter_map(|(mount_point, new_usage_ratio)| {
+            let old_usage_ratio = *old.get(&mount_point)?;
+            [
+                (Severity::Critical, thresholds.disk_critical),
+                (Severity::Warning, thresholds.disk_warning),
+            ]
+            .into_iter()
+            .find(|(_, threshold)| old_usage_ratio < *threshold && new_usage_ratio >= *threshold)
+            .map(|(severity, threshold)| ThresholdCrossing {
+                mount_point,
+                severity,
+                threshold,
+                old_usage_ratio,
+                new_usage_ratio,
+            })
+        })
+        .collect()
+}
+
+/// Containers by first name (without the leading `/`), falling back to the id.
+fn containers(document: &Value) -> BTreeSet<String> {
+    section_body(document, "docker")
+        .and_then(|body| body.get("containers"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|container| {
+            let name = container
+                .get("names")
+                .and_then(Value::as_array)
+                .and_then(|names| names.first())
+                .and_then(Value::as_str)
+                .map(|name| name.trim_start_matches('/'));
+            name.or_else(|| container.get("id")?.as_str())
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+fn failed_services(document: &Value) -> BTreeSet<String> {
+    section_body(document, "services")
+        .and_then(|body| body.get("failed"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|service| Some(service.get("unit")?.as_str()?.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Report, Section};
+    use serde_json::json;
+
+    fn report(root_usage: f64, containers: &[&str], failed: &[&str], cron_ok: bool) -> Value {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    { "mount_point": "/", "fs_type": "ext4", "operational": true, "usage_ratio": root_usage, "available_bytes": 100u64 << 30 }
+                ]
+            }),
+        );
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": containers
+                    .iter()
+                    .map(|name| json!({ "id": format!("{name}-id"), "names": [format!("/{name}")] }))
+                    .collect::<Vec<_>>()
+            }),
+        );
+        let services = Section::success(
+            "services",
+            "System Services",
+            json!({ "failed": failed.iter().map(|unit| json!({ "unit": unit })).collect::<Vec<_>>() }),
+        );
+        let cron = if cron_ok {
+            Section::success("cron", "Scheduled Jobs", json!({}))
+        } else {
+            Section::error("cron", "Scheduled Jobs", "permission denied".to_string())
+        };
+        Report::new(vec![storage, docker, services, cron]).to_json_value()
+    }
+
+    #[test]
+    fn identical_reports_have_no_material_changes() {
+        let old = report(0.50, &["web"], &[], true);
+        let diff = diff_reports(&old, &old.clone(), &DiffOptions::default());
+        assert!(!diff.is_material());
+        assert!(diff.new_findings.is_empty());
+    }
+
+    #[test]
+    fn changes_are_classified() {
+        let old = report(0.50, &["web", "db"], &["a.service"], true);
+        let new = report(0.96, &["web", "cache"], &["a.service", "b.service"], false);
+        let diff = diff_reports(&old, &new, &DiffOptions::default());
+
+        assert!(diff.is_material());
+        assert_eq!(diff.containers_added, ["cache"]);
+        assert_eq!(diff.containers_removed, ["db"]);
+        assert_eq!(diff.newly_failed_services, ["b.service"]);
+        assert_eq!(
+            diff.status_changes,
+            [StatusChange {
+                id: "cron".to_string(),
+                old: Some("success".to_string()),
+                new: Some("error".to_string()),
+            }]
+        );
+        assert_eq!(diff.mount_changes.len(), 1);
+        assert_eq!(diff.mount_changes[0].mount_point, "/");
+        assert!(diff.resolved_findings.is_empty());
+        assert!(diff.new_findings.iter().any(
+            |finding| finding.source_id == "storage" && finding.severity == Severity::Critical
+        ));
+
+        let json = diff.to_json_value();
+        assert_eq!(json["containers_added"][0], "cache");
+        let md = diff.to_markdown().expect("markdown");
+        assert!(md.contains("| cron | success | error |"));
+        assert!(md.contains("| / | 50.0% | 96.0% | +46.0 pp |"));
+        assert!(md.contains("- b.service"));
+    }
+
+    #[test]
+    fn drifting_numbers_do_not_count_as_new_findings() {
+        let old = report(0.962, &[], &[], true);
+        let new = report(0.967, &[], &[], true);
+        let diff = diff_reports(&old, &new, &DiffOptions::default());
+        assert!(!diff.is_material(), "{:?}", diff);
+
+        let strict = DiffOptions {
+            usage_delta: 0.001,
+            ..DiffOptions::default()
+        };
+        assert_eq!(diff_reports(&old, &new, &strict).mount_changes.len(), 1);
+    }
+
+    #[test]
+    fn crossing_a_threshold_is_a_regression() {
+        let options = DiffOptions {
+            thresholds: Some(DigestThresholds::default()),
+            ..DiffOptions::default()
+        };
+        let old = report(0.88, &[], &[], true);
+        let new = report(0.91, &[], &[], true);
+        let diff = diff_reports(&old, &new, &options);
+        assert!(diff.mount_changes.is_empty());
+        assert_eq!(diff.threshold_crossings.len(), 1);
+        assert_eq!(diff.threshold_crossings[0].severity, Severity::Warning);
+        assert!(diff.is_material() && diff.is_regression());
+        let md = diff.to_markdown().expect("markdown");
+        assert!(
+            md.contains("- /: 88.0% → 91.0% (**Warning** ≥ 90.0%)"),
+            "{md}"
+        );
+
+        // Already past the warning level: only reaching critical counts.
+        let worse = report(0.96, &[], &[], true);
+        let diff = diff_reports(&new, &worse, &options);
+        assert_eq!(diff.threshold_crossings[0].severity, Severity::Critical);
+        assert!(
+            diff_reports(&worse, &new, &options)
+                .threshold_crossings
+                .is_empty()
+        );
+        assert!(!diff_reports(&new, &new, &options).is_regression());
+        assert!(
+            diff_reports(&old, &new, &DiffOptions::default())
+                .threshold_crossings
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn load_report_names_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let corrupt = dir.path().join("corrupt.json");
+        fs::write(&corrupt, "{ not json").unwrap();
+        let error = format!("{:#}", load_report(&corrupt).unwrap_err());
+        assert!(error.contains("corrupt.json is not valid JSON"));
+
+        let invalid = dir.path().join("invalid.json");
+        fs::write(&invalid, "{}").unwrap();
+        let error = format!("{:#}", load_report(&invalid).unwrap_err());
+        assert!(error.starts_with(&format!("in {}", invalid.display())));
+        assert!(error.contains("report does not match the schema"));
+
+        let valid = dir.path().join("valid.json");
+        fs::write(&valid, report(0.5, &[], &[], true).to_string()).unwrap();
+        assert!(load_report(&valid).is_ok());
+    }
+}
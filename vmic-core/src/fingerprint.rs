@@ -0,0 +1,61 @@
+//! Salted host fingerprint for correlating reports from the same machine.
+//!
+//! The fingerprint is a SHA-256 over the salt and the machine id (hostname when
+//! no machine id is available). The same host and salt always produce the same
+//! value; without the salt it cannot be linked back to the host.
+
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs;
+
+const IDENTITY_SOURCES: [&str; 4] = [
+    "/etc/machine-id",
+    "/var/lib/dbus/machine-id",
+    "/proc/sys/kernel/hostname",
+    "/etc/hostname",
+];
+
+/// Fingerprint of the current host, or `None` when no identity could be read.
+pub(crate) fn host_fingerprint(salt: &str) -> Option<String> {
+    let identity = IDENTITY_SOURCES.iter().find_map(|path| {
+        let value = fs::read_to_string(path).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    })?;
+    Some(fingerprint(&identity, salt))
+}
+
+fn fingerprint(identity: &str, salt: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    // Separator keeps ("ab", "c") and ("a", "bc") distinct.
+    hasher.update([0]);
+    hasher.update(identity.as_bytes());
+
+    let mut output = String::with_capacity(64);
+    for byte in hasher.finalize() {
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_salt_yields_same_fingerprint() {
+        let first = fingerprint("4f1c0e2a9b", "fleet-a");
+        assert_eq!(first, fingerprint("4f1c0e2a9b", "fleet-a"));
+        assert_eq!(first.len(), 64);
+        assert!(!first.contains("4f1c0e2a9b"));
+    }
+
+    #[test]
+    fn different_salts_or_hosts_differ() {
+        let base = fingerprint("4f1c0e2a9b", "fleet-a");
+        assert_ne!(base, fingerprint("4f1c0e2a9b", "fleet-b"));
+        assert_ne!(base, fingerprint("77aa01", "fleet-a"));
+        assert_ne!(fingerprint("bc", "a"), fingerprint("c", "ab"));
+    }
+}
@@ -0,0 +1,436 @@
+//! Append-only run history and trend computation.
+//!
+//! Each run appends one compact JSON line with the handful of values worth
+//! tracking over time. Loading tolerates corrupt or partial lines so a crash
+//! mid-write never blocks the next run.
+
+use crate::{Report, Severity};
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write as _};
+use std::path::Path;
+use vmic_sdk::SectionStatus;
+
+/// Number of past entries considered when attaching trends to a report.
+pub const DEFAULT_TREND_WINDOW: usize = 30;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// One history line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch, copied from the report metadata.
+    pub timestamp: i64,
+    pub overall: Severity,
+    /// Usage ratio per operational mount point.
+    #[serde(default)]
+    pub mounts: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub memory_usage_ratio: Option<f64>,
+    #[serde(default)]
+    pub container_count: Option<usize>,
+    /// Ids of sections that were degraded in this run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded_sections: Vec<String>,
+}
+
+impl Entry {
+    pub fn from_report(report: &Report) -> Self {
+        let mut entry = Entry {
+            timestamp: report.metadata.generated_at_epoch,
+            overall: report.health_digest.overall,
+            mounts: BTreeMap::new(),
+            memory_usage_ratio: None,
+            container_count: None,
+            degraded_sections: Vec::new(),
+        };
+
+        for section in &report.sections {
+            if matches!(section.status, SectionStatus::Degraded) {
+                entry.degraded_sections.push(section.id.to_string());
+            }
+            match section.id.as_ref() {
+                "storage" => {
+                    let mounts = section
+                        .body
+                        .get("operating_mounts")
+                        .and_then(Value::as_array)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+                    for mount in mounts {
+                        let operational = mount
+                            .get("operational")
+                            .and_then(Value::as_bool)
+                            .unwrap_or(false);
+                        if !operational {
+                            continue;
+                        }
+                        if let (Some(point), Some(ratio)) = (
+                            mount.get("mount_point").and_then(Value::as_str),
+                            mount.get("usage_ratio").and_then(Value::as_f64),
+                        ) {
+                            entry.mounts.insert(point.to_string(), ratio);
+                        }
+                    }
+                }
+                "proc" => {
+                    entry.memory_usage_ratio = section
+                        .body
+                        .pointer("/memory/host/usage_ratio")
+                        .and_then(Value::as_f64);
+                }
+                "docker" if matches!(section.status, SectionStatus::Success) => {
+                    entry.container_count = section
+                        .body
+                        .get("containers")
+                        .and_then(Value::as_array)
+                        .map(Vec::len);
+                }
+                _ => {}
+            }
+        }
+
+        entry
+    }
+}
+
+/// Entries read from a history file plus notes about skipped lines.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    pub entries: Vec<Entry>,
+    pub notes: Vec<String>,
+}
+
+/// Change of a single tracked value across the loaded entries.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricTrend {
+    pub latest: f64,
+    /// Difference to the entry before the latest one.
+    pub delta_since_previous: Option<f64>,
+    /// Difference between the oldest and latest entry.
+    pub delta_over_window: f64,
+    /// Least-squares slope per day; absent with fewer than two distinct timestamps.
+    pub rate_per_day: Option<f64>,
+    pub samples: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TrendSummary {
+    pub entries: usize,
+    pub span_seconds: i64,
+    pub mounts: BTreeMap<String, MetricTrend>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_usage_ratio: Option<MetricTrend>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_count: Option<MetricTrend>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// Number of most recent consecutive entries in which each section was degraded.
+/// Sections that were not degraded in the latest entry are absent.
+pub fn degraded_streaks(entries: &[Entry]) -> BTreeMap<String, usize> {
+    let mut streaks = BTreeMap::new();
+    let Some(latest) = entries.last() else {
+        return streaks;
+    };
+    for id in &latest.degraded_sections {
+        let runs = entries
+            .iter()
+            .rev()
+            .take_while(|entry| entry.degraded_sections.contains(id))
+            .count();
+        streaks.insert(id.clone(), runs);
+    }
+    streaks
+}
+
+/// Appends a compact JSON line describing `report`, creating the file if needed.
+pub fn append(path: &Path, report: &Report) -> Result<()> {
+    let mut line = serde_json::to_string(&Entry::from_report(report))?;
+    line.push('\n');
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open history file {}", path.display()))?;
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("failed to append to history file {}", path.display()))
+}
+
+/// Reads the last `n` valid entries. A missing file yields an empty history;
+/// unparsable lines are skipped and noted.
+pub fn load(path: &Path, n: usize) -> Result<History> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(History::default()),
+        Err(error) => {
+            return Err(error)
+                .with_context(|| format!("failed to read history file {}", path.display()));
+        }
+    };
+
+    let mut history = History::default();
+    let mut skipped = 0;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Entry>(line) {
+            Ok(entry) => history.entries.push(entry),
+            Err(_) => skipped += 1,
+        }
+    }
+    if skipped > 0 {
+        history.notes.push(format!(
+            "skipped {} unreadable line(s) in {}",
+            skipped,
+            path.display()
+        ));
+    }
+
+    let excess = history.entries.len().saturating_sub(n);
+    history.entries.drain(..excess);
+    Ok(history)
+}
+
+/// Computes per-metric deltas and linear growth rates over `entries`, oldest first.
+pub fn trend(entries: &[Entry]) -> TrendSummary {
+    let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+        return TrendSummary::default();
+    };
+
+    let mounts = last
+        .mounts
+        .keys()
+        .filter_map(|point| {
+            let series = series(entries, |entry| entry.mounts.get(point).copied());
+            metric_trend(&series).map(|trend| (point.clone(), trend))
+        })
+        .collect();
+
+    TrendSummary {
+        entries: entries.len(),
+        span_seconds: last.timestamp - first.timestamp,
+        mounts,
+        memory_usage_ratio: metric_trend(&series(entries, |entry| entry.memory_usage_ratio)),
+        container_count: metric_trend(&series(entries, |entry| {
+            entry.container_count.map(|count| count as f64)
+        })),
+        notes: Vec::new(),
+    }
+}
+
+fn series(entries: &[Entry], value: impl Fn(&Entry) -> Option<f64>) -> Vec<(i64, f64)> {
+    entries
+        .iter()
+        .filter_map(|entry| value(entry).map(|value| (entry.timestamp, value)))
+        .collect()
+}
+
+fn metric_trend(series: &[(i64, f64)]) -> Option<MetricTrend> {
+    let &(_, latest) = series.last()?;
+    let &(_, oldest) = series.first()?;
+    let previous = series.len().checked_sub(2).map(|index| series[index].1);
+
+    Some(MetricTrend {
+        latest,
+        delta_since_previous: previous.map(|previous| latest - previous),
+        delta_over_window: latest - oldest,
+        rate_per_day: slope(series).map(|per_second| per_second * SECONDS_PER_DAY),
+        samples: series.len(),
+    })
+}
+
+fn slope(series: &[(i64, f64)]) -> Option<f64> {
+    if series.len() < 2 {
+        return None;
+    }
+    let count = series.len() as f64;
+    let mean_t = series.iter().map(|(t, _)| *t as f64).sum::<f64>() / count;
+    let mean_v = series.iter().map(|(_, v)| *v).sum::<f64>() / count;
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for (t, v) in series {
+        let dt = *t as f64 - mean_t;
+        numerator += dt * (v - mean_v);
+        denominator += dt * dt;
+    }
+    if denominator == 0.0 {
+        None
+    } else {
+        Some(numerator / denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vmic_sdk::Section;
+
+    fn entry(timestamp: i64, root: f64) -> Entry {
+        Entry {
+            timestamp,
+            overall: Severity::Info,
+            mounts: BTreeMap::from([("/".to_string(), root)]),
+            memory_usage_ratio: Some(0.5),
+            container_count: None,
+            degraded_sections: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn entry_captures_tracked_values() {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    { "mount_point": "/", "operational": true, "usage_ratio": 0.42 },
+                    { "mount_point": "/boot", "operational": false, "usage_ratio": 0.9 }
+                ]
+            }),
+        );
+        let proc = Section::success(
+            "proc",
+            "Processes and Resources",
+            json!({ "memory": { "host": { "usage_ratio": 0.6 } } }),
+        );
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({ "containers": [{ "id": "a" }, { "id": "b" }] }),
+        );
+        let entry = Entry::from_report(&Report::new(vec![storage, proc, docker]));
+
+        assert_eq!(entry.mounts, BTreeMap::from([("/".to_string(), 0.42)]));
+        assert_eq!(entry.memory_usage_ratio, Some(0.6));
+        assert_eq!(entry.container_count, Some(2));
+    }
+
+    #[test]
+    fn degraded_streaks_count_trailing_runs() {
+        let runs: Vec<Entry> = [
+            vec!["docker"],
+            vec!["docker", "journal"],
+            vec![],
+            vec!["docker"],
+            vec!["docker", "journal"],
+            vec!["docker", "journal"],
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, ids)| Entry {
+            degraded_sections: ids.into_iter().map(str::to_string).collect(),
+            ..entry(index as i64, 0.1)
+        })
+        .collect();
+
+        assert_eq!(
+            degraded_streaks(&runs),
+            BTreeMap::from([("docker".to_string(), 3), ("journal".to_string(), 2)])
+        );
+        assert!(degraded_streaks(&runs[..3]).is_empty());
+        assert!(degraded_streaks(&[]).is_empty());
+
+        let legacy: Entry = serde_json::from_str(r#"{"timestamp": 1, "overall": "info"}"#).unwrap();
+        assert!(legacy.degraded_sections.is_empty());
+    }
+
+    #[test]
+    fn load_skips_corrupt_lines_and_keeps_last_n() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let lines = [
+            serde_json::to_string(&entry(100, 0.1)).unwrap(),
+            "{not json".to_string(),
+            serde_json::to_string(&entry(200, 0.2)).unwrap(),
+            serde_json::to_string(&entry(300, 0.3)).unwrap(),
+            "{\"timestamp\": 400, \"overa".to_string(),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+
+        let history = load(&path, 2).unwrap();
+        assert_eq!(
+            history
+                .entries
+                .iter()
+                .map(|entry| entry.timestamp)
+                .collect::<Vec<_>>(),
+            vec![200, 300]
+        );
+        assert_eq!(history.notes.len(), 1);
+        assert!(history.notes[0].starts_with("skipped 2 unreadable line(s)"));
+
+        let missing = load(&dir.path().join("absent.jsonl"), 5).unwrap();
+        assert!(missing.entries.is_empty());
+    }
+
+    #[test]
+    fn append_round_trips_through_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let report = Report::new(vec![]);
+        append(&path, &report).unwrap();
+        append(&path, &report).unwrap();
+
+        let history = load(&path, 10).unwrap();
+        assert_eq!(history.entries.len(), 2);
+        assert!(history.notes.is_empty());
+    }
+
+    #[test]
+    fn attached_trends_serialize_into_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        fs::write(
+            &path,
+            format!(
+                "{}\ngarbage\n",
+                serde_json::to_string(&entry(1, 0.4)).unwrap()
+            ),
+        )
+        .unwrap();
+
+        let mut report = Report::new(vec![]);
+        report.attach_history(&path).unwrap();
+        let trends = report.metadata.trends.as_ref().expect("trends attached");
+        assert_eq!(trends.entries, 2);
+        assert_eq!(trends.notes.len(), 1);
+
+        let document = report.to_json_value();
+        assert_eq!(document["metadata"]["trends"]["entries"], 2);
+        let compiled = jsonschema::JSONSchema::compile(crate::schema::report_schema()).unwrap();
+        assert!(compiled.is_valid(&document));
+    }
+
+    #[test]
+    fn trend_reports_deltas_and_daily_rate() {
+        let day = SECONDS_PER_DAY as i64;
+        let entries = vec![entry(0, 0.50), entry(day, 0.52), entry(2 * day, 0.56)];
+        let summary = trend(&entries);
+
+        assert_eq!(summary.entries, 3);
+        assert_eq!(summary.span_seconds, 2 * day);
+        let root = &summary.mounts["/"];
+        assert!((root.latest - 0.56).abs() < 1e-9);
+        assert!((root.delta_since_previous.unwrap() - 0.04).abs() < 1e-9);
+        assert!((root.delta_over_window - 0.06).abs() < 1e-9);
+        assert!((root.rate_per_day.unwrap() - 0.03).abs() < 1e-9);
+        assert_eq!(summary.memory_usage_ratio.unwrap().delta_over_window, 0.0);
+        assert!(summary.container_count.is_none());
+    }
+
+    #[test]
+    fn single_entry_has_no_rate() {
+        let summary = trend(&[entry(10, 0.3)]);
+        let root = &summary.mounts["/"];
+        assert_eq!(root.delta_since_previous, None);
+        assert_eq!(root.rate_per_day, None);
+    }
+}
@@ -0,0 +1,292 @@
+//! Translation tables for section titles and render labels.
+//!
+//! Labels are keyed by their English text, so a missing translation falls
+//! back to English instead of failing. JSON body keys never pass through here.
+
+use vmic_sdk::Locale;
+
+/// Canonical section title for a known collector id.
+pub(crate) fn section_title(locale: Locale, id: &str) -> Option<&'static str> {
+    let (en, ru) = match id {
+        "os" => ("Operating System", "Операционная система"),
+        "proc" => ("Processes and Resources", "Процессы и ресурсы"),
+        "storage" => ("Storage Overview", "Хранилище"),
+        "services" => ("System Services", "Системные службы"),
+        "network" => ("Network Overview", "Сеть"),
+        "docker" => ("Docker Containers", "Контейнеры Docker"),
+        "containers" => ("Alternative Containers", "Другие контейнерные среды"),
+        "journal" => ("systemd journal", "Журнал systemd"),
+        "cron" => ("Scheduled Jobs", "Запланированные задания"),
+        "users" => ("Local Users", "Локальные пользователи"),
+        "security" => ("Security Posture", "Безопасность"),
+        "sar" => ("Sysstat Metrics", "Метрики sysstat"),
+        _ => return None,
+    };
+    Some(match locale {
+        Locale::En => en,
+        Locale::Ru => ru,
+    })
+}
+
+/// Translates a render label, returning `key` unchanged when no translation exists.
+pub(crate) fn translate(locale: Locale, key: &str) -> &str {
+    match locale {
+        Locale::En => key,
+        Locale::Ru => russian(key).unwrap_or(key),
+    }
+}
+
+fn russian(key: &str) -> Option<&'static str> {
+    let value = match key {
+        // Report chrome.
+        "VMIC System Report" => "Отчёт VMIC о системе",
+        "System Report" => "Отчёт о системе",
+        "Generated at" => "Сформирован",
+        "Generated" => "Сформирован",
+        "Total sections" => "Всего разделов",
+        "Sections" => "Разделы",
+        "Contents" => "Содержание",
+        "Critical Health Digest" => "Сводка критичных проблем",
+        "Overall status" => "Общий статус",
+        "Overall Status" => "Общий статус",
+        "No critical findings detected." => "Критичных проблем не обнаружено.",
+        "Highlights" => "Обозначения",
+        "Approaching threshold / warning" => "Близко к порогу / предупреждение",
+        "Critical threshold exceeded" => "Превышен критический порог",
+        "Status" => "Статус",
+        "Fleet Report" => "Сводный отчёт по хостам",
+        "Fleet" => "Парк хостов",
+        "Hosts" => "Хосты",
+        "Host" => "Хост",
+        "Findings" => "Проблемы",
+        "Section" => "Раздел",
+        "Total" => "Итого",
+        "Summary" => "Сводка",
+        "lower-severity findings hidden" => "проблем с меньшей важностью скрыто",
+        "Duration" => "Длительность",
+        "Timing" => "Время сбора",
+        "Total wall time" => "Общее время",
+        "Slow collectors" => "Медленные сборщики",
+        "Slowest sections" => "Самые медленные разделы",
+        "Timed out" => "Превышен тайм-аут",
+        "Notes" => "Примечания",
+        "Report Diff" => "Сравнение отчётов",
+        "Before" => "До",
+        "After" => "После",
+        "No material changes." => "Существенных изменений нет.",
+        "New" => "Новая",
+        "Resolved" => "Устранена",
+        "Section status" => "Статус разделов",
+        "Mount usage" => "Заполнение разделов",
+        "Change" => "Изменение",
+        "Added" => "Добавлен",
+        "Removed" => "Удалён",
+        "Newly failed services" => "Новые сбойные службы",
+        "Thresholds crossed" => "Пересечённые пороги",
+        "Changes since baseline" => "Изменения с эталонного отчёта",
+        "Baseline" => "Эталон",
+        "absent" => "нет",
+        "unknown" => "неизвестно",
+        "Back to top" => "Наверх",
+        "Raw JSON" => "Исходный JSON",
+        "Collector version" => "Версия коллектора",
+        "showing" => "показано",
+        "of" => "из",
+        "total" => "всего",
+        "Detail level" => "Уровень детализации",
+        "minimal" => "минимальный",
+        "full" => "полный",
+        // Statuses and severities.
+        "Success" => "Успешно",
+        "Degraded" => "Частично",
+        "Error" => "Ошибка",
+        "Error chain" => "Цепочка ошибок",
+        "Info" => "Инфо",
+        "Warning" => "Предупреждение",
+        "Critical" => "Критично",
+        // Section tables and key/values.
+        "API over TCP without TLS" => "API по TCP без TLS",
+        "API version" => "Версия API",
+        "Action" => "Действие",
+        "Active" => "Активно",
+        "Addresses" => "Адреса",
+        "Age" => "Возраст",
+        "Architecture" => "Архитектура",
+        "Available" => "Доступно",
+        "Build cache" => "Кэш сборки",
+        "CPU (some)" => "CPU (some)",
+        "CPU Averages" => "Средняя загрузка CPU",
+        "Cgroup Limit" => "Лимит cgroup",
+        "Cgroup Remaining" => "Остаток cgroup",
+        "Cgroup controllers" => "Контроллеры cgroup",
+        "Cgroup driver" => "Драйвер cgroup",
+        "Cgroup v2 unified" => "Единая иерархия cgroup v2",
+        "Command" => "Команда",
+        "Compose projects" => "Проекты Compose",
+        "Compressed" => "Сжато",
+        "Configured" => "Настроено",
+        "Container logs" => "Логи контейнеров",
+        "Container writable layers" => "Записываемые слои контейнеров",
+        "Containers" => "Контейнеры",
+        "Count" => "Количество",
+        "Data root" => "Каталог данных",
+        "Dangling images" => "Висячие образы",
+        "Default log driver" => "Драйвер логов по умолчанию",
+        "Description" => "Описание",
+        "Detected runtimes" => "Обнаруженные среды",
+        "Device" => "Устройство",
+        "Distribution" => "Дистрибутив",
+        "Docker data root" => "Каталог данных Docker",
+        "Docker storage" => "Хранилище Docker",
+        "Docker total" => "Всего Docker",
+        "Driver" => "Драйвер",
+        "Endpoint" => "Точка подключения",
+        "Engine events" => "События движка",
+        "Engine status" => "Статус движка",
+        "Engine version" => "Версия движка",
+        "Event" => "Событие",
+        "Event counts" => "Счётчики событий",
+        "Exit code" => "Код выхода",
+        "FS" => "ФС",
+        "Failed Services" => "Сбойные службы",
+        "Failing health checks" => "Проваленные проверки здоровья",
+        "Finding" => "Проблема",
+        "Free" => "Свободно",
+        "Host Memory" => "Память хоста",
+        "Hotspots" => "Крупнейшие объекты",
+        "Host Memory Total" => "Всего памяти хоста",
+        "ID Like" => "Совместим с",
+        "IOWait" => "Ожидание I/O",
+        "Idle" => "Простой",
+        "Image" => "Образ",
+        "Image storage" => "Место под образы",
+        "Images & Pseudo FS" => "Образы и псевдо-ФС",
+        "In use" => "Используется",
+        "Inodes" => "Иноды",
+        "Insecure registries" => "Небезопасные реестры",
+        "Interactive" => "Интерактивный",
+        "Interactive shells" => "Интерактивные оболочки",
+        "Internal" => "Внутренняя",
+        "Interface" => "Интерфейс",
+        "Kernel Release" => "Релиз ядра",
+        "Kernel Version" => "Версия ядра",
+        "Largest Directories" => "Крупнейшие каталоги",
+        "Largest Log Files" => "Крупнейшие лог-файлы",
+        "Largest images" => "Крупнейшие образы",
+        "Listening sockets" => "Слушающие сокеты",
+        "Live restore" => "Live restore",
+        "Load (15m)" => "Нагрузка (15 мин)",
+        "Load (1m)" => "Нагрузка (1 мин)",
+        "Load (5m)" => "Нагрузка (5 мин)",
+        "Log driver" => "Драйвер логов",
+        "MTU" => "MTU",
+        "Memory" => "Память",
+        "Mode" => "Режим",
+        "Mount" => "Точка монтирования",
+        "Name" => "Имя",
+        "Network Interfaces" => "Сетевые интерфейсы",
+        "Networks" => "Сети",
+        "Nice" => "Nice",
+        "Not running" => "Не запущены",
+        "Object" => "Объект",
+        "Operating Mounts" => "Рабочие разделы",
+        "Output" => "Вывод",
+        "Overlay diff" => "Слои overlay",
+        "Path" => "Путь",
+        "Ports" => "Порты",
+        "Ports on all interfaces" => "Порты на всех интерфейсах",
+        "Pressure Stall (avg%)" => "Pressure Stall (сред. %)",
+        "Priority" => "Приоритет",
+        "Priv" => "Привил.",
+        "Project" => "Проект",
+        "Protocol" => "Протокол",
+        "RX packets" => "RX пакеты",
+        "TX packets" => "TX пакеты",
+        "Recent journal entries" => "Последние записи журнала",
+        "Recent events" => "Последние события",
+        "Reclaimable image layers" => "Освобождаемые слои образов",
+        "Reclaimable image space" => "Освобождаемое место под образы",
+        "Regular users" => "Обычные пользователи",
+        "Replicas" => "Реплики",
+        "Resource" => "Ресурс",
+        "Restart" => "Перезапуск",
+        "Running Services" => "Запущенные службы",
+        "SSH auth failures" => "Ошибки аутентификации SSH",
+        "SSH crypto hardening" => "Криптонастройки SSH",
+        "SSH invalid users" => "Неизвестные пользователи SSH",
+        "Sample accounts" => "Примеры учётных записей",
+        "Sample listeners" => "Примеры слушающих сокетов",
+        "Schedule" => "Расписание",
+        "Scope" => "Область",
+        "Secret-looking env vars" => "Переменные окружения, похожие на секреты",
+        "Security Findings" => "Проблемы безопасности",
+        "Service" => "Сервис",
+        "Severity" => "Важность",
+        "Shell" => "Оболочка",
+        "Size" => "Размер",
+        "Source" => "Источник",
+        "State" => "Состояние",
+        "Steal" => "Steal",
+        "Storage driver" => "Драйвер хранилища",
+        "Subnets" => "Подсети",
+        "Sudo" => "Sudo",
+        "Sudo access" => "Доступ sudo",
+        "Sudoers include dir" => "Каталог includedir в sudoers",
+        "Summarized containers" => "Контейнеры без детализации",
+        "Summarized containers by image" => "Контейнеры без детализации по образам",
+        "Swap Devices" => "Устройства подкачки",
+        "Swap Free" => "Свободно подкачки",
+        "Swap Total" => "Всего подкачки",
+        "Swarm" => "Swarm",
+        "Swarm services" => "Сервисы Swarm",
+        "System" => "Система",
+        "System crontab" => "Системный crontab",
+        "System users" => "Системные пользователи",
+        "Time" => "Время",
+        "Top SSH source IPs" => "Основные IP-источники SSH",
+        "Top SSH usernames" => "Основные имена SSH",
+        "Total Capacity" => "Общий объём",
+        "Type" => "Тип",
+        "UID" => "UID",
+        "Unit" => "Юнит",
+        "Uptime" => "Время работы",
+        "Usage" => "Использование",
+        "Used" => "Занято",
+        "Used Capacity" => "Занятый объём",
+        "User" => "Пользователь",
+        "Userns remap" => "Переназначение userns",
+        "Users" => "Пользователи",
+        "Version" => "Версия",
+        "Volumes" => "Тома",
+        "ZRAM Devices" => "Устройства ZRAM",
+        _ => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_is_identity() {
+        assert_eq!(translate(Locale::En, "Used"), "Used");
+        assert_eq!(translate(Locale::En, "anything"), "anything");
+    }
+
+    #[test]
+    fn russian_falls_back_to_english() {
+        assert_eq!(translate(Locale::Ru, "Used"), "Занято");
+        assert_eq!(
+            translate(Locale::Ru, "not in the table"),
+            "not in the table"
+        );
+    }
+
+    #[test]
+    fn titles_exist_for_both_locales() {
+        assert_eq!(section_title(Locale::En, "os"), Some("Operating System"));
+        assert_eq!(section_title(Locale::Ru, "storage"), Some("Хранилище"));
+        assert_eq!(section_title(Locale::Ru, "custom"), None);
+    }
+}
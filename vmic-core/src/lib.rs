@@ -1,37 +1,237 @@
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use chrono::{DateTime, Utc};
-use serde::Serialize;
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
-use vmic_sdk::{self, CollectionContext, Section};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+use vmic_sdk::{self, CollectionContext, Collector};
 
 use crate::health::{HealthDigest, build_health_digest};
-pub use health::{DigestThresholds, Severity};
-
-pub use vmic_sdk::{CollectionContext as Context, SectionStatus};
-
+pub use health::{
+    CriticalFinding, DegradedAging, DigestSummary, DigestThresholds, Severity, SeverityCounts,
+};
+pub use redact::{RedactionPolicy, local_hostname};
+pub use render::RenderOptions;
+
+pub use vmic_sdk::{
+    Availability, CollectionContext as Context, CollectorMetadata, DetailLevel, ErrorKind, Locale,
+    Section, SectionStatus, SubSection,
+};
+
+mod budget;
+mod checksum;
+mod correlate;
+pub mod diff;
+mod fingerprint;
+pub mod history;
+mod i18n;
+pub mod merge;
+mod order;
+mod prometheus;
+mod redact;
 pub mod schema;
 
 #[derive(Debug, Serialize)]
 pub struct ReportMetadata {
+    /// Seconds since the Unix epoch, UTC.
+    pub generated_at_epoch: i64,
+    /// RFC 3339 form of `generated_at_epoch`; use [`ReportMetadata::set_generated_at`]
+    /// to keep the two in step.
     pub generated_at: String,
     pub sections: usize,
+    /// `sha256:`-prefixed hash of the canonical sections and health digest.
+    pub checksum: String,
+    /// Salted hash of the machine id, present when a fingerprint salt was configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_fingerprint: Option<String>,
+    /// Changes since earlier runs, present when a history file was supplied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trends: Option<history::TrendSummary>,
+    /// Where collection time went; present for reports built by `collect_report`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<CollectionTiming>,
+    /// Operator-supplied tags such as `env = prod`; keys are valid Prometheus
+    /// label names.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Collection depth the sections were gathered at; present for reports
+    /// built by `collect_report`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail_level: Option<DetailLevel>,
+}
+
+/// Number of sections listed in [`CollectionTiming::slowest`].
+const SLOWEST_SECTIONS: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CollectionTiming {
+    /// Wall time for running every collector.
+    pub total_ms: u64,
+    /// Up to three slowest sections, slowest first.
+    pub slowest: Vec<SectionTiming>,
+    pub slow_threshold_ms: u64,
+    /// Collectors that took longer than `slow_threshold_ms`.
+    pub slow_collectors: usize,
+    /// Ids of collectors that exceeded their timeout, whether cancelled with a
+    /// partial section or abandoned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed_out: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionTiming {
+    pub id: String,
+    pub duration_ms: u64,
 }
 
 impl ReportMetadata {
     pub fn generated_at_utc(&self) -> Option<DateTime<Utc>> {
-        let seconds = self.generated_at.parse::<i64>().ok()?;
-        DateTime::<Utc>::from_timestamp(seconds, 0)
+        DateTime::<Utc>::from_timestamp(self.generated_at_epoch, 0)
     }
 
     pub fn generated_at_iso8601(&self) -> String {
-        self.generated_at_utc()
-            .map(|dt| dt.to_rfc3339())
-            .unwrap_or_else(|| "unknown".to_string())
+        self.generated_at.clone()
+    }
+
+    /// Detail level worth pointing out to readers: anything but the default.
+    pub fn non_standard_detail_level(&self) -> Option<&'static str> {
+        self.detail_level
+            .filter(|level| *level != DetailLevel::Standard)
+            .map(|level| level.as_str())
+    }
+
+    /// Sets both timestamp fields from seconds since the Unix epoch.
+    pub fn set_generated_at(&mut self, epoch: i64) {
+        self.generated_at_epoch = epoch;
+        self.generated_at = rfc3339(epoch);
+    }
+
+    /// Reads the generation time from a serialized `metadata` object. Reports
+    /// before schema 1.2 only carry `generated_at` as a string of epoch seconds,
+    /// so that form is accepted alongside `generated_at_epoch` and RFC 3339.
+    pub fn generated_at_from_json(metadata: &serde_json::Value) -> Option<DateTime<Utc>> {
+        if let Some(epoch) = metadata
+            .get("generated_at_epoch")
+            .and_then(serde_json::Value::as_i64)
+        {
+            return DateTime::<Utc>::from_timestamp(epoch, 0);
+        }
+        let value = metadata.get("generated_at")?.as_str()?;
+        match value.parse::<i64>() {
+            Ok(epoch) => DateTime::<Utc>::from_timestamp(epoch, 0),
+            Err(_) => DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+}
+
+fn rfc3339(epoch: i64) -> String {
+    DateTime::<Utc>::from_timestamp(epoch, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn metadata_field<T: serde::de::DeserializeOwned>(
+    metadata: &Value,
+    name: &str,
+) -> Result<Option<T>> {
+    metadata
+        .get(name)
+        .filter(|value| !value.is_null())
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .with_context(|| format!("invalid metadata.{}", name))
+}
+
+/// One `sections[]` object of a saved report.
+fn section_from_json(value: &Value) -> Result<Section> {
+    let text = |name: &str| value.get(name).and_then(Value::as_str);
+    let strings = |name: &str| -> Vec<String> {
+        value
+            .get(name)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect()
+    };
+    let status = value.get("status").cloned().unwrap_or(Value::Null);
+    Ok(Section {
+        id: text("id").unwrap_or_default().to_string().into(),
+        title: text("title").unwrap_or_default().to_string().into(),
+        description: text("description").map(intern),
+        tags: value
+            .get("tags")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(intern)
+            .collect(),
+        collector_version: text("collector_version").map(intern),
+        cost: value
+            .get("cost")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .context("invalid cost")?,
+        status: serde_json::from_value(status).context("invalid status")?,
+        summary: text("summary").map(str::to_string),
+        body: value.get("body").cloned().unwrap_or(Value::Null),
+        notes: strings("notes"),
+        duration_ms: value.get("duration_ms").and_then(Value::as_u64),
+        findings: value
+            .get("findings")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .context("invalid findings")?
+            .unwrap_or_default(),
+        parts: Vec::new(),
+        sensitive: Vec::new(),
+        subsections: value
+            .get("subsections")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|subsection| {
+                let text = |name: &str| subsection.get(name).and_then(Value::as_str);
+                SubSection::new(
+                    text("id").unwrap_or_default(),
+                    text("title").unwrap_or_default(),
+                    subsection.get("body").cloned().unwrap_or(Value::Null),
+                )
+            })
+            .collect(),
+    })
+}
+
+/// `Section` holds static strings, so ids and titles read back from a document
+/// are leaked, once per distinct value.
+fn intern(value: &str) -> &'static str {
+    static INTERNED: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+    let mut interned = INTERNED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(known) = interned.get(value) {
+        return known;
     }
+    let leaked: &'static str = Box::leak(value.to_string().into_boxed_str());
+    interned.insert(leaked);
+    leaked
 }
 
+/// Version of the JSON layout produced by [`Report::to_json_value`].
+pub const SCHEMA_VERSION: &str = "1.2";
+
 #[derive(Debug, Serialize)]
 pub struct Report {
+    pub schema_version: &'static str,
     pub metadata: ReportMetadata,
     pub sections: Vec<Section>,
     pub health_digest: HealthDigest,
@@ -43,29 +243,124 @@ impl Report {
     }
 
     pub fn with_digest_config(sections: Vec<Section>, thresholds: DigestThresholds) -> Self {
-        let generated_at = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_secs().to_string())
-            .unwrap_or_else(|_| "0".to_string());
+        Self::with_digest_history(sections, thresholds, None)
+    }
+
+    /// Like [`Report::with_digest_config`], escalating long-degraded sections
+    /// according to `aging`.
+    pub fn with_digest_history(
+        sections: Vec<Section>,
+        thresholds: DigestThresholds,
+        aging: Option<&DegradedAging>,
+    ) -> Self {
+        let generated_at_epoch = Utc::now().timestamp();
 
         let count = sections.len();
 
-        let health_digest = build_health_digest(&sections, &thresholds);
+        let health_digest = build_health_digest(&sections, &thresholds, aging);
 
-        Self {
+        let mut report = Self {
+            schema_version: SCHEMA_VERSION,
             metadata: ReportMetadata {
-                generated_at,
+                generated_at_epoch,
+                generated_at: rfc3339(generated_at_epoch),
                 sections: count,
+                checksum: String::new(),
+                host_fingerprint: None,
+                trends: None,
+                timing: None,
+                labels: BTreeMap::new(),
+                detail_level: None,
+            },
+            sections,
+            health_digest,
+        };
+        report.refresh_checksum();
+        report
+    }
+
+    /// Rebuilds a report from a saved JSON document so it can be rendered again
+    /// without collecting. The document must match the report schema. The stored
+    /// digest and checksum are kept; documents without a digest get one built
+    /// with the default thresholds.
+    pub fn from_json_value(document: &Value) -> Result<Self> {
+        schema::validate_report(document)?;
+        let metadata = document.get("metadata").unwrap_or(&Value::Null);
+        let sections = document
+            .get("sections")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, value)| {
+                section_from_json(value).with_context(|| format!("in sections[{}]", index))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let health_digest = match document
+            .get("health_digest")
+            .or_else(|| metadata.get("health_digest"))
+        {
+            Some(digest) => {
+                serde_json::from_value(digest.clone()).context("invalid health_digest")?
+            }
+            None => build_health_digest(&sections, &DigestThresholds::default(), None),
+        };
+
+        let mut report = Self {
+            schema_version: SCHEMA_VERSION,
+            metadata: ReportMetadata {
+                generated_at_epoch: 0,
+                generated_at: String::new(),
+                sections: sections.len(),
+                checksum: metadata_field(metadata, "checksum")?.unwrap_or_default(),
+                host_fingerprint: metadata_field(metadata, "host_fingerprint")?,
+                trends: metadata_field(metadata, "trends")?,
+                timing: metadata_field(metadata, "timing")?,
+                labels: metadata_field(metadata, "labels")?.unwrap_or_default(),
+                detail_level: metadata_field(metadata, "detail_level")?,
             },
             sections,
             health_digest,
+        };
+        let generated_at = ReportMetadata::generated_at_from_json(metadata)
+            .map_or(0, |timestamp| timestamp.timestamp());
+        report.metadata.set_generated_at(generated_at);
+        if report.metadata.checksum.is_empty() {
+            report.refresh_checksum();
         }
+        Ok(report)
     }
 
+    /// Recomputes `metadata.checksum`; call after mutating sections or the digest.
+    pub fn refresh_checksum(&mut self) {
+        let sections = serde_json::to_value(&self.sections).expect("sections serialize to JSON");
+        let digest =
+            serde_json::to_value(&self.health_digest).expect("health digest serializes to JSON");
+        self.metadata.checksum = checksum::compute(&sections, &digest);
+    }
+
+    /// Recomputes the checksum of a parsed report document and compares it with
+    /// the stored `metadata.checksum`. Errors when the document lacks the fields
+    /// needed to verify.
+    pub fn verify_checksum(document: &serde_json::Value) -> Result<bool> {
+        checksum::verify(document)
+    }
+
+    /// Serializes the report in the published schema layout; identical to
+    /// serializing `Report` with serde.
     pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("report serializes to JSON")
+    }
+
+    /// Pre-1.1 layout with `health_digest` nested under `metadata` and no
+    /// `schema_version`. Kept for one release to ease migration.
+    #[deprecated(
+        note = "use `to_json_value`; the legacy layout will be removed in the next release"
+    )]
+    pub fn to_json_value_v1(&self) -> serde_json::Value {
         serde_json::json!({
             "metadata": {
-                "generated_at": self.metadata.generated_at,
+                "generated_at": self.metadata.generated_at_epoch.to_string(),
                 "sections": self.metadata.sections,
                 "health_digest": self.health_digest,
             },
@@ -74,50 +369,591 @@ impl Report {
     }
 
     pub fn to_markdown(&self) -> Result<String> {
-        render::render_markdown(self).map_err(Into::into)
+        self.to_markdown_with(&RenderOptions::default())
+    }
+
+    pub fn to_markdown_with(&self, options: &RenderOptions) -> Result<String> {
+        render::render_markdown(self, options).map_err(Into::into)
+    }
+
+    /// Compact Markdown with the digest and one line per section, sized for chat
+    /// notifications.
+    pub fn to_markdown_summary(&self) -> Result<String> {
+        self.to_markdown_with(&RenderOptions {
+            summary_only: true,
+            ..RenderOptions::default()
+        })
     }
 
     pub fn to_html(&self) -> Result<String> {
-        render::render_html(self).map_err(Into::into)
+        self.to_html_with(&RenderOptions::default())
+    }
+
+    pub fn to_html_with(&self, options: &RenderOptions) -> Result<String> {
+        render::render_html(self, options).map_err(Into::into)
+    }
+
+    /// Loads recent entries from `path` and attaches trends computed over them
+    /// and this report. Unreadable lines are recorded as trend notes.
+    pub fn attach_history(&mut self, path: &std::path::Path) -> Result<()> {
+        let mut loaded = history::load(path, history::DEFAULT_TREND_WINDOW - 1)?;
+        loaded.entries.push(history::Entry::from_report(self));
+        let mut trends = history::trend(&loaded.entries);
+        trends.notes = loaded.notes;
+        self.metadata.trends = Some(trends);
+        Ok(())
+    }
+
+    pub fn to_prometheus(&self) -> String {
+        prometheus::render_prometheus(self)
+    }
+
+    /// Replaces the values selected by `policy` with stable hashes in every section
+    /// and digest finding, so all output formats render the redacted data.
+    pub fn redact(&mut self, policy: &RedactionPolicy) {
+        redact::redact_report(self, policy);
+        self.refresh_checksum();
     }
 }
 
-fn collect_sections(ctx: &CollectionContext) -> Vec<Section> {
-    let mut sections = Vec::new();
+/// Metadata of every collector linked into the binary, sorted by id. Collectors
+/// are neither instantiated nor run.
+pub fn registered_collectors() -> Vec<CollectorMetadata> {
+    let mut collectors: Vec<CollectorMetadata> = vmic_sdk::iter_registered_collectors()
+        .map(|entry| (entry.metadata)())
+        .collect();
+    collectors.sort_by_key(|metadata| metadata.id);
+    collectors
+}
+
+/// Prerequisite check of every linked collector (or of the context's
+/// registry, when set), sorted by id; nothing is collected.
+pub fn probe_collectors(ctx: &CollectionContext) -> Vec<(CollectorMetadata, Availability)> {
+    let collectors: Vec<Box<dyn Collector>> = match ctx.registry() {
+        Some(registry) => registry.collectors(&|_| true),
+        None => vmic_sdk::iter_registered_collectors()
+            .map(|entry| (entry.constructor)())
+            .collect(),
+    };
+    let mut probes: Vec<(CollectorMetadata, Availability)> = collectors
+        .into_iter()
+        .map(|collector| (collector.metadata(), collector.probe(ctx)))
+        .collect();
+    probes.sort_by_key(|(metadata, _)| metadata.id);
+    probes
+}
+
+/// Ids of every collector linked into the binary, sorted.
+pub fn registered_collector_ids() -> Vec<&'static str> {
+    registered_collectors()
+        .into_iter()
+        .map(|metadata| metadata.id)
+        .collect()
+}
+
+/// Progress reported to the callback of [`collect_report_with_progress`]. With
+/// parallel jobs the events of different collectors interleave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectorEvent {
+    Started {
+        id: &'static str,
+        total: usize,
+    },
+    Finished {
+        id: &'static str,
+        /// Status of the resulting section; timeouts count as `Error`.
+        status: SectionStatus,
+        timed_out: bool,
+        elapsed: Duration,
+        /// Collectors finished so far, this one included.
+        completed: usize,
+        total: usize,
+    },
+}
+
+/// Callback for [`CollectorEvent`]s; called from the collection workers.
+pub type ProgressFn<'a> = &'a (dyn Fn(CollectorEvent) + Sync);
+
+/// Multi-thread runtime shared by every collector that awaits, built on first
+/// use; `None` (with a warning) when it cannot be created.
+#[cfg(feature = "async")]
+fn shared_runtime() -> Option<&'static tokio::runtime::Runtime> {
+    use once_cell::sync::Lazy;
+
+    static RUNTIME: Lazy<Option<tokio::runtime::Runtime>> = Lazy::new(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("vmic-async")
+            .enable_all()
+            .build()
+            .map_err(|err| log::warn!("shared Tokio runtime unavailable: {}", err))
+            .ok()
+    });
+    RUNTIME.as_ref()
+}
+
+/// `ctx` with the shared runtime installed, unless the caller set its own.
+#[cfg(feature = "async")]
+fn with_shared_runtime(ctx: &CollectionContext) -> std::borrow::Cow<'_, CollectionContext> {
+    use std::borrow::Cow;
+
+    if ctx.runtime().is_some() {
+        return Cow::Borrowed(ctx);
+    }
+    match shared_runtime() {
+        Some(runtime) => {
+            let mut ctx = ctx.clone();
+            ctx.set_runtime(Some(runtime.handle().clone()));
+            Cow::Owned(ctx)
+        }
+        None => Cow::Borrowed(ctx),
+    }
+}
 
-    for entry in vmic_sdk::iter_registered_collectors() {
-        let collector = (entry.constructor)();
+fn collect_sections(
+    ctx: &CollectionContext,
+    include: &dyn Fn(&CollectorMetadata) -> bool,
+    progress: Option<ProgressFn<'_>>,
+) -> (Vec<Section>, CollectionTiming) {
+    #[cfg(feature = "async")]
+    let shared = with_shared_runtime(ctx);
+    #[cfg(feature = "async")]
+    let ctx: &CollectionContext = &shared;
+    let mut sections = Vec::new();
+    let slow_threshold_ms = ctx.slow_collector_threshold().as_millis() as u64;
+    let mut slow_collectors = 0;
+    let mut timed_out = Vec::new();
+    let started = Instant::now();
+
+    let mut skipped = Vec::new();
+    let candidates: Vec<Box<dyn Collector>> = match ctx.registry() {
+        Some(registry) => registry.collectors(include),
+        None => vmic_sdk::iter_registered_collectors()
+            .filter(|entry| include(&(entry.metadata)()))
+            .map(|entry| (entry.constructor)())
+            .collect(),
+    };
+    let mut collectors: Vec<Box<dyn Collector>> = candidates
+        .into_iter()
+        .filter(|collector| {
+            if !ctx.skip_unavailable() {
+                return true;
+            }
+            match collector.probe(ctx) {
+                Availability::Unavailable(reason) => {
+                    log::info!("collector {} skipped: {}", collector.metadata().id, reason);
+                    skipped.push((collector.metadata(), reason));
+                    false
+                }
+                _ => true,
+            }
+        })
+        .collect();
+    // The worker queue hands collectors out in this order.
+    collectors.sort_by_cached_key(|collector| {
         let metadata = collector.metadata();
-        let start = Instant::now();
-        let result = collector.collect(ctx);
-        let elapsed_ms = start.elapsed().as_millis() as u64;
+        (metadata.order, metadata.id)
+    });
+    let metadata: Vec<CollectorMetadata> = collectors.iter().map(|c| c.metadata()).collect();
+    let mut hints: HashMap<&str, i32> = metadata.iter().map(|m| (m.id, m.order)).collect();
+    let results = run_collectors(collectors, ctx, progress);
 
+    for (metadata, (result, elapsed_ms)) in metadata.into_iter().zip(results) {
         let mut section = match result {
-            Ok(section) => section,
-            Err(error) => Section::error(metadata.id, metadata.title, error.to_string()),
+            CollectorOutcome::Collected(section) => *section,
+            CollectorOutcome::Cancelled(section, _) => {
+                timed_out.push(metadata.id.to_string());
+                *section
+            }
+            CollectorOutcome::Failed(error) => {
+                Section::from_error(metadata.id, metadata.title, &error)
+            }
+            CollectorOutcome::TimedOut(limit) => {
+                timed_out.push(metadata.id.to_string());
+                let mut section = Section::error(
+                    metadata.id,
+                    metadata.title,
+                    format!("collector timed out after {:.1}s", limit.as_secs_f64()),
+                );
+                if let Some(body) = section.body.as_object_mut() {
+                    body.insert("error_kind".into(), serde_json::json!(ErrorKind::Timeout));
+                }
+                section
+            }
         };
         section.duration_ms = Some(elapsed_ms);
+        section.description = Some(metadata.description);
+        section.tags = metadata.all_tags();
+        section.collector_version = Some(metadata.version);
+        section.cost = Some(metadata.cost);
+        if elapsed_ms > slow_threshold_ms {
+            slow_collectors += 1;
+            section
+                .notes
+                .push(format!("collector took {:.1}s", elapsed_ms as f64 / 1000.0));
+        }
+        if let Some(title) = i18n::section_title(ctx.locale(), &section.id) {
+            section.title = title.into();
+        }
+        if ctx.validate_schemas()
+            && let Some(note) = schema::validate_section_body(&section)
+        {
+            section.notes.push(note);
+        }
         sections.push(section);
     }
+    for (metadata, reason) in skipped {
+        hints.insert(metadata.id, metadata.order);
+        sections.push(skipped_section(ctx, &metadata, reason));
+    }
+
+    let total_ms = started.elapsed().as_millis() as u64;
+
+    let mut slowest: Vec<SectionTiming> = sections
+        .iter()
+        .map(|section| SectionTiming {
+            id: section.id.to_string(),
+            duration_ms: section.duration_ms.unwrap_or(0),
+        })
+        .collect();
+    slowest.sort_by_key(|entry| std::cmp::Reverse(entry.duration_ms));
+    slowest.truncate(SLOWEST_SECTIONS);
+
+    order::order_sections(&mut sections, ctx.section_order(), &hints);
+    let timing = CollectionTiming {
+        total_ms,
+        slowest,
+        slow_threshold_ms,
+        slow_collectors,
+        timed_out,
+    };
+    (sections, timing)
+}
+
+/// Degraded stand-in for a collector whose probe reported it unavailable. The
+/// body names the reason and is not checked against the section schema.
+fn skipped_section(
+    ctx: &CollectionContext,
+    metadata: &CollectorMetadata,
+    reason: String,
+) -> Section {
+    let mut section = Section::degraded(
+        metadata.id,
+        metadata.title,
+        format!("skipped: {}", reason),
+        serde_json::json!({ "skipped": true, "reason": reason }),
+    );
+    section.description = Some(metadata.description);
+    section.tags = metadata.all_tags();
+    section.collector_version = Some(metadata.version);
+    section.cost = Some(metadata.cost);
+    section
+        .notes
+        .push("collector not run because its probe reported it unavailable".to_string());
+    if let Some(title) = i18n::section_title(ctx.locale(), &section.id) {
+        section.title = title.into();
+    }
+    section
+}
+
+/// Worker count for `count` collectors: the context's limit, or the available
+/// parallelism when unset, never more than there are collectors.
+fn collection_jobs(ctx: &CollectionContext, count: usize) -> usize {
+    let limit = ctx.max_jobs().map_or_else(
+        || thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        NonZeroUsize::get,
+    );
+    limit.min(count).max(1)
+}
+
+/// Runs every collector and returns each outcome with its duration in
+/// milliseconds, in the order given. Workers pull collectors from a shared
+/// queue; with one job they run in sequence on the calling thread.
+fn run_collectors(
+    collectors: Vec<Box<dyn Collector>>,
+    ctx: &CollectionContext,
+    progress: Option<ProgressFn<'_>>,
+) -> Vec<(CollectorOutcome, u64)> {
+    let total = collectors.len();
+    let completed = AtomicUsize::new(0);
+    let run = |collector: Box<dyn Collector>| {
+        let id = collector.metadata().id;
+        let timeout = ctx.collector_timeout(id);
+        log::info!("collector {} started", id);
+        if let Some(progress) = progress {
+            progress(CollectorEvent::Started { id, total });
+        }
+        let start = Instant::now();
+        let outcome = run_collector(collector, ctx, timeout);
+        let elapsed = start.elapsed();
+        let elapsed_ms = elapsed.as_millis() as u64;
+        log_outcome(id, &outcome, elapsed_ms);
+        if let Some(progress) = progress {
+            let (status, timed_out) = match &outcome {
+                CollectorOutcome::Collected(section) => (section.status, false),
+                CollectorOutcome::Cancelled(section, _) => (section.status, true),
+                CollectorOutcome::Failed(_) => (SectionStatus::Error, false),
+                CollectorOutcome::TimedOut(_) => (SectionStatus::Error, true),
+            };
+            progress(CollectorEvent::Finished {
+                id,
+                status,
+                timed_out,
+                elapsed,
+                completed: completed.fetch_add(1, Ordering::SeqCst) + 1,
+                total,
+            });
+        }
+        (outcome, elapsed_ms)
+    };
+
+    let jobs = collection_jobs(ctx, collectors.len());
+    if jobs == 1 {
+        return collectors.into_iter().map(run).collect();
+    }
+
+    let count = collectors.len();
+    let queue = Mutex::new(collectors.into_iter().enumerate());
+    let finished = Mutex::new(Vec::with_capacity(count));
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                loop {
+                    let next = queue.lock().expect("collector queue lock").next();
+                    let Some((index, collector)) = next else {
+                        break;
+                    };
+                    let result = run(collector);
+                    finished
+                        .lock()
+                        .expect("collector results lock")
+                        .push((index, result));
+                }
+            });
+        }
+    });
+
+    let mut finished = finished.into_inner().expect("collector results lock");
+    finished.sort_by_key(|(index, _)| *index);
+    finished.into_iter().map(|(_, result)| result).collect()
+}
+
+enum CollectorOutcome {
+    /// Boxed so the enum stays small while results wait in the worker queue.
+    Collected(Box<Section>),
+    /// Partial section returned after the timeout cancelled the collector.
+    Cancelled(Box<Section>, Duration),
+    Failed(anyhow::Error),
+    TimedOut(Duration),
+}
+
+impl From<Result<Section>> for CollectorOutcome {
+    fn from(result: Result<Section>) -> Self {
+        match result {
+            Ok(section) => CollectorOutcome::Collected(Box::new(section)),
+            Err(error) => CollectorOutcome::Failed(error),
+        }
+    }
+}
+
+/// Logs a finished collector as soon as it returns: failures and timeouts at
+/// warn level, everything else at info.
+fn log_outcome(id: &str, outcome: &CollectorOutcome, elapsed_ms: u64) {
+    match outcome {
+        CollectorOutcome::Collected(section) => match section.status {
+            SectionStatus::Error => log::warn!(
+                "collector {} failed: {}",
+                id,
+                section.summary.as_deref().unwrap_or("no details")
+            ),
+            SectionStatus::Degraded => log::info!(
+                "collector {} finished degraded in {} ms: {}",
+                id,
+                elapsed_ms,
+                section.summary.as_deref().unwrap_or("no details")
+            ),
+            SectionStatus::Success => {
+                log::info!("collector {} finished in {} ms", id, elapsed_ms)
+            }
+        },
+        CollectorOutcome::Cancelled(_, limit) => log::warn!(
+            "collector {} cancelled after {:.1}s; keeping its partial section",
+            id,
+            limit.as_secs_f64()
+        ),
+        CollectorOutcome::Failed(error) => {
+            log::warn!("collector {} failed: {:#}", id, error)
+        }
+        CollectorOutcome::TimedOut(limit) => log::warn!(
+            "collector {} timed out after {:.1}s",
+            id,
+            limit.as_secs_f64()
+        ),
+    }
+}
+
+/// How long a collector whose timeout expired has, once its cancellation token
+/// is tripped, to return a partial section before it is abandoned.
+const CANCELLATION_GRACE: Duration = Duration::from_millis(500);
+
+/// Runs `collector` on a worker thread when a timeout applies. When the limit
+/// expires the collector's cancellation token is tripped; a section returned
+/// within [`CANCELLATION_GRACE`] is kept as degraded, otherwise the collector
+/// is left running detached and its result is discarded.
+fn run_collector(
+    collector: Box<dyn Collector>,
+    ctx: &CollectionContext,
+    timeout: Option<Duration>,
+) -> CollectorOutcome {
+    let Some(limit) = timeout else {
+        return collector.collect(ctx).into();
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let mut worker_ctx = ctx.clone();
+    let cancellation = ctx.cancellation().child();
+    worker_ctx.set_cancellation(cancellation.clone());
+    let spawned = thread::Builder::new()
+        .name(format!("vmic-{}", collector.metadata().id))
+        .spawn(move || {
+            let _ = sender.send(collector.collect(&worker_ctx));
+        });
+    if let Err(error) = spawned {
+        return CollectorOutcome::Failed(
+            anyhow::Error::new(error).context("failed to start collector thread"),
+        );
+    }
+
+    let result = match receiver.recv_timeout(limit) {
+        Ok(result) => return result.into(),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            cancellation.cancel();
+            receiver.recv_timeout(CANCELLATION_GRACE)
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(mpsc::RecvTimeoutError::Disconnected),
+    };
+    match result {
+        Ok(Ok(section)) => {
+            CollectorOutcome::Cancelled(Box::new(partial_section(section, limit)), limit)
+        }
+        Ok(Err(_)) | Err(mpsc::RecvTimeoutError::Timeout) => CollectorOutcome::TimedOut(limit),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            CollectorOutcome::Failed(anyhow::anyhow!("collector panicked"))
+        }
+    }
+}
 
-    sections
+/// Marks a section returned after cancellation: at least degraded, with a note.
+fn partial_section(mut section: Section, limit: Duration) -> Section {
+    let message = format!(
+        "collector cancelled after {:.1}s; results are partial",
+        limit.as_secs_f64()
+    );
+    if section.status == SectionStatus::Success {
+        section.status = SectionStatus::Degraded;
+        section.summary = Some(message.clone());
+    }
+    section.notes.push(message);
+    section
 }
 
 pub fn collect_report(ctx: &CollectionContext) -> Report {
-    Report::new(collect_sections(ctx))
+    collect_report_with_digest(ctx, DigestThresholds::default())
 }
 
 pub fn collect_report_with_digest(ctx: &CollectionContext, thresholds: DigestThresholds) -> Report {
-    Report::with_digest_config(collect_sections(ctx), thresholds)
+    collect_report_with_history(ctx, thresholds, None)
 }
 
-mod health {
-    use super::{Section, SectionStatus};
-    use anyhow::{Result, anyhow};
-    use serde::Serialize;
-    use serde_json::Value;
+/// Collects a report whose digest escalates sections that `aging` shows as
+/// degraded for too many consecutive runs.
+pub fn collect_report_with_history(
+    ctx: &CollectionContext,
+    thresholds: DigestThresholds,
+    aging: Option<&DegradedAging>,
+) -> Report {
+    collect_report_filtered(ctx, thresholds, aging, |_| true)
+}
+
+/// Like [`collect_report_with_history`], running only the collectors for which
+/// `include` returns true.
+pub fn collect_report_filtered<F>(
+    ctx: &CollectionContext,
+    thresholds: DigestThresholds,
+    aging: Option<&DegradedAging>,
+    include: F,
+) -> Report
+where
+    F: Fn(&CollectorMetadata) -> bool,
+{
+    collect_filtered(ctx, thresholds, aging, &include, None)
+}
+
+/// Like [`collect_report_filtered`], calling `progress` as each collector
+/// starts and finishes.
+pub fn collect_report_with_progress<F>(
+    ctx: &CollectionContext,
+    thresholds: DigestThresholds,
+    aging: Option<&DegradedAging>,
+    include: F,
+    progress: ProgressFn<'_>,
+) -> Report
+where
+    F: Fn(&CollectorMetadata) -> bool,
+{
+    collect_filtered(ctx, thresholds, aging, &include, Some(progress))
+}
+
+fn collect_filtered(
+    ctx: &CollectionContext,
+    thresholds: DigestThresholds,
+    aging: Option<&DegradedAging>,
+    include: &dyn Fn(&CollectorMetadata) -> bool,
+    progress: Option<ProgressFn<'_>>,
+) -> Report {
+    let (sections, timing) = collect_sections(ctx, include, progress);
+    let mut report = build_report(ctx, sections, thresholds, aging);
+    report.metadata.timing = Some(timing);
+    report
+}
+
+/// Builds the digest from the complete section data before applying any
+/// context-driven post-processing that may drop detail.
+fn build_report(
+    ctx: &CollectionContext,
+    sections: Vec<Section>,
+    thresholds: DigestThresholds,
+    aging: Option<&DegradedAging>,
+) -> Report {
+    let mut sections = sections;
+    correlate::name_listener_containers(&mut sections);
+    let mut report = Report::with_digest_history(sections, thresholds, aging);
+    report.metadata.detail_level = Some(ctx.detail_level());
+    if let Some(salt) = ctx.fingerprint_salt() {
+        report.metadata.host_fingerprint = fingerprint::host_fingerprint(salt);
+    }
+    if let Some(budget) = ctx.body_budget_bytes() {
+        for section in &mut report.sections {
+            budget::apply_body_budget(section, budget);
+        }
+        report.refresh_checksum();
+    }
+    report
+}
 
-    #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub mod health {
+    use super::{Section, SectionStatus, history, schema};
+    use anyhow::{Context as _, Result, anyhow};
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::Path;
+    use vmic_sdk::{FindingSeverity, SectionFinding};
+
+    #[derive(
+        Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default,
+    )]
     #[serde(rename_all = "lowercase")]
     pub enum Severity {
         #[default]
@@ -144,37 +980,207 @@ mod health {
         }
     }
 
-    #[derive(Debug, Clone, Serialize, Default)]
+    #[derive(Debug, Clone, Serialize, Deserialize, Default)]
     pub struct HealthDigest {
         pub overall: Severity,
         pub findings: Vec<CriticalFinding>,
+        pub summary: DigestSummary,
+    }
+
+    impl From<FindingSeverity> for Severity {
+        fn from(severity: FindingSeverity) -> Self {
+            match severity {
+                FindingSeverity::Info => Severity::Info,
+                FindingSeverity::Warning => Severity::Warning,
+                FindingSeverity::Critical => Severity::Critical,
+            }
+        }
+    }
+
+    impl std::str::FromStr for Severity {
+        type Err = anyhow::Error;
+
+        fn from_str(value: &str) -> Result<Self> {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "info" => Ok(Severity::Info),
+                "warning" => Ok(Severity::Warning),
+                "critical" => Ok(Severity::Critical),
+                other => Err(anyhow!(
+                    "unknown severity '{}' (expected info, warning, or critical)",
+                    other
+                )),
+            }
+        }
+    }
+
+    impl HealthDigest {
+        /// Copy that keeps only findings at or above `min`, with the summary
+        /// recomputed. `overall` stays the unfiltered maximum.
+        pub fn filtered(&self, min: Severity) -> HealthDigest {
+            let findings: Vec<CriticalFinding> = self
+                .findings
+                .iter()
+                .filter(|finding| finding.severity >= min)
+                .cloned()
+                .collect();
+            let summary = DigestSummary::from_findings(&findings);
+            HealthDigest {
+                overall: self.overall,
+                findings,
+                summary,
+            }
+        }
+
+        /// Plain-text digest: the overall severity, then one
+        /// `<severity> <section>: <message>` line per finding, worst first.
+        /// Multi-line messages are joined onto their line. `color` wraps
+        /// severities in ANSI colors for terminals.
+        pub fn to_text(&self, color: bool) -> String {
+            let paint = |severity: Severity| {
+                let label = severity.as_str();
+                if !color {
+                    return label.to_string();
+                }
+                let code = match severity {
+                    Severity::Critical => "1;31",
+                    Severity::Warning => "33",
+                    Severity::Info => "36",
+                };
+                format!("\x1b[{}m{}\x1b[0m", code, label)
+            };
+            if self.findings.is_empty() {
+                return format!("overall: {}, no findings\n", paint(self.overall));
+            }
+
+            let mut findings: Vec<&CriticalFinding> = self.findings.iter().collect();
+            findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+            let mut text = format!(
+                "overall: {}, {} finding{}\n",
+                paint(self.overall),
+                findings.len(),
+                if findings.len() == 1 { "" } else { "s" }
+            );
+            for finding in findings {
+                text.push_str(&format!(
+                    "{} {}: {}\n",
+                    paint(finding.severity),
+                    finding.source_id,
+                    finding
+                        .message
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ));
+            }
+            text
+        }
+    }
+
+    /// Finding counts per severity with a per-section breakdown.
+    #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+    pub struct DigestSummary {
+        pub critical: usize,
+        pub warning: usize,
+        pub info: usize,
+        pub sections: BTreeMap<String, SeverityCounts>,
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+    pub struct SeverityCounts {
+        pub critical: usize,
+        pub warning: usize,
+        pub info: usize,
     }
 
-    #[derive(Debug, Clone, Serialize)]
+    impl SeverityCounts {
+        pub(crate) fn record(&mut self, severity: Severity) {
+            match severity {
+                Severity::Critical => self.critical += 1,
+                Severity::Warning => self.warning += 1,
+                Severity::Info => self.info += 1,
+            }
+        }
+
+        pub fn total(&self) -> usize {
+            self.critical + self.warning + self.info
+        }
+    }
+
+    impl DigestSummary {
+        /// Recomputes the counts from `findings`; call again after filtering them.
+        pub fn from_findings(findings: &[CriticalFinding]) -> Self {
+            let mut totals = SeverityCounts::default();
+            let mut sections: BTreeMap<String, SeverityCounts> = BTreeMap::new();
+            for finding in findings {
+                totals.record(finding.severity);
+                sections
+                    .entry(finding.source_id.clone())
+                    .or_default()
+                    .record(finding.severity);
+            }
+            Self {
+                critical: totals.critical,
+                warning: totals.warning,
+                info: totals.info,
+                sections,
+            }
+        }
+
+        pub fn total(&self) -> usize {
+            self.critical + self.warning + self.info
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct CriticalFinding {
         pub source_id: String,
         pub source_title: String,
         pub severity: Severity,
         pub message: String,
+        /// Check identifier of a finding the collector reported itself.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub code: Option<String>,
     }
 
     impl CriticalFinding {
-        fn new(section: &Section, severity: Severity, message: String) -> Self {
+        fn new(section: &SectionInput<'_>, severity: Severity, message: String) -> Self {
             Self {
                 source_id: section.id.to_string(),
                 source_title: section.title.to_string(),
                 severity,
                 message,
+                code: None,
+            }
+        }
+
+        fn from_collector(section: &SectionInput<'_>, finding: &SectionFinding) -> Self {
+            Self {
+                code: Some(finding.code.clone()),
+                ..Self::new(section, finding.severity.into(), finding.message.clone())
             }
         }
+
+        /// Same section and the same message up to case and surrounding space.
+        fn is_equivalent(&self, other: &CriticalFinding) -> bool {
+            self.source_id == other.source_id
+                && self
+                    .message
+                    .trim()
+                    .eq_ignore_ascii_case(other.message.trim())
+        }
     }
 
-    #[derive(Debug, Clone, Copy, Serialize)]
+    #[derive(Debug, Clone, Copy, Serialize, PartialEq)]
     pub struct DigestThresholds {
         pub disk_warning: f64,
         pub disk_critical: f64,
         pub memory_warning: f64,
         pub memory_critical: f64,
+        /// Filesystems at or below this size (GiB) are judged by usage ratio
+        /// only; the absolute free-space rules would flag them permanently.
+        pub free_space_min_fs_gib: f64,
     }
 
     impl Default for DigestThresholds {
@@ -184,11 +1190,82 @@ mod health {
                 disk_critical: 0.95,
                 memory_warning: 0.10,
                 memory_critical: 0.05,
+                free_space_min_fs_gib: 16.0,
             }
         }
     }
 
+    /// Threshold file layout; every key is optional and unknown keys are errors.
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct ThresholdsFile {
+        disk_warning: Option<f64>,
+        disk_critical: Option<f64>,
+        memory_warning: Option<f64>,
+        memory_critical: Option<f64>,
+        free_space_min_fs_gib: Option<f64>,
+    }
+
+    /// Reads values above 1.0 as percentages; the result must lie in 0.0-1.0.
+    pub fn percent_to_ratio(value: f64) -> Result<f64> {
+        let ratio = if value > 1.0 { value / 100.0 } else { value };
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(anyhow!("threshold must be between 0 and 100 (or 0.0-1.0)"));
+        }
+        Ok(ratio)
+    }
+
     impl DigestThresholds {
+        /// Parses thresholds from TOML, starting from the defaults for omitted
+        /// keys. Percent values (over 1.0) are converted to ratios and the result
+        /// is checked with [`DigestThresholds::validate`].
+        pub fn from_toml_str(source: &str) -> Result<Self> {
+            let file: ThresholdsFile = toml::from_str(source)
+                .map_err(|error| anyhow!("invalid digest thresholds: {}", error.message()))?;
+            let mut thresholds = Self::default();
+            for (name, value, slot) in [
+                (
+                    "disk_warning",
+                    file.disk_warning,
+                    &mut thresholds.disk_warning,
+                ),
+                (
+                    "disk_critical",
+                    file.disk_critical,
+                    &mut thresholds.disk_critical,
+                ),
+                (
+                    "memory_warning",
+                    file.memory_warning,
+                    &mut thresholds.memory_warning,
+                ),
+                (
+                    "memory_critical",
+                    file.memory_critical,
+                    &mut thresholds.memory_critical,
+                ),
+            ] {
+                if let Some(value) = value {
+                    *slot = percent_to_ratio(value).with_context(|| format!("invalid {}", name))?;
+                }
+            }
+            if let Some(value) = file.free_space_min_fs_gib {
+                thresholds.free_space_min_fs_gib = value;
+            }
+            thresholds.validate()?;
+            Ok(thresholds)
+        }
+
+        pub fn from_path(path: &Path) -> Result<Self> {
+            let source = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            Self::from_toml_str(&source).with_context(|| format!("in {}", path.display()))
+        }
+
+        pub fn to_toml_string(&self) -> String {
+            toml::to_string(self).expect("thresholds serialize to TOML")
+        }
+
         pub fn validate(&self) -> Result<()> {
             for (name, value) in [
                 ("disk_warning", self.disk_warning),
@@ -201,6 +1278,12 @@ mod health {
                 }
             }
 
+            if !(self.free_space_min_fs_gib.is_finite() && self.free_space_min_fs_gib >= 0.0) {
+                return Err(anyhow!(
+                    "free_space_min_fs_gib must be a non-negative number"
+                ));
+            }
+
             if self.disk_warning > self.disk_critical {
                 return Err(anyhow!(
                     "disk_warning ({:.2}%) must be <= disk_critical ({:.2}%)",
@@ -221,26 +1304,153 @@ mod health {
         }
     }
 
+    /// Section fields the digest rules read, borrowed either from a collected
+    /// [`Section`] or from a section object in a parsed report document.
+    struct SectionInput<'a> {
+        id: &'a str,
+        title: &'a str,
+        status: SectionStatus,
+        summary: Option<&'a str>,
+        body: &'a Value,
+        findings: Vec<SectionFinding>,
+    }
+
+    impl<'a> SectionInput<'a> {
+        fn from_section(section: &'a Section) -> Self {
+            Self {
+                id: &section.id,
+                title: &section.title,
+                status: section.status,
+                summary: section.summary.as_deref(),
+                body: &section.body,
+                findings: section.findings.clone(),
+            }
+        }
+
+        fn from_json(index: usize, value: &'a Value) -> Result<Self> {
+            let field = |name: &str| {
+                value
+                    .get(name)
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow!("sections[{}] has no string '{}'", index, name))
+            };
+            let status = match field("status")? {
+                "success" => SectionStatus::Success,
+                "degraded" => SectionStatus::Degraded,
+                "error" => SectionStatus::Error,
+                other => {
+                    return Err(anyhow!(
+                        "sections[{}] has unknown status '{}'",
+                        index,
+                        other
+                    ));
+                }
+            };
+            Ok(Self {
+                id: field("id")?,
+                title: field("title")?,
+                status,
+                summary: value.get("summary").and_then(Value::as_str),
+                body: value.get("body").unwrap_or(&Value::Null),
+                findings: value
+                    .get("findings")
+                    .cloned()
+                    .map(serde_json::from_value)
+                    .transpose()
+                    .with_context(|| format!("sections[{}] has invalid findings", index))?
+                    .unwrap_or_default(),
+            })
+        }
+    }
+
+    /// Consecutive degraded runs after which a degraded section is reported as critical.
+    pub const DEFAULT_DEGRADED_ESCALATION_RUNS: usize = 5;
+
+    /// How long each section has been degraded in earlier runs, used to escalate
+    /// collectors that stay degraded.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct DegradedAging {
+        /// Consecutive degraded runs per section id, ending with the previous run.
+        pub previous_runs: BTreeMap<String, usize>,
+        /// A section degraded for more than this many runs (the current one
+        /// included) is escalated to critical.
+        pub escalate_after: usize,
+    }
+
+    impl DegradedAging {
+        pub fn from_history(entries: &[history::Entry], escalate_after: usize) -> Self {
+            Self {
+                previous_runs: history::degraded_streaks(entries),
+                escalate_after,
+            }
+        }
+
+        /// Consecutive degraded runs including the current one, when that exceeds
+        /// the escalation limit.
+        fn escalated_runs(&self, id: &str) -> Option<usize> {
+            let runs = self.previous_runs.get(id).copied().unwrap_or(0) + 1;
+            (runs > self.escalate_after).then_some(runs)
+        }
+    }
+
+    /// Evaluates the digest rules over collected sections. With `aging`, degraded
+    /// sections that stayed degraded for too many runs become critical.
     pub fn build_health_digest(
         sections: &[Section],
         thresholds: &DigestThresholds,
+        aging: Option<&DegradedAging>,
     ) -> HealthDigest {
-        let mut findings: Vec<CriticalFinding> = Vec::new();
+        let inputs: Vec<SectionInput<'_>> =
+            sections.iter().map(SectionInput::from_section).collect();
+        digest_from_inputs(&inputs, thresholds, aging)
+    }
+
+    /// Re-evaluates the digest of a stored report document with `thresholds`,
+    /// applying the same rules as [`build_health_digest`]. The document must
+    /// match the published report schema.
+    pub fn digest_from_json(
+        document: &Value,
+        thresholds: &DigestThresholds,
+    ) -> Result<HealthDigest> {
+        schema::validate_report(document)?;
+        let sections = document
+            .get("sections")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("document has no sections array"))?;
+        let inputs = sections
+            .iter()
+            .enumerate()
+            .map(|(index, value)| SectionInput::from_json(index, value))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(digest_from_inputs(&inputs, thresholds, None))
+    }
+
+    fn digest_from_inputs(
+        sections: &[SectionInput<'_>],
+        thresholds: &DigestThresholds,
+        aging: Option<&DegradedAging>,
+    ) -> HealthDigest {
+        let mut findings: Vec<CriticalFinding> = Vec::new();
 
         for section in sections {
             match section.status {
                 SectionStatus::Success => {}
                 SectionStatus::Degraded => {
-                    let message = section
+                    let mut message = section
                         .summary
-                        .clone()
+                        .map(str::to_string)
                         .unwrap_or_else(|| "Collector reported a degraded state".to_string());
-                    findings.push(CriticalFinding::new(section, Severity::Warning, message));
+                    let mut severity = Severity::Warning;
+                    if let Some(runs) = aging.and_then(|aging| aging.escalated_runs(section.id)) {
+                        severity = Severity::Critical;
+                        message = format!("{} (degraded for {} consecutive runs)", message, runs);
+                    }
+                    findings.push(CriticalFinding::new(section, severity, message));
                 }
                 SectionStatus::Error => {
                     let message = section
                         .summary
-                        .clone()
+                        .map(str::to_string)
                         .unwrap_or_else(|| "Collector failed".to_string());
                     findings.push(CriticalFinding::new(section, Severity::Critical, message));
                 }
@@ -248,6 +1458,12 @@ mod health {
 
             collect_storage_alerts(section, thresholds, &mut findings);
             collect_proc_alerts(section, thresholds, &mut findings);
+            for finding in &section.findings {
+                merge_finding(
+                    &mut findings,
+                    CriticalFinding::from_collector(section, finding),
+                );
+            }
         }
 
         let overall = findings
@@ -256,11 +1472,36 @@ mod health {
             .max()
             .unwrap_or(Severity::Info);
 
-        HealthDigest { overall, findings }
+        let summary = DigestSummary::from_findings(&findings);
+
+        HealthDigest {
+            overall,
+            findings,
+            summary,
+        }
+    }
+
+    /// Adds a collector finding unless an equivalent one is already present; the
+    /// kept finding takes the higher severity and the collector's code.
+    fn merge_finding(findings: &mut Vec<CriticalFinding>, finding: CriticalFinding) {
+        match findings
+            .iter_mut()
+            .find(|existing| existing.is_equivalent(&finding))
+        {
+            Some(existing) => {
+                existing.severity = existing.severity.max(finding.severity);
+                if existing.code.is_none() {
+                    existing.code = finding.code;
+                }
+            }
+            None => findings.push(finding),
+        }
     }
 
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
     fn collect_storage_alerts(
-        section: &Section,
+        section: &SectionInput<'_>,
         thresholds: &DigestThresholds,
         findings: &mut Vec<CriticalFinding>,
     ) {
@@ -305,7 +1546,14 @@ mod health {
                 .get("available_bytes")
                 .and_then(Value::as_u64)
                 .unwrap_or(0);
-            let free_gib = available_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+            let free_gib = available_bytes as f64 / GIB;
+            // Without a reported size the mount is treated as large enough.
+            let size_gib = mount
+                .get("total_bytes")
+                .and_then(Value::as_u64)
+                .map(|total| total as f64 / GIB);
+            let absolute_rules_apply =
+                size_gib.is_none_or(|size| size > thresholds.free_space_min_fs_gib);
 
             let inodes_ratio = mount
                 .get("inodes_usage_ratio")
@@ -329,12 +1577,14 @@ mod health {
                 reasons.push(format!("usage {:.1}%", ratio * 100.0));
             }
 
-            if free_gib <= 2.0 {
-                escalate(&mut severity, Severity::Critical);
-                reasons.push(format!("free space {:.2} GiB", free_gib));
-            } else if free_gib <= 5.0 {
-                escalate(&mut severity, Severity::Warning);
-                reasons.push(format!("free space {:.2} GiB", free_gib));
+            if absolute_rules_apply {
+                if free_gib <= 2.0 {
+                    escalate(&mut severity, Severity::Critical);
+                    reasons.push(format!("free space {:.2} GiB", free_gib));
+                } else if free_gib <= 5.0 {
+                    escalate(&mut severity, Severity::Warning);
+                    reasons.push(format!("free space {:.2} GiB", free_gib));
+                }
             }
 
             if inodes_ratio >= 0.90 {
@@ -370,7 +1620,7 @@ mod health {
     }
 
     fn collect_proc_alerts(
-        section: &Section,
+        section: &SectionInput<'_>,
         thresholds: &DigestThresholds,
         findings: &mut Vec<CriticalFinding>,
     ) {
@@ -456,13 +1706,61 @@ mod render {
     use askama::Template;
     use std::cmp::Ordering;
 
-    use super::{Report, SectionStatus};
+    use super::diff::ReportDiff;
+    use super::merge::{HostFinding, MergedReport};
+    use super::{HealthDigest, Locale, Report, SectionStatus, Severity, i18n};
     use serde_json::Value;
+    use vmic_sdk::{SectionPart, SubSection};
 
     #[derive(Template)]
     #[template(path = "report.md", escape = "none")]
     struct MarkdownReport<'a> {
         report: &'a Report,
+        sections: Vec<SectionView>,
+        digest: HealthDigest,
+        hidden_findings: usize,
+        baseline: Option<BaselineView>,
+        timing: Option<TimingView>,
+        locale: Locale,
+    }
+
+    impl MarkdownReport<'_> {
+        fn t<'k>(&self, key: &'k str) -> &'k str {
+            i18n::translate(self.locale, key)
+        }
+
+        fn cell(&self, text: &str) -> String {
+            markdown_escape(text)
+        }
+    }
+
+    #[derive(Template)]
+    #[template(path = "summary.md", escape = "none")]
+    struct MarkdownSummary<'a> {
+        report: &'a Report,
+        findings: Vec<SummaryFinding>,
+        hidden_findings: usize,
+        rows: Vec<SummaryRow>,
+        locale: Locale,
+    }
+
+    impl MarkdownSummary<'_> {
+        fn t<'k>(&self, key: &'k str) -> &'k str {
+            i18n::translate(self.locale, key)
+        }
+    }
+
+    struct SummaryFinding {
+        severity: &'static str,
+        source: String,
+        message: String,
+    }
+
+    struct SummaryRow {
+        id: String,
+        status: String,
+        summary: String,
+        duration: String,
     }
 
     #[derive(Template)]
@@ -470,24 +1768,562 @@ mod render {
     struct HtmlReport<'a> {
         report: &'a Report,
         sections: Vec<SectionView>,
+        digest: HealthDigest,
+        hidden_findings: usize,
+        baseline: Option<BaselineView>,
+        timing: Option<TimingView>,
+        locale: Locale,
+    }
+
+    impl HtmlReport<'_> {
+        fn t<'k>(&self, key: &'k str) -> &'k str {
+            i18n::translate(self.locale, key)
+        }
+    }
+
+    const DEFAULT_MAX_TABLE_ROWS: usize = 12;
+    const DEFAULT_MAX_LIST_ITEMS: usize = 20;
+    const DEFAULT_RAW_BODY_MAX_BYTES: usize = 64 * 1024;
+
+    /// Controls how much detail the renderers emit.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct RenderOptions {
+        /// Upper bound on rows per table, 12 by default; `None` shows every row.
+        pub max_table_rows: Option<usize>,
+        /// Upper bound on items per list, 20 by default; `None` shows every item.
+        pub max_list_items: Option<usize>,
+        /// Caps compact tables (swap devices, interfaces) shorter than
+        /// `max_table_rows`. On by default; turn it off when the row limit was
+        /// chosen explicitly so it applies to every table as given.
+        pub compact_tables: bool,
+        /// List counterpart of `compact_tables`.
+        pub compact_lists: bool,
+        /// Appends a collapsed block with each section's pretty-printed body.
+        pub include_raw_body: bool,
+        /// Byte budget for each raw body block; longer bodies are cut with a note.
+        pub raw_body_max_bytes: usize,
+        /// Language for static labels, table headers, and status names.
+        pub locale: Locale,
+        /// Markdown only: emit the digest and a one-line-per-section table instead
+        /// of full section bodies.
+        pub summary_only: bool,
+        /// Digest findings below this severity are hidden in Markdown/HTML; JSON
+        /// output always carries the full list.
+        pub min_finding_severity: Severity,
+        /// Comparison with a stored report, rendered as a "Changes since
+        /// baseline" block at the end of Markdown/HTML reports.
+        pub baseline: Option<ReportDiff>,
+    }
+
+    impl Default for RenderOptions {
+        fn default() -> Self {
+            Self {
+                max_table_rows: Some(DEFAULT_MAX_TABLE_ROWS),
+                max_list_items: Some(DEFAULT_MAX_LIST_ITEMS),
+                compact_tables: true,
+                compact_lists: true,
+                include_raw_body: false,
+                raw_body_max_bytes: DEFAULT_RAW_BODY_MAX_BYTES,
+                locale: Locale::default(),
+                summary_only: false,
+                min_finding_severity: Severity::Info,
+                baseline: None,
+            }
+        }
+    }
+
+    impl RenderOptions {
+        /// Options that lift every row and item limit.
+        pub fn full_detail() -> Self {
+            Self {
+                max_table_rows: None,
+                max_list_items: None,
+                compact_tables: false,
+                compact_lists: false,
+                ..Self::default()
+            }
+        }
+    }
+
+    /// Markdown shares the HTML section views, so every option applies to both.
+    pub fn render_markdown(report: &Report, options: &RenderOptions) -> askama::Result<String> {
+        if options.summary_only {
+            return render_markdown_summary(report, options);
+        }
+        let (digest, hidden_findings) = visible_digest(report, options);
+        MarkdownReport {
+            report,
+            sections: build_section_views(report, options),
+            digest,
+            hidden_findings,
+            baseline: baseline_view(options),
+            timing: timing_view(report, options),
+            locale: options.locale,
+        }
+        .render()
+    }
+
+    fn render_markdown_summary(report: &Report, options: &RenderOptions) -> askama::Result<String> {
+        let (digest, hidden_findings) = visible_digest(report, options);
+        let findings = digest
+            .findings
+            .iter()
+            .map(|finding| SummaryFinding {
+                severity: finding.severity.display_label(),
+                source: finding.source_title.clone(),
+                message: markdown_cell(&finding.message, SUMMARY_MAX_CHARS),
+            })
+            .collect();
+        let rows = report
+            .sections
+            .iter()
+            .map(|section| SummaryRow {
+                id: section.id.to_string(),
+                status: i18n::translate(options.locale, &status_label(&section.status)).to_string(),
+                summary: section
+                    .summary
+                    .as_deref()
+                    .map(|summary| markdown_cell(summary, SUMMARY_ROW_MAX_CHARS))
+                    .unwrap_or_else(|| "-".to_string()),
+                duration: format_duration(section.duration_ms).unwrap_or_else(|| "-".to_string()),
+            })
+            .collect();
+
+        MarkdownSummary {
+            report,
+            findings,
+            hidden_findings,
+            rows,
+            locale: options.locale,
+        }
+        .render()
     }
 
-    pub fn render_markdown(report: &Report) -> askama::Result<String> {
-        MarkdownReport { report }.render()
+    /// Flattens text onto one line and escapes pipes so it fits a Markdown table cell.
+    fn markdown_cell(text: &str, max_chars: usize) -> String {
+        markdown_escape(&truncate(text, max_chars))
     }
 
-    pub fn render_html(report: &Report) -> askama::Result<String> {
+    fn markdown_escape(text: &str) -> String {
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace('|', "\\|")
+    }
+
+    pub fn render_html(report: &Report, options: &RenderOptions) -> askama::Result<String> {
+        let (digest, hidden_findings) = visible_digest(report, options);
         HtmlReport {
             report,
-            sections: build_section_views(report),
+            sections: build_section_views(report, options),
+            digest,
+            hidden_findings,
+            baseline: baseline_view(options),
+            timing: timing_view(report, options),
+            locale: options.locale,
+        }
+        .render()
+    }
+
+    #[derive(Template)]
+    #[template(path = "diff.md", escape = "none")]
+    struct MarkdownDiff<'a> {
+        diff: &'a ReportDiff,
+        old_generated_at: String,
+        new_generated_at: String,
+        statuses: Vec<[String; 3]>,
+        mounts: Vec<[String; 4]>,
+        crossings: Vec<String>,
+        locale: Locale,
+    }
+
+    impl MarkdownDiff<'_> {
+        fn t<'k>(&self, key: &'k str) -> &'k str {
+            i18n::translate(self.locale, key)
+        }
+
+        fn cell(&self, text: &str) -> String {
+            markdown_escape(text)
+        }
+    }
+
+    pub fn render_diff_markdown(
+        diff: &ReportDiff,
+        options: &RenderOptions,
+    ) -> askama::Result<String> {
+        let missing = i18n::translate(options.locale, "absent");
+        let unknown = || i18n::translate(options.locale, "unknown").to_string();
+        let statuses = diff
+            .status_changes
+            .iter()
+            .map(|change| {
+                let status =
+                    |value: &Option<String>| value.as_deref().unwrap_or(missing).to_string();
+                [change.id.clone(), status(&change.old), status(&change.new)]
+            })
+            .collect();
+        let mounts = diff
+            .mount_changes
+            .iter()
+            .map(|change| {
+                [
+                    change.mount_point.clone(),
+                    format!("{:.1}%", change.old_usage_ratio * 100.0),
+                    format!("{:.1}%", change.new_usage_ratio * 100.0),
+                    format!(
+                        "{:+.1} pp",
+                        (change.new_usage_ratio - change.old_usage_ratio) * 100.0
+                    ),
+                ]
+            })
+            .collect();
+        let crossings = diff
+            .threshold_crossings
+            .iter()
+            .map(|crossing| {
+                format!(
+                    "{}: {:.1}% → {:.1}% (**{}** ≥ {:.1}%)",
+                    markdown_escape(&crossing.mount_point),
+                    crossing.old_usage_ratio * 100.0,
+                    crossing.new_usage_ratio * 100.0,
+                    i18n::translate(options.locale, crossing.severity.display_label()),
+                    crossing.threshold * 100.0
+                )
+            })
+            .collect();
+        MarkdownDiff {
+            diff,
+            old_generated_at: diff.old_generated_at.clone().unwrap_or_else(unknown),
+            new_generated_at: diff.new_generated_at.clone().unwrap_or_else(unknown),
+            statuses,
+            mounts,
+            crossings,
+            locale: options.locale,
+        }
+        .render()
+    }
+
+    #[derive(Template)]
+    #[template(path = "merged.md", escape = "none")]
+    struct MarkdownMerged<'a> {
+        merged: &'a MergedReport,
+        fleet: Vec<KeyValue>,
+        hosts: Vec<HostView>,
+        findings: Vec<HostFinding>,
+        hidden_findings: usize,
+        locale: Locale,
+    }
+
+    impl MarkdownMerged<'_> {
+        fn t<'k>(&self, key: &'k str) -> &'k str {
+            i18n::translate(self.locale, key)
+        }
+
+        fn cell(&self, text: &str) -> String {
+            markdown_escape(text)
+        }
+    }
+
+    #[derive(Template)]
+    #[template(path = "merged.html")]
+    struct HtmlMerged<'a> {
+        merged: &'a MergedReport,
+        fleet: Vec<KeyValue>,
+        hosts: Vec<HostView>,
+        findings: Vec<HostFinding>,
+        hidden_findings: usize,
+        locale: Locale,
+    }
+
+    impl HtmlMerged<'_> {
+        fn t<'k>(&self, key: &'k str) -> &'k str {
+            i18n::translate(self.locale, key)
+        }
+
+        /// Id of the host-scoped section card a finding points at.
+        fn finding_anchor(&self, entry: &HostFinding) -> String {
+            format!(
+                "host-{}-{}",
+                heading_slug(&entry.host),
+                entry.finding.source_id
+            )
+        }
+    }
+
+    struct HostView {
+        name: String,
+        slug: String,
+        overall: Severity,
+        findings: usize,
+        sections: Vec<SectionView>,
+    }
+
+    pub fn render_merged_markdown(
+        merged: &MergedReport,
+        options: &RenderOptions,
+    ) -> askama::Result<String> {
+        let (findings, hidden_findings) = visible_host_findings(merged, options);
+        MarkdownMerged {
+            merged,
+            fleet: fleet_key_values(merged, options),
+            hosts: host_views(merged, options, false),
+            findings,
+            hidden_findings,
+            locale: options.locale,
+        }
+        .render()
+    }
+
+    pub fn render_merged_html(
+        merged: &MergedReport,
+        options: &RenderOptions,
+    ) -> askama::Result<String> {
+        let (findings, hidden_findings) = visible_host_findings(merged, options);
+        HtmlMerged {
+            merged,
+            fleet: fleet_key_values(merged, options),
+            hosts: host_views(merged, options, true),
+            findings,
+            hidden_findings,
+            locale: options.locale,
         }
         .render()
     }
 
+    fn visible_host_findings(
+        merged: &MergedReport,
+        options: &RenderOptions,
+    ) -> (Vec<HostFinding>, usize) {
+        let all = &merged.health_digest.findings;
+        let visible: Vec<HostFinding> = all
+            .iter()
+            .filter(|entry| entry.finding.severity >= options.min_finding_severity)
+            .cloned()
+            .collect();
+        let hidden = all.len() - visible.len();
+        (visible, hidden)
+    }
+
+    fn fleet_key_values(merged: &MergedReport, options: &RenderOptions) -> Vec<KeyValue> {
+        let fleet = &merged.fleet;
+        [
+            ("Hosts", fleet.hosts.to_string()),
+            ("Total Capacity", format_bytes(fleet.total_capacity_bytes)),
+            ("Used Capacity", format_bytes(fleet.used_capacity_bytes)),
+            ("Failed Services", fleet.failed_services.to_string()),
+        ]
+        .into_iter()
+        .map(|(key, value)| KeyValue {
+            key: i18n::translate(options.locale, key).to_string(),
+            value,
+            bar: None,
+        })
+        .collect()
+    }
+
+    /// Section views per host; HTML ids are prefixed with the host anchor so the
+    /// same section id can appear once per host.
+    fn host_views(
+        merged: &MergedReport,
+        options: &RenderOptions,
+        scoped_ids: bool,
+    ) -> Vec<HostView> {
+        merged
+            .hosts
+            .iter()
+            .map(|entry| {
+                let slug = heading_slug(&entry.host);
+                let mut sections = build_section_views(&entry.report, options);
+                if scoped_ids {
+                    for view in &mut sections {
+                        view.id = format!("host-{}-{}", slug, view.id);
+                    }
+                }
+                HostView {
+                    name: entry.host.clone(),
+                    slug,
+                    overall: entry.report.health_digest.overall,
+                    findings: entry.report.health_digest.findings.len(),
+                    sections,
+                }
+            })
+            .collect()
+    }
+
+    /// Anchor GitHub generates for a heading: lowercase, punctuation dropped,
+    /// spaces turned into hyphens.
+    fn heading_slug(text: &str) -> String {
+        text.trim()
+            .to_lowercase()
+            .chars()
+            .filter_map(|ch| match ch {
+                ' ' => Some('-'),
+                '-' | '_' => Some(ch),
+                ch if ch.is_alphanumeric() => Some(ch),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// One line per change in `RenderOptions::baseline`, keyed by its kind.
+    struct BaselineView {
+        generated_at: String,
+        items: Vec<KeyValue>,
+    }
+
+    fn baseline_view(options: &RenderOptions) -> Option<BaselineView> {
+        let diff = options.baseline.as_ref()?;
+        let t = |key| i18n::translate(options.locale, key);
+        let percent = |ratio: f64| format!("{:.1}%", ratio * 100.0);
+        let finding = |finding: &super::diff::DiffFinding| {
+            format!(
+                "[{}] {}: {}",
+                t(finding.severity.display_label()),
+                finding.source_id,
+                finding.message
+            )
+        };
+
+        let mut items: Vec<(&str, String)> = Vec::new();
+        items.extend(diff.new_findings.iter().map(|f| ("New", finding(f))));
+        items.extend(
+            diff.resolved_findings
+                .iter()
+                .map(|f| ("Resolved", finding(f))),
+        );
+        items.extend(diff.threshold_crossings.iter().map(|crossing| {
+            (
+                "Thresholds crossed",
+                format!(
+                    "{}: {} → {} ({} ≥ {})",
+                    crossing.mount_point,
+                    percent(crossing.old_usage_ratio),
+                    percent(crossing.new_usage_ratio),
+                    t(crossing.severity.display_label()),
+                    percent(crossing.threshold)
+                ),
+            )
+        }));
+        items.extend(diff.status_changes.iter().map(|change| {
+            let status =
+                |value: &Option<String>| value.as_deref().unwrap_or(t("absent")).to_string();
+            (
+                "Section status",
+                format!(
+                    "{}: {} → {}",
+                    change.id,
+                    status(&change.old),
+                    status(&change.new)
+                ),
+            )
+        }));
+        items.extend(diff.mount_changes.iter().map(|change| {
+            (
+                "Mount usage",
+                format!(
+                    "{}: {} → {} ({:+.1} pp)",
+                    change.mount_point,
+                    percent(change.old_usage_ratio),
+                    percent(change.new_usage_ratio),
+                    (change.new_usage_ratio - change.old_usage_ratio) * 100.0
+                ),
+            )
+        }));
+        items.extend(
+            diff.containers_added
+                .iter()
+                .map(|name| ("Containers", format!("{}: {}", t("Added"), name))),
+        );
+        items.extend(
+            diff.containers_removed
+                .iter()
+                .map(|name| ("Containers", format!("{}: {}", t("Removed"), name))),
+        );
+        items.extend(
+            diff.newly_failed_services
+                .iter()
+                .map(|unit| ("Newly failed services", unit.clone())),
+        );
+
+        Some(BaselineView {
+            generated_at: diff
+                .old_generated_at
+                .clone()
+                .unwrap_or_else(|| t("unknown").to_string()),
+            items: items
+                .into_iter()
+                .map(|(key, value)| KeyValue {
+                    key: t(key).to_string(),
+                    value,
+                    bar: None,
+                })
+                .collect(),
+        })
+    }
+
+    /// Formatted copy of `metadata.timing`; the values themselves come from collection.
+    struct TimingView {
+        items: Vec<KeyValue>,
+        slowest: TableView,
+    }
+
+    fn timing_view(report: &Report, options: &RenderOptions) -> Option<TimingView> {
+        let timing = report.metadata.timing.as_ref()?;
+        let label = |ms| format_duration(Some(ms)).unwrap_or_default();
+        let mut items = vec![
+            ("Total wall time", label(timing.total_ms)),
+            (
+                "Slow collectors",
+                format!(
+                    "{} (> {})",
+                    timing.slow_collectors,
+                    label(timing.slow_threshold_ms)
+                ),
+            ),
+        ];
+        if !timing.timed_out.is_empty() {
+            items.push(("Timed out", timing.timed_out.join(", ")));
+        }
+        let items = items
+            .into_iter()
+            .map(|(key, value)| KeyValue {
+                key: i18n::translate(options.locale, key).to_string(),
+                value,
+                bar: None,
+            })
+            .collect();
+        let rows: Vec<Vec<String>> = timing
+            .slowest
+            .iter()
+            .map(|entry| vec![entry.id.clone(), label(entry.duration_ms)])
+            .collect();
+        let mut slowest = TableView {
+            title: Some(i18n::translate(options.locale, "Slowest sections").to_string()),
+            headers: ["Section", "Duration"]
+                .iter()
+                .map(|header| i18n::translate(options.locale, header).to_string())
+                .collect(),
+            rows,
+            row_classes: Vec::new(),
+            bars: Vec::new(),
+        };
+        slowest.ensure_row_classes();
+        Some(TimingView { items, slowest })
+    }
+
+    /// Digest limited to `options.min_finding_severity` plus the number of hidden findings.
+    fn visible_digest(report: &Report, options: &RenderOptions) -> (HealthDigest, usize) {
+        let digest = report.health_digest.filtered(options.min_finding_severity);
+        let hidden = report.health_digest.findings.len() - digest.findings.len();
+        (digest, hidden)
+    }
+
     #[derive(Debug)]
     struct SectionView {
         id: String,
         title: String,
+        description: Option<String>,
+        collector_version: Option<String>,
         status_class: &'static str,
         status_label: String,
         summary: Option<String>,
@@ -502,48 +2338,160 @@ mod render {
         has_lists: bool,
         has_notes: bool,
         has_duration: bool,
+        raw_body: Option<String>,
+        /// Views of the section's subsections; only their id, title, and
+        /// content fields are rendered.
+        subsections: Vec<SectionView>,
+        table_limit: Option<usize>,
+        list_limit: Option<usize>,
+        compact_tables: bool,
+        compact_lists: bool,
+        locale: Locale,
     }
 
     impl SectionView {
-        fn new(section: &super::Section) -> Self {
-            let duration_label = format_duration(section.duration_ms).unwrap_or_default();
+        fn new(section: &super::Section, options: &RenderOptions) -> Self {
+            let mut view = Self::blank(section.id.to_string(), section.title.to_string(), options);
+            view.description = section.description.map(str::to_string);
+            view.collector_version = section.collector_version.map(str::to_string);
+            view.status_class = status_class(&section.status);
+            view.status_label =
+                i18n::translate(options.locale, &status_label(&section.status)).to_string();
+            view.summary = section.summary.clone();
+            view.notes = section.notes.clone();
+            view.duration_label = format_duration(section.duration_ms).unwrap_or_default();
+            view.has_notes = !section.notes.is_empty();
+            view.has_duration = section.duration_ms.is_some();
+            view.raw_body = options
+                .include_raw_body
+                .then(|| raw_body(&section.body, options.raw_body_max_bytes));
+            view
+        }
+
+        fn subsection(subsection: &SubSection, options: &RenderOptions) -> Self {
+            let title = i18n::translate(options.locale, &subsection.title).to_string();
+            Self::blank(subsection.id.clone(), title, options)
+        }
+
+        fn blank(id: String, title: String, options: &RenderOptions) -> Self {
             Self {
-                id: section.id.to_string(),
-                title: section.title.to_string(),
-                status_class: status_class(&section.status),
-                status_label: status_label(&section.status),
-                summary: section.summary.clone(),
-                notes: section.notes.clone(),
+                id,
+                title,
+                description: None,
+                collector_version: None,
+                status_class: "",
+                status_label: String::new(),
+                summary: None,
+                notes: Vec::new(),
                 key_values: Vec::new(),
                 tables: Vec::new(),
                 lists: Vec::new(),
                 paragraph: None,
-                duration_label,
+                duration_label: String::new(),
                 has_key_values: false,
                 has_tables: false,
                 has_lists: false,
-                has_notes: !section.notes.is_empty(),
-                has_duration: section.duration_ms.is_some(),
+                has_notes: false,
+                has_duration: false,
+                raw_body: None,
+                subsections: Vec::new(),
+                table_limit: options.max_table_rows,
+                list_limit: options.max_list_items,
+                compact_tables: options.compact_tables,
+                compact_lists: options.compact_lists,
+                locale: options.locale,
             }
         }
 
+        fn tr<'k>(&self, key: &'k str) -> &'k str {
+            i18n::translate(self.locale, key)
+        }
+
         fn add_kv<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+            let key = key.into();
             self.key_values.push(KeyValue {
-                key: key.into(),
+                key: self.tr(&key).to_string(),
                 value: value.into(),
+                bar: None,
             });
         }
 
-        fn add_table(&mut self, mut table: TableView) {
-            if !table.rows.is_empty() {
-                table.ensure_row_classes();
-                self.tables.push(table);
+        fn add_kv_with_bar<K: Into<String>, V: Into<String>>(
+            &mut self,
+            key: K,
+            value: V,
+            ratio: f64,
+        ) {
+            self.add_kv(key, value);
+            if let Some(item) = self.key_values.last_mut() {
+                item.bar = Some(UsageBar::new(ratio));
+            }
+        }
+
+        fn add_table(&mut self, table: TableView) {
+            self.push_table(table, self.table_limit);
+        }
+
+        /// Adds a compact table that shows at most `cap` rows while compact
+        /// tables are on.
+        fn add_table_capped(&mut self, table: TableView, cap: usize) {
+            let limit = compact_limit(self.table_limit, cap, self.compact_tables);
+            self.push_table(table, limit);
+        }
+
+        fn push_table(&mut self, mut table: TableView, limit: Option<usize>) {
+            if table.rows.is_empty() {
+                return;
+            }
+            table.title = table.title.map(|title| self.tr(&title).to_string());
+            for header in &mut table.headers {
+                *header = self.tr(header).to_string();
             }
+            let total = table.rows.len();
+            if let Some(limit) = limit.filter(|&limit| total > limit) {
+                table.rows.truncate(limit);
+                table.row_classes.truncate(limit);
+                table.bars.truncate(limit);
+                table.title = Some(self.showing_note(table.title.take(), limit, total));
+            }
+            table.ensure_row_classes();
+            self.tables.push(table);
         }
 
         fn add_list(&mut self, list: ListView) {
-            if !list.items.is_empty() {
-                self.lists.push(list);
+            self.push_list(list, self.list_limit);
+        }
+
+        /// List counterpart of [`SectionView::add_table_capped`].
+        fn add_list_capped(&mut self, list: ListView, cap: usize) {
+            let limit = compact_limit(self.list_limit, cap, self.compact_lists);
+            self.push_list(list, limit);
+        }
+
+        fn push_list(&mut self, mut list: ListView, limit: Option<usize>) {
+            if list.items.is_empty() {
+                return;
+            }
+            list.title = list.title.map(|title| self.tr(&title).to_string());
+            let total = list.items.len();
+            if let Some(limit) = limit.filter(|&limit| total > limit) {
+                list.items.truncate(limit);
+                list.title = Some(self.showing_note(list.title.take(), limit, total));
+            }
+            self.lists.push(list);
+        }
+
+        fn showing_note(&self, title: Option<String>, shown: usize, total: usize) -> String {
+            let note = format!(
+                "({} {} {} {})",
+                self.tr("showing"),
+                shown,
+                self.tr("of"),
+                total
+            );
+            match title {
+                Some(title) => format!("{} {}", title, note),
+                None => note,
             }
         }
 
@@ -560,6 +2508,7 @@ mod render {
     struct KeyValue {
         key: String,
         value: String,
+        bar: Option<UsageBar>,
     }
 
     #[derive(Debug)]
@@ -568,6 +2517,8 @@ mod render {
         headers: Vec<String>,
         rows: Vec<Vec<String>>,
         row_classes: Vec<String>,
+        /// Optional usage bar per cell, parallel to `rows`; HTML only.
+        bars: Vec<Vec<Option<UsageBar>>>,
     }
 
     impl TableView {
@@ -576,6 +2527,45 @@ mod render {
                 self.row_classes.resize(self.rows.len(), String::new());
             }
         }
+
+        /// Takes references because Askama passes template locals by reference.
+        fn bar(&self, row: &usize, column: &usize) -> Option<&UsageBar> {
+            self.bars.get(*row)?.get(*column)?.as_ref()
+        }
+    }
+
+    const USAGE_WARNING_RATIO: f64 = 0.80;
+    const USAGE_CRITICAL_RATIO: f64 = 0.90;
+
+    /// Inline CSS bar for a 0-1 usage ratio, colored like the usage row highlights.
+    #[derive(Debug, Clone)]
+    struct UsageBar {
+        width: String,
+        class: &'static str,
+    }
+
+    impl UsageBar {
+        fn new(ratio: f64) -> Self {
+            let class = match usage_row_class(ratio) {
+                "row-critical" => "bar-critical",
+                "row-warning" => "bar-warning",
+                _ => "bar-ok",
+            };
+            Self {
+                width: format!("{:.1}", (ratio * 100.0).clamp(0.0, 100.0)),
+                class,
+            }
+        }
+    }
+
+    fn usage_row_class(ratio: f64) -> &'static str {
+        if ratio >= USAGE_CRITICAL_RATIO {
+            "row-critical"
+        } else if ratio >= USAGE_WARNING_RATIO {
+            "row-warning"
+        } else {
+            ""
+        }
     }
 
     #[derive(Debug)]
@@ -584,19 +2574,116 @@ mod render {
         items: Vec<String>,
     }
 
-    fn build_section_views(report: &Report) -> Vec<SectionView> {
+    fn raw_body(body: &Value, max_bytes: usize) -> String {
+        let pretty = serde_json::to_string_pretty(body).unwrap_or_else(|_| body.to_string());
+        if pretty.len() <= max_bytes {
+            return pretty;
+        }
+        let mut end = max_bytes;
+        while !pretty.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!(
+            "{}\n… truncated ({} of {} bytes shown)",
+            &pretty[..end],
+            end,
+            pretty.len()
+        )
+    }
+
+    /// `limit` lowered to `cap` when `compact` is on; an unlimited view stays
+    /// unlimited.
+    fn compact_limit(limit: Option<usize>, cap: usize, compact: bool) -> Option<usize> {
+        match limit {
+            Some(limit) if compact => Some(limit.min(cap)),
+            limit => limit,
+        }
+    }
+
+    fn build_section_views(report: &Report, options: &RenderOptions) -> Vec<SectionView> {
         report
             .sections
             .iter()
             .map(|section| {
-                let mut view = SectionView::new(section);
-                populate_section(&mut view, section.id, &section.body);
+                let mut view = SectionView::new(section, options);
+                if matches!(section.status, SectionStatus::Error) {
+                    populate_error_chain(&mut view, &section.body);
+                }
+                if section.parts.is_empty() {
+                    populate_section(&mut view, &section.id, &section.body);
+                } else {
+                    populate_parts(&mut view, &section.parts);
+                }
+                for subsection in &section.subsections {
+                    let mut child = SectionView::subsection(subsection, options);
+                    if subsection.parts.is_empty() {
+                        populate_subsection(&mut child, &section.id, subsection);
+                    } else {
+                        populate_parts(&mut child, &subsection.parts);
+                    }
+                    child.finalize();
+                    view.subsections.push(child);
+                }
                 view.finalize();
                 view
             })
             .collect()
     }
 
+    fn populate_error_chain(view: &mut SectionView, body: &Value) {
+        if let Some(kind) = body.get("error_kind").and_then(Value::as_str) {
+            view.add_kv("Error kind", kind);
+        }
+        let Some(chain) = body.get("error_chain").and_then(Value::as_array) else {
+            return;
+        };
+        let items: Vec<String> = chain
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+        view.add_list(ListView {
+            title: Some("Error chain".to_string()),
+            items,
+        });
+    }
+
+    /// Views built by the collector itself; titles, headers, and keys go
+    /// through the same translation as the body-derived ones.
+    fn populate_parts(view: &mut SectionView, parts: &[SectionPart]) {
+        for part in parts {
+            match part {
+                SectionPart::KeyValues(set) => {
+                    for (key, value) in &set.items {
+                        view.add_kv(key.as_str(), value.as_str());
+                    }
+                }
+                SectionPart::Table(table) => {
+                    let title = match (&table.title, table.show_total) {
+                        (Some(title), true) => Some(format!(
+                            "{} ({} {})",
+                            view.tr(title),
+                            table.rows.len(),
+                            view.tr("total")
+                        )),
+                        (title, _) => title.clone(),
+                    };
+                    view.add_table(TableView {
+                        title,
+                        headers: table.headers.clone(),
+                        rows: table.rows.clone(),
+                        row_classes: Vec::new(),
+                        bars: Vec::new(),
+                    });
+                }
+                SectionPart::List(list) => view.add_list(ListView {
+                    title: list.title.clone(),
+                    items: list.items.clone(),
+                }),
+            }
+        }
+    }
+
     fn populate_section(view: &mut SectionView, id: &str, body: &Value) {
         match id {
             "os" => populate_os(view, body),
@@ -609,6 +2696,17 @@ mod render {
             "docker" => populate_docker(view, body),
             "containers" => populate_containers(view, body),
             "users" => populate_users(view, body),
+            "security" => populate_security(view, body),
+            "sar" => populate_sar(view, body),
+            _ => populate_generic(view, body),
+        }
+    }
+
+    fn populate_subsection(view: &mut SectionView, section_id: &str, subsection: &SubSection) {
+        let body = &subsection.body;
+        match (section_id, subsection.id.as_str()) {
+            ("storage", "docker") => populate_storage_docker(view, body),
+            ("storage", "hotspots") => populate_storage_hotspots(view, body),
             _ => populate_generic(view, body),
         }
     }
@@ -663,15 +2761,17 @@ mod render {
                     view.add_kv("Host Memory Total", format_bytes(total));
                 }
                 if let Some(available) = host.get("available_bytes").and_then(Value::as_u64) {
-                    let mut value = format_bytes(available);
-                    if let Some(ratio) = host.get("usage_ratio").and_then(Value::as_f64) {
-                        value = format!(
-                            "{} free ({:.1}% used)",
-                            format_bytes(available),
-                            ratio * 100.0
-                        );
+                    match host.get("usage_ratio").and_then(Value::as_f64) {
+                        Some(ratio) => {
+                            let value = format!(
+                                "{} free ({:.1}% used)",
+                                format_bytes(available),
+                                ratio * 100.0
+                            );
+                            view.add_kv_with_bar("Host Memory", value, ratio);
+                        }
+                        None => view.add_kv("Host Memory", format_bytes(available)),
                     }
-                    view.add_kv("Host Memory", value);
                 }
             }
 
@@ -704,43 +2804,44 @@ mod render {
                     view.add_kv("Swap Free", format_bytes(free));
                 }
 
-                if let Some(devices) = swap.get("devices").and_then(Value::as_array) {
-                    if !devices.is_empty() {
-                        let rows: Vec<Vec<String>> = devices
-                            .iter()
-                            .take(6)
-                            .map(|device| {
-                                vec![
-                                    device
-                                        .get("name")
-                                        .and_then(Value::as_str)
-                                        .unwrap_or("-")
-                                        .to_string(),
-                                    device
-                                        .get("kind")
-                                        .and_then(Value::as_str)
-                                        .unwrap_or("-")
-                                        .to_string(),
-                                    device
-                                        .get("priority")
-                                        .and_then(Value::as_i64)
-                                        .map(|p| p.to_string())
-                                        .unwrap_or_else(|| "0".to_string()),
-                                    device
-                                        .get("used_bytes")
-                                        .and_then(Value::as_u64)
-                                        .map(format_bytes)
-                                        .unwrap_or_else(|| "-".to_string()),
-                                    device
-                                        .get("size_bytes")
-                                        .and_then(Value::as_u64)
-                                        .map(format_bytes)
-                                        .unwrap_or_else(|| "-".to_string()),
-                                ]
-                            })
-                            .collect();
-
-                        view.add_table(TableView {
+                if let Some(devices) = swap.get("devices").and_then(Value::as_array)
+                    && !devices.is_empty()
+                {
+                    let rows: Vec<Vec<String>> = devices
+                        .iter()
+                        .map(|device| {
+                            vec![
+                                device
+                                    .get("name")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or("-")
+                                    .to_string(),
+                                device
+                                    .get("kind")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or("-")
+                                    .to_string(),
+                                device
+                                    .get("priority")
+                                    .and_then(Value::as_i64)
+                                    .map(|p| p.to_string())
+                                    .unwrap_or_else(|| "0".to_string()),
+                                device
+                                    .get("used_bytes")
+                                    .and_then(Value::as_u64)
+                                    .map(format_bytes)
+                                    .unwrap_or_else(|| "-".to_string()),
+                                device
+                                    .get("size_bytes")
+                                    .and_then(Value::as_u64)
+                                    .map(format_bytes)
+                                    .unwrap_or_else(|| "-".to_string()),
+                            ]
+                        })
+                        .collect();
+
+                    view.add_table_capped(
+                        TableView {
                             title: Some("Swap Devices".to_string()),
                             headers: vec![
                                 "Device".to_string(),
@@ -751,42 +2852,45 @@ mod render {
                             ],
                             rows,
                             row_classes: Vec::new(),
-                        });
-                    }
+                            bars: Vec::new(),
+                        },
+                        6,
+                    );
                 }
 
-                if let Some(zram) = swap.get("zram_devices").and_then(Value::as_array) {
-                    if !zram.is_empty() {
-                        let rows: Vec<Vec<String>> = zram
-                            .iter()
-                            .take(6)
-                            .map(|device| {
-                                vec![
-                                    device
-                                        .get("name")
-                                        .and_then(Value::as_str)
-                                        .unwrap_or("-")
-                                        .to_string(),
-                                    device
-                                        .get("disksize_bytes")
-                                        .and_then(Value::as_u64)
-                                        .map(format_bytes)
-                                        .unwrap_or_else(|| "-".to_string()),
-                                    device
-                                        .get("compressed_bytes")
-                                        .and_then(Value::as_u64)
-                                        .map(format_bytes)
-                                        .unwrap_or_else(|| "-".to_string()),
-                                    device
-                                        .get("active")
-                                        .and_then(Value::as_bool)
-                                        .map(|flag| if flag { "yes" } else { "no" }.to_string())
-                                        .unwrap_or_else(|| "no".to_string()),
-                                ]
-                            })
-                            .collect();
-
-                        view.add_table(TableView {
+                if let Some(zram) = swap.get("zram_devices").and_then(Value::as_array)
+                    && !zram.is_empty()
+                {
+                    let rows: Vec<Vec<String>> = zram
+                        .iter()
+                        .map(|device| {
+                            vec![
+                                device
+                                    .get("name")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or("-")
+                                    .to_string(),
+                                device
+                                    .get("disksize_bytes")
+                                    .and_then(Value::as_u64)
+                                    .map(format_bytes)
+                                    .unwrap_or_else(|| "-".to_string()),
+                                device
+                                    .get("compressed_bytes")
+                                    .and_then(Value::as_u64)
+                                    .map(format_bytes)
+                                    .unwrap_or_else(|| "-".to_string()),
+                                device
+                                    .get("active")
+                                    .and_then(Value::as_bool)
+                                    .map(|flag| if flag { "yes" } else { "no" }.to_string())
+                                    .unwrap_or_else(|| "no".to_string()),
+                            ]
+                        })
+                        .collect();
+
+                    view.add_table_capped(
+                        TableView {
                             title: Some("ZRAM Devices".to_string()),
                             headers: vec![
                                 "Device".to_string(),
@@ -796,35 +2900,37 @@ mod render {
                             ],
                             rows,
                             row_classes: Vec::new(),
-                        });
-                    }
+                            bars: Vec::new(),
+                        },
+                        6,
+                    );
                 }
             }
         }
 
         if let Some(psi) = body.get("psi").and_then(Value::as_object) {
             let mut rows = Vec::new();
-            if let Some(cpu) = psi.get("cpu").and_then(Value::as_object) {
-                if let Some(metrics) = cpu.get("some").and_then(Value::as_object) {
-                    rows.push(vec![
-                        "CPU (some)".to_string(),
-                        metrics
-                            .get("avg10")
-                            .and_then(Value::as_f64)
-                            .map(|v| format!("{:.2}", v))
-                            .unwrap_or_else(|| "-".to_string()),
-                        metrics
-                            .get("avg60")
-                            .and_then(Value::as_f64)
-                            .map(|v| format!("{:.2}", v))
-                            .unwrap_or_else(|| "-".to_string()),
-                        metrics
-                            .get("avg300")
-                            .and_then(Value::as_f64)
-                            .map(|v| format!("{:.2}", v))
-                            .unwrap_or_else(|| "-".to_string()),
-                    ]);
-                }
+            if let Some(cpu) = psi.get("cpu").and_then(Value::as_object)
+                && let Some(metrics) = cpu.get("some").and_then(Value::as_object)
+            {
+                rows.push(vec![
+                    "CPU (some)".to_string(),
+                    metrics
+                        .get("avg10")
+                        .and_then(Value::as_f64)
+                        .map(|v| format!("{:.2}", v))
+                        .unwrap_or_else(|| "-".to_string()),
+                    metrics
+                        .get("avg60")
+                        .and_then(Value::as_f64)
+                        .map(|v| format!("{:.2}", v))
+                        .unwrap_or_else(|| "-".to_string()),
+                    metrics
+                        .get("avg300")
+                        .and_then(Value::as_f64)
+                        .map(|v| format!("{:.2}", v))
+                        .unwrap_or_else(|| "-".to_string()),
+                ]);
             }
             for key in ["memory", "io"] {
                 if let Some(resource) = psi.get(key).and_then(Value::as_object) {
@@ -882,6 +2988,7 @@ mod render {
                     ],
                     rows,
                     row_classes: Vec::new(),
+                    bars: Vec::new(),
                 });
             }
         }
@@ -889,7 +2996,7 @@ mod render {
 
     fn populate_storage(view: &mut SectionView, body: &Value) {
         if let Some(mounts) = body.get("operating_mounts").and_then(Value::as_array) {
-            let mut entries: Vec<(f64, Vec<String>)> = mounts
+            let mut entries: Vec<(f64, Option<f64>, Vec<String>)> = mounts
                 .iter()
                 .filter_map(|mount| {
                     let mount_point = mount.get("mount_point")?.as_str()?.to_string();
@@ -918,14 +3025,14 @@ mod render {
                         .and_then(Value::as_f64)
                         .unwrap_or(0.0);
                     let usage = format_percent(ratio);
-                    let inode_ratio = mount
-                        .get("inodes_usage_ratio")
-                        .and_then(Value::as_f64)
+                    let inodes = mount.get("inodes_usage_ratio").and_then(Value::as_f64);
+                    let inode_ratio = inodes
                         .map(format_percent)
                         .unwrap_or_else(|| "n/a".to_string());
 
                     Some((
                         ratio,
+                        inodes,
                         vec![
                             mount_point,
                             fs_type.to_string(),
@@ -941,20 +3048,22 @@ mod render {
 
             entries.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
             let mut row_classes: Vec<String> = Vec::new();
+            let mut bars = Vec::new();
             let rows: Vec<Vec<String>> = entries
                 .into_iter()
-                .map(|(ratio, row)| {
-                    let class = if ratio >= 0.90 {
-                        "row-critical"
-                    } else if ratio >= 0.80 {
-                        "row-warning"
-                    } else {
-                        ""
-                    };
-                    row_classes.push(class.to_string());
+                .map(|(ratio, inodes, row)| {
+                    row_classes.push(usage_row_class(ratio).to_string());
+                    bars.push(vec![
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        Some(UsageBar::new(ratio)),
+                        inodes.map(UsageBar::new),
+                    ]);
                     row
                 })
-                .take(12)
                 .collect();
 
             if !rows.is_empty() {
@@ -971,13 +3080,14 @@ mod render {
                     ],
                     rows,
                     row_classes,
+                    bars,
                 });
             }
         }
 
         if let Some(mounts) = body.get("pseudo_mounts").and_then(Value::as_array) {
             let mut rows = Vec::new();
-            for mount in mounts.iter().take(12) {
+            for mount in mounts.iter() {
                 let mount_point = mount
                     .get("mount_point")
                     .and_then(Value::as_str)
@@ -996,6 +3106,7 @@ mod render {
                     headers: vec!["Mount".to_string(), "FS".to_string(), "Usage".to_string()],
                     rows,
                     row_classes: Vec::new(),
+                    bars: Vec::new(),
                 });
             }
         }
@@ -1012,33 +3123,73 @@ mod render {
             }
         }
 
-        if let Some(docker) = body.get("docker").and_then(Value::as_object) {
-            let root = docker
-                .get("data_root")
-                .and_then(Value::as_str)
-                .unwrap_or("/var/lib/docker");
-            view.add_kv("Docker data root", root.to_string());
-            if let Some(total) = docker.get("total_bytes").and_then(Value::as_u64) {
-                view.add_kv("Docker total", format_bytes(total));
-            }
-            if let Some(diff) = docker.get("overlay_bytes").and_then(Value::as_u64) {
-                view.add_kv("Overlay diff", format_bytes(diff));
-            }
-            if let Some(logs) = docker.get("container_logs_bytes").and_then(Value::as_u64) {
-                view.add_kv("Container logs", format_bytes(logs));
-            }
-            if let Some(volumes) = docker.get("volumes_bytes").and_then(Value::as_u64) {
-                view.add_kv("Volumes", format_bytes(volumes));
-            }
+        // Reports saved before the Docker breakdown and hotspots moved to
+        // subsections still carry them in the body.
+        if let Some(docker) = body.get("docker") {
+            populate_storage_docker(view, docker);
+        }
+        if let Some(hotspots) = body.get("hotspots") {
+            populate_storage_hotspots(view, hotspots);
+        }
+    }
+
+    fn populate_storage_docker(view: &mut SectionView, docker: &Value) {
+        let Some(docker) = docker.as_object() else {
+            return;
+        };
+        let root = docker
+            .get("data_root")
+            .and_then(Value::as_str)
+            .unwrap_or("/var/lib/docker");
+        view.add_kv("Docker data root", root.to_string());
+        if let Some(total) = docker.get("total_bytes").and_then(Value::as_u64) {
+            view.add_kv("Docker total", format_bytes(total));
+        }
+        if let Some(diff) = docker.get("overlay_bytes").and_then(Value::as_u64) {
+            view.add_kv("Overlay diff", format_bytes(diff));
+        }
+        if let Some(logs) = docker.get("container_logs_bytes").and_then(Value::as_u64) {
+            view.add_kv("Container logs", format_bytes(logs));
+        }
+        if let Some(volumes) = docker.get("volumes_bytes").and_then(Value::as_u64) {
+            view.add_kv("Volumes", format_bytes(volumes));
+        }
+    }
+
+    fn populate_storage_hotspots(view: &mut SectionView, hotspots: &Value) {
+        for (key, title) in [
+            ("directories", "Largest Directories"),
+            ("logs", "Largest Log Files"),
+        ] {
+            let Some(entries) = hotspots.get(key).and_then(Value::as_array) else {
+                continue;
+            };
+            let rows: Vec<Vec<String>> = entries
+                .iter()
+                .filter_map(|entry| {
+                    let path = entry.get("path")?.as_str()?.to_string();
+                    let size = entry
+                        .get("size_bytes")
+                        .and_then(Value::as_u64)
+                        .map(format_bytes)
+                        .unwrap_or_else(|| "-".to_string());
+                    Some(vec![path, size])
+                })
+                .collect();
+            view.add_table(TableView {
+                title: Some(title.to_string()),
+                headers: vec!["Path".to_string(), "Size".to_string()],
+                rows,
+                row_classes: Vec::new(),
+                bars: Vec::new(),
+            });
         }
     }
 
     fn populate_services(view: &mut SectionView, body: &Value) {
         fn add_service_table(view: &mut SectionView, entries: &[Value], title: String) {
-            const MAX_ROWS: usize = 12;
             let rows: Vec<Vec<String>> = entries
                 .iter()
-                .take(MAX_ROWS)
                 .map(|entry| {
                     let unit = entry
                         .get("unit")
@@ -1063,16 +3214,19 @@ mod render {
                     ],
                     rows,
                     row_classes: Vec::new(),
+                    bars: Vec::new(),
                 });
             }
         }
 
         if let Some(running) = body.get("running").and_then(Value::as_array) {
-            add_service_table(
-                view,
-                running,
-                format!("Running Services ({} total)", running.len()),
+            let title = format!(
+                "{} ({} {})",
+                view.tr("Running Services"),
+                running.len(),
+                view.tr("total")
             );
+            add_service_table(view, running, title);
         }
 
         if let Some(failed) = body.get("failed").and_then(Value::as_array) {
@@ -1089,8 +3243,38 @@ mod render {
     fn populate_network(view: &mut SectionView, body: &Value) {
         if let Some(interfaces) = body.get("interfaces").and_then(Value::as_array) {
             let mut rows = Vec::new();
-            for iface in interfaces.iter().take(10) {
+            for iface in interfaces.iter() {
                 let name = iface.get("name").and_then(Value::as_str).unwrap_or("?");
+                let loopback = iface
+                    .get("loopback")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                let name = if loopback {
+                    format!("{name} (loopback)")
+                } else {
+                    name.to_string()
+                };
+                let state = iface
+                    .get("state")
+                    .and_then(Value::as_str)
+                    .unwrap_or("-")
+                    .to_string();
+                let addresses: Vec<&str> = ["ipv4_addresses", "ipv6_addresses"]
+                    .iter()
+                    .filter_map(|family| iface.get(*family).and_then(Value::as_array))
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .collect();
+                let addresses = if addresses.is_empty() {
+                    "-".to_string()
+                } else {
+                    addresses.join(", ")
+                };
+                let mtu = iface
+                    .get("mtu")
+                    .and_then(Value::as_u64)
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "-".to_string());
                 let rx_bytes = iface
                     .get("rx_bytes")
                     .and_then(Value::as_u64)
@@ -1112,26 +3296,29 @@ mod render {
                     .map(|v| v.to_string())
                     .unwrap_or_else(|| "-".to_string());
                 rows.push(vec![
-                    name.to_string(),
-                    rx_bytes,
-                    tx_bytes,
-                    rx_packets,
-                    tx_packets,
+                    name, state, addresses, mtu, rx_bytes, tx_bytes, rx_packets, tx_packets,
                 ]);
             }
             if !rows.is_empty() {
-                view.add_table(TableView {
-                    title: Some("Network Interfaces".to_string()),
-                    headers: vec![
-                        "Interface".to_string(),
-                        "RX".to_string(),
-                        "TX".to_string(),
-                        "RX packets".to_string(),
-                        "TX packets".to_string(),
-                    ],
-                    rows,
-                    row_classes: Vec::new(),
-                });
+                view.add_table_capped(
+                    TableView {
+                        title: Some("Network Interfaces".to_string()),
+                        headers: vec![
+                            "Interface".to_string(),
+                            "State".to_string(),
+                            "Addresses".to_string(),
+                            "MTU".to_string(),
+                            "RX".to_string(),
+                            "TX".to_string(),
+                            "RX packets".to_string(),
+                            "TX packets".to_string(),
+                        ],
+                        rows,
+                        row_classes: Vec::new(),
+                        bars: Vec::new(),
+                    },
+                    10,
+                );
             }
         }
 
@@ -1149,6 +3336,7 @@ mod render {
                         headers: vec!["Protocol".to_string(), "Count".to_string()],
                         rows,
                         row_classes: Vec::new(),
+                        bars: Vec::new(),
                     });
                 }
             }
@@ -1156,7 +3344,6 @@ mod render {
             if let Some(samples) = listeners.get("samples").and_then(Value::as_array) {
                 let items: Vec<String> = samples
                     .iter()
-                    .take(10)
                     .map(|sample| {
                         let addr = sample
                             .get("local_address")
@@ -1190,12 +3377,19 @@ mod render {
                                             .get("container")
                                             .and_then(Value::as_str)
                                             .unwrap_or("");
+                                        // `container_id` is only set once the
+                                        // docker section resolved the name.
+                                        let resolved = proc.get("container_id").is_some();
                                         if container.is_empty() {
                                             format!("pid {pid} {command} (uid {uid})")
-                                        } else {
+                                        } else if !resolved {
                                             format!(
                                                 "pid {pid} {command} (uid {uid}, cgroup {container})"
                                             )
+                                        } else {
+                                            format!(
+                                                "pid {pid} {command} (uid {uid}, container {container})"
+                                            )
                                         }
                                     })
                                     .collect::<Vec<_>>()
@@ -1209,10 +3403,13 @@ mod render {
                     })
                     .collect();
                 if !items.is_empty() {
-                    view.add_list(ListView {
-                        title: Some("Sample listeners".to_string()),
-                        items,
-                    });
+                    view.add_list_capped(
+                        ListView {
+                            title: Some("Sample listeners".to_string()),
+                            items,
+                        },
+                        10,
+                    );
                 }
             }
         }
@@ -1222,7 +3419,6 @@ mod render {
         fn add_top_list(view: &mut SectionView, entries: &[Value], title: &str) {
             let items: Vec<String> = entries
                 .iter()
-                .take(5)
                 .map(|entry| {
                     let name = entry.get("name").and_then(Value::as_str).unwrap_or("-");
                     let count = entry.get("count").and_then(Value::as_u64).unwrap_or(0);
@@ -1231,10 +3427,13 @@ mod render {
                 .collect();
 
             if !items.is_empty() {
-                view.add_list(ListView {
-                    title: Some(title.to_string()),
-                    items,
-                });
+                view.add_list_capped(
+                    ListView {
+                        title: Some(title.to_string()),
+                        items,
+                    },
+                    5,
+                );
             }
         }
 
@@ -1262,7 +3461,6 @@ mod render {
         if let Some(entries) = body.get("entries").and_then(Value::as_array) {
             let items: Vec<String> = entries
                 .iter()
-                .take(20)
                 .map(|entry| {
                     let timestamp = entry
                         .get("timestamp")
@@ -1272,7 +3470,7 @@ mod render {
                     let message = entry
                         .get("message")
                         .and_then(Value::as_str)
-                        .map(truncate)
+                        .map(|message| truncate(message, SUMMARY_MAX_CHARS))
                         .unwrap_or_else(|| "(no message)".to_string());
                     format!("{timestamp} — {source}: {message}")
                 })
@@ -1324,6 +3522,7 @@ mod render {
                     ],
                     rows,
                     row_classes: Vec::new(),
+                    bars: Vec::new(),
                 });
             }
         }
@@ -1346,6 +3545,7 @@ mod render {
                             ],
                             rows,
                             row_classes: Vec::new(),
+                            bars: Vec::new(),
                         });
                     }
                 }
@@ -1364,13 +3564,99 @@ mod render {
             if let Some(api) = engine.get("api_version").and_then(Value::as_str) {
                 view.add_kv("API version", api);
             }
+            if let Some(endpoint) = engine.get("endpoint").and_then(Value::as_str) {
+                view.add_kv("Endpoint", endpoint);
+            }
+        }
+
+        if let Some(storage) = body.get("storage").filter(|storage| storage.is_object()) {
+            if let Some(bytes) = storage.get("image_total_bytes").and_then(Value::as_u64) {
+                view.add_kv("Image storage", format_bytes(bytes));
+            }
+            if let Some(dangling) = storage.get("dangling_image_count").and_then(Value::as_u64) {
+                view.add_kv("Dangling images", dangling.to_string());
+            }
+            if let Some(bytes) = storage
+                .get("reclaimable_image_bytes")
+                .and_then(Value::as_u64)
+            {
+                view.add_kv("Reclaimable image space", format_bytes(bytes));
+            }
+            if let Some(bytes) = storage
+                .get("images_reclaimable_bytes")
+                .and_then(Value::as_u64)
+            {
+                view.add_kv("Reclaimable image layers", format_bytes(bytes));
+            }
+            if let Some(bytes) = storage.get("containers_size_bytes").and_then(Value::as_u64) {
+                view.add_kv("Container writable layers", format_bytes(bytes));
+            }
+            if let Some(bytes) = storage
+                .get("build_cache_total_bytes")
+                .and_then(Value::as_u64)
+            {
+                let label = match storage
+                    .get("build_cache_reclaimable_bytes")
+                    .and_then(Value::as_u64)
+                {
+                    Some(reclaimable) => format!(
+                        "{} ({} reclaimable)",
+                        format_bytes(bytes),
+                        format_bytes(reclaimable)
+                    ),
+                    None => format_bytes(bytes),
+                };
+                view.add_kv("Build cache", label);
+            }
+            if let Some(root) = storage.get("data_root").and_then(Value::as_str) {
+                let label = match storage
+                    .get("data_root_filesystem_bytes")
+                    .and_then(Value::as_u64)
+                {
+                    Some(bytes) => format!("{} ({} filesystem)", root, format_bytes(bytes)),
+                    None => root.to_string(),
+                };
+                view.add_kv("Data root", label);
+            }
         }
 
+        populate_docker_compose_projects(view, body);
+
         if let Some(containers) = body.get("containers").and_then(Value::as_array) {
+            let memory_percent = |container: &Value| {
+                container
+                    .pointer("/metrics/memory_percent")
+                    .and_then(Value::as_f64)
+            };
+            let with_memory = containers
+                .iter()
+                .any(|container| memory_percent(container).is_some());
+            let with_ports = containers.iter().any(|container| {
+                container
+                    .get("ports")
+                    .and_then(Value::as_array)
+                    .is_some_and(|ports| !ports.is_empty())
+                    || docker_host_network(container)
+            });
+            let has_field = |key: &str| {
+                containers
+                    .iter()
+                    .any(|container| container.get(key).is_some_and(Value::is_string))
+            };
+            let with_security = containers
+                .iter()
+                .any(|container| container.get("security").is_some_and(Value::is_object));
+            let with_uptime = has_field("uptime");
+            let with_restart = has_field("restart_policy");
+            let with_log_driver = has_field("log_driver");
+            let all_interfaces: usize = containers
+                .iter()
+                .map(|container| docker_port_labels(container).1)
+                .sum();
             let mut row_classes = Vec::new();
+            let mut bars = Vec::new();
             let rows: Vec<Vec<String>> = containers
                 .iter()
-                .take(12)
                 .map(|container| {
                     let name = container
                         .get("names")
@@ -1388,7 +3674,11 @@ mod render {
                         .or_else(|| container.get("status").and_then(Value::as_str))
                         .unwrap_or("?");
                     let state_lower = state.to_ascii_lowercase();
-                    let class = if state_lower.contains("unhealthy") {
+                    let privileged = container
+                        .pointer("/security/privileged")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+                    let class = if state_lower.contains("unhealthy") || privileged {
                         "row-critical"
                     } else if state_lower.contains("restarting") || state_lower.contains("exited") {
                         "row-warning"
@@ -1396,306 +3686,2997 @@ mod render {
                         ""
                     };
                     row_classes.push(class.to_string());
-                    vec![name.to_string(), image.to_string(), state.to_string()]
+                    let mut row = vec![name.to_string(), image.to_string(), state.to_string()];
+                    if with_uptime {
+                        row.push(
+                            container
+                                .get("uptime")
+                                .and_then(Value::as_str)
+                                .unwrap_or("-")
+                                .to_string(),
+                        );
+                    }
+                    if with_security {
+                        row.push(if privileged { "priv" } else { "-" }.to_string());
+                    }
+                    if with_ports {
+                        let labels = if docker_host_network(container) {
+                            vec!["host network".to_string()]
+                        } else {
+                            docker_port_labels(container).0
+                        };
+                        row.push(if labels.is_empty() {
+                            "-".to_string()
+                        } else {
+                            labels.join(", ")
+                        });
+                    }
+                    if with_restart {
+                        row.push(
+                            container
+                                .get("restart_policy")
+                                .and_then(Value::as_str)
+                                .unwrap_or("-")
+                                .to_string(),
+                        );
+                    }
+                    if with_log_driver {
+                        row.push(docker_log_label(container, "log_options"));
+                    }
+                    if with_memory {
+                        let percent = memory_percent(container);
+                        row.push(
+                            percent
+                                .map(|value| format!("{:.1}%", value))
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        let mut row_bars = vec![None; row.len() - 1];
+                        row_bars.push(percent.map(|value| UsageBar::new(value / 100.0)));
+                        bars.push(row_bars);
+                    }
+                    row
                 })
                 .collect();
             if !rows.is_empty() {
+                let mut headers =
+                    vec!["Name".to_string(), "Image".to_string(), "State".to_string()];
+                if with_uptime {
+                    headers.push("Uptime".to_string());
+                }
+                if with_security {
+                    headers.push("Priv".to_string());
+                }
+                if with_ports {
+                    headers.push("Ports".to_string());
+                }
+                if with_restart {
+                    headers.push("Restart".to_string());
+                }
+                if with_log_driver {
+                    headers.push("Log driver".to_string());
+                }
+                if with_memory {
+                    headers.push("Memory".to_string());
+                }
                 view.add_table(TableView {
                     title: Some("Containers".to_string()),
-                    headers: vec!["Name".to_string(), "Image".to_string(), "State".to_string()],
+                    headers,
                     rows,
                     row_classes,
+                    bars,
                 });
             }
-        }
-    }
-
-    fn populate_containers(view: &mut SectionView, body: &Value) {
-        if let Some(runtimes) = body.get("runtimes").and_then(Value::as_array) {
-            let items: Vec<String> = runtimes
+            if all_interfaces > 0 {
+                view.add_kv("Ports on all interfaces", all_interfaces.to_string());
+            }
+            populate_docker_health_checks(view, containers);
+            let sensitive: Vec<String> = containers
                 .iter()
-                .filter_map(Value::as_str)
-                .map(ToOwned::to_owned)
+                .filter_map(|container| {
+                    let vars: Vec<&str> = container
+                        .get("sensitive_env_vars")
+                        .and_then(Value::as_array)?
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect();
+                    if vars.is_empty() {
+                        return None;
+                    }
+                    let name = container
+                        .get("names")
+                        .and_then(Value::as_array)
+                        .and_then(|arr| arr.iter().filter_map(Value::as_str).next())
+                        .or_else(|| container.get("id").and_then(Value::as_str))
+                        .unwrap_or("unknown");
+                    Some(format!("{} ({})", name, vars.join(", ")))
+                })
                 .collect();
-            if !items.is_empty() {
-                view.add_list(ListView {
-                    title: Some("Detected runtimes".to_string()),
-                    items,
-                });
+            if !sensitive.is_empty() {
+                view.add_kv("Secret-looking env vars", sensitive.join("; "));
             }
         }
+
+        populate_docker_summarized(view, body);
+        populate_docker_swarm(view, body);
+        populate_docker_daemon(view, body);
+        populate_docker_networks(view, body);
+        populate_docker_images(view, body);
+        populate_docker_events(view, body);
     }
 
-    fn populate_users(view: &mut SectionView, body: &Value) {
-        if let Some(users) = body.get("users").and_then(Value::as_array) {
-            let total = users.len();
-            let system = users
-                .iter()
-                .filter(|user| user.get("system").and_then(Value::as_bool).unwrap_or(false))
-                .count();
-            let regular = total.saturating_sub(system);
-            let interactive = users
-                .iter()
-                .filter(|user| {
-                    user.get("interactive")
-                        .and_then(Value::as_bool)
-                        .unwrap_or(false)
+    fn populate_docker_summarized(view: &mut SectionView, body: &Value) {
+        let Some(summarized) = body
+            .get("summarized_containers")
+            .filter(|summarized| summarized.is_object())
+        else {
+            return;
+        };
+        let sorted_counts = |key: &str| {
+            let mut counts: Vec<(String, u64)> = summarized
+                .get(key)
+                .and_then(Value::as_object)
+                .map(|counts| {
+                    counts
+                        .iter()
+                        .map(|(name, count)| (name.clone(), count.as_u64().unwrap_or(0)))
+                        .collect()
                 })
-                .count();
-            let sudo = users
-                .iter()
-                .filter(|user| user.get("sudo").and_then(Value::as_bool).unwrap_or(false))
-                .count();
-            view.add_kv("Users", format!("{} total", total));
-            view.add_kv("System users", system.to_string());
-            view.add_kv("Regular users", regular.to_string());
-            view.add_kv("Interactive shells", interactive.to_string());
-            view.add_kv("Sudo access", sudo.to_string());
-
-            let mut row_classes = Vec::new();
-            let rows: Vec<Vec<String>> = users
+                .unwrap_or_default();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            counts
+        };
+        let count = summarized.get("count").and_then(Value::as_u64).unwrap_or(0);
+        let states = sorted_counts("by_state");
+        let label = if states.is_empty() {
+            count.to_string()
+        } else {
+            let states: Vec<String> = states
                 .iter()
-                .take(12)
-                .map(|user| {
-                    let name = user.get("name").and_then(Value::as_str).unwrap_or("?");
-                    let uid = user
-                        .get("uid")
-                        .and_then(Value::as_u64)
-                        .map(|v| v.to_string())
-                        .unwrap_or_else(|| "-".to_string());
-                    let shell = user.get("shell").and_then(Value::as_str).unwrap_or("-");
-                    let is_system = user.get("system").and_then(Value::as_bool).unwrap_or(false);
-                    let role = if is_system { "system" } else { "regular" };
-                    let interactive = if user
-                        .get("interactive")
-                        .and_then(Value::as_bool)
-                        .unwrap_or(false)
-                    {
-                        "yes"
-                    } else {
-                        "no"
-                    };
-                    let has_sudo = user.get("sudo").and_then(Value::as_bool).unwrap_or(false);
-                    let sudo = if has_sudo { "yes" } else { "no" };
-                    if has_sudo && !is_system {
-                        row_classes.push("row-warning".to_string());
-                    } else {
-                        row_classes.push(String::new());
-                    }
-                    vec![
-                        name.to_string(),
-                        uid,
-                        shell.to_string(),
-                        role.to_string(),
-                        interactive.to_string(),
-                        sudo.to_string(),
-                    ]
-                })
+                .map(|(state, count)| format!("{} {}", state, count))
                 .collect();
-            if !rows.is_empty() {
-                view.add_table(TableView {
-                    title: Some("Sample accounts".to_string()),
-                    headers: vec![
-                        "User".to_string(),
-                        "UID".to_string(),
-                        "Shell".to_string(),
-                        "Type".to_string(),
-                        "Interactive".to_string(),
-                        "Sudo".to_string(),
-                    ],
-                    rows,
-                    row_classes,
-                });
-            }
+            format!("{} ({})", count, states.join(", "))
+        };
+        view.add_kv("Summarized containers", label);
+
+        let images = sorted_counts("by_image");
+        if !images.is_empty() {
+            view.add_table(TableView {
+                title: Some("Summarized containers by image".to_string()),
+                headers: vec!["Image".to_string(), "Containers".to_string()],
+                rows: images
+                    .into_iter()
+                    .map(|(image, count)| vec![image, count.to_string()])
+                    .collect(),
+                row_classes: Vec::new(),
+                bars: Vec::new(),
+            });
         }
     }
 
-    fn populate_generic(view: &mut SectionView, body: &Value) {
-        match body {
-            Value::Object(map) => {
-                for (key, value) in map.iter() {
-                    view.add_kv(key, summarize_value(value));
-                }
-            }
-            Value::Array(items) => {
-                let list: Vec<String> = items.iter().take(20).map(summarize_value).collect();
-                if !list.is_empty() {
-                    view.add_list(ListView {
-                        title: None,
-                        items: list,
-                    });
+    /// Last probe of every container whose most recent health check failed.
+    fn populate_docker_health_checks(view: &mut SectionView, containers: &[Value]) {
+        let rows: Vec<Vec<String>> = containers
+            .iter()
+            .filter_map(|container| {
+                let check = container.get("last_health_check")?;
+                let exit_code = check.get("exit_code").and_then(Value::as_i64)?;
+                if exit_code == 0 {
+                    return None;
                 }
-            }
-            Value::String(s) => {
-                view.paragraph = Some(s.clone());
-            }
-            Value::Number(num) => {
-                view.paragraph = Some(num.to_string());
-            }
-            Value::Bool(b) => {
-                view.paragraph = Some(b.to_string());
-            }
-            Value::Null => {}
+                let name = container
+                    .get("names")
+                    .and_then(Value::as_array)
+                    .and_then(|arr| arr.iter().filter_map(Value::as_str).next())
+                    .or_else(|| container.get("id").and_then(Value::as_str))
+                    .unwrap_or("unknown");
+                let text = |key: &str| check.get(key).and_then(Value::as_str).unwrap_or("-");
+                Some(vec![
+                    name.to_string(),
+                    exit_code.to_string(),
+                    text("output").to_string(),
+                    text("finished_at").to_string(),
+                ])
+            })
+            .collect();
+        if rows.is_empty() {
+            return;
         }
+        view.add_table(TableView {
+            title: Some("Failing health checks".to_string()),
+            headers: vec![
+                "Name".to_string(),
+                "Exit code".to_string(),
+                "Output".to_string(),
+                "Time".to_string(),
+            ],
+            row_classes: vec!["row-critical".to_string(); rows.len()],
+            rows,
+            bars: Vec::new(),
+        });
     }
 
-    fn status_class(status: &SectionStatus) -> &'static str {
-        match status {
-            SectionStatus::Success => "success",
-            SectionStatus::Degraded => "degraded",
-            SectionStatus::Error => "error",
+    fn populate_docker_daemon(view: &mut SectionView, body: &Value) {
+        let Some(daemon) = body.get("daemon").filter(|daemon| daemon.is_object()) else {
+            return;
+        };
+        let text = |key: &str| daemon.get(key).and_then(Value::as_str);
+        if let Some(driver) = text("storage_driver") {
+            view.add_kv("Storage driver", driver);
+        }
+        if let Some(driver) = text("cgroup_driver") {
+            view.add_kv("Cgroup driver", driver);
+        }
+        if let Some(enabled) = daemon.get("live_restore").and_then(Value::as_bool) {
+            view.add_kv("Live restore", if enabled { "yes" } else { "no" });
+        }
+        view.add_kv("Userns remap", text("userns_remap").unwrap_or("off"));
+        if daemon.get("log_driver").is_some_and(Value::is_string) {
+            view.add_kv("Default log driver", docker_log_label(daemon, "log_opts"));
+        }
+        let list = |key: &str| -> Vec<&str> {
+            daemon
+                .get(key)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect()
+        };
+        let registries = list("insecure_registries");
+        if !registries.is_empty() {
+            view.add_kv("Insecure registries", registries.join(", "));
+        }
+        let tcp_hosts = list("tcp_hosts_without_tls");
+        if !tcp_hosts.is_empty() {
+            view.add_kv("API over TCP without TLS", tcp_hosts.join(", "));
         }
     }
 
-    fn status_label(status: &SectionStatus) -> String {
-        let mut label = status.to_string();
-        if let Some(first) = label.get_mut(0..1) {
-            first.make_ascii_uppercase();
+    fn populate_docker_swarm(view: &mut SectionView, body: &Value) {
+        let Some(swarm) = body.get("swarm").filter(|swarm| swarm.is_object()) else {
+            return;
+        };
+        let status = swarm
+            .get("status")
+            .and_then(Value::as_str)
+            .unwrap_or("inactive");
+        if status == "inactive" {
+            return;
         }
-        label
+        let mut details = Vec::new();
+        if let Some(role) = swarm.get("role").and_then(Value::as_str) {
+            details.push(role.to_string());
+        }
+        if let Some(nodes) = swarm.get("nodes").and_then(Value::as_u64) {
+            details.push(format!("nodes {}", nodes));
+        }
+        if let Some(managers) = swarm.get("managers").and_then(Value::as_u64) {
+            details.push(format!("managers {}", managers));
+        }
+        let label = if details.is_empty() {
+            status.to_string()
+        } else {
+            format!("{} ({})", status, details.join(", "))
+        };
+        view.add_kv("Swarm", label);
+
+        let services = swarm
+            .get("services")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if services.is_empty() {
+            return;
+        }
+        let mut row_classes = Vec::new();
+        let rows = services
+            .iter()
+            .map(|service| {
+                let text = |key: &str| service.get(key).and_then(Value::as_str).unwrap_or("-");
+                let count = |key: &str| service.get(key).and_then(Value::as_u64).unwrap_or(0);
+                let (running, desired) = (count("running"), count("desired"));
+                row_classes.push(if running < desired { "row-warning" } else { "" }.to_string());
+                vec![
+                    text("name").to_string(),
+                    text("mode").to_string(),
+                    format!("{}/{}", running, desired),
+                    text("image").to_string(),
+                ]
+            })
+            .collect();
+        view.add_table(TableView {
+            title: Some("Swarm services".to_string()),
+            headers: vec![
+                "Service".to_string(),
+                "Mode".to_string(),
+                "Replicas".to_string(),
+                "Image".to_string(),
+            ],
+            rows,
+            row_classes,
+            bars: Vec::new(),
+        });
     }
 
-    fn format_bytes(bytes: u64) -> String {
-        const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
-        let mut value = bytes as f64;
-        let mut unit = 0;
-        while value >= 1024.0 && unit < UNITS.len() - 1 {
-            value /= 1024.0;
-            unit += 1;
+    fn populate_docker_events(view: &mut SectionView, body: &Value) {
+        let Some(events) = body.get("events").filter(|events| events.is_object()) else {
+            return;
+        };
+        let total = events.get("total").and_then(Value::as_u64).unwrap_or(0);
+        let label = match events.get("since").and_then(Value::as_str) {
+            Some(since) => format!("{} since {}", total, since),
+            None => total.to_string(),
+        };
+        view.add_kv("Engine events", label);
+
+        if let Some(counts) = events.get("counts").and_then(Value::as_object)
+            && !counts.is_empty()
+        {
+            let mut counts: Vec<(&String, u64)> = counts
+                .iter()
+                .map(|(event, count)| (event, count.as_u64().unwrap_or(0)))
+                .collect();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            view.add_table(TableView {
+                title: Some("Event counts".to_string()),
+                headers: vec!["Event".to_string(), "Count".to_string()],
+                rows: counts
+                    .into_iter()
+                    .map(|(event, count)| vec![event.clone(), count.to_string()])
+                    .collect(),
+                row_classes: Vec::new(),
+                bars: Vec::new(),
+            });
         }
-        if unit == 0 {
-            format!("{} {}", bytes, UNITS[unit])
-        } else {
-            format!("{:.1} {}", value, UNITS[unit])
+
+        let recent = events
+            .get("recent")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if recent.is_empty() {
+            return;
         }
+        let mut row_classes = Vec::new();
+        let rows = recent
+            .iter()
+            .map(|event| {
+                let text = |key: &str| event.get(key).and_then(Value::as_str).unwrap_or("-");
+                let action = text("action");
+                let class = if action == "oom" || action.ends_with("unhealthy") {
+                    "row-critical"
+                } else if action == "die" || action == "kill" {
+                    "row-warning"
+                } else {
+                    ""
+                };
+                row_classes.push(class.to_string());
+                vec![
+                    text("time").to_string(),
+                    text("type").to_string(),
+                    action.to_string(),
+                    text("object").to_string(),
+                    event
+                        .get("exit_code")
+                        .and_then(Value::as_i64)
+                        .map(|code| code.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ]
+            })
+            .collect();
+        view.add_table(TableView {
+            title: Some("Recent events".to_string()),
+            headers: vec![
+                "Time".to_string(),
+                "Type".to_string(),
+                "Action".to_string(),
+                "Object".to_string(),
+                "Exit code".to_string(),
+            ],
+            rows,
+            row_classes,
+            bars: Vec::new(),
+        });
     }
 
-    fn format_percent(ratio: f64) -> String {
-        format!("{:.1}%", ratio * 100.0)
+    fn populate_docker_networks(view: &mut SectionView, body: &Value) {
+        let Some(networks) = body.get("networks").and_then(Value::as_array) else {
+            return;
+        };
+        let rows: Vec<Vec<String>> = networks
+            .iter()
+            .map(|network| {
+                let text = |key: &str| {
+                    network
+                        .get(key)
+                        .and_then(Value::as_str)
+                        .unwrap_or("-")
+                        .to_string()
+                };
+                let subnets: Vec<&str> = network
+                    .get("subnets")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .collect();
+                vec![
+                    text("name"),
+                    text("driver"),
+                    text("scope"),
+                    if subnets.is_empty() {
+                        "-".to_string()
+                    } else {
+                        subnets.join(", ")
+                    },
+                    if network.get("internal").and_then(Value::as_bool) == Some(true) {
+                        "yes"
+                    } else {
+                        "no"
+                    }
+                    .to_string(),
+                    network
+                        .get("containers")
+                        .and_then(Value::as_u64)
+                        .unwrap_or(0)
+                        .to_string(),
+                ]
+            })
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+        view.add_table(TableView {
+            title: Some("Networks".to_string()),
+            headers: vec![
+                "Name".to_string(),
+                "Driver".to_string(),
+                "Scope".to_string(),
+                "Subnets".to_string(),
+                "Internal".to_string(),
+                "Containers".to_string(),
+            ],
+            rows,
+            row_classes: Vec::new(),
+            bars: Vec::new(),
+        });
     }
 
-    fn format_duration(duration_ms: Option<u64>) -> Option<String> {
-        duration_ms.map(|ms| {
-            if ms >= 10_000 {
-                format!("{:.1}s", ms as f64 / 1000.0)
-            } else if ms >= 1000 {
-                format!("{:.2}s", ms as f64 / 1000.0)
-            } else {
-                format!("{} ms", ms)
-            }
-        })
+    const DOCKER_IMAGE_ROWS: usize = 10;
+
+    fn populate_docker_images(view: &mut SectionView, body: &Value) {
+        let Some(images) = body.pointer("/storage/images").and_then(Value::as_array) else {
+            return;
+        };
+        let mut row_classes = Vec::new();
+        let rows: Vec<Vec<String>> = images
+            .iter()
+            .take(DOCKER_IMAGE_ROWS)
+            .map(|image| {
+                let flag = |key: &str| image.get(key).and_then(Value::as_bool).unwrap_or(false);
+                row_classes.push(if flag("dangling") { "row-warning" } else { "" }.to_string());
+                vec![
+                    image
+                        .get("reference")
+                        .and_then(Value::as_str)
+                        .unwrap_or("<none>")
+                        .to_string(),
+                    image
+                        .get("size_bytes")
+                        .and_then(Value::as_u64)
+                        .map(format_bytes)
+                        .unwrap_or_else(|| "-".to_string()),
+                    image
+                        .get("age_days")
+                        .and_then(Value::as_u64)
+                        .map(|days| format!("{}d", days))
+                        .unwrap_or_else(|| "-".to_string()),
+                    if flag("in_use") { "yes" } else { "no" }.to_string(),
+                ]
+            })
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+        view.add_table(TableView {
+            title: Some("Largest images".to_string()),
+            headers: vec![
+                "Image".to_string(),
+                "Size".to_string(),
+                "Age".to_string(),
+                "In use".to_string(),
+            ],
+            rows,
+            row_classes,
+            bars: Vec::new(),
+        });
     }
 
-    fn summarize_value(value: &Value) -> String {
-        match value {
-            Value::Null => "n/a".to_string(),
-            Value::Bool(b) => b.to_string(),
-            Value::Number(num) => num.to_string(),
-            Value::String(text) => truncate(text),
-            Value::Array(arr) => format!("{} entries", arr.len()),
-            Value::Object(map) => format!("{} keys", map.len()),
+    /// Per-project table, shown only when at least one container belongs to
+    /// a compose project; a lone standalone bucket would repeat the list.
+    fn populate_docker_compose_projects(view: &mut SectionView, body: &Value) {
+        let Some(projects) = body.get("compose_projects").and_then(Value::as_array) else {
+            return;
+        };
+        let has_compose = projects
+            .iter()
+            .any(|project| project.get("name").and_then(Value::as_str) != Some("(standalone)"));
+        if !has_compose {
+            return;
         }
+        let mut row_classes = Vec::new();
+        let rows: Vec<Vec<String>> = projects
+            .iter()
+            .map(|project| {
+                let count = |key: &str| project.get(key).and_then(Value::as_u64).unwrap_or(0);
+                let not_running = count("not_running");
+                row_classes.push(if not_running > 0 { "row-warning" } else { "" }.to_string());
+                vec![
+                    project
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown")
+                        .to_string(),
+                    count("containers").to_string(),
+                    not_running.to_string(),
+                    project
+                        .get("memory_usage_bytes")
+                        .and_then(Value::as_u64)
+                        .map(format_bytes)
+                        .unwrap_or_else(|| "-".to_string()),
+                ]
+            })
+            .collect();
+        view.add_table(TableView {
+            title: Some("Compose projects".to_string()),
+            headers: vec![
+                "Project".to_string(),
+                "Containers".to_string(),
+                "Not running".to_string(),
+                "Memory".to_string(),
+            ],
+            rows,
+            row_classes,
+            bars: Vec::new(),
+        });
     }
 
-    fn truncate(input: &str) -> String {
-        if input.len() > 120 {
-            format!("{}…", &input[..117])
+    /// Driver plus its rotation limits, e.g. `json-file (max-size 10m, max-file 3)`.
+    /// `log_driver` with its rotation limits read from `options_key`.
+    fn docker_log_label(value: &Value, options_key: &str) -> String {
+        let Some(driver) = value.get("log_driver").and_then(Value::as_str) else {
+            return "-".to_string();
+        };
+        let limits: Vec<String> = ["max-size", "max-file"]
+            .into_iter()
+            .filter_map(|key| {
+                value
+                    .pointer(&format!("/{}/{}", options_key, key))
+                    .and_then(Value::as_str)
+                    .map(|value| format!("{} {}", key, value))
+            })
+            .collect();
+        if limits.is_empty() {
+            driver.to_string()
         } else {
-            input.to_string()
+            format!("{} ({})", driver, limits.join(", "))
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use jsonschema::JSONSchema;
-    use serde_json::{Value, json};
-    use vmic_sdk::SectionStatus;
+    fn docker_host_network(container: &Value) -> bool {
+        container.get("network_mode").and_then(Value::as_str) == Some("host")
+    }
 
-    // Link modules so their collectors register during tests.
-    #[allow(unused_imports)]
-    use {
-        mod_containers as _, mod_cron as _, mod_docker as _, mod_journal as _, mod_os as _,
-        mod_proc as _, mod_sar as _, mod_services as _, mod_users as _,
-    };
+    /// Published mappings as `host:port->port/proto`, plus how many of them
+    /// listen on every interface. Docker reports a wildcard binding once for
+    /// `0.0.0.0` and once for `::`; those collapse into a single `*` entry.
+    fn docker_port_labels(container: &Value) -> (Vec<String>, usize) {
+        let mut labels: Vec<String> = Vec::new();
+        let mut all_interfaces = 0;
+        let ports = container
+            .get("ports")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for port in ports {
+            let Some(container_port) = port.get("container_port").and_then(Value::as_u64) else {
+                continue;
+            };
+            let protocol = port
+                .get("protocol")
+                .and_then(Value::as_str)
+                .unwrap_or("tcp");
+            let Some(host_port) = port.get("host_port").and_then(Value::as_u64) else {
+                continue;
+            };
+            let label = match port.get("host_ip").and_then(Value::as_str) {
+                None | Some("0.0.0.0") | Some("::") => format!(
+                    "*:{}->{}/{} (all interfaces)",
+                    host_port, container_port, protocol
+                ),
+                Some(ip) if ip.contains(':') => {
+                    format!("[{}]:{}->{}/{}", ip, host_port, container_port, protocol)
+                }
+                Some(ip) => format!("{}:{}->{}/{}", ip, host_port, container_port, protocol),
+            };
+            if labels.contains(&label) {
+                continue;
+            }
+            if label.starts_with('*') {
+                all_interfaces += 1;
+            }
+            labels.push(label);
+        }
+        (labels, all_interfaces)
+    }
 
-    #[test]
-    fn default_digest_thresholds_match_updated_values() {
-        let thresholds = DigestThresholds::default();
-        assert_eq!(thresholds.disk_warning, 0.90);
-        assert_eq!(thresholds.disk_critical, 0.95);
-        assert_eq!(thresholds.memory_warning, 0.10);
-        assert_eq!(thresholds.memory_critical, 0.05);
+    fn populate_containers(view: &mut SectionView, body: &Value) {
+        if let Some(runtimes) = body.get("runtimes").and_then(Value::as_array) {
+            let items: Vec<String> = runtimes
+                .iter()
+                .filter_map(Value::as_str)
+                .map(ToOwned::to_owned)
+                .collect();
+            if !items.is_empty() {
+                view.add_list(ListView {
+                    title: Some("Detected runtimes".to_string()),
+                    items,
+                });
+            }
+        }
     }
 
-    #[test]
-    fn collect_report_returns_sections() {
-        let ctx = Context::new();
-        let report = collect_report(&ctx);
-        assert!(!report.sections.is_empty());
-        assert!(report.sections.iter().any(|s| s.id == "os"));
-        assert!(
-            report
-                .sections
+    fn populate_users(view: &mut SectionView, body: &Value) {
+        if let Some(users) = body.get("users").and_then(Value::as_array) {
+            let total = users.len();
+            let system = users
                 .iter()
-                .all(|s| !matches!(s.status, SectionStatus::Error))
-        );
-        assert_eq!(report.metadata.sections, report.sections.len());
-        let expected_overall = report
-            .health_digest
-            .findings
-            .iter()
-            .map(|f| f.severity)
-            .max()
-            .unwrap_or(Severity::Info);
-        assert_eq!(report.health_digest.overall, expected_overall);
+                .filter(|user| user.get("system").and_then(Value::as_bool).unwrap_or(false))
+                .count();
+            let regular = total.saturating_sub(system);
+            let interactive = users
+                .iter()
+                .filter(|user| {
+                    user.get("interactive")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false)
+                })
+                .count();
+            let sudo = users
+                .iter()
+                .filter(|user| user.get("sudo").and_then(Value::as_bool).unwrap_or(false))
+                .count();
+            view.add_kv("Users", format!("{} total", total));
+            view.add_kv("System users", system.to_string());
+            view.add_kv("Regular users", regular.to_string());
+            view.add_kv("Interactive shells", interactive.to_string());
+            view.add_kv("Sudo access", sudo.to_string());
+
+            let mut row_classes = Vec::new();
+            let rows: Vec<Vec<String>> = users
+                .iter()
+                .map(|user| {
+                    let name = user.get("name").and_then(Value::as_str).unwrap_or("?");
+                    let uid = user
+                        .get("uid")
+                        .and_then(Value::as_u64)
+                        .map(|v| v.to_string())
+                        .unwrap_or_else(|| "-".to_string());
+                    let shell = user.get("shell").and_then(Value::as_str).unwrap_or("-");
+                    let is_system = user.get("system").and_then(Value::as_bool).unwrap_or(false);
+                    let role = if is_system { "system" } else { "regular" };
+                    let interactive = if user
+                        .get("interactive")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false)
+                    {
+                        "yes"
+                    } else {
+                        "no"
+                    };
+                    let has_sudo = user.get("sudo").and_then(Value::as_bool).unwrap_or(false);
+                    let sudo = if has_sudo { "yes" } else { "no" };
+                    if has_sudo && !is_system {
+                        row_classes.push("row-warning".to_string());
+                    } else {
+                        row_classes.push(String::new());
+                    }
+                    vec![
+                        name.to_string(),
+                        uid,
+                        shell.to_string(),
+                        role.to_string(),
+                        interactive.to_string(),
+                        sudo.to_string(),
+                    ]
+                })
+                .collect();
+            if !rows.is_empty() {
+                view.add_table(TableView {
+                    title: Some("Sample accounts".to_string()),
+                    headers: vec![
+                        "User".to_string(),
+                        "UID".to_string(),
+                        "Shell".to_string(),
+                        "Type".to_string(),
+                        "Interactive".to_string(),
+                        "Sudo".to_string(),
+                    ],
+                    rows,
+                    row_classes,
+                    bars: Vec::new(),
+                });
+            }
+        }
+    }
+
+    fn populate_security(view: &mut SectionView, body: &Value) {
+        let flag = |value: Option<bool>| match value {
+            Some(true) => "yes".to_string(),
+            Some(false) => "no".to_string(),
+            None => "unknown".to_string(),
+        };
+
+        let sshd = body.get("sshd");
+        let sudoers = body.get("sudoers");
+        let cgroups = body.get("cgroups");
+
+        view.add_kv(
+            "SSH crypto hardening",
+            flag(
+                sshd.and_then(|value| value.get("hardening_present"))
+                    .and_then(Value::as_bool),
+            ),
+        );
+        view.add_kv(
+            "Sudoers include dir",
+            flag(
+                sudoers
+                    .and_then(|value| value.get("includes_dir"))
+                    .and_then(Value::as_bool),
+            ),
+        );
+        view.add_kv(
+            "Cgroup v2 unified",
+            flag(
+                cgroups
+                    .and_then(|value| value.get("unified_hierarchy"))
+                    .and_then(Value::as_bool),
+            ),
+        );
+        if let Some(controllers) = cgroups
+            .and_then(|value| value.get("controllers"))
+            .and_then(Value::as_array)
+            .filter(|controllers| !controllers.is_empty())
+        {
+            let names: Vec<&str> = controllers.iter().filter_map(Value::as_str).collect();
+            view.add_kv("Cgroup controllers", names.join(", "));
+        }
+
+        let mut rows = Vec::new();
+        let mut row_classes = Vec::new();
+        for (source, label) in [(sshd, "sshd"), (sudoers, "sudoers"), (cgroups, "cgroups")] {
+            let Some(findings) = source
+                .and_then(|value| value.get("findings"))
+                .and_then(Value::as_array)
+            else {
+                continue;
+            };
+            for finding in findings {
+                let severity = finding
+                    .get("severity")
+                    .and_then(Value::as_str)
+                    .unwrap_or("info");
+                let message = finding
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("-");
+                row_classes.push(severity_row_class(severity).to_string());
+                rows.push(vec![
+                    label.to_string(),
+                    severity.to_string(),
+                    message.to_string(),
+                ]);
+            }
+        }
+
+        view.add_table(TableView {
+            title: Some("Security Findings".to_string()),
+            headers: vec![
+                "Source".to_string(),
+                "Severity".to_string(),
+                "Finding".to_string(),
+            ],
+            rows,
+            row_classes,
+            bars: Vec::new(),
+        });
+    }
+
+    fn populate_sar(view: &mut SectionView, body: &Value) {
+        let Some(cpu) = body.get("cpu").and_then(Value::as_object) else {
+            return;
+        };
+
+        let metric = |key: &str| {
+            cpu.get(key)
+                .and_then(Value::as_f64)
+                .map(|value| format!("{:.2}%", value))
+                .unwrap_or_else(|| "-".to_string())
+        };
+
+        if cpu.contains_key("iowait") {
+            view.add_kv("IOWait", metric("iowait"));
+        }
+
+        view.add_table(TableView {
+            title: Some("CPU Averages".to_string()),
+            headers: vec![
+                "User".to_string(),
+                "Nice".to_string(),
+                "System".to_string(),
+                "IOWait".to_string(),
+                "Steal".to_string(),
+                "Idle".to_string(),
+            ],
+            rows: vec![vec![
+                metric("user"),
+                metric("nice"),
+                metric("system"),
+                metric("iowait"),
+                metric("steal"),
+                metric("idle"),
+            ]],
+            row_classes: Vec::new(),
+            bars: Vec::new(),
+        });
+    }
+
+    fn severity_row_class(severity: &str) -> &'static str {
+        match severity {
+            "critical" => "row-critical",
+            "warning" => "row-warning",
+            _ => "",
+        }
+    }
+
+    fn populate_generic(view: &mut SectionView, body: &Value) {
+        match body {
+            Value::Object(map) => {
+                for (key, value) in map.iter() {
+                    view.add_kv(key, summarize_value(value));
+                }
+            }
+            Value::Array(items) => {
+                let list: Vec<String> = items.iter().map(summarize_value).collect();
+                if !list.is_empty() {
+                    view.add_list(ListView {
+                        title: None,
+                        items: list,
+                    });
+                }
+            }
+            Value::String(s) => {
+                view.paragraph = Some(s.clone());
+            }
+            Value::Number(num) => {
+                view.paragraph = Some(num.to_string());
+            }
+            Value::Bool(b) => {
+                view.paragraph = Some(b.to_string());
+            }
+            Value::Null => {}
+        }
+    }
+
+    fn status_class(status: &SectionStatus) -> &'static str {
+        match status {
+            SectionStatus::Success => "success",
+            SectionStatus::Degraded => "degraded",
+            SectionStatus::Error => "error",
+        }
+    }
+
+    fn status_label(status: &SectionStatus) -> String {
+        let mut label = status.to_string();
+        if let Some(first) = label.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        label
+    }
+
+    fn format_bytes(bytes: u64) -> String {
+        const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+        let mut value = bytes as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            format!("{} {}", bytes, UNITS[unit])
+        } else {
+            format!("{:.1} {}", value, UNITS[unit])
+        }
+    }
+
+    fn format_percent(ratio: f64) -> String {
+        format!("{:.1}%", ratio * 100.0)
+    }
+
+    fn format_duration(duration_ms: Option<u64>) -> Option<String> {
+        duration_ms.map(|ms| {
+            if ms >= 10_000 {
+                format!("{:.1}s", ms as f64 / 1000.0)
+            } else if ms >= 1000 {
+                format!("{:.2}s", ms as f64 / 1000.0)
+            } else {
+                format!("{} ms", ms)
+            }
+        })
+    }
+
+    fn summarize_value(value: &Value) -> String {
+        match value {
+            Value::Null => "n/a".to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Number(num) => num.to_string(),
+            Value::String(text) => truncate(text, SUMMARY_MAX_CHARS),
+            Value::Array(arr) => format!("{} entries", arr.len()),
+            Value::Object(map) => format!("{} keys", map.len()),
+        }
+    }
+
+    /// Maximum number of characters kept when summarizing free-form strings.
+    const SUMMARY_MAX_CHARS: usize = 120;
+    const SUMMARY_ROW_MAX_CHARS: usize = 80;
+
+    /// Shortens `input` to at most `max_chars` characters, replacing the tail with an
+    /// ellipsis. Counts characters rather than bytes so multi-byte text never splits.
+    fn truncate(input: &str, max_chars: usize) -> String {
+        if input.char_indices().nth(max_chars).is_none() {
+            return input.to_string();
+        }
+        let keep = max_chars.saturating_sub(3);
+        let end = input
+            .char_indices()
+            .nth(keep)
+            .map(|(index, _)| index)
+            .unwrap_or(input.len());
+        format!("{}…", &input[..end])
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn usage_bar_width_is_clamped() {
+            assert_eq!(UsageBar::new(1.7).width, "100.0");
+            assert_eq!(UsageBar::new(-0.2).width, "0.0");
+            assert_eq!(UsageBar::new(0.85).class, "bar-warning");
+        }
+
+        #[test]
+        fn truncate_keeps_short_strings() {
+            assert_eq!(truncate("short", 120), "short");
+            let exact = "a".repeat(120);
+            assert_eq!(truncate(&exact, 120), exact);
+        }
+
+        #[test]
+        fn truncate_matches_ascii_behaviour() {
+            let long = "a".repeat(121);
+            let truncated = truncate(&long, 120);
+            assert_eq!(truncated, format!("{}…", "a".repeat(117)));
+        }
+
+        #[test]
+        fn truncate_handles_cyrillic() {
+            let exact = "я".repeat(120);
+            assert_eq!(truncate(&exact, 120), exact);
+
+            // Byte 117 falls inside a two-byte character here.
+            let long = format!("a{}", "ж".repeat(130));
+            let truncated = truncate(&long, 120);
+            assert_eq!(truncated.chars().count(), 118);
+            assert!(truncated.ends_with("ж…"));
+        }
+
+        #[test]
+        fn truncate_handles_emoji() {
+            let long = "🚀".repeat(200);
+            let truncated = truncate(&long, 10);
+            assert_eq!(truncated, format!("{}…", "🚀".repeat(7)));
+            assert_eq!(truncate("🚀🚀", 2), "🚀🚀");
+            assert_eq!(truncate("🚀🚀🚀", 2), "…");
+        }
+
+        fn docker_section(count: usize) -> crate::Section {
+            let containers: Vec<Value> = (0..count)
+                .map(|index| {
+                    serde_json::json!({
+                        "names": [format!("app-{index}")],
+                        "image": "nginx",
+                        "state": "running"
+                    })
+                })
+                .collect();
+            crate::Section::success(
+                "docker",
+                "Docker Engine",
+                serde_json::json!({ "containers": containers }),
+            )
+        }
+
+        #[test]
+        fn default_limits_note_truncated_tables() {
+            let report = Report::new(vec![docker_section(40)]);
+            let html = render_html(&report, &RenderOptions::default()).expect("html");
+            assert!(html.contains("Containers (showing 12 of 40)"));
+            assert!(html.contains("app-11"));
+            assert!(!html.contains("<td>app-12</td>"));
+        }
+
+        #[test]
+        fn full_detail_renders_every_row() {
+            let report = Report::new(vec![docker_section(40)]);
+            for options in [
+                RenderOptions::full_detail(),
+                RenderOptions {
+                    max_table_rows: None,
+                    ..RenderOptions::default()
+                },
+            ] {
+                let html = render_html(&report, &options).expect("html");
+                let markdown = render_markdown(&report, &options).expect("markdown");
+                assert!(html.contains("app-39") && markdown.contains("app-39"));
+                assert!(!html.contains("(showing") && !markdown.contains("(showing"));
+            }
+
+            let options = RenderOptions {
+                max_table_rows: Some(30),
+                ..RenderOptions::default()
+            };
+            let html = render_html(&report, &options).expect("html");
+            assert!(html.contains("Containers (showing 30 of 40)"));
+        }
+
+        #[test]
+        fn explicit_limits_override_compact_caps() {
+            let interfaces: Vec<serde_json::Value> = (0..20)
+                .map(|index| serde_json::json!({ "name": format!("veth{index:02}") }))
+                .collect();
+            let network = crate::Section::success(
+                "network",
+                "Network",
+                serde_json::json!({ "interfaces": interfaces }),
+            );
+            let report = Report::new(vec![network]);
+
+            let options = RenderOptions::default();
+            let html = render_html(&report, &options).expect("html");
+            let markdown = render_markdown(&report, &options).expect("markdown");
+            assert!(html.contains("Network Interfaces (showing 10 of 20)"));
+            assert!(markdown.contains("Network Interfaces (showing 10 of 20)"));
+
+            for limit in [11, 12, 13] {
+                let options = RenderOptions {
+                    max_table_rows: Some(limit),
+                    compact_tables: false,
+                    ..RenderOptions::default()
+                };
+                let note = format!("Network Interfaces (showing {limit} of 20)");
+                assert!(
+                    render_html(&report, &options)
+                        .expect("html")
+                        .contains(&note)
+                );
+                assert!(
+                    render_markdown(&report, &options)
+                        .expect("markdown")
+                        .contains(&note)
+                );
+            }
+            assert_eq!(compact_limit(Some(12), 6, true), Some(6));
+            assert_eq!(compact_limit(Some(12), 6, false), Some(12));
+            assert_eq!(compact_limit(None, 6, true), None);
+        }
+
+        #[test]
+        fn small_tables_render_without_note() {
+            let report = Report::new(vec![docker_section(3)]);
+            let html = render_html(&report, &RenderOptions::default()).expect("html");
+            assert!(html.contains("<h3>Containers</h3>"));
+            assert!(!html.contains("(showing"));
+        }
+
+        #[test]
+        fn raw_body_details_only_when_enabled() {
+            let section =
+                crate::Section::success("demo", "Demo", serde_json::json!({ "value": "<script>" }));
+            let report = Report::new(vec![section]);
+
+            let html = render_html(&report, &RenderOptions::default()).expect("html");
+            assert!(!html.contains("class=\"raw-body\""));
+
+            let options = RenderOptions {
+                include_raw_body: true,
+                ..RenderOptions::default()
+            };
+            let html = render_html(&report, &options).expect("html");
+            assert!(html.contains("<details class=\"raw-body\">"));
+            assert!(html.contains("&#60;script&#62;"));
+            assert!(!html.contains("\"<script>\""));
+        }
+
+        #[test]
+        fn raw_body_respects_byte_budget() {
+            let body = serde_json::json!({ "text": "ж".repeat(100) });
+            let raw = raw_body(&body, 21);
+            assert!(raw.contains("… truncated"));
+            assert!(raw.starts_with("{\n  \"text\": \""));
+            assert_eq!(
+                raw_body(&body, 10_000),
+                serde_json::to_string_pretty(&body).unwrap()
+            );
+        }
+
+        #[test]
+        fn html_render_survives_multibyte_journal_messages() {
+            let journal = crate::Section::success(
+                "journal",
+                "Journal Warnings",
+                serde_json::json!({
+                    "entries": [
+                        { "timestamp": "now", "source": "sshd", "message": format!("a{}", "ошибка ".repeat(40)) }
+                    ]
+                }),
+            );
+            let html =
+                render_html(&Report::new(vec![journal]), &RenderOptions::default()).expect("html");
+            assert!(html.contains("ошибка"));
+        }
+
+        #[test]
+        fn markdown_renders_section_views_as_tables() {
+            let mut storage = crate::Section::success(
+                "storage",
+                "Storage Overview",
+                serde_json::json!({
+                    "operating_mounts": [
+                        { "mount_point": "/", "source": "/dev/sda1", "fs_type": "ext4", "read_only": false, "operational": true, "total_bytes": 4096, "used_bytes": 1024, "available_bytes": 3072, "usage_ratio": 0.25 }
+                    ]
+                }),
+            );
+            storage.summary = Some("1 operating mounts".to_string());
+            storage.notes.push("hotspot scan | skipped".to_string());
+            let mut report = Report::new(vec![storage]);
+            report.metadata.set_generated_at(0);
+
+            let markdown = render_markdown(&report, &RenderOptions::default()).expect("markdown");
+            let expected = "\
+## Storage Overview
+
+Status: `Success`
+
+> 1 operating mounts
+
+### Operating Mounts
+
+| Mount | FS | Mode | Used | Free | Usage | Inodes |
+| --- | --- | --- | --- | --- | --- | --- |
+| / | ext4 | rw | 1.0 KiB | 3.0 KiB | 25.0% | n/a |
+
+**Notes**
+
+- hotspot scan | skipped
+";
+            assert!(markdown.starts_with("# System Report\n"));
+            assert!(
+                markdown.contains(expected),
+                "unexpected markdown:\n{markdown}"
+            );
+            assert!(!markdown.contains("```json"));
+
+            let os = crate::Section::success(
+                "os",
+                "Operating System",
+                serde_json::json!({ "os_release": { "pretty_name": "Debian | 12" } }),
+            );
+            let options = RenderOptions {
+                include_raw_body: true,
+                ..RenderOptions::default()
+            };
+            let markdown = render_markdown(&Report::new(vec![os]), &options).expect("markdown");
+            assert!(markdown.contains("- **Distribution:** Debian \\| 12\n"));
+            assert!(markdown.contains("```json\n{\n  \"os_release\""));
+        }
+
+        #[test]
+        fn structured_parts_take_precedence_over_the_body() {
+            let mut services = crate::Section::success(
+                "services",
+                "System Services",
+                serde_json::json!({ "running": [{ "unit": "from-body.service" }] }),
+            );
+            let mut running = vmic_sdk::Table::new(["Unit", "State"])
+                .titled("Running Services")
+                .with_total();
+            running.push_row(["from-parts.service", "active / running"]);
+            services.add_table(running);
+            services.add_key_values(vmic_sdk::KeyValueSet::new().with("Manager", "systemd"));
+            let mut report = Report::new(vec![services]);
+            report.metadata.set_generated_at(0);
+
+            let markdown = render_markdown(&report, &RenderOptions::default()).expect("markdown");
+            assert!(markdown.contains("### Running Services (1 total)"));
+            assert!(markdown.contains("| from-parts.service | active / running |"));
+            assert!(markdown.contains("- **Manager:** systemd"));
+            assert!(!markdown.contains("from-body.service"));
+
+            let options = RenderOptions {
+                locale: Locale::Ru,
+                ..RenderOptions::default()
+            };
+            let markdown = render_markdown(&report, &options).expect("markdown");
+            assert!(markdown.contains(&format!(
+                "### {} (1 {})",
+                i18n::translate(Locale::Ru, "Running Services"),
+                i18n::translate(Locale::Ru, "total")
+            )));
+        }
+
+        #[test]
+        fn summary_only_markdown_snapshot() {
+            let mut os = crate::Section::success("os", "Operating System", serde_json::json!({}));
+            os.summary = Some("Debian 12 | kernel 6.1".to_string());
+            os.duration_ms = Some(4);
+            let mut storage = crate::Section::degraded(
+                "storage",
+                "Storage Overview",
+                "1 operating mounts,\nworst 97.0% at /".to_string(),
+                serde_json::json!({ "operating_mounts": (0..50).map(|i| serde_json::json!({ "mount_point": format!("/m{i}") })).collect::<Vec<_>>() }),
+            );
+            storage.duration_ms = Some(1500);
+            let mut report = Report::new(vec![os, storage]);
+            report.metadata.set_generated_at(0);
+
+            let options = RenderOptions {
+                summary_only: true,
+                ..RenderOptions::default()
+            };
+            let markdown = render_markdown(&report, &options).expect("markdown");
+            let expected = "\
+# System Report
+
+Generated at: 1970-01-01T00:00:00+00:00
+Overall status: `Warning`
+
+- **Warning** (Storage Overview): 1 operating mounts, worst 97.0% at /
+
+| Section | Status | Summary | Duration |
+| --- | --- | --- | ---: |
+| os | Success | Debian 12 \\| kernel 6.1 | 4 ms |
+| storage | Degraded | 1 operating mounts, worst 97.0% at / | 1.50s |
+";
+            assert_eq!(markdown, expected);
+            assert!(!markdown.contains("/m49"));
+            assert_eq!(report.to_markdown_summary().unwrap(), expected);
+        }
+
+        #[test]
+        fn summary_only_markdown_stays_small() {
+            let sections = (0..12)
+                .map(|index| {
+                    let mut section = crate::Section::success(
+                        [
+                            "os",
+                            "proc",
+                            "storage",
+                            "services",
+                            "network",
+                            "docker",
+                            "containers",
+                            "journal",
+                            "cron",
+                            "users",
+                            "security",
+                            "sar",
+                        ][index],
+                        "Section",
+                        serde_json::json!({ "rows": vec!["x".repeat(200); 100] }),
+                    );
+                    section.summary = Some("s".repeat(400));
+                    section.duration_ms = Some(25);
+                    section
+                })
+                .collect();
+            let markdown = Report::new(sections)
+                .to_markdown_summary()
+                .expect("markdown");
+            assert!(
+                markdown.len() < 2048,
+                "summary was {} bytes",
+                markdown.len()
+            );
+        }
+
+        #[test]
+        fn min_finding_severity_hides_lower_findings_in_renderers_only() {
+            let degraded = crate::Section::degraded(
+                "docker",
+                "Docker Containers",
+                "daemon unavailable".to_string(),
+                serde_json::json!({}),
+            );
+            let failed = crate::Section::error("cron", "Scheduled Jobs", "boom".to_string());
+            let report = Report::new(vec![degraded, failed]);
+            let options = RenderOptions {
+                min_finding_severity: Severity::Critical,
+                ..RenderOptions::default()
+            };
+
+            let html = render_html(&report, &options).expect("html");
+            assert!(html.contains("1 lower-severity findings hidden"));
+            assert!(html.contains("Overall Status: Critical"));
+            assert!(!html.contains("daemon unavailable</span>"));
+            assert!(html.contains("boom"));
+
+            let markdown = render_markdown(&report, &options).expect("markdown");
+            assert!(markdown.contains("_1 lower-severity findings hidden_"));
+            assert!(!markdown.contains("(Docker Containers): daemon unavailable"));
+
+            let default_html = render_html(&report, &RenderOptions::default()).expect("html");
+            assert!(!default_html.contains("lower-severity findings hidden"));
+            assert_eq!(
+                report.to_json_value()["health_digest"]["findings"]
+                    .as_array()
+                    .unwrap()
+                    .len(),
+                2
+            );
+        }
+
+        #[test]
+        fn filtered_digest_keeps_unfiltered_overall() {
+            let degraded = crate::Section::degraded(
+                "docker",
+                "Docker Containers",
+                "daemon unavailable".to_string(),
+                serde_json::json!({}),
+            );
+            let report = Report::new(vec![degraded]);
+            let filtered = report.health_digest.filtered(Severity::Critical);
+            assert!(filtered.findings.is_empty());
+            assert_eq!(filtered.summary.total(), 0);
+            assert_eq!(filtered.overall, Severity::Warning);
+        }
+
+        #[test]
+        fn russian_locale_translates_labels_but_not_body() {
+            let os = crate::Section::success(
+                "os",
+                "Operating System",
+                serde_json::json!({ "os_release": { "pretty_name": "Debian 12" } }),
+            );
+            let storage = crate::Section::success(
+                "storage",
+                "Storage Overview",
+                serde_json::json!({
+                    "operating_mounts": [
+                        { "mount_point": "/", "fs_type": "ext4", "used_bytes": 1024, "available_bytes": 4096, "usage_ratio": 0.2 }
+                    ]
+                }),
+            );
+            let report = Report::new(vec![os, storage, docker_section(30)]);
+            let options = RenderOptions {
+                locale: Locale::Ru,
+                include_raw_body: true,
+                ..RenderOptions::default()
+            };
+
+            let html = render_html(&report, &options).expect("html");
+            assert!(html.contains("<html lang=\"ru\">"));
+            assert!(html.contains("Содержание"));
+            assert!(html.contains("Дистрибутив"));
+            assert!(html.contains("<th>Занято</th>"));
+            assert!(html.contains("<th>Свободно</th>"));
+            assert!(html.contains("mount_point"));
+            assert!(html.contains("(показано 12 из 30)"));
+            assert!(html.contains("pretty_name"));
+            assert!(!html.contains("<h2>Contents</h2>"));
+
+            let markdown = render_markdown(&report, &options).expect("markdown");
+            assert!(markdown.contains("# Отчёт о системе"));
+            assert!(markdown.contains("pretty_name") && markdown.contains("Debian 12"));
+
+            let english = render_html(&report, &RenderOptions::default()).expect("html");
+            assert!(english.contains("<html lang=\"en\">"));
+            assert!(english.contains("Distribution"));
+            assert!(english.contains("<th>Used</th>"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonschema::JSONSchema;
+    use serde_json::{Value, json};
+    use vmic_sdk::SectionStatus;
+    use vmic_sdk::testing::{FakeCollector, assert_section_golden, assert_section_matches_schema};
+
+    // Link modules so their collectors register during tests.
+    #[allow(unused_imports)]
+    use {
+        mod_containers as _, mod_cron as _, mod_docker as _, mod_journal as _, mod_network as _,
+        mod_os as _, mod_proc as _, mod_sar as _, mod_services as _, mod_storage as _,
+        mod_users as _,
+    };
+
+    #[test]
+    fn digest_text_without_findings_is_one_line() {
+        let report = Report::new(vec![Section::success("os", "Operating System", json!({}))]);
+        assert_eq!(
+            report.health_digest.to_text(false),
+            "overall: info, no findings\n"
+        );
+        assert_eq!(
+            report.health_digest.to_text(true),
+            "overall: \x1b[36minfo\x1b[0m, no findings\n"
+        );
+    }
+
+    #[test]
+    fn digest_text_lists_findings_worst_first() {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    { "mount_point": "/", "fs_type": "ext4", "operational": true, "usage_ratio": 0.97, "available_bytes": 100u64 << 30 }
+                ]
+            }),
+        );
+        let docker = Section::degraded(
+            "docker",
+            "Docker Containers",
+            "engine unavailable".to_string(),
+            json!({}),
+        );
+        let report = Report::new(vec![docker, storage]);
+        let text = report.health_digest.to_text(false);
+        let lines: Vec<&str> = text.lines().collect();
+        let count = report.health_digest.findings.len();
+        assert_eq!(lines.len(), count + 1);
+        assert_eq!(lines[0], format!("overall: critical, {} findings", count));
+        assert!(lines[1].starts_with("critical storage: "));
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.starts_with("warning docker: "))
+        );
+        assert!(!text.contains('\x1b'));
+        assert!(
+            report
+                .health_digest
+                .to_text(true)
+                .contains("\x1b[1;31mcritical\x1b[0m storage: ")
+        );
+    }
+
+    #[test]
+    fn default_digest_thresholds_match_updated_values() {
+        let thresholds = DigestThresholds::default();
+        assert_eq!(thresholds.disk_warning, 0.90);
+        assert_eq!(thresholds.disk_critical, 0.95);
+        assert_eq!(thresholds.memory_warning, 0.10);
+        assert_eq!(thresholds.memory_critical, 0.05);
+        assert_eq!(thresholds.free_space_min_fs_gib, 16.0);
+    }
+
+    #[test]
+    fn collect_report_filtered_runs_only_selected_collectors() {
+        let ctx = Context::new();
+        let report = collect_report_filtered(&ctx, DigestThresholds::default(), None, |metadata| {
+            matches!(metadata.id, "os" | "users")
+        });
+        let mut ids: Vec<&str> = report.sections.iter().map(|s| s.id.as_ref()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["os", "users"]);
+        assert_eq!(report.metadata.sections, 2);
+
+        let ids = registered_collector_ids();
+        assert!(ids.contains(&"storage"));
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        let storage = registered_collectors()
+            .into_iter()
+            .find(|metadata| metadata.id == "storage")
+            .expect("storage collector registered");
+        assert!(!storage.description.is_empty());
+    }
+
+    #[test]
+    fn collect_report_returns_sections() {
+        let ctx = Context::new();
+        let report = collect_report(&ctx);
+        assert!(!report.sections.is_empty());
+        assert!(report.sections.iter().any(|s| s.id == "os"));
+        assert!(
+            report
+                .sections
+                .iter()
+                .all(|s| !matches!(s.status, SectionStatus::Error))
+        );
+        assert_eq!(report.metadata.sections, report.sections.len());
+        let expected_overall = report
+            .health_digest
+            .findings
+            .iter()
+            .map(|f| f.severity)
+            .max()
+            .unwrap_or(Severity::Info);
+        assert_eq!(report.health_digest.overall, expected_overall);
+    }
+
+    #[test]
+    fn report_json_conforms_to_schema() {
+        let mut section = Section::success(
+            "demo",
+            "Demo Section",
+            json!({
+                "value": 42,
+            }),
+        );
+        section.summary = Some("Demo summary".to_string());
+
+        let report = Report::with_digest_config(vec![section], DigestThresholds::default());
+        let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
+        let document = report.to_json_value();
+
+        if let Err(errors) = compiled.validate(&document) {
+            let collected: Vec<String> = errors.map(|err| format!("{}", err)).collect();
+            panic!(
+                "report JSON did not match schema:\n{}",
+                collected.join("\n")
+            );
+        }
+
+        assert_eq!(document["schema_version"], SCHEMA_VERSION);
+        assert!(document.get("health_digest").is_some());
+        assert!(document["metadata"].get("health_digest").is_none());
+        assert_eq!(document, serde_json::to_value(&report).unwrap());
+    }
+
+    #[test]
+    fn host_fingerprint_requires_salt_and_is_stable_per_salt() {
+        let build = |salt: Option<&str>| {
+            let mut ctx = Context::new();
+            ctx.set_fingerprint_salt(salt);
+            build_report(&ctx, Vec::new(), DigestThresholds::default(), None)
+        };
+
+        let unsalted = build(None).to_json_value();
+        assert!(unsalted["metadata"].get("host_fingerprint").is_none());
+
+        let first = build(Some("fleet-a"));
+        let second = build(Some("fleet-a"));
+        let other = build(Some("fleet-b"));
+        let fingerprint = first
+            .metadata
+            .host_fingerprint
+            .clone()
+            .expect("host identity readable");
+        assert_eq!(second.metadata.host_fingerprint, Some(fingerprint.clone()));
+        assert_ne!(other.metadata.host_fingerprint, Some(fingerprint));
+
+        let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
+        assert!(compiled.is_valid(&first.to_json_value()));
+        assert!(compiled.is_valid(&unsalted));
+    }
+
+    #[test]
+    fn checksum_detects_single_byte_changes() {
+        let mut section = Section::success("demo", "Demo", json!({ "value": "abc", "n": 1.5 }));
+        section.summary = Some("ok".to_string());
+        let report = Report::new(vec![section]);
+        let serialized = serde_json::to_string_pretty(&report.to_json_value()).unwrap();
+
+        let parsed: Value = serde_json::from_str(&serialized).unwrap();
+        assert!(Report::verify_checksum(&parsed).unwrap());
+
+        let tampered = serialized.replacen("\"abc\"", "\"abd\"", 1);
+        let parsed: Value = serde_json::from_str(&tampered).unwrap();
+        assert!(!Report::verify_checksum(&parsed).unwrap());
+
+        let truncated: Value =
+            serde_json::from_str(&serialized.replacen("\"ok\"", "\"o\"", 1)).unwrap();
+        assert!(!Report::verify_checksum(&truncated).unwrap());
+    }
+
+    #[test]
+    fn checksum_survives_metadata_changes_and_tracks_redaction() {
+        let mut report = Report::new(vec![Section::success(
+            "users",
+            "Local Users",
+            json!({ "users": [{ "name": "alice", "system": false }] }),
+        )]);
+        report.metadata.set_generated_at(1);
+        assert!(Report::verify_checksum(&report.to_json_value()).unwrap());
+
+        let before = report.metadata.checksum.clone();
+        report.redact(&RedactionPolicy::all());
+        assert_ne!(report.metadata.checksum, before);
+        assert!(Report::verify_checksum(&report.to_json_value()).unwrap());
+    }
+
+    #[test]
+    fn sections_without_summary_conform_to_schema() {
+        let section = Section::success("demo", "Demo Section", json!({}));
+        let report = Report::new(vec![section]);
+        let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
+        assert!(compiled.is_valid(&report.to_json_value()));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legacy_json_layout_nests_digest_under_metadata() {
+        let report = Report::new(vec![Section::success("demo", "Demo", json!({}))]);
+        let legacy = report.to_json_value_v1();
+        assert!(legacy.get("schema_version").is_none());
+        assert!(legacy["metadata"].get("health_digest").is_some());
+    }
+
+    #[test]
+    fn timing_block_is_computed_during_collection() {
+        let mut ctx = Context::new();
+        ctx.set_slow_collector_threshold(std::time::Duration::ZERO);
+        let report = collect_report(&ctx);
+        let timing = report.metadata.timing.as_ref().expect("timing block");
+
+        assert_eq!(timing.slow_threshold_ms, 0);
+        assert!(timing.slowest.len() <= 3);
+        assert!(
+            timing
+                .slowest
+                .windows(2)
+                .all(|pair| pair[0].duration_ms >= pair[1].duration_ms)
+        );
+        let slowest = timing.slowest.first().map_or(0, |entry| entry.duration_ms);
+        assert!(timing.total_ms >= slowest);
+
+        let flagged: Vec<&Section> = report
+            .sections
+            .iter()
+            .filter(|section| section.duration_ms.unwrap_or(0) > 0)
+            .collect();
+        assert_eq!(timing.slow_collectors, flagged.len());
+        for section in flagged {
+            assert!(
+                section
+                    .notes
+                    .iter()
+                    .any(|note| note.starts_with("collector took ") && note.ends_with('s'))
+            );
+        }
+
+        let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
+        assert!(compiled.is_valid(&report.to_json_value()));
+    }
+
+    #[test]
+    fn timing_block_renders_at_the_bottom() {
+        let mut report = Report::new(vec![Section::success("demo", "Demo", json!({}))]);
+        report.metadata.timing = Some(CollectionTiming {
+            total_ms: 8_450,
+            slowest: vec![SectionTiming {
+                id: "demo".to_string(),
+                duration_ms: 8_200,
+            }],
+            slow_threshold_ms: 5_000,
+            slow_collectors: 1,
+            timed_out: vec!["docker".to_string()],
+        });
+
+        let md = report.to_markdown().expect("markdown");
+        let timing = md.find("## Timing").expect("timing heading");
+        assert!(timing > md.find("## Demo").unwrap());
+        assert!(md.contains("- **Total wall time:** 8.45s"));
+        assert!(md.contains("- **Slow collectors:** 1 (> 5.00s)"));
+        assert!(md.contains("- **Timed out:** docker"));
+        assert!(md.contains("| demo | 8.20s |"));
+
+        let html = report.to_html().expect("html");
+        let card = html.find("id=\"timing\"").expect("timing card");
+        assert!(card > html.find("id=\"demo\"").unwrap());
+        assert!(html.contains("<td><a href=\"#demo\">demo</a></td>"));
+
+        let plain = Report::new(Vec::new());
+        assert!(!plain.to_markdown().unwrap().contains("## Timing"));
+    }
+
+    fn sleepy_collectors(delay: Duration) -> Vec<Box<dyn Collector>> {
+        ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|id| {
+                Box::new(FakeCollector::new(id, SectionStatus::Success, json!({})).delayed(delay))
+                    as Box<dyn Collector>
+            })
+            .collect()
+    }
+
+    fn outcome_ids(results: &[(CollectorOutcome, u64)]) -> Vec<&str> {
+        results
+            .iter()
+            .map(|(outcome, _)| match outcome {
+                CollectorOutcome::Collected(section) => section.id.as_ref(),
+                _ => "unfinished",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collectors_run_in_parallel_and_keep_their_order() {
+        let delay = Duration::from_millis(300);
+        let mut ctx = Context::new();
+        ctx.set_max_jobs(NonZeroUsize::new(4));
+        let started = Instant::now();
+        let results = run_collectors(sleepy_collectors(delay), &ctx, None);
+        assert!(started.elapsed() < delay * 3);
+        assert_eq!(outcome_ids(&results), ["a", "b", "c", "d"]);
+        assert!(results.iter().all(|(_, elapsed)| *elapsed >= 300));
+
+        ctx.set_max_jobs(NonZeroUsize::new(1));
+        let started = Instant::now();
+        let results = run_collectors(sleepy_collectors(delay), &ctx, None);
+        assert!(started.elapsed() >= delay * 4);
+        assert_eq!(outcome_ids(&results), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn progress_reports_every_start_and_finish() {
+        let mut ctx = Context::new();
+        ctx.set_max_jobs(NonZeroUsize::new(4));
+        let events = Mutex::new(Vec::new());
+        let record = |event: CollectorEvent| events.lock().unwrap().push(event);
+        let results = run_collectors(sleepy_collectors(Duration::ZERO), &ctx, Some(&record));
+        assert_eq!(results.len(), 4);
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(events.len(), 8);
+        let started = events
+            .iter()
+            .filter(|event| matches!(event, CollectorEvent::Started { total: 4, .. }))
+            .count();
+        assert_eq!(started, 4);
+        let mut completed: Vec<usize> = events
+            .iter()
+            .filter_map(|event| match event {
+                CollectorEvent::Finished {
+                    status: SectionStatus::Success,
+                    timed_out: false,
+                    completed,
+                    total: 4,
+                    ..
+                } => Some(*completed),
+                _ => None,
+            })
+            .collect();
+        completed.sort_unstable();
+        assert_eq!(completed, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn job_count_is_capped_by_the_collectors() {
+        let mut ctx = Context::new();
+        assert!(collection_jobs(&ctx, 12) >= 1);
+        ctx.set_max_jobs(NonZeroUsize::new(2));
+        assert_eq!(collection_jobs(&ctx, 12), 2);
+        assert_eq!(collection_jobs(&ctx, 1), 1);
+        assert_eq!(collection_jobs(&ctx, 0), 1);
+    }
+
+    #[test]
+    fn collectors_over_their_timeout_are_abandoned() {
+        let ctx = Context::new();
+        let sleepy = FakeCollector::new("sleepy", SectionStatus::Success, json!({}));
+        let slow = Box::new(sleepy.clone().delayed(Duration::from_secs(5)));
+        let started = Instant::now();
+        let outcome = run_collector(slow, &ctx, Some(Duration::from_millis(50)));
+        assert!(matches!(outcome, CollectorOutcome::TimedOut(limit) if limit.as_millis() == 50));
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let quick = Box::new(sleepy);
+        let outcome = run_collector(quick, &ctx, Some(Duration::from_secs(5)));
+        assert!(matches!(outcome, CollectorOutcome::Collected(section) if section.id == "sleepy"));
+    }
+
+    #[test]
+    fn cancelled_collectors_return_partial_sections() {
+        let mut ctx = fake_context(vec![
+            fake("storage")
+                .delayed(Duration::from_secs(30))
+                .cancellable(),
+        ]);
+        ctx.set_collector_timeout(Some(Duration::from_millis(50)));
+        let started = Instant::now();
+        let report = collect_report(&ctx);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let storage = &report.sections[0];
+        assert_eq!(storage.status, SectionStatus::Degraded);
+        assert_eq!(
+            storage.summary.as_deref(),
+            Some("collector cancelled after 0.1s; results are partial")
+        );
+        assert!(storage.notes.iter().any(|note| note.contains("cancelled")));
+        let timing = report.metadata.timing.as_ref().expect("timing");
+        assert_eq!(timing.timed_out, ["storage"]);
+        assert!(!ctx.is_cancelled());
+    }
+
+    #[test]
+    fn collector_timeouts_prefer_per_collector_overrides() {
+        let mut ctx = Context::new();
+        assert_eq!(ctx.collector_timeout("docker"), None);
+        ctx.set_collector_timeout(Some(Duration::from_secs(30)));
+        ctx.set_collector_timeout_for("docker", Duration::from_secs(10));
+        assert_eq!(
+            ctx.collector_timeout("docker"),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(ctx.collector_timeout("os"), Some(Duration::from_secs(30)));
+
+        let mut ctx = fake_context(vec![
+            fake("os").delayed(Duration::from_secs(2)),
+            fake("users"),
+            fake("cron").delayed(Duration::from_secs(30)).cancellable(),
+        ]);
+        ctx.set_collector_timeout_for("os", Duration::ZERO);
+        ctx.set_collector_timeout_for("users", Duration::from_secs(30));
+        ctx.set_collector_timeout_for("cron", Duration::from_millis(50));
+        let report = collect_report(&ctx);
+        let timing = report.metadata.timing.as_ref().expect("timing");
+        // Cancelled collectors keep a partial section but still count as timed out.
+        assert_eq!(timing.timed_out, ["cron", "os"]);
+        let cron = report.sections.iter().find(|s| s.id == "cron").unwrap();
+        assert_eq!(cron.status, SectionStatus::Degraded);
+        let os = report.sections.iter().find(|s| s.id == "os").unwrap();
+        assert_eq!(os.status, SectionStatus::Error);
+        assert_eq!(
+            os.summary.as_deref(),
+            Some("collector timed out after 0.0s")
+        );
+        let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
+        assert!(compiled.is_valid(&report.to_json_value()));
+    }
+
+    /// Real collectors that read their host files through the context root,
+    /// run against a fixture root. The other section schemas are checked
+    /// against their collector's section builder in the module's own tests.
+    #[test]
+    fn collector_bodies_match_section_schemas() {
+        let root = tempfile::tempdir().unwrap();
+        let write = |path: &str, contents: &str| {
+            let path = root.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        };
+        write(
+            "etc/passwd",
+            "root:x:0:0:root:/root:/bin/bash\n\
+             daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin\n\
+             alice:x:1000:1000:Alice:/home/alice:/bin/zsh\n",
+        );
+        write("etc/group", "sudo:x:27:alice\n");
+        write("proc/loadavg", "0.50 0.40 0.30 2/123 4567\n");
+        write("proc/uptime", "7305.01 4644.53\n");
+        write(
+            "proc/swaps",
+            "Filename\tType\tSize\tUsed\tPriority\n/swap.img\tfile\t524284\t0\t-2\n",
+        );
+        let meminfo: String = [
+            ("MemTotal", 2048000),
+            ("MemFree", 512000),
+            ("MemAvailable", 1024000),
+            ("Buffers", 32000),
+            ("Cached", 400000),
+            ("SwapCached", 0),
+            ("Active", 600000),
+            ("Inactive", 500000),
+            ("Active(anon)", 300000),
+            ("Inactive(anon)", 100000),
+            ("Active(file)", 300000),
+            ("Inactive(file)", 400000),
+            ("Unevictable", 0),
+            ("Mlocked", 0),
+            ("SwapTotal", 524284),
+            ("SwapFree", 524284),
+            ("Dirty", 100),
+            ("Writeback", 0),
+            ("AnonPages", 400000),
+            ("Mapped", 100000),
+            ("Shmem", 2000),
+            ("KReclaimable", 50000),
+            ("Slab", 80000),
+            ("SReclaimable", 50000),
+            ("SUnreclaim", 30000),
+            ("KernelStack", 4000),
+            ("PageTables", 8000),
+            ("NFS_Unstable", 0),
+            ("Bounce", 0),
+            ("WritebackTmp", 0),
+            ("CommitLimit", 1548284),
+            ("Committed_AS", 900000),
+            ("VmallocTotal", 34359738367_u64),
+            ("VmallocUsed", 20000),
+            ("VmallocChunk", 0),
+            ("HugePages_Total", 0),
+            ("HugePages_Free", 0),
+            ("HugePages_Rsvd", 0),
+            ("HugePages_Surp", 0),
+            ("Hugepagesize", 2048),
+        ]
+        .iter()
+        .map(|(key, value)| {
+            let unit = if key.starts_with("HugePages_") {
+                ""
+            } else {
+                " kB"
+            };
+            format!("{}: {}{}\n", key, value, unit)
+        })
+        .collect();
+        write("proc/meminfo", &meminfo);
+
+        let mut ctx = Context::new();
+        ctx.set_root(Some(root.path()));
+        ctx.set_validate_schemas(true);
+        let (sections, _) = collect_sections(
+            &ctx,
+            &|metadata| matches!(metadata.id, "users" | "proc"),
+            None,
+        );
+        let ids: Vec<&str> = sections.iter().map(|section| section.id.as_ref()).collect();
+        assert_eq!(ids, ["proc", "users"]);
+
+        for section in &sections {
+            assert_ne!(
+                section.status,
+                SectionStatus::Error,
+                "{:?}",
+                section.summary
+            );
+            assert_section_matches_schema(section, schema::section_schema(&section.id).unwrap());
+            assert!(
+                !section
+                    .notes
+                    .iter()
+                    .any(|note| note.starts_with("body does not match")),
+                "{}: {:?}",
+                section.id,
+                section.notes
+            );
+        }
+        assert_eq!(sections[0].body["loadavg"]["one"], 0.5);
+        assert_eq!(sections[1].body["users"][2]["name"], "alice");
+    }
+
+    /// Context collecting only from `collectors`.
+    fn fake_context(collectors: Vec<FakeCollector>) -> Context {
+        let mut registry = vmic_sdk::CollectorRegistry::new();
+        for collector in collectors {
+            registry.register(move || collector.clone());
+        }
+        let mut ctx = Context::new();
+        ctx.set_registry(Some(registry));
+        ctx
+    }
+
+    fn fake(id: &'static str) -> FakeCollector {
+        FakeCollector::new(id, SectionStatus::Success, json!({}))
+    }
+
+    #[test]
+    fn collectors_are_scheduled_and_reported_by_order_hint() {
+        let mut ctx = fake_context(vec![
+            fake("users").ordered(100),
+            fake("os").ordered(10),
+            fake("storage").ordered(30),
+            fake("proc").ordered(20),
+            fake("extra").ordered(20),
+        ]);
+        ctx.set_max_jobs(NonZeroUsize::new(1));
+        let started = Mutex::new(Vec::new());
+        let record = |event: CollectorEvent| {
+            if let CollectorEvent::Started { id, .. } = event {
+                started.lock().unwrap().push(id);
+            }
+        };
+        let (sections, _) =
+            collect_sections(&ctx, &|metadata| metadata.id != "extra", Some(&record));
+
+        let expected = ["os", "proc", "storage", "users"];
+        assert_eq!(started.into_inner().unwrap(), expected);
+        let ids: Vec<&str> = sections.iter().map(|section| section.id.as_ref()).collect();
+        assert_eq!(ids, expected);
+
+        ctx.set_section_order(["users"]);
+        let (sections, _) = collect_sections(&ctx, &|_| true, None);
+        let ids: Vec<&str> = sections.iter().map(|section| section.id.as_ref()).collect();
+        assert_eq!(ids, ["users", "os", "extra", "proc", "storage"]);
+    }
+
+    #[test]
+    fn collection_uses_the_context_registry() {
+        let ctx = fake_context(vec![
+            FakeCollector::new("disk", SectionStatus::Degraded, json!({ "free": 1 }))
+                .titled("Disk"),
+            fake("broken").failing("exit status 2"),
+            fake("fine"),
+        ]);
+        let report = collect_report(&ctx);
+
+        let ids: Vec<&str> = report
+            .sections
+            .iter()
+            .map(|section| section.id.as_ref())
+            .collect();
+        assert_eq!(ids, ["broken", "disk", "fine"]);
+        assert_eq!(report.sections[0].status, SectionStatus::Error);
+        assert_eq!(report.sections[0].body["error"], "exit status 2");
+        assert_section_golden(
+            &report.sections[1],
+            &json!({
+                "id": "disk",
+                "title": "Disk",
+                "description": "Fake collector for tests",
+                "collector_version": "0.0.0-test",
+                "cost": { "cpu": "low", "io": "low", "spawns_processes": false },
+                "status": "degraded",
+                "summary": "disk degraded",
+                "body": { "free": 1 },
+                "notes": []
+            }),
+        );
+        assert!(
+            report
+                .health_digest
+                .findings
+                .iter()
+                .any(|finding| finding.message.contains("disk degraded"))
+        );
+        let probed: Vec<&str> = probe_collectors(&ctx)
+            .iter()
+            .map(|(metadata, _)| metadata.id)
+            .collect();
+        assert_eq!(probed, ["broken", "disk", "fine"]);
+    }
+
+    #[test]
+    fn detail_level_is_recorded_and_shown_only_when_not_standard() {
+        let mut ctx = fake_context(vec![fake("disk")]);
+        let standard = collect_report(&ctx);
+        assert_eq!(standard.metadata.detail_level, Some(DetailLevel::Standard));
+        assert!(!standard.to_markdown().unwrap().contains("Detail level"));
+
+        ctx.set_detail_level(DetailLevel::Minimal);
+        let report = collect_report(&ctx);
+        let document = serde_json::to_value(&report).unwrap();
+        assert_eq!(document["metadata"]["detail_level"], "minimal");
+        assert!(
+            report
+                .to_markdown()
+                .unwrap()
+                .contains("Detail level: minimal")
+        );
+        assert!(report.to_html().unwrap().contains("Detail level: minimal"));
+
+        let reread = Report::from_json_value(&document).unwrap();
+        assert_eq!(reread.metadata.detail_level, Some(DetailLevel::Minimal));
+        let mut legacy = document;
+        legacy["metadata"]
+            .as_object_mut()
+            .unwrap()
+            .remove("detail_level");
+        assert_eq!(
+            Report::from_json_value(&legacy)
+                .unwrap()
+                .metadata
+                .detail_level,
+            None
+        );
+    }
+
+    #[test]
+    fn collectors_read_host_files_under_the_context_root() {
+        let root = tempfile::tempdir().unwrap();
+        let etc = root.path().join("etc");
+        std::fs::create_dir_all(etc.join("cron.d")).unwrap();
+        std::fs::write(
+            etc.join("passwd"),
+            "root:x:0:0:root:/root:/bin/bash\n\
+             daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin\n\
+             alice:x:1000:1000:Alice:/home/alice:/bin/zsh\n",
+        )
+        .unwrap();
+        std::fs::write(etc.join("group"), "sudo:x:27:alice\n").unwrap();
+        std::fs::write(
+            etc.join("crontab"),
+            "# m h dom mon dow user command\n17 * * * * root run-parts /etc/cron.hourly\n",
+        )
+        .unwrap();
+        std::fs::write(
+            etc.join("cron.d").join("backup"),
+            "0 3 * * * root /usr/local/bin/backup\n",
+        )
+        .unwrap();
+
+        let mut ctx = Context::new();
+        ctx.set_root(Some(root.path()));
+        let (sections, _) = collect_sections(
+            &ctx,
+            &|metadata| matches!(metadata.id, "users" | "cron"),
+            None,
+        );
+
+        let cron = &sections[0];
+        assert_eq!(cron.id, "cron");
+        assert_eq!(cron.status, SectionStatus::Success);
+        assert_eq!(
+            cron.body,
+            json!({
+                "system_crontab": [
+                    {"schedule": "17 * * * *", "user": "root", "command": "run-parts /etc/cron.hourly"}
+                ],
+                "cron_d": [{
+                    "path": "/etc/cron.d/backup",
+                    "entries": [
+                        {"schedule": "0 3 * * *", "user": "root", "command": "/usr/local/bin/backup"}
+                    ]
+                }]
+            })
+        );
+
+        let users = &sections[1];
+        assert_eq!(users.id, "users");
+        assert_eq!(users.status, SectionStatus::Success);
+        assert_eq!(
+            users.body,
+            json!({
+                "users": [
+                    {"name": "root", "uid": 0, "gid": 0, "home": "/root", "shell": "/bin/bash",
+                     "system": true, "interactive": true, "sudo": false},
+                    {"name": "daemon", "uid": 1, "gid": 1, "home": "/usr/sbin",
+                     "shell": "/usr/sbin/nologin", "system": true, "interactive": false, "sudo": false},
+                    {"name": "alice", "uid": 1000, "gid": 1000, "home": "/home/alice",
+                     "shell": "/bin/zsh", "system": false, "interactive": true, "sudo": true}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn unavailable_collectors_are_replaced_by_skipped_stubs() {
+        let mut ctx = fake_context(vec![
+            fake("ready"),
+            fake("slow").probed(Availability::Degraded("slow disk".to_string())),
+            fake("missing").probed(Availability::Unavailable("tool not found".to_string())),
+        ]);
+        ctx.set_skip_unavailable(true);
+        let report = collect_report(&ctx);
+
+        let ids: Vec<&str> = report
+            .sections
+            .iter()
+            .map(|section| section.id.as_ref())
+            .collect();
+        assert_eq!(ids, ["missing", "ready", "slow"]);
+        let missing = &report.sections[0];
+        assert_eq!(missing.status, SectionStatus::Degraded);
+        assert_eq!(missing.duration_ms, None);
+        assert_eq!(missing.summary.as_deref(), Some("skipped: tool not found"));
+        assert_eq!(
+            missing.body,
+            json!({ "skipped": true, "reason": "tool not found" })
+        );
+        for section in &report.sections[1..] {
+            assert_eq!(section.status, SectionStatus::Success, "{}", section.id);
+            assert!(section.body.get("skipped").is_none());
+        }
+        let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
+        assert!(compiled.is_valid(&report.to_json_value()));
+    }
+
+    #[test]
+    fn skipped_section_keeps_collector_metadata() {
+        let metadata = FakeCollector::new("sar", SectionStatus::Success, json!({})).metadata();
+        let mut ctx = Context::new();
+        ctx.set_locale(Locale::Ru);
+        let section = skipped_section(&ctx, &metadata, "sar not found in PATH".to_string());
+
+        assert_eq!(section.status, SectionStatus::Degraded);
+        assert_eq!(
+            section.summary.as_deref(),
+            Some("skipped: sar not found in PATH")
+        );
+        assert_eq!(section.body["reason"], "sar not found in PATH");
+        assert_eq!(section.description, Some("Fake collector for tests"));
+        assert_eq!(section.title, "Метрики sysstat");
+    }
+
+    #[test]
+    fn markdown_render_contains_section_title() {
+        let ctx = Context::new();
+        let report = collect_report(&ctx);
+        let md = report.to_markdown().expect("markdown render");
+        assert!(md.contains("# System Report"));
+        assert!(md.contains("Critical Health Digest"));
+    }
+
+    #[test]
+    fn html_render_contains_structure() {
+        let ctx = Context::new();
+        let report = collect_report(&ctx);
+        let html = report.to_html().expect("html render");
+        assert!(html.contains("<!DOCTYPE html>"));
+        assert!(html.contains("System Report"));
+        assert!(html.contains("<nav class=\"toc\""));
+        assert!(html.contains("class=\"card digest status-"));
+        assert!(html.contains("section-summary"));
+        assert!(html.contains("Back to top"));
+    }
+
+    #[test]
+    fn html_renders_security_sar_and_hotspots() {
+        let security = Section::degraded(
+            "security",
+            "Security Posture",
+            "1 potential security issues".to_string(),
+            json!({
+                "sshd": {
+                    "hardening_present": false,
+                    "findings": [
+                        { "message": "PermitRootLogin enabled", "severity": "critical" }
+                    ]
+                },
+                "sudoers": { "includes_dir": true, "findings": [] },
+                "cgroups": { "unified_hierarchy": true, "controllers": ["cpu", "memory"], "findings": [] }
+            }),
+        );
+        let sar = Section::success(
+            "sar",
+            "Sysstat Metrics",
+            json!({
+                "cpu": { "user": 0.8, "nice": 0.0, "system": 1.5, "iowait": 0.1, "steal": 0.0, "idle": 97.6 }
+            }),
+        );
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [],
+                "hotspots": {
+                    "directories": [{ "path": "/var/lib/docker", "size_bytes": 2_147_483_648u64 }],
+                    "logs": [{ "path": "/var/log/syslog", "size_bytes": 1_048_576u64 }]
+                }
+            }),
+        );
+
+        let html = Report::new(vec![security, sar, storage])
+            .to_html()
+            .expect("html");
+        assert!(html.contains("Security Findings"));
+        assert!(html.contains("row-critical"));
+        assert!(html.contains("PermitRootLogin enabled"));
+        assert!(html.contains("cpu, memory"));
+        assert!(html.contains("CPU Averages"));
+        assert!(html.contains("97.60%"));
+        assert!(html.contains("Largest Directories"));
+        assert!(html.contains("2.0 GiB"));
+        assert!(html.contains("/var/log/syslog"));
+        assert!(!html.contains("sshd: "));
+    }
+
+    #[test]
+    fn sections_with_run_time_ids_render_and_survive_json() {
+        let sections: Vec<Section> = ["sda", "sdb"]
+            .into_iter()
+            .map(|disk| {
+                Section::success_owned(
+                    format!("disk-{disk}"),
+                    format!("Disk {disk}"),
+                    json!({ "device": disk }),
+                )
+            })
+            .collect();
+        let report = Report::new(sections);
+
+        let markdown = report.to_markdown().unwrap();
+        assert!(markdown.contains("Disk sda") && markdown.contains("Disk sdb"));
+        assert!(report.to_html().unwrap().contains("id=\"disk-sdb\""));
+
+        let reloaded = Report::from_json_value(&report.to_json_value()).unwrap();
+        let ids: Vec<&str> = reloaded
+            .sections
+            .iter()
+            .map(|section| section.id.as_ref())
+            .collect();
+        assert_eq!(ids, ["disk-sda", "disk-sdb"]);
+        assert!(Report::verify_checksum(&reloaded.to_json_value()).unwrap());
+    }
+
+    #[test]
+    fn subsections_render_under_anchored_sub_headers_and_survive_json() {
+        let mut storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({ "operating_mounts": [] }),
+        );
+        storage.add_subsection(SubSection::new(
+            "docker",
+            "Docker storage",
+            json!({
+                "data_root": "/var/lib/docker",
+                "total_bytes": 3_221_225_472u64,
+                "overlay_bytes": 0,
+                "container_logs_bytes": 0,
+                "volumes_bytes": 0
+            }),
+        ));
+        storage.add_subsection(SubSection::new(
+            "hotspots",
+            "Hotspots",
+            json!({
+                "directories": [{ "path": "/srv/cache", "size_bytes": 2_147_483_648u64 }],
+                "logs": []
+            }),
+        ));
+        let mut custom = Section::success("custom", "Custom", json!({}));
+        let mut queue = SubSection::new("queue", "Queue", json!({}));
+        let mut jobs = vmic_sdk::Table::new(["Job", "State"]).titled("Jobs");
+        jobs.push_row(["nightly", "waiting"]);
+        queue.add_table(jobs);
+        custom.add_subsection(queue);
+        let report = Report::new(vec![storage, custom]);
+
+        let html = report.to_html().expect("html");
+        assert!(html.contains("<a href=\"#storage-docker\">Docker storage</a>"));
+        assert!(html.contains("<div class=\"subsection\" id=\"storage-hotspots\">"));
+        assert!(html.contains("<dd>3.0 GiB</dd>"));
+        assert!(html.contains("<h4>Largest Directories</h4>"));
+        assert!(html.contains("<h4>Jobs</h4>"));
+
+        let markdown = report.to_markdown().expect("markdown");
+        assert!(markdown.contains("### Docker storage\n"));
+        assert!(markdown.contains("- **Docker total:** 3.0 GiB"));
+        assert!(markdown.contains("#### Largest Directories"));
+        assert!(markdown.contains("### Queue\n"));
+        assert!(markdown.contains("| nightly | waiting |"));
+
+        let json = report.to_json_value();
+        assert_eq!(json["sections"][0]["subsections"][1]["id"], "hotspots");
+        let reloaded = Report::from_json_value(&json).expect("reload");
+        let hotspots = reloaded.sections[0]
+            .subsection("hotspots")
+            .expect("hotspots");
+        assert_eq!(hotspots.title, "Hotspots");
+        assert_eq!(hotspots.body["directories"][0]["path"], json!("/srv/cache"));
+        let rerendered = reloaded.to_markdown().expect("markdown");
+        assert!(rerendered.contains("| /srv/cache | 2.0 GiB |"));
+    }
+
+    #[test]
+    fn html_renders_usage_bars_for_mounts_memory_and_containers() {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    { "mount_point": "/data", "fs_type": "ext4", "usage_ratio": 0.95, "inodes_usage_ratio": 0.42 },
+                    { "mount_point": "/", "fs_type": "ext4", "usage_ratio": 0.5 }
+                ]
+            }),
+        );
+        let proc = Section::success(
+            "proc",
+            "Processes and Resources",
+            json!({
+                "memory": { "host": { "total_bytes": 1024, "available_bytes": 128, "usage_ratio": 0.875 } }
+            }),
+        );
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [
+                    { "name": "web", "image": "nginx", "state": "running", "metrics": { "memory_percent": 12.5 } },
+                    { "name": "job", "image": "busybox", "state": "exited" }
+                ]
+            }),
+        );
+
+        let report = Report::new(vec![storage, proc, docker]);
+        let html = report.to_html().expect("html");
+        assert!(html.contains("usage-bar bar-critical\"><span style=\"width: 95.0%\">"));
+        assert!(html.contains("usage-bar bar-ok\"><span style=\"width: 42.0%\">"));
+        assert!(html.contains("usage-bar bar-warning\"><span style=\"width: 87.5%\">"));
+        assert!(html.contains("<th>Memory</th>"));
+        assert!(html.contains("<span style=\"width: 12.5%\"></span></span>12.5%"));
+        assert!(html.contains("<td>-</td>"));
+
+        let md = report.to_markdown().expect("markdown");
+        assert!(!md.contains("usage-bar"));
+    }
+
+    #[test]
+    fn docker_ports_render_published_and_host_network_containers() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [
+                    {
+                        "id": "a", "names": ["web"], "image": "nginx", "state": "running", "mounts": [],
+                        "metrics": { "memory_percent": 10.0 },
+                        "ports": [
+                            { "container_port": 80, "protocol": "tcp", "host_ip": "0.0.0.0", "host_port": 8080 },
+                            { "container_port": 80, "protocol": "tcp", "host_ip": "::", "host_port": 8080 },
+                            { "container_port": 443, "protocol": "tcp", "host_ip": null, "host_port": null }
+                        ]
+                    },
+                    {
+                        "id": "b", "names": ["db"], "image": "postgres", "state": "running", "mounts": [],
+                        "ports": [
+                            { "container_port": 5432, "protocol": "tcp", "host_ip": "127.0.0.1", "host_port": 5432 }
+                        ]
+                    },
+                    { "id": "c", "names": ["proxy"], "image": "traefik", "state": "running", "mounts": [], "ports": [], "network_mode": "host" },
+                    { "id": "d", "names": ["job"], "image": "busybox", "state": "exited", "mounts": [], "ports": [] }
+                ]
+            }),
+        );
+
+        let report = Report::new(vec![docker]);
+        let md = report.to_markdown().expect("markdown");
+        assert!(md.contains("| Name | Image | State | Ports | Memory |"));
+        assert!(md.contains("| web | nginx | running | *:8080->80/tcp (all interfaces) | 10.0% |"));
+        assert!(md.contains("| db | postgres | running | 127.0.0.1:5432->5432/tcp | - |"));
+        assert!(md.contains("| proxy | traefik | running | host network | - |"));
+        assert!(md.contains("| job | busybox | exited | - | - |"));
+        assert!(md.contains("- **Ports on all interfaces:** 1"));
+
+        let html = report.to_html().expect("html");
+        assert!(html.contains("<span style=\"width: 10.0%\"></span></span>10.0%"));
+    }
+
+    #[test]
+    fn docker_compose_projects_render_above_containers() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [
+                    { "id": "a", "names": ["shop-web-1"], "image": "nginx", "state": "running", "mounts": [], "compose_project": "shop" },
+                    { "id": "b", "names": ["cache"], "image": "redis", "state": "exited", "mounts": [] }
+                ],
+                "compose_projects": [
+                    { "name": "shop", "containers": 1, "not_running": 0, "memory_usage_bytes": 2048 },
+                    { "name": "(standalone)", "containers": 1, "not_running": 1, "memory_usage_bytes": null }
+                ]
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        let projects = md.find("| Project | Containers | Not running | Memory |");
+        let containers = md.find("| Name | Image | State |");
+        assert!(projects.is_some() && projects < containers);
+        assert!(md.contains("| shop | 1 | 0 | 2.0 KiB |"));
+        assert!(md.contains("| (standalone) | 1 | 1 | - |"));
+
+        let standalone_only = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [{ "id": "b", "names": ["cache"], "image": "redis", "state": "running", "mounts": [] }],
+                "compose_projects": [{ "name": "(standalone)", "containers": 1, "not_running": 0 }]
+            }),
+        );
+        let md = Report::new(vec![standalone_only])
+            .to_markdown()
+            .expect("markdown");
+        assert!(!md.contains("Compose projects"));
+    }
+
+    #[test]
+    fn docker_uptime_column_and_failing_health_checks_render() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [
+                    {
+                        "id": "a", "names": ["api"], "image": "api", "state": "running", "mounts": [],
+                        "started_at": "2025-03-02T09:00:00Z", "uptime_seconds": 600, "uptime": "up 10m",
+                        "last_health_check": { "exit_code": 1, "output": "curl: (7) connection refused", "output_truncated": false, "finished_at": "2025-03-02T09:09:55Z" }
+                    },
+                    {
+                        "id": "b", "names": ["web"], "image": "nginx", "state": "running", "mounts": [],
+                        "uptime": "up 2d",
+                        "last_health_check": { "exit_code": 0, "output": "ok", "output_truncated": false }
+                    },
+                    { "id": "c", "names": ["job"], "image": "busybox", "state": "exited", "mounts": [] }
+                ]
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("| Name | Image | State | Uptime |"));
+        assert!(md.contains("| api | api | running | up 10m |"));
+        assert!(md.contains("| job | busybox | exited | - |"));
+        assert!(md.contains("| Name | Exit code | Output | Time |"));
+        assert!(md.contains("| api | 1 | curl: (7) connection refused | 2025-03-02T09:09:55Z |"));
+        assert!(!md.contains("| web | 0 |"));
+    }
+
+    #[test]
+    fn docker_daemon_settings_render_as_key_values() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [],
+                "daemon": {
+                    "storage_driver": "overlay2",
+                    "cgroup_driver": "systemd",
+                    "live_restore": false,
+                    "userns_remap": null,
+                    "insecure_registries": ["registry.local:5000"],
+                    "log_driver": "json-file",
+                    "log_opts": { "max-size": "10m", "max-file": "3" },
+                    "tcp_hosts_without_tls": ["tcp://0.0.0.0:2375"],
+                    "api_tcp_without_tls": true,
+                    "config_read": true
+                }
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Storage driver:** overlay2"));
+        assert!(md.contains("- **Cgroup driver:** systemd"));
+        assert!(md.contains("- **Live restore:** no"));
+        assert!(md.contains("- **Userns remap:** off"));
+        assert!(md.contains("- **Default log driver:** json-file (max-size 10m, max-file 3)"));
+        assert!(md.contains("- **Insecure registries:** registry.local:5000"));
+        assert!(md.contains("- **API over TCP without TLS:** tcp://0.0.0.0:2375"));
+    }
+
+    #[test]
+    fn docker_swarm_services_render_replicas_on_managers_only() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [],
+                "swarm": {
+                    "status": "active",
+                    "role": "manager",
+                    "nodes": 3,
+                    "managers": 1,
+                    "services": [
+                        { "name": "agent", "mode": "global", "desired": 3, "running": 3, "image": "agent:2" },
+                        { "name": "web", "mode": "replicated", "desired": 3, "running": 1, "image": "nginx:1.27" }
+                    ]
+                }
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Swarm:** active (manager, nodes 3, managers 1)"));
+        assert!(md.contains("| Service | Mode | Replicas | Image |"));
+        assert!(md.contains("| web | replicated | 1/3 | nginx:1.27 |"));
+
+        let inactive = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({ "containers": [], "swarm": { "status": "inactive" } }),
+        );
+        let md = Report::new(vec![inactive]).to_markdown().expect("markdown");
+        assert!(!md.contains("Swarm"));
+    }
+
+    #[test]
+    fn docker_privileged_containers_get_a_priv_marker() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [
+                    { "id": "a", "names": ["agent"], "image": "agent", "state": "running", "mounts": [], "security": { "privileged": true, "cap_add": [], "security_opt": [] } },
+                    { "id": "b", "names": ["web"], "image": "nginx", "state": "running", "mounts": [], "security": { "privileged": false, "cap_add": [], "security_opt": [] } }
+                ]
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("| Name | Image | State | Priv |"));
+        assert!(md.contains("| agent | agent | running | priv |"));
+        assert!(md.contains("| web | nginx | running | - |"));
+
+        let uninspected = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({ "containers": [{ "id": "b", "names": ["web"], "image": "nginx", "state": "running", "mounts": [] }] }),
+        );
+        let md = Report::new(vec![uninspected])
+            .to_markdown()
+            .expect("markdown");
+        assert!(!md.contains("Priv"));
+    }
+
+    #[test]
+    fn docker_sensitive_env_var_names_render_per_container() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [
+                    { "id": "a", "names": ["db"], "image": "postgres", "state": "running", "mounts": [], "sensitive_env_vars": ["POSTGRES_PASSWORD"] },
+                    { "id": "b", "names": ["web"], "image": "nginx", "state": "running", "mounts": [], "sensitive_env_vars": [] }
+                ]
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Secret-looking env vars:** db (POSTGRES_PASSWORD)"));
+        assert!(!md.contains("web ("));
+    }
+
+    #[test]
+    fn docker_summarized_containers_render_counts_by_state_and_image() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [{ "id": "a", "names": ["db"], "image": "postgres", "state": "exited", "mounts": [] }],
+                "summarized_containers": {
+                    "count": 3,
+                    "by_state": { "exited": 1, "running": 2 },
+                    "by_image": { "nginx": 2, "worker": 1 }
+                }
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Summarized containers:** 3 (running 2, exited 1)"));
+        assert!(md.contains("| Image | Containers |"));
+        assert!(md.contains("| nginx | 2 |"));
+        assert!(md.contains("| worker | 1 |"));
+    }
+
+    #[test]
+    fn docker_images_render_top_ten_and_reclaimable_space() {
+        let images: Vec<Value> = (0..12)
+            .map(|index| {
+                json!({
+                    "id": format!("sha256:{index}"),
+                    "reference": if index == 1 { "<none>".to_string() } else { format!("app:{index}") },
+                    "size_bytes": (12 - index) * 1024 * 1024,
+                    "created": "2024-01-01T00:00:00Z",
+                    "age_days": 30,
+                    "in_use": index == 0,
+                    "dangling": index == 1
+                })
+            })
+            .collect();
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [],
+                "storage": {
+                    "image_total_bytes": 78 * 1024 * 1024,
+                    "image_count": 12,
+                    "volume_count": 0,
+                    "dangling_image_count": 1,
+                    "reclaimable_image_bytes": 66 * 1024 * 1024,
+                    "images": images
+                }
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Reclaimable image space:** 66.0 MiB"));
+        assert!(md.contains("- **Dangling images:** 1"));
+        assert!(md.contains("| Image | Size | Age | In use |"));
+        assert!(md.contains("| app:0 | 12.0 MiB | 30d | yes |"));
+        assert!(md.contains("| <none> | 11.0 MiB | 30d | no |"));
+        assert!(md.contains("app:9"));
+        assert!(!md.contains("app:10"));
+        assert!(!md.contains("Build cache"));
+    }
+
+    #[test]
+    fn docker_storage_renders_disk_usage_and_build_cache() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [],
+                "storage": {
+                    "image_count": 0,
+                    "volume_count": 0,
+                    "containers_size_bytes": 3 * 1024 * 1024,
+                    "images_reclaimable_bytes": 512 * 1024 * 1024,
+                    "build_cache_total_bytes": 4 * 1024 * 1024 * 1024_u64,
+                    "build_cache_reclaimable_bytes": 3 * 1024 * 1024 * 1024_u64,
+                    "data_root": "/var/lib/docker",
+                    "data_root_filesystem_bytes": 50 * 1024 * 1024 * 1024_u64
+                }
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Reclaimable image layers:** 512.0 MiB"));
+        assert!(md.contains("- **Container writable layers:** 3.0 MiB"));
+        assert!(md.contains("- **Build cache:** 4.0 GiB (3.0 GiB reclaimable)"));
+        assert!(md.contains("- **Data root:** /var/lib/docker (50.0 GiB filesystem)"));
+    }
+
+    #[test]
+    fn docker_restart_policy_and_log_driver_render_as_columns() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [
+                    {
+                        "id": "a", "names": ["web"], "image": "nginx", "state": "running", "mounts": [],
+                        "restart_policy": "always", "log_driver": "json-file", "log_options": {}
+                    },
+                    {
+                        "id": "b", "names": ["api"], "image": "api", "state": "running", "mounts": [],
+                        "restart_policy": "on-failure:5", "log_driver": "json-file",
+                        "log_options": { "max-size": "10m", "max-file": "3", "compress": "true" }
+                    },
+                    { "id": "c", "names": ["job"], "image": "busybox", "state": "exited", "mounts": [] }
+                ]
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("| Name | Image | State | Restart | Log driver |"));
+        assert!(md.contains("| web | nginx | running | always | json-file |"));
+        assert!(md.contains(
+            "| api | api | running | on-failure:5 | json-file (max-size 10m, max-file 3) |"
+        ));
+        assert!(md.contains("| job | busybox | exited | - | - |"));
+    }
+
+    #[test]
+    fn docker_events_render_counts_and_recent_entries() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "containers": [],
+                "events": {
+                    "since": "2025-03-01T09:45:00Z",
+                    "total": 3,
+                    "counts": { "container die": 2, "container oom": 1 },
+                    "recent": [
+                        { "time": "2025-03-01T09:58:00Z", "type": "container", "action": "die", "object": "db", "exit_code": 137 },
+                        { "time": "2025-03-01T09:57:59Z", "type": "container", "action": "oom", "object": "db", "exit_code": null }
+                    ]
+                }
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Engine events:** 3 since 2025-03-01T09:45:00Z"));
+        assert!(md.contains("| container die | 2 |\n| container oom | 1 |"));
+        assert!(md.contains("| Time | Type | Action | Object | Exit code |"));
+        assert!(md.contains("| 2025-03-01T09:58:00Z | container | die | db | 137 |"));
+        assert!(md.contains("| 2025-03-01T09:57:59Z | container | oom | db | - |"));
+
+        let quiet = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({ "containers": [], "events": { "since": "2025-03-01T09:45:00Z", "total": 0, "counts": {}, "recent": [] } }),
+        );
+        let md = Report::new(vec![quiet]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Engine events:** 0 since 2025-03-01T09:45:00Z"));
+        assert!(!md.contains("Recent events"));
+    }
+
+    #[test]
+    fn docker_networks_render_as_a_table() {
+        let docker = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "engine": { "version": "5.4.0", "api_version": "1.41", "endpoint": "unix:///run/podman/podman.sock" },
+                "containers": [],
+                "networks": [
+                    { "name": "bridge", "driver": "bridge", "scope": "local", "subnets": ["172.17.0.0/16"], "internal": false, "containers": 2 },
+                    { "name": "none", "driver": "null", "scope": "local", "subnets": [], "internal": false, "containers": 0 },
+                    { "name": "vault", "driver": "bridge", "scope": "local", "subnets": ["10.1.0.0/24", "fd00::/64"], "internal": true, "containers": 1 }
+                ]
+            }),
+        );
+
+        let md = Report::new(vec![docker]).to_markdown().expect("markdown");
+        assert!(md.contains("- **Endpoint:** unix:///run/podman/podman.sock"));
+        assert!(md.contains("| Name | Driver | Scope | Subnets | Internal | Containers |"));
+        assert!(md.contains("| bridge | bridge | local | 172.17.0.0/16 | no | 2 |"));
+        assert!(md.contains("| none | null | local | - | no | 0 |"));
+        assert!(md.contains("| vault | bridge | local | 10.1.0.0/24, fd00::/64 | yes | 1 |"));
+    }
+
+    #[test]
+    fn collector_errors_keep_the_full_cause_chain() {
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "permission denied");
+        let error = anyhow::Error::new(io)
+            .context("failed to read /proc/mounts")
+            .context("storage inventory failed");
+        let section = Section::from_error("storage", "Storage Overview", &error);
+
+        assert_eq!(section.summary.as_deref(), Some("storage inventory failed"));
+        assert_eq!(
+            section.body["error_chain"],
+            json!([
+                "storage inventory failed",
+                "failed to read /proc/mounts",
+                "permission denied"
+            ])
+        );
+        assert_eq!(section.body["error_kind"], json!("permission_denied"));
+
+        let report = Report::new(vec![section]);
+        let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
+        assert!(compiled.is_valid(&report.to_json_value()));
+
+        let html = report.to_html().expect("html");
+        assert!(html.contains("Error chain"));
+        for layer in [
+            "<li>storage inventory failed</li>",
+            "<li>failed to read /proc/mounts</li>",
+            "<li>permission denied</li>",
+        ] {
+            assert!(html.contains(layer), "missing {layer}");
+        }
+        assert!(html.contains("permission_denied"));
     }
 
     #[test]
-    fn report_json_conforms_to_schema() {
-        let mut section = Section::success(
-            "demo",
-            "Demo Section",
-            json!({
-                "value": 42,
-            }),
-        );
-        section.summary = Some("Demo summary".to_string());
+    fn collector_descriptions_reach_json_html_and_markdown() {
+        let ctx = Context::new();
+        let report = collect_report(&ctx);
+        let storage = report
+            .sections
+            .iter()
+            .find(|section| section.id == "storage")
+            .expect("storage section");
+        let description = storage.description.expect("collector description");
 
-        let report = Report::with_digest_config(vec![section], DigestThresholds::default());
         let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
         let document = report.to_json_value();
+        assert!(compiled.is_valid(&document));
+        assert!(
+            document["sections"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|section| section["description"].is_string())
+        );
 
-        if let Err(errors) = compiled.validate(&document) {
-            let collected: Vec<String> = errors.map(|err| format!("{}", err)).collect();
-            panic!(
-                "report JSON did not match schema:\n{}",
-                collected.join("\n")
-            );
-        }
+        let html = report.to_html().expect("html");
+        assert!(html.contains(&format!(
+            "<p class=\"section-description\">{}</p>",
+            description
+        )));
+        let md = report.to_markdown().expect("markdown");
+        assert!(md.contains(&format!(
+            "## {}\n\n> {}\n\nStatus: ",
+            storage.title, description
+        )));
+
+        let manual = Section::success("demo", "Demo", json!({}));
+        assert!(manual.description.is_none());
+        let manual_report = Report::new(vec![manual]);
+        assert!(
+            manual_report.to_json_value()["sections"][0]
+                .get("description")
+                .is_none()
+        );
+        assert!(
+            !manual_report
+                .to_html()
+                .unwrap()
+                .contains("section-description\">")
+        );
     }
 
+    #[cfg(feature = "async")]
     #[test]
-    fn markdown_render_contains_section_title() {
+    fn collection_installs_one_shared_runtime() {
         let ctx = Context::new();
-        let report = collect_report(&ctx);
-        let md = report.to_markdown().expect("markdown render");
-        assert!(md.contains("# System Report"));
-        assert!(md.contains("Critical Health Digest"));
+        let shared = with_shared_runtime(&ctx);
+        assert!(shared.runtime().is_some());
+        let worker = shared
+            .block_on(async {
+                tokio::spawn(async { std::thread::current().name().map(str::to_string) })
+                    .await
+                    .unwrap()
+            })
+            .unwrap();
+        assert_eq!(worker.as_deref(), Some("vmic-async"));
+
+        let own = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let mut custom = Context::new();
+        custom.set_runtime(Some(own.handle().clone()));
+        assert_eq!(
+            with_shared_runtime(&custom)
+                .runtime()
+                .unwrap()
+                .runtime_flavor(),
+            tokio::runtime::RuntimeFlavor::CurrentThread
+        );
     }
 
     #[test]
-    fn html_render_contains_structure() {
+    fn registered_metadata_matches_the_constructed_collectors() {
+        for entry in vmic_sdk::iter_registered_collectors() {
+            let registered = (entry.metadata)();
+            let constructed = (entry.constructor)().metadata();
+            assert_eq!(registered.id, constructed.id);
+            assert_eq!(registered.title, constructed.title);
+            assert_eq!(registered.order, constructed.order);
+            assert_eq!(registered.all_tags(), constructed.all_tags());
+        }
+    }
+
+    #[test]
+    fn collector_versions_reach_json_and_html() {
         let ctx = Context::new();
-        let report = collect_report(&ctx);
-        let html = report.to_html().expect("html render");
-        assert!(html.contains("<!DOCTYPE html>"));
-        assert!(html.contains("System Report"));
-        assert!(html.contains("<nav class=\"toc\""));
-        assert!(html.contains("class=\"card digest status-"));
-        assert!(html.contains("section-summary"));
-        assert!(html.contains("Back to top"));
+        let (sections, _) = collect_sections(&ctx, &|metadata| metadata.id == "os", None);
+        let report = Report::new(sections);
+        let version = report.sections[0].collector_version.expect("version");
+        let os = registered_collectors()
+            .into_iter()
+            .find(|metadata| metadata.id == "os")
+            .expect("os collector");
+        assert_eq!(version, os.version);
+        assert!(version.split('.').count() >= 3);
+
+        let document = report.to_json_value();
+        assert_eq!(document["sections"][0]["collector_version"], json!(version));
+        let compiled = JSONSchema::compile(schema::report_schema()).expect("schema compilation");
+        assert!(compiled.is_valid(&document));
+        let reread = Report::from_json_value(&document).expect("reread");
+        assert_eq!(reread.sections[0].collector_version, Some(version));
+
+        let html = report.to_html().expect("html");
+        assert!(html.contains(&format!(
+            "<p class=\"section-footer\">Collector version {}</p>",
+            version
+        )));
+
+        let manual = Report::new(vec![Section::success("demo", "Demo", json!({}))]);
+        assert!(
+            manual.to_json_value()["sections"][0]
+                .get("collector_version")
+                .is_none()
+        );
+        assert!(
+            !manual
+                .to_html()
+                .unwrap()
+                .contains("<p class=\"section-footer\">")
+        );
     }
 
     #[test]
@@ -1707,6 +6688,33 @@ mod tests {
         assert!(iso.ends_with("+00:00"));
     }
 
+    #[test]
+    fn metadata_serializes_epoch_and_rfc3339_timestamps() {
+        let mut report = Report::new(Vec::new());
+        report.metadata.set_generated_at(1_718_000_000);
+        let json = report.to_json_value();
+        assert_eq!(json["metadata"]["generated_at_epoch"], 1_718_000_000);
+        assert_eq!(
+            json["metadata"]["generated_at"],
+            "2024-06-10T06:13:20+00:00"
+        );
+        assert_eq!(
+            report.metadata.generated_at_utc().unwrap().timestamp(),
+            1_718_000_000
+        );
+
+        let expected = report.metadata.generated_at_utc();
+        assert_eq!(
+            ReportMetadata::generated_at_from_json(&json["metadata"]),
+            expected
+        );
+        let legacy = json!({ "generated_at": "1718000000" });
+        assert_eq!(ReportMetadata::generated_at_from_json(&legacy), expected);
+        let iso_only = json!({ "generated_at": "2024-06-10T08:13:20+02:00" });
+        assert_eq!(ReportMetadata::generated_at_from_json(&iso_only), expected);
+        assert_eq!(ReportMetadata::generated_at_from_json(&json!({})), None);
+    }
+
     #[test]
     fn digest_highlights_degraded_sections() {
         let degraded = Section::degraded("demo", "Demo", "something off".to_string(), json!({}));
@@ -1720,6 +6728,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn long_degraded_sections_escalate_with_history() {
+        let degraded = || {
+            vec![Section::degraded(
+                "docker",
+                "Docker Containers",
+                "engine unreachable".to_string(),
+                json!({}),
+            )]
+        };
+        let aging = |previous| DegradedAging {
+            previous_runs: std::collections::BTreeMap::from([("docker".to_string(), previous)]),
+            escalate_after: health::DEFAULT_DEGRADED_ESCALATION_RUNS,
+        };
+
+        let escalated =
+            Report::with_digest_history(degraded(), DigestThresholds::default(), Some(&aging(6)));
+        let finding = &escalated.health_digest.findings[0];
+        assert_eq!(finding.severity, Severity::Critical);
+        assert_eq!(
+            finding.message,
+            "engine unreachable (degraded for 7 consecutive runs)"
+        );
+
+        let recent =
+            Report::with_digest_history(degraded(), DigestThresholds::default(), Some(&aging(4)));
+        assert_eq!(recent.health_digest.findings[0].severity, Severity::Warning);
+        assert_eq!(
+            recent.health_digest.findings[0].message,
+            "engine unreachable"
+        );
+
+        let without = Report::new(degraded());
+        assert_eq!(
+            serde_json::to_value(&without.health_digest).unwrap(),
+            serde_json::to_value(&recent.health_digest).unwrap()
+        );
+    }
+
+    #[test]
+    fn degraded_aging_is_built_from_history_entries() {
+        let mut entries = Vec::new();
+        for _ in 0..5 {
+            let report = Report::new(vec![Section::degraded(
+                "docker",
+                "Docker Containers",
+                "engine unreachable".to_string(),
+                json!({}),
+            )]);
+            entries.push(history::Entry::from_report(&report));
+        }
+        let aging = DegradedAging::from_history(&entries, 5);
+        assert_eq!(aging.previous_runs.get("docker"), Some(&5));
+
+        let current = vec![Section::degraded(
+            "docker",
+            "Docker Containers",
+            "engine unreachable".to_string(),
+            json!({}),
+        )];
+        let report =
+            Report::with_digest_history(current, DigestThresholds::default(), Some(&aging));
+        assert_eq!(report.health_digest.overall, Severity::Critical);
+        assert!(
+            report.health_digest.findings[0]
+                .message
+                .ends_with("(degraded for 6 consecutive runs)")
+        );
+    }
+
     #[test]
     fn digest_flags_high_disk_usage() {
         let storage = Section::success(
@@ -1756,6 +6834,423 @@ mod tests {
         );
     }
 
+    fn storage_with_mount(mount_point: &str, total_bytes: u64, available_bytes: u64) -> Section {
+        let used_bytes = total_bytes - available_bytes;
+        Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    {
+                        "mount_point": mount_point,
+                        "fs_type": "ext4",
+                        "read_only": false,
+                        "category": "operating",
+                        "operational": true,
+                        "total_bytes": total_bytes,
+                        "used_bytes": used_bytes,
+                        "available_bytes": available_bytes,
+                        "usage_ratio": used_bytes as f64 / total_bytes as f64,
+                        "inodes_usage_ratio": 0.1
+                    }
+                ],
+                "pseudo_mounts": [],
+                "totals": json!({}),
+                "docker": Value::Null
+            }),
+        )
+    }
+
+    #[test]
+    fn digest_skips_absolute_free_space_rules_on_tiny_filesystems() {
+        const GIB: u64 = 1 << 30;
+        let report = Report::new(vec![storage_with_mount("/srv/scratch", GIB, GIB * 2 / 5)]);
+        assert!(report.health_digest.findings.is_empty());
+        assert_eq!(report.health_digest.overall, Severity::Info);
+
+        let strict = DigestThresholds {
+            free_space_min_fs_gib: 0.5,
+            ..DigestThresholds::default()
+        };
+        let report = Report::with_digest_config(
+            vec![storage_with_mount("/srv/scratch", GIB, GIB * 2 / 5)],
+            strict,
+        );
+        assert_eq!(report.health_digest.overall, Severity::Critical);
+    }
+
+    #[test]
+    fn digest_keeps_absolute_free_space_rules_on_large_filesystems() {
+        const GIB: u64 = 1 << 30;
+        let report = Report::new(vec![storage_with_mount("/data", 500 * GIB, GIB * 3 / 2)]);
+        assert_eq!(report.health_digest.overall, Severity::Critical);
+        let finding = &report.health_digest.findings[0];
+        assert_eq!(finding.severity, Severity::Critical);
+        assert!(finding.message.contains("free space 1.50 GiB"));
+    }
+
+    #[test]
+    fn digest_keeps_boot_rule_on_small_boot_volumes() {
+        const GIB: u64 = 1 << 30;
+        let report = Report::new(vec![storage_with_mount("/boot", GIB, GIB / 5)]);
+        let finding = &report.health_digest.findings[0];
+        assert_eq!(finding.severity, Severity::Critical);
+        assert!(finding.message.contains("boot volume nearly full"));
+        assert!(!finding.message.contains("free space"));
+    }
+
+    #[test]
+    fn thresholds_round_trip_through_toml() {
+        let thresholds = DigestThresholds {
+            disk_warning: 0.8,
+            disk_critical: 0.9,
+            memory_warning: 0.2,
+            memory_critical: 0.1,
+            free_space_min_fs_gib: 32.0,
+        };
+        let encoded = thresholds.to_toml_string();
+        assert_eq!(
+            DigestThresholds::from_toml_str(&encoded).unwrap(),
+            thresholds
+        );
+        assert_eq!(
+            DigestThresholds::from_toml_str("").unwrap(),
+            DigestThresholds::default()
+        );
+    }
+
+    #[test]
+    fn thresholds_toml_accepts_percentages_and_the_example_file() {
+        let parsed = DigestThresholds::from_toml_str("disk_warning = 85\ndisk_critical = 0.97\n")
+            .expect("percent and ratio values");
+        assert_eq!(parsed.disk_warning, 0.85);
+        assert_eq!(parsed.disk_critical, 0.97);
+        assert_eq!(
+            parsed.memory_warning,
+            DigestThresholds::default().memory_warning
+        );
+
+        let example = include_str!("../config/digest-thresholds.example.toml");
+        assert_eq!(
+            DigestThresholds::from_toml_str(example).unwrap(),
+            DigestThresholds::default()
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("thresholds.toml");
+        std::fs::write(&path, "memory_warning = 25\nmemory_critical = 10\n").unwrap();
+        let loaded = DigestThresholds::from_path(&path).unwrap();
+        assert_eq!(loaded.memory_warning, 0.25);
+    }
+
+    #[test]
+    fn thresholds_toml_rejects_unknown_keys_and_invalid_values() {
+        let unknown = DigestThresholds::from_toml_str("disk_warnign = 80\n").unwrap_err();
+        assert!(unknown.to_string().contains("unknown field `disk_warnign`"));
+
+        let out_of_range = DigestThresholds::from_toml_str("disk_warning = 250\n").unwrap_err();
+        assert!(format!("{:#}", out_of_range).contains("invalid disk_warning"));
+
+        let inverted =
+            DigestThresholds::from_toml_str("disk_warning = 97\ndisk_critical = 90\n").unwrap_err();
+        assert!(inverted.to_string().contains("must be <= disk_critical"));
+
+        let negative = DigestThresholds::from_toml_str("free_space_min_fs_gib = -1\n").unwrap_err();
+        assert!(negative.to_string().contains("free_space_min_fs_gib"));
+    }
+
+    #[test]
+    fn digest_from_json_matches_collected_digest() {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    {
+                        "mount_point": "/data",
+                        "fs_type": "ext4",
+                        "operational": true,
+                        "available_bytes": 8_000_000_000u64,
+                        "usage_ratio": 0.92,
+                        "inodes_usage_ratio": 0.5
+                    }
+                ]
+            }),
+        );
+        let proc = Section::success(
+            "proc",
+            "Processes and Resources",
+            json!({
+                "memory": {
+                    "host": { "total_bytes": 1000, "available_bytes": 80 },
+                    "cgroup": { "limit_bytes": 1000, "usage_bytes": 920 }
+                }
+            }),
+        );
+        let degraded = Section::degraded("demo", "Demo", "partial data".to_string(), json!({}));
+        let failed = Section::error("cron", "Scheduled Jobs", "permission denied".to_string());
+        let report = Report::new(vec![storage, proc, degraded, failed]);
+        let document: Value =
+            serde_json::from_str(&serde_json::to_string(&report.to_json_value()).unwrap()).unwrap();
+
+        let digest = health::digest_from_json(&document, &DigestThresholds::default())
+            .expect("digest from stored report");
+        assert_eq!(
+            serde_json::to_value(&digest).unwrap(),
+            serde_json::to_value(&report.health_digest).unwrap()
+        );
+
+        let relaxed = DigestThresholds {
+            disk_warning: 0.95,
+            disk_critical: 0.99,
+            memory_warning: 0.01,
+            memory_critical: 0.005,
+            ..DigestThresholds::default()
+        };
+        let rescored = health::digest_from_json(&document, &relaxed).expect("rescored");
+        assert!(rescored.findings.len() < digest.findings.len());
+        assert!(rescored.findings.iter().all(|f| f.source_id != "proc"));
+    }
+
+    #[test]
+    fn digest_from_json_rejects_documents_outside_the_schema() {
+        let mut document = Report::new(Vec::new()).to_json_value();
+        document["sections"] = json!([{ "id": "storage", "status": "fine" }]);
+        let error = health::digest_from_json(&document, &DigestThresholds::default())
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("report does not match the schema: "));
+    }
+
+    #[test]
+    fn saved_reports_read_back_unchanged() {
+        let mut storage = Section::degraded(
+            "storage",
+            "Storage Overview",
+            "df failed".to_string(),
+            json!({ "operating_mounts": [] }),
+        );
+        storage.notes.push("fallback to /proc/mounts".to_string());
+        storage.duration_ms = Some(12);
+        let mut report = Report::new(vec![
+            storage,
+            Section::success("os", "Operating System", json!({ "kernel": "6.1" })),
+        ]);
+        report
+            .metadata
+            .labels
+            .insert("env".to_string(), "prod".to_string());
+        report.refresh_checksum();
+
+        let document = report.to_json_value();
+        let restored = Report::from_json_value(&document).unwrap();
+        assert_eq!(restored.to_json_value(), document);
+        assert_eq!(
+            Report::verify_checksum(&restored.to_json_value()).ok(),
+            Some(true)
+        );
+
+        let mut invalid = document.clone();
+        invalid["sections"][0]["status"] = json!("fine");
+        let error = Report::from_json_value(&invalid).unwrap_err().to_string();
+        assert!(error.starts_with("report does not match the schema: "));
+    }
+
+    #[test]
+    fn digest_summary_matches_findings() {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    {
+                        "mount_point": "/data",
+                        "fs_type": "ext4",
+                        "read_only": false,
+                        "operational": true,
+                        "available_bytes": 1_000_000u64,
+                        "usage_ratio": 0.97
+                    },
+                    {
+                        "mount_point": "/srv",
+                        "fs_type": "ext4",
+                        "read_only": false,
+                        "operational": true,
+                        "available_bytes": 9_000_000_000u64,
+                        "usage_ratio": 0.91
+                    }
+                ]
+            }),
+        );
+        let docker = Section::degraded(
+            "docker",
+            "Docker Containers",
+            "daemon unavailable".to_string(),
+            json!({}),
+        );
+        let cron = Section::error("cron", "Scheduled Jobs", "boom".to_string());
+        let report = Report::new(vec![storage, docker, cron]);
+        let digest = &report.health_digest;
+
+        let count = |severity| {
+            digest
+                .findings
+                .iter()
+                .filter(|finding| finding.severity == severity)
+                .count()
+        };
+        assert_eq!(digest.summary.critical, count(Severity::Critical));
+        assert_eq!(digest.summary.warning, count(Severity::Warning));
+        assert_eq!(digest.summary.info, count(Severity::Info));
+        assert_eq!(digest.summary.total(), digest.findings.len());
+        assert_eq!(digest.summary.sections["cron"].critical, 1);
+        assert_eq!(digest.summary.sections["docker"].warning, 1);
+        let storage_findings = digest
+            .findings
+            .iter()
+            .filter(|finding| finding.source_id == "storage")
+            .count();
+        assert_eq!(digest.summary.sections["storage"].total(), storage_findings);
+        assert!(!digest.summary.sections.contains_key("os"));
+
+        let markdown = report.to_markdown().expect("markdown");
+        assert!(markdown.contains("| Section | Critical | Warning |"));
+        assert!(markdown.contains("| cron | 1 | 0 |"));
+        let html = report.to_html().expect("html");
+        assert!(html.contains("class=\"data digest-summary\""));
+        assert!(html.contains("<a href=\"#docker\">docker</a>"));
+
+        let value = report.to_json_value();
+        assert_eq!(
+            value["health_digest"]["summary"]["sections"]["cron"]["critical"],
+            1
+        );
+    }
+
+    #[test]
+    fn body_budget_keeps_digest_findings() {
+        let mounts: Vec<Value> = (0..200)
+            .map(|index| {
+                json!({
+                    "mount_point": format!("/srv/volume-{index:03}"),
+                    "fs_type": "ext4",
+                    "read_only": false,
+                    "operational": true,
+                    "available_bytes": 1_000_000u64,
+                    "usage_ratio": 0.99,
+                    "inodes_usage_ratio": 0.1
+                })
+            })
+            .collect();
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({ "operating_mounts": mounts }),
+        );
+
+        let mut ctx = Context::new();
+        ctx.set_body_budget_bytes(Some(4 * 1024));
+        let report = build_report(&ctx, vec![storage], DigestThresholds::default(), None);
+
+        assert_eq!(report.health_digest.findings.len(), 200);
+        let section = &report.sections[0];
+        assert!(section.body["operating_mounts"].as_array().unwrap().len() < 200);
+        assert!(
+            section
+                .notes
+                .iter()
+                .any(|note| note.starts_with("body truncated"))
+        );
+        assert!(matches!(section.status, SectionStatus::Success));
+    }
+
+    #[test]
+    fn listener_cgroup_ids_are_named_from_the_docker_section() {
+        let id = "4f1c2a9b8e7d6c5b4a39281706f5e4d3c2b1a09f8e7d6c5b4a3928170abcdef0";
+        let network = || {
+            Section::success(
+                "network",
+                "Network",
+                json!({
+                    "interfaces": [],
+                    "listeners": {
+                        "counts": { "total": 1 },
+                        "samples": [{
+                            "protocol": "tcp",
+                            "local_address": "0.0.0.0:80",
+                            "state": "listen",
+                            "processes": [{ "pid": 12, "command": "nginx", "uid": 0, "container": id }]
+                        }],
+                        "groups": [{ "container": id, "socket_count": 1, "process_count": 1, "processes": [] }],
+                        "insights": []
+                    }
+                }),
+            )
+        };
+        let docker = Section::success(
+            "docker",
+            "Docker",
+            json!({ "containers": [{ "id": id, "names": ["db"], "mounts": [] }] }),
+        );
+        let ctx = Context::new();
+
+        let report = build_report(
+            &ctx,
+            vec![network(), docker],
+            DigestThresholds::default(),
+            None,
+        );
+        assert_eq!(
+            report.sections[0].body["listeners"]["groups"][0]["container"],
+            "db"
+        );
+        assert!(
+            report
+                .to_markdown()
+                .unwrap()
+                .contains("pid 12 nginx (uid 0, container db)")
+        );
+
+        let report = build_report(&ctx, vec![network()], DigestThresholds::default(), None);
+        assert_eq!(
+            report.sections[0].body["listeners"]["groups"][0]["container"],
+            id
+        );
+        assert!(
+            report
+                .to_markdown()
+                .unwrap()
+                .contains(&format!("pid 12 nginx (uid 0, cgroup {id})"))
+        );
+    }
+
+    #[test]
+    fn interfaces_table_shows_state_addresses_and_loopback() {
+        let section = Section::success(
+            "network",
+            "Network",
+            json!({
+                "interfaces": [
+                    {
+                        "name": "eth0", "loopback": false, "state": "up", "mtu": 1500,
+                        "mac": "52:54:00:12:34:56",
+                        "ipv4_addresses": ["192.0.2.2/24"], "ipv6_addresses": ["fd00::2/64"],
+                        "rx_bytes": 2048, "tx_bytes": 1024, "rx_packets": 20, "tx_packets": 10
+                    },
+                    {
+                        "name": "lo", "loopback": true, "state": "unknown", "mtu": 65536,
+                        "mac": null, "ipv4_addresses": ["127.0.0.1/8"], "ipv6_addresses": [],
+                        "rx_bytes": 0, "tx_bytes": 0, "rx_packets": 0, "tx_packets": 0
+                    }
+                ]
+            }),
+        );
+        let markdown = Report::new(vec![section]).to_markdown().unwrap();
+        assert!(markdown.contains("| Interface | State | Addresses | MTU |"));
+        assert!(markdown.contains("| eth0 | up | 192.0.2.2/24, fd00::2/64 | 1500 |"));
+        assert!(markdown.contains("| lo (loopback) | unknown | 127.0.0.1/8 | 65536 |"));
+    }
+
     #[test]
     fn custom_thresholds_trigger_warning() {
         let storage = Section::success(
@@ -1798,4 +7293,52 @@ mod tests {
                 .any(|f| f.source_id == "storage" && f.severity == Severity::Warning)
         );
     }
+
+    #[test]
+    fn collector_findings_join_the_digest_without_duplicates() {
+        let mut security = Section::degraded(
+            "security",
+            "Security Posture",
+            "1 potential security issues".to_string(),
+            json!({}),
+        );
+        security.add_finding(
+            vmic_sdk::FindingSeverity::Critical,
+            "sshd.permit_root_login",
+            "PermitRootLogin allows direct root access",
+        );
+        // Same alert as the degraded-status rule, reported at a higher severity.
+        security.add_finding(
+            vmic_sdk::FindingSeverity::Critical,
+            "security.issues",
+            "1 Potential security issues ",
+        );
+
+        let report = Report::new(vec![security]);
+        let digest = &report.health_digest;
+        assert_eq!(digest.overall, Severity::Critical);
+        assert_eq!(digest.findings.len(), 2);
+        assert_eq!(digest.findings[0].message, "1 potential security issues");
+        assert_eq!(digest.findings[0].severity, Severity::Critical);
+        assert_eq!(digest.findings[0].code.as_deref(), Some("security.issues"));
+        assert_eq!(
+            digest.findings[1].code.as_deref(),
+            Some("sshd.permit_root_login")
+        );
+
+        let document = report.to_json_value();
+        assert_eq!(
+            document["sections"][0]["findings"][0]["severity"],
+            "critical"
+        );
+        assert_eq!(
+            document["health_digest"]["findings"][1]["code"],
+            "sshd.permit_root_login"
+        );
+        let rescored =
+            health::digest_from_json(&document, &DigestThresholds::default()).expect("rescored");
+        assert_eq!(rescored.findings.len(), 2);
+        let restored = Report::from_json_value(&document).expect("restored");
+        assert_eq!(restored.sections[0].findings, report.sections[0].findings);
+    }
 }
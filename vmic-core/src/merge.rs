@@ -0,0 +1,282 @@
+//! Roll-up of reports collected on several hosts.
+//!
+//! Each input report stays intact under its host label. The merged digest
+//! takes the worst overall severity across hosts and tags every finding with
+//! the host it came from; fleet statistics sum storage capacity and failed
+//! services over all hosts.
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::{CriticalFinding, RenderOptions, Report, Section, Severity, SeverityCounts, render};
+
+/// Reports from several hosts combined into one document.
+#[derive(Debug, Serialize)]
+pub struct MergedReport {
+    pub schema_version: &'static str,
+    pub generated_at_epoch: i64,
+    pub generated_at: String,
+    pub fleet: FleetStats,
+    pub health_digest: MergedDigest,
+    pub hosts: Vec<HostReport>,
+}
+
+/// One input report with the label it was merged under.
+#[derive(Debug, Serialize)]
+pub struct HostReport {
+    pub host: String,
+    pub report: Report,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct MergedDigest {
+    /// Highest overall severity of any host.
+    pub overall: Severity,
+    pub findings: Vec<HostFinding>,
+    pub summary: SeverityCounts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HostFinding {
+    pub host: String,
+    #[serde(flatten)]
+    pub finding: CriticalFinding,
+}
+
+/// Totals across every host; hosts without the relevant section contribute nothing.
+#[derive(Debug, Clone, Serialize, Default, PartialEq, Eq)]
+pub struct FleetStats {
+    pub hosts: usize,
+    pub total_capacity_bytes: u64,
+    pub used_capacity_bytes: u64,
+    pub failed_services: usize,
+}
+
+/// Combines per-host reports, keeping their order. Fails when host labels repeat
+/// or when the inputs do not share one schema version.
+pub fn merge_reports(reports: Vec<(String, Report)>) -> Result<MergedReport> {
+    let Some((first_host, first)) = reports.first() else {
+        return Err(anyhow!("no reports to merge"));
+    };
+    let schema_version = first.schema_version;
+
+    let mut seen = HashSet::new();
+    for (host, report) in &reports {
+        if !seen.insert(host.as_str()) {
+            return Err(anyhow!("duplicate host label '{}'", host));
+        }
+        if report.schema_version != schema_version {
+            return Err(anyhow!(
+                "conflicting schema versions: '{}' has {}, '{}' has {}",
+                first_host,
+                schema_version,
+                host,
+                report.schema_version
+            ));
+        }
+    }
+
+    let mut fleet = FleetStats {
+        hosts: reports.len(),
+        ..FleetStats::default()
+    };
+    let mut digest = MergedDigest::default();
+    for (host, report) in &reports {
+        digest.overall = digest.overall.max(report.health_digest.overall);
+        for finding in &report.health_digest.findings {
+            digest.summary.record(finding.severity);
+            digest.findings.push(HostFinding {
+                host: host.clone(),
+                finding: finding.clone(),
+            });
+        }
+        for section in &report.sections {
+            accumulate_fleet_stats(&mut fleet, section);
+        }
+    }
+    digest
+        .findings
+        .sort_by_key(|entry| std::cmp::Reverse(entry.finding.severity));
+
+    let generated_at_epoch = Utc::now().timestamp();
+
+    Ok(MergedReport {
+        schema_version,
+        generated_at_epoch,
+        generated_at: crate::rfc3339(generated_at_epoch),
+        fleet,
+        health_digest: digest,
+        hosts: reports
+            .into_iter()
+            .map(|(host, report)| HostReport { host, report })
+            .collect(),
+    })
+}
+
+fn accumulate_fleet_stats(fleet: &mut FleetStats, section: &Section) {
+    match section.id.as_ref() {
+        "storage" => {
+            let totals = section.body.get("totals");
+            let bytes = |key: &str| {
+                totals
+                    .and_then(|totals| totals.get(key))
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0)
+            };
+            fleet.total_capacity_bytes += bytes("total_bytes");
+            fleet.used_capacity_bytes += bytes("used_bytes");
+        }
+        "services" => {
+            fleet.failed_services += section
+                .body
+                .get("failed")
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len);
+        }
+        _ => {}
+    }
+}
+
+impl MergedReport {
+    /// Section `id` as reported by `host`.
+    pub fn section(&self, host: &str, id: &str) -> Option<&Section> {
+        self.hosts
+            .iter()
+            .find(|entry| entry.host == host)?
+            .report
+            .sections
+            .iter()
+            .find(|section| section.id == id)
+    }
+
+    pub fn to_json_value(&self) -> Value {
+        serde_json::to_value(self).expect("merged report serializes to JSON")
+    }
+
+    pub fn to_markdown(&self) -> Result<String> {
+        self.to_markdown_with(&RenderOptions::default())
+    }
+
+    pub fn to_markdown_with(&self, options: &RenderOptions) -> Result<String> {
+        render::render_merged_markdown(self, options).map_err(Into::into)
+    }
+
+    pub fn to_html(&self) -> Result<String> {
+        self.to_html_with(&RenderOptions::default())
+    }
+
+    pub fn to_html_with(&self, options: &RenderOptions) -> Result<String> {
+        render::render_merged_html(self, options).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn host_report(used: u64, failed: usize, usage_ratio: f64) -> Report {
+        let storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    { "mount_point": "/", "fs_type": "ext4", "operational": true, "usage_ratio": usage_ratio, "available_bytes": 100u64 << 30 }
+                ],
+                "totals": { "total_bytes": 1000, "used_bytes": used }
+            }),
+        );
+        let services = Section::success(
+            "services",
+            "System Services",
+            json!({
+                "running": [],
+                "failed": (0..failed)
+                    .map(|index| json!({ "unit": format!("unit{index}.service") }))
+                    .collect::<Vec<_>>()
+            }),
+        );
+        Report::new(vec![storage, services])
+    }
+
+    #[test]
+    fn digests_and_fleet_stats_are_aggregated() {
+        let merged = merge_reports(vec![
+            ("web-1".to_string(), host_report(200, 0, 0.2)),
+            ("db-1".to_string(), host_report(700, 2, 0.97)),
+        ])
+        .expect("merge");
+
+        assert_eq!(
+            merged.fleet,
+            FleetStats {
+                hosts: 2,
+                total_capacity_bytes: 2000,
+                used_capacity_bytes: 900,
+                failed_services: 2,
+            }
+        );
+        assert_eq!(merged.health_digest.overall, Severity::Critical);
+        let first = &merged.health_digest.findings[0];
+        assert_eq!(first.host, "db-1");
+        assert_eq!(first.finding.severity, Severity::Critical);
+        assert!(merged.health_digest.summary.critical >= 1);
+
+        let storage = merged.section("web-1", "storage").expect("section");
+        assert_eq!(storage.body["totals"]["used_bytes"], 200);
+        assert!(merged.section("web-2", "storage").is_none());
+
+        let json = merged.to_json_value();
+        assert_eq!(json["hosts"][1]["host"], "db-1");
+        assert_eq!(json["health_digest"]["findings"][0]["host"], "db-1");
+        assert!(json["health_digest"]["findings"][0]["source_id"].is_string());
+    }
+
+    #[test]
+    fn conflicting_schema_versions_are_rejected() {
+        let mut legacy = host_report(100, 0, 0.1);
+        legacy.schema_version = "1.0";
+        let error = merge_reports(vec![
+            ("a".to_string(), host_report(100, 0, 0.1)),
+            ("b".to_string(), legacy),
+        ])
+        .unwrap_err();
+        assert!(error.to_string().contains("conflicting schema versions"));
+    }
+
+    #[test]
+    fn duplicate_hosts_and_empty_input_are_rejected() {
+        assert!(merge_reports(Vec::new()).is_err());
+        let error = merge_reports(vec![
+            ("a".to_string(), host_report(1, 0, 0.1)),
+            ("a".to_string(), host_report(2, 0, 0.1)),
+        ])
+        .unwrap_err();
+        assert!(error.to_string().contains("duplicate host label"));
+    }
+
+    #[test]
+    fn renders_host_index_and_host_scoped_anchors() {
+        let merged = merge_reports(vec![
+            ("web-1".to_string(), host_report(200, 0, 0.2)),
+            ("db.internal".to_string(), host_report(700, 2, 0.97)),
+        ])
+        .expect("merge");
+
+        let md = merged.to_markdown().expect("markdown");
+        assert!(md.contains("| [web-1](#web-1) |"));
+        assert!(md.contains("| [db.internal](#dbinternal) |"));
+        assert!(md.contains("## db.internal"));
+        assert!(md.contains("### Storage Overview"));
+        assert!(md.contains("(db.internal / Storage Overview)"));
+
+        let html = merged.to_html().expect("html");
+        assert!(html.contains("id=\"host-dbinternal\""));
+        assert!(html.contains("id=\"host-web-1-storage\""));
+        assert!(html.contains("href=\"#host-dbinternal-storage\""));
+        assert!(html.find("host-index").unwrap() < html.find("id=\"host-web-1\"").unwrap());
+    }
+}
@@ -0,0 +1,96 @@
+//! Deterministic section ordering.
+//!
+//! Inventory registration order depends on link order, so sections are sorted
+//! after collection: ids from the context override come first, then sections
+//! by their collector's `order` hint (ties broken by id), then any remaining
+//! ids alphabetically.
+
+use std::collections::HashMap;
+
+use vmic_sdk::Section;
+
+pub(crate) fn order_sections(
+    sections: &mut [Section],
+    overrides: &[String],
+    hints: &HashMap<&str, i32>,
+) {
+    sections.sort_by_cached_key(|section| sort_key(&section.id, overrides, hints));
+}
+
+fn sort_key(id: &str, overrides: &[String], hints: &HashMap<&str, i32>) -> (u8, i64, String) {
+    if let Some(position) = overrides.iter().position(|entry| entry == id) {
+        return (0, position as i64, String::new());
+    }
+    match hints.get(id) {
+        Some(order) => (1, i64::from(*order), id.to_string()),
+        None => (2, 0, id.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sections(ids: &[&'static str]) -> Vec<Section> {
+        ids.iter()
+            .map(|id| Section::success(*id, *id, json!({})))
+            .collect()
+    }
+
+    fn ids(sections: &[Section]) -> Vec<&str> {
+        sections.iter().map(|section| section.id.as_ref()).collect()
+    }
+
+    fn hints() -> HashMap<&'static str, i32> {
+        HashMap::from([
+            ("os", 10),
+            ("proc", 20),
+            ("storage", 30),
+            ("journal", 80),
+            ("docker", 60),
+            ("security", 110),
+            ("sar", 120),
+        ])
+    }
+
+    #[test]
+    fn hint_order_ignores_input_order() {
+        let expected = vec!["os", "proc", "storage", "docker", "sar", "alpha", "zeta"];
+        let mut forward = sections(&["zeta", "sar", "docker", "alpha", "storage", "os", "proc"]);
+        let mut reversed = sections(&["proc", "os", "storage", "alpha", "docker", "sar", "zeta"]);
+
+        order_sections(&mut forward, &[], &hints());
+        order_sections(&mut reversed, &[], &hints());
+
+        assert_eq!(ids(&forward), expected);
+        assert_eq!(ids(&reversed), expected);
+    }
+
+    #[test]
+    fn equal_hints_fall_back_to_id() {
+        let mut list = sections(&["b", "c", "a"]);
+        let hints = HashMap::from([("a", 5), ("b", 5), ("c", 1)]);
+
+        order_sections(&mut list, &[], &hints);
+
+        assert_eq!(ids(&list), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn overrides_come_first_and_rest_stays_hinted() {
+        let mut list = sections(&["os", "custom", "journal", "storage", "security", "proc"]);
+        let overrides = vec![
+            "security".to_string(),
+            "custom".to_string(),
+            "missing".to_string(),
+        ];
+
+        order_sections(&mut list, &overrides, &hints());
+
+        assert_eq!(
+            ids(&list),
+            vec!["security", "custom", "os", "proc", "storage", "journal"]
+        );
+    }
+}
@@ -0,0 +1,392 @@
+//! Prometheus text exposition rendering for collected reports.
+//!
+//! Metrics are derived from the same section bodies the health digest reads,
+//! so scraped values line up with the findings shown in Markdown/HTML output.
+//! Samples whose source value is missing are omitted rather than emitted as
+//! zero.
+
+use crate::{Report, Severity};
+use serde_json::Value;
+use std::fmt::Write as _;
+
+struct MetricFamily {
+    name: String,
+    help: &'static str,
+    samples: Vec<(Vec<(String, String)>, f64)>,
+}
+
+impl MetricFamily {
+    fn new(name: &str, help: &'static str) -> Self {
+        Self {
+            name: sanitize_metric_name(name),
+            help,
+            samples: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, labels: &[(&str, &str)], value: f64) {
+        let labels = labels
+            .iter()
+            .map(|(name, value)| (sanitize_label_name(name), (*value).to_string()))
+            .collect();
+        self.samples.push((labels, value));
+    }
+
+    fn write_to(&self, output: &mut String) {
+        if self.samples.is_empty() {
+            return;
+        }
+
+        let _ = writeln!(output, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(output, "# TYPE {} gauge", self.name);
+        for (labels, value) in &self.samples {
+            output.push_str(&self.name);
+            if !labels.is_empty() {
+                let rendered: Vec<String> = labels
+                    .iter()
+                    .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+                    .collect();
+                output.push('{');
+                output.push_str(&rendered.join(","));
+                output.push('}');
+            }
+            output.push(' ');
+            output.push_str(&format_value(*value));
+            output.push('\n');
+        }
+    }
+}
+
+pub(crate) fn render_prometheus(report: &Report) -> String {
+    let mut mount_usage = MetricFamily::new(
+        "vmic_mount_usage_ratio",
+        "Used fraction of an operational mount (0-1).",
+    );
+    let mut mount_available = MetricFamily::new(
+        "vmic_mount_available_bytes",
+        "Bytes available to unprivileged users on an operational mount.",
+    );
+    let mut mount_inodes = MetricFamily::new(
+        "vmic_mount_inodes_usage_ratio",
+        "Used fraction of inodes on an operational mount (0-1).",
+    );
+    let mut memory_available = MetricFamily::new(
+        "vmic_memory_available_bytes",
+        "Host memory available for new allocations.",
+    );
+    let mut memory_total = MetricFamily::new("vmic_memory_total_bytes", "Total host memory.");
+    let mut container_restarts = MetricFamily::new(
+        "vmic_container_restart_count",
+        "Restart count reported by the Docker engine per container.",
+    );
+    let mut section_duration = MetricFamily::new(
+        "vmic_section_duration_ms",
+        "Wall-clock time spent collecting a section in milliseconds.",
+    );
+    let mut section_status = MetricFamily::new(
+        "vmic_section_status",
+        "Section status (0 = success, 1 = degraded, 2 = error).",
+    );
+
+    for section in &report.sections {
+        if let Some(duration) = section.duration_ms {
+            section_duration.push(&[("section", &section.id)], duration as f64);
+        }
+        let status = match section.status {
+            vmic_sdk::SectionStatus::Success => 0.0,
+            vmic_sdk::SectionStatus::Degraded => 1.0,
+            vmic_sdk::SectionStatus::Error => 2.0,
+        };
+        section_status.push(&[("section", &section.id)], status);
+
+        match section.id.as_ref() {
+            "storage" => {
+                let mounts = section
+                    .body
+                    .get("operating_mounts")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for mount in mounts {
+                    let Some(point) = mount.get("mount_point").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let operational = mount
+                        .get("operational")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+                    if !operational {
+                        continue;
+                    }
+                    let fs_type = mount.get("fs_type").and_then(Value::as_str).unwrap_or("");
+                    let labels = [("mount", point), ("fs", fs_type)];
+                    if let Some(ratio) = mount.get("usage_ratio").and_then(Value::as_f64) {
+                        mount_usage.push(&labels, ratio);
+                    }
+                    if let Some(bytes) = mount.get("available_bytes").and_then(Value::as_u64) {
+                        mount_available.push(&labels, bytes as f64);
+                    }
+                    if let Some(ratio) = mount.get("inodes_usage_ratio").and_then(Value::as_f64) {
+                        mount_inodes.push(&labels, ratio);
+                    }
+                }
+            }
+            "proc" => {
+                if let Some(host) = section.body.pointer("/memory/host") {
+                    if let Some(bytes) = host.get("available_bytes").and_then(Value::as_u64) {
+                        memory_available.push(&[], bytes as f64);
+                    }
+                    if let Some(bytes) = host.get("total_bytes").and_then(Value::as_u64) {
+                        memory_total.push(&[], bytes as f64);
+                    }
+                }
+            }
+            "docker" => {
+                let containers = section
+                    .body
+                    .get("containers")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for container in containers {
+                    let Some(restarts) = container.get("restart_count").and_then(Value::as_u64)
+                    else {
+                        continue;
+                    };
+                    let name = container
+                        .get("names")
+                        .and_then(Value::as_array)
+                        .and_then(|names| names.first())
+                        .and_then(Value::as_str)
+                        .or_else(|| container.get("id").and_then(Value::as_str))
+                        .unwrap_or("unknown");
+                    container_restarts.push(&[("name", name)], restarts as f64);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut report_timestamp = MetricFamily::new(
+        "vmic_report_timestamp_seconds",
+        "Unix time at which the report was generated.",
+    );
+    report_timestamp.push(&[], report.metadata.generated_at_epoch as f64);
+
+    let mut report_info = MetricFamily::new(
+        "vmic_report_info",
+        "Always 1; labels carry the report labels set with --label.",
+    );
+    let labels: Vec<(&str, &str)> = report
+        .metadata
+        .labels
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    report_info.push(&labels, 1.0);
+
+    let mut digest_severity = MetricFamily::new(
+        "vmic_digest_severity",
+        "Overall health digest severity (0 = info, 1 = warning, 2 = critical).",
+    );
+    digest_severity.push(&[], severity_value(report.health_digest.overall));
+
+    let mut finding_count = MetricFamily::new(
+        "vmic_finding_count",
+        "Number of health digest findings per severity.",
+    );
+    for severity in [Severity::Info, Severity::Warning, Severity::Critical] {
+        let count = report
+            .health_digest
+            .findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count();
+        finding_count.push(&[("severity", severity.as_str())], count as f64);
+    }
+
+    let mut output = format!("# vmic_generated_at {}\n", report.metadata.generated_at);
+    for family in [
+        &report_timestamp,
+        &report_info,
+        &digest_severity,
+        &finding_count,
+        &section_status,
+        &section_duration,
+        &mount_usage,
+        &mount_available,
+        &mount_inodes,
+        &memory_available,
+        &memory_total,
+        &container_restarts,
+    ] {
+        family.write_to(&mut output);
+    }
+    output
+}
+
+fn severity_value(severity: Severity) -> f64 {
+    match severity {
+        Severity::Info => 0.0,
+        Severity::Warning => 1.0,
+        Severity::Critical => 2.0,
+    }
+}
+
+fn sanitize_metric_name(name: &str) -> String {
+    sanitize(name, true)
+}
+
+fn sanitize_label_name(name: &str) -> String {
+    sanitize(name, false)
+}
+
+fn sanitize(name: &str, allow_colon: bool) -> String {
+    let mut output = String::with_capacity(name.len());
+    for (index, ch) in name.chars().enumerate() {
+        let valid = ch.is_ascii_alphabetic()
+            || ch == '_'
+            || (allow_colon && ch == ':')
+            || (index > 0 && ch.is_ascii_digit());
+        output.push(if valid { ch } else { '_' });
+    }
+    if output.is_empty() {
+        output.push('_');
+    }
+    output
+}
+
+fn escape_label_value(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\n' => output.push_str("\\n"),
+            other => output.push(other),
+        }
+    }
+    output
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vmic_sdk::Section;
+
+    fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.chars().enumerate().all(|(index, ch)| {
+                ch.is_ascii_alphabetic()
+                    || ch == '_'
+                    || ch == ':'
+                    || (index > 0 && ch.is_ascii_digit())
+            })
+    }
+
+    #[test]
+    fn exposition_lines_are_well_formed() {
+        let mut storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    {
+                        "mount_point": "/data \"main\"",
+                        "fs_type": "ext4",
+                        "operational": true,
+                        "available_bytes": 5_000_000_000u64,
+                        "usage_ratio": 0.95
+                    }
+                ]
+            }),
+        );
+        storage.duration_ms = Some(12);
+        let proc = Section::success("proc", "Process Snapshot", json!({ "memory": {} }));
+        let report = Report::new(vec![storage, proc]);
+
+        let output = report.to_prometheus();
+        assert!(output.contains("# TYPE vmic_mount_usage_ratio gauge"));
+        assert!(
+            output
+                .contains("vmic_mount_usage_ratio{mount=\"/data \\\"main\\\"\",fs=\"ext4\"} 0.95")
+        );
+        assert!(output.contains("vmic_section_duration_ms{section=\"storage\"} 12"));
+        assert!(output.contains("vmic_digest_severity 2"));
+        assert!(output.starts_with(&format!(
+            "# vmic_generated_at {}\n",
+            report.metadata.generated_at
+        )));
+        assert!(output.contains(&format!(
+            "vmic_report_timestamp_seconds {}\n",
+            report.metadata.generated_at_epoch
+        )));
+        assert!(output.contains("vmic_finding_count{severity=\"critical\"} 1"));
+        assert!(!output.contains("vmic_memory_available_bytes"));
+        assert!(!output.contains("vmic_mount_inodes_usage_ratio"));
+
+        for line in output.lines().skip(1) {
+            if let Some(comment) = line.strip_prefix("# ") {
+                let mut parts = comment.splitn(3, ' ');
+                let kind = parts.next().unwrap();
+                assert!(matches!(kind, "HELP" | "TYPE"), "bad comment: {line}");
+                assert!(is_valid_name(parts.next().unwrap()), "bad name: {line}");
+                continue;
+            }
+            let (series, value) = line.rsplit_once(' ').expect("sample has a value");
+            assert!(value.parse::<f64>().is_ok(), "bad value: {line}");
+            let name = series.split('{').next().unwrap();
+            assert!(is_valid_name(name), "bad metric name: {line}");
+            if series.contains('{') {
+                assert!(series.ends_with('}'), "unterminated labels: {line}");
+            }
+        }
+    }
+
+    #[test]
+    fn report_labels_are_info_labels() {
+        let mut report = Report::new(Vec::new());
+        assert!(report.to_prometheus().contains("\nvmic_report_info 1\n"));
+
+        report
+            .metadata
+            .labels
+            .insert("env".to_string(), "prod".to_string());
+        report
+            .metadata
+            .labels
+            .insert("role".to_string(), "db \"primary\"".to_string());
+        assert!(
+            report
+                .to_prometheus()
+                .contains("vmic_report_info{env=\"prod\",role=\"db \\\"primary\\\"\"} 1\n")
+        );
+        let json = report.to_json_value();
+        assert_eq!(json["metadata"]["labels"]["env"], "prod");
+        assert!(crate::schema::validate_report(&json).is_ok());
+        assert!(
+            report
+                .to_html()
+                .unwrap()
+                .contains("<span class=\"label\">env=prod</span>")
+        );
+    }
+
+    #[test]
+    fn names_and_label_values_are_sanitized() {
+        assert_eq!(sanitize_metric_name("9bad-name:ok"), "_bad_name:ok");
+        assert_eq!(sanitize_label_name("mount:point"), "mount_point");
+        assert_eq!(escape_label_value("a\\b\"c\nd"), "a\\\\b\\\"c\\nd");
+    }
+}
@@ -0,0 +1,783 @@
+//! Report redaction for sharing reports outside the organisation.
+//!
+//! Sensitive values are replaced with short, salted hashes (`ip-3f9a01c2`) so
+//! the same value maps to the same token everywhere in one report and
+//! correlations between sections survive.
+
+use crate::Report;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use vmic_sdk::{SectionPart, SensitiveCategory, SensitiveField};
+
+/// Selects which categories of values [`Report::redact`] replaces.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedactionPolicy {
+    /// IPv4/IPv6 addresses anywhere in the report (loopback and wildcard addresses are kept).
+    pub ip_addresses: bool,
+    /// Non-system account names from the users section.
+    pub usernames: bool,
+    /// Usernames and hosts listed in the journal SSH summary.
+    pub ssh_activity: bool,
+    /// Host names: this machine's hostname, SSH peers that are not IP
+    /// addresses, and servers of network mounts (`nfs.internal:/export`,
+    /// `//fileserver/share`).
+    pub hostnames: bool,
+    /// Mount sources (block devices, NFS exports) from the storage section.
+    pub mount_sources: bool,
+    /// Docker container names.
+    pub container_names: bool,
+    /// Command lines collectors mark as sensitive, such as cron commands.
+    pub commands: bool,
+    /// Log message text collectors mark as sensitive, such as journal messages.
+    pub log_messages: bool,
+    /// Mixed into every hash so tokens cannot be matched against other reports.
+    pub salt: String,
+}
+
+impl RedactionPolicy {
+    /// Policy with every category enabled.
+    pub fn all() -> Self {
+        Self {
+            ip_addresses: true,
+            usernames: true,
+            ssh_activity: true,
+            hostnames: true,
+            mount_sources: true,
+            container_names: true,
+            commands: true,
+            log_messages: true,
+            salt: String::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !(self.ip_addresses
+            || self.usernames
+            || self.ssh_activity
+            || self.hostnames
+            || self.mount_sources
+            || self.container_names
+            || self.commands
+            || self.log_messages)
+    }
+
+    /// `hostname` as a report redacted with this policy shows it: the same
+    /// token the report body uses when host names are redacted, unchanged
+    /// otherwise. For output outside the report such as archive manifests.
+    pub fn redact_hostname(&self, hostname: &str) -> String {
+        if self.hostnames {
+            Redactor::new(self).token("host", hostname)
+        } else {
+            hostname.to_string()
+        }
+    }
+}
+
+/// Kernel hostname of this machine, or `None` when it cannot be read.
+pub fn local_hostname() -> Option<String> {
+    ["/proc/sys/kernel/hostname", "/etc/hostname"]
+        .iter()
+        .find_map(|path| {
+            let name = fs::read_to_string(path).ok()?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+}
+
+pub(crate) fn redact_report(report: &mut Report, policy: &RedactionPolicy) {
+    if policy.is_empty() {
+        return;
+    }
+
+    let mut redactor = Redactor::new(policy);
+    if let Some(hostname) = local_hostname() {
+        redactor.learn_local_host(&hostname);
+    }
+    for section in &report.sections {
+        redactor.learn(&section.id, &section.body);
+        redactor.learn_marked(&section.body, &section.sensitive);
+    }
+    redactor.finish_learning();
+
+    for section in &mut report.sections {
+        redactor.redact_marked(&mut section.body, &section.sensitive);
+        redactor.redact_value(&mut section.body);
+        if let Some(summary) = section.summary.as_mut() {
+            *summary = redactor.redact_text(summary);
+        }
+        for note in &mut section.notes {
+            *note = redactor.redact_text(note);
+        }
+        for finding in &mut section.findings {
+            finding.message = redactor.redact_text(&finding.message);
+        }
+        for part in &mut section.parts {
+            redact_part(&redactor, part);
+        }
+        for subsection in &mut section.subsections {
+            redactor.redact_value(&mut subsection.body);
+            for part in &mut subsection.parts {
+                redact_part(&redactor, part);
+            }
+        }
+    }
+
+    for finding in &mut report.health_digest.findings {
+        finding.message = redactor.redact_text(&finding.message);
+    }
+}
+
+/// Structured parts repeat body values as display text, so every cell, value,
+/// and item goes through the same text redaction as summaries.
+fn redact_part(redactor: &Redactor<'_>, part: &mut SectionPart) {
+    let redact = |text: &mut String| *text = redactor.redact_text(text);
+    match part {
+        SectionPart::KeyValues(set) => set.items.iter_mut().for_each(|(_, value)| redact(value)),
+        SectionPart::Table(table) => table.rows.iter_mut().flatten().for_each(redact),
+        SectionPart::List(list) => list.items.iter_mut().for_each(redact),
+    }
+}
+
+struct Redactor<'a> {
+    policy: &'a RedactionPolicy,
+    known: HashMap<String, String>,
+    ordered: Vec<(String, String)>,
+}
+
+impl<'a> Redactor<'a> {
+    fn new(policy: &'a RedactionPolicy) -> Self {
+        Self {
+            policy,
+            known: HashMap::new(),
+            ordered: Vec::new(),
+        }
+    }
+
+    fn learn(&mut self, section_id: &str, body: &Value) {
+        match section_id {
+            "users" if self.policy.usernames => {
+                for user in array(body, "/users") {
+                    if user.get("system").and_then(Value::as_bool) == Some(true) {
+                        continue;
+                    }
+                    if let Some(name) = user.get("name").and_then(Value::as_str) {
+                        self.remember("user", name);
+                    }
+                }
+            }
+            "journal" if self.policy.ssh_activity || self.policy.hostnames => {
+                if self.policy.ssh_activity {
+                    for entry in array(body, "/ssh_summary/top_usernames") {
+                        if let Some(name) = entry.get("name").and_then(Value::as_str) {
+                            self.remember("user", name);
+                        }
+                    }
+                }
+                for entry in array(body, "/ssh_summary/top_hosts") {
+                    if let Some(name) = entry.get("name").and_then(Value::as_str) {
+                        if parse_ip(name).is_some() {
+                            if self.policy.ssh_activity {
+                                self.remember("ip", name);
+                            }
+                        } else {
+                            self.remember("host", name);
+                        }
+                    }
+                }
+            }
+            "storage" if self.policy.mount_sources || self.policy.hostnames => {
+                for pointer in ["/operating_mounts", "/pseudo_mounts"] {
+                    for mount in array(body, pointer) {
+                        let Some(source) = mount.get("source").and_then(Value::as_str) else {
+                            continue;
+                        };
+                        if self.policy.mount_sources {
+                            self.remember("src", source);
+                        }
+                        if self.policy.hostnames
+                            && let Some(server) = mount_server(source)
+                            && parse_ip(server).is_none()
+                        {
+                            self.remember("host", server);
+                        }
+                    }
+                }
+            }
+            "docker" if self.policy.container_names => {
+                for container in array(body, "/containers") {
+                    for name in container
+                        .get("names")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                    {
+                        self.remember("ctr", name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The machine's own hostname turns up in free text (journal messages,
+    /// prompts, mount options) without any section listing it.
+    fn learn_local_host(&mut self, hostname: &str) {
+        if self.policy.hostnames {
+            self.remember("host", hostname);
+        }
+    }
+
+    /// Remembers marked values so copies in summaries, parts, and other
+    /// sections are replaced too. Addresses are left to the IP pass.
+    fn learn_marked(&mut self, body: &Value, marks: &[SensitiveField]) {
+        for mark in marks {
+            let Some(prefix) = self.marked_prefix(mark.category) else {
+                continue;
+            };
+            if mark.category == SensitiveCategory::IpAddress {
+                continue;
+            }
+            let mut values = Vec::new();
+            if let Some(value) = body.pointer(&mark.pointer) {
+                collect_strings(value, &mut values);
+            }
+            for value in values {
+                self.remember(prefix, &value);
+            }
+        }
+    }
+
+    /// Token prefix for a marked category, or `None` when the policy keeps it.
+    fn marked_prefix(&self, category: SensitiveCategory) -> Option<&'static str> {
+        let (enabled, prefix) = match category {
+            SensitiveCategory::IpAddress => (self.policy.ip_addresses, "ip"),
+            SensitiveCategory::Hostname => (self.policy.hostnames, "host"),
+            SensitiveCategory::Username => (self.policy.usernames, "user"),
+            SensitiveCategory::ContainerName => (self.policy.container_names, "ctr"),
+            SensitiveCategory::MountSource => (self.policy.mount_sources, "src"),
+            SensitiveCategory::Command => (self.policy.commands, "cmd"),
+            SensitiveCategory::LogMessage => (self.policy.log_messages, "msg"),
+        };
+        enabled.then_some(prefix)
+    }
+
+    /// Replaces exactly the marked values, whatever their length.
+    fn redact_marked(&self, body: &mut Value, marks: &[SensitiveField]) {
+        for mark in marks {
+            let Some(prefix) = self.marked_prefix(mark.category) else {
+                continue;
+            };
+            let Some(value) = body.pointer_mut(&mark.pointer) else {
+                continue;
+            };
+            self.replace_strings(value, &|text| {
+                if mark.category == SensitiveCategory::IpAddress {
+                    self.redact_ips(text)
+                } else {
+                    self.token(prefix, text)
+                }
+            });
+        }
+    }
+
+    fn replace_strings(&self, value: &mut Value, replace: &dyn Fn(&str) -> String) {
+        match value {
+            Value::String(text) => *text = replace(text),
+            Value::Array(items) => items
+                .iter_mut()
+                .for_each(|item| self.replace_strings(item, replace)),
+            Value::Object(map) => map
+                .values_mut()
+                .for_each(|item| self.replace_strings(item, replace)),
+            _ => {}
+        }
+    }
+
+    fn remember(&mut self, prefix: &str, value: &str) {
+        // Very short values would match too much free text to be replaced safely.
+        if value.len() < 3 || self.known.contains_key(value) {
+            return;
+        }
+        let token = self.token(prefix, value);
+        self.known.insert(value.to_string(), token);
+    }
+
+    fn finish_learning(&mut self) {
+        self.ordered = self
+            .known
+            .iter()
+            .map(|(value, token)| (value.clone(), token.clone()))
+            .collect();
+        // Longest first so a value never clobbers part of a longer one.
+        self.ordered
+            .sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+    }
+
+    fn token(&self, prefix: &str, value: &str) -> String {
+        let hash = fnv1a(self.policy.salt.as_bytes(), value.as_bytes());
+        format!("{}-{:08x}", prefix, hash as u32)
+    }
+
+    fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::String(text) => *text = self.redact_text(text),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_value(item)),
+            Value::Object(map) => map.values_mut().for_each(|item| self.redact_value(item)),
+            _ => {}
+        }
+    }
+
+    fn redact_text(&self, input: &str) -> String {
+        if let Some(token) = self.known.get(input) {
+            return token.clone();
+        }
+
+        let mut output = input.to_string();
+        for (value, token) in &self.ordered {
+            if output.contains(value.as_str()) {
+                output = replace_words(&output, value, token);
+            }
+        }
+
+        if self.policy.ip_addresses {
+            output = self.redact_ips(&output);
+        }
+        output
+    }
+
+    fn redact_ips(&self, input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input;
+        while let Some(start) = rest.find(is_address_char) {
+            output.push_str(&rest[..start]);
+            let run = &rest[start..];
+            let end = run.find(|ch| !is_address_char(ch)).unwrap_or(run.len());
+            output.push_str(&self.redact_run(&run[..end]));
+            rest = &run[end..];
+        }
+        output.push_str(rest);
+        output
+    }
+
+    /// Sentence punctuation sticks to a run (`from 10.0.0.5.`), so trailing
+    /// dots and colons are peeled off one at a time until an address parses,
+    /// then put back after the token.
+    fn redact_run(&self, run: &str) -> String {
+        let mut end = run.len();
+        loop {
+            if let Some(redacted) = self.redact_address(&run[..end]) {
+                return format!("{}{}", redacted, &run[end..]);
+            }
+            match run[..end].strip_suffix(['.', ':']) {
+                Some(shorter) if !shorter.is_empty() => end = shorter.len(),
+                _ => return run.to_string(),
+            }
+        }
+    }
+
+    fn redact_address(&self, text: &str) -> Option<String> {
+        if let Some(ip) = parse_ip(text) {
+            return Some(self.ip_token(text, ip));
+        }
+        // `10.0.0.5:22` style socket addresses.
+        if let Some((host, port)) = text.rsplit_once(':')
+            && let Ok(ip) = host.parse::<Ipv4Addr>()
+        {
+            return Some(format!("{}:{}", self.ip_token(host, IpAddr::V4(ip)), port));
+        }
+        None
+    }
+
+    fn ip_token(&self, text: &str, ip: IpAddr) -> String {
+        if ip.is_loopback() || ip.is_unspecified() {
+            text.to_string()
+        } else {
+            self.token("ip", &ip.to_string())
+        }
+    }
+}
+
+fn collect_strings(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(text) => out.push(text.clone()),
+        Value::Array(items) => items.iter().for_each(|item| collect_strings(item, out)),
+        Value::Object(map) => map.values().for_each(|item| collect_strings(item, out)),
+        _ => {}
+    }
+}
+
+fn array<'v>(body: &'v Value, pointer: &str) -> &'v [Value] {
+    body.pointer(pointer)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Server part of a network mount source: `server:/path` or `//server/share`.
+fn mount_server(source: &str) -> Option<&str> {
+    let server = if let Some(rest) = source.strip_prefix("//") {
+        rest.split('/').next()?
+    } else if !source.starts_with('/') {
+        source.split_once(":/")?.0
+    } else {
+        return None;
+    };
+    (!server.is_empty()).then_some(server)
+}
+
+fn parse_ip(text: &str) -> Option<IpAddr> {
+    if let Ok(ip) = text.parse::<Ipv4Addr>() {
+        return Some(IpAddr::V4(ip));
+    }
+    if text.contains(':')
+        && let Ok(ip) = text.parse::<Ipv6Addr>()
+    {
+        return Some(IpAddr::V6(ip));
+    }
+    None
+}
+
+fn is_address_char(ch: char) -> bool {
+    ch.is_ascii_hexdigit() || ch == '.' || ch == ':'
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '-'
+}
+
+/// Replaces occurrences of `needle` that are not embedded in a longer word.
+fn replace_words(haystack: &str, needle: &str, replacement: &str) -> String {
+    let mut output = String::with_capacity(haystack.len());
+    let mut last = 0;
+    for (index, _) in haystack.match_indices(needle) {
+        if index < last {
+            continue;
+        }
+        let before = haystack[..index].chars().next_back();
+        let after = haystack[index + needle.len()..].chars().next();
+        if before.is_some_and(is_word_char) || after.is_some_and(is_word_char) {
+            continue;
+        }
+        output.push_str(&haystack[last..index]);
+        output.push_str(replacement);
+        last = index + needle.len();
+    }
+    output.push_str(&haystack[last..]);
+    output
+}
+
+fn fnv1a(salt: &[u8], value: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in salt.iter().chain(value) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vmic_sdk::Section;
+
+    fn synthetic_report() -> Report {
+        let network = Section::success(
+            "network",
+            "Network Overview",
+            json!({
+                "listeners": {
+                    "samples": [
+                        { "local_address": "10.20.30.40:22", "protocol": "tcp", "state": "listen" },
+                        { "local_address": "[2001:db8::7]:443", "protocol": "tcp6", "state": "listen" },
+                        { "local_address": "0.0.0.0:80", "protocol": "tcp", "state": "listen" }
+                    ]
+                }
+            }),
+        );
+        let users = Section::success(
+            "users",
+            "Local Users",
+            json!({
+                "users": [
+                    { "name": "alice", "uid": 1000, "home": "/home/alice", "system": false },
+                    { "name": "daemon", "uid": 1, "home": "/usr/sbin", "system": true }
+                ]
+            }),
+        );
+        let mut journal = Section::success(
+            "journal",
+            "Journal Warnings",
+            json!({
+                "entries": [
+                    { "message": "Failed password for mallory from 192.168.7.9 port 5022" }
+                ],
+                "ssh_summary": {
+                    "top_usernames": [{ "name": "mallory", "count": 3 }],
+                    "top_hosts": [{ "name": "192.168.7.9", "count": 3 }]
+                }
+            }),
+        );
+        journal.summary = Some("3 SSH failures from 192.168.7.9".to_string());
+        let mut storage = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [
+                    {
+                        "mount_point": "/data",
+                        "source": "nfs.internal:/exports/data",
+                        "fs_type": "nfs4",
+                        "operational": true,
+                        "usage_ratio": 0.99,
+                        "available_bytes": 1_000u64
+                    }
+                ]
+            }),
+        );
+        storage.add_subsection(vmic_sdk::SubSection::new(
+            "hotspots",
+            "Hotspots",
+            json!({
+                "directories": [{ "path": "/home/alice/cache", "size_bytes": 4096 }],
+                "logs": []
+            }),
+        ));
+        let mut docker = Section::success(
+            "docker",
+            "Docker Engine",
+            json!({ "containers": [{ "names": ["billing-api"], "image": "billing:1", "state": "running" }] }),
+        );
+        docker
+            .notes
+            .push("Container billing-api reported unhealthy status".to_string());
+        let mut containers = vmic_sdk::Table::new(["Name", "Address"]).titled("Containers");
+        containers.push_row(["billing-api", "10.20.30.40:8080"]);
+        docker.add_table(containers);
+
+        Report::new(vec![network, users, journal, storage, docker])
+    }
+
+    const SECRETS: &[&str] = &[
+        "10.20.30.40",
+        "2001:db8::7",
+        "192.168.7.9",
+        "alice",
+        "mallory",
+        "nfs.internal",
+        "billing-api",
+    ];
+
+    #[test]
+    fn nothing_leaks_into_rendered_output() {
+        let mut report = synthetic_report();
+        report.redact(&RedactionPolicy::all());
+
+        let markdown = report.to_markdown().expect("markdown");
+        let html = report.to_html().expect("html");
+        let json = report.to_json_value().to_string();
+        for output in [&markdown, &html, &json] {
+            for secret in SECRETS {
+                assert!(!output.contains(secret), "{secret} leaked");
+            }
+        }
+        assert!(json.contains("0.0.0.0:80"));
+        assert!(json.contains("daemon"));
+    }
+
+    #[test]
+    fn tokens_are_stable_within_a_report() {
+        let mut report = synthetic_report();
+        report.redact(&RedactionPolicy::all());
+        let journal = &report.sections[2];
+        let host = journal
+            .body
+            .pointer("/ssh_summary/top_hosts/0/name")
+            .and_then(Value::as_str)
+            .unwrap()
+            .to_string();
+        assert!(host.starts_with("ip-"));
+        assert!(journal.summary.as_deref().unwrap().contains(&host));
+        let message = journal
+            .body
+            .pointer("/entries/0/message")
+            .and_then(Value::as_str)
+            .unwrap();
+        assert!(message.contains(&host));
+        let home = report.sections[1]
+            .body
+            .pointer("/users/0/home")
+            .and_then(Value::as_str)
+            .unwrap();
+        assert!(home.starts_with("/home/user-"));
+    }
+
+    #[test]
+    fn digest_messages_are_redacted() {
+        let mut report = synthetic_report();
+        report.health_digest.findings[0].message = "Peer 10.20.30.40 unreachable".to_string();
+        report.redact(&RedactionPolicy {
+            ip_addresses: true,
+            ..RedactionPolicy::default()
+        });
+        assert!(
+            !report.health_digest.findings[0]
+                .message
+                .contains("10.20.30.40")
+        );
+        // Categories that are off stay untouched.
+        let json = report.to_json_value().to_string();
+        assert!(json.contains("alice"));
+    }
+
+    #[test]
+    fn hostnames_cover_ssh_peers_and_mount_servers() {
+        let mut report = synthetic_report();
+        report.sections[2].body["ssh_summary"]["top_hosts"] =
+            json!([{ "name": "scanner.example.net", "count": 2 }]);
+        report.redact(&RedactionPolicy {
+            hostnames: true,
+            ..RedactionPolicy::default()
+        });
+        let json = report.to_json_value().to_string();
+        assert!(!json.contains("scanner.example.net"));
+        assert!(!json.contains("nfs.internal"));
+        assert!(json.contains(":/exports/data"));
+        assert!(json.contains("mallory"));
+
+        assert_eq!(mount_server("//files/share"), Some("files"));
+        assert_eq!(mount_server("/dev/sda1"), None);
+        assert_eq!(mount_server("overlay"), None);
+    }
+
+    #[test]
+    fn local_hostname_is_redacted_like_other_hosts() {
+        let policy = RedactionPolicy {
+            hostnames: true,
+            ..RedactionPolicy::default()
+        };
+        let mut redactor = Redactor::new(&policy);
+        redactor.learn_local_host("web-01.internal");
+        redactor.finish_learning();
+        let token = policy.redact_hostname("web-01.internal");
+        assert!(token.starts_with("host-"));
+        assert_eq!(
+            redactor.redact_text("backup on web-01.internal failed"),
+            format!("backup on {} failed", token)
+        );
+
+        let kept = RedactionPolicy {
+            usernames: true,
+            ..RedactionPolicy::default()
+        };
+        let mut redactor = Redactor::new(&kept);
+        redactor.learn_local_host("web-01.internal");
+        redactor.finish_learning();
+        assert_eq!(kept.redact_hostname("web-01.internal"), "web-01.internal");
+        assert_eq!(
+            redactor.redact_text("backup on web-01.internal failed"),
+            "backup on web-01.internal failed"
+        );
+    }
+
+    #[test]
+    fn sentence_final_addresses_are_redacted() {
+        let mut report = synthetic_report();
+        report.sections[2].body["entries"] = json!([
+            { "message": "Connection closed by 10.0.0.5." },
+            { "message": "Dropped peer 2001:db8::1: timeout" },
+            { "message": "Bound to 10.0.0.6:22." }
+        ]);
+        report.redact(&RedactionPolicy {
+            ip_addresses: true,
+            ..RedactionPolicy::default()
+        });
+        let entries = &report.sections[2].body["entries"];
+        let message = |index: usize| entries[index]["message"].as_str().unwrap();
+        assert!(!message(0).contains("10.0.0.5"));
+        assert!(message(0).starts_with("Connection closed by ip-"));
+        assert!(message(0).ends_with('.'));
+        assert!(!message(1).contains("2001:db8::1"));
+        assert!(message(1).ends_with(": timeout"));
+        assert!(!message(2).contains("10.0.0.6"));
+        assert!(message(2).ends_with(":22."));
+    }
+
+    #[test]
+    fn word_replacement_respects_boundaries() {
+        assert_eq!(
+            replace_words("app-1 app-10 app-1.", "app-1", "X"),
+            "X app-10 X."
+        );
+        assert_eq!(replace_words("/home/alice", "alice", "u"), "/home/u");
+    }
+
+    #[test]
+    fn salt_changes_tokens() {
+        let plain = RedactionPolicy::all();
+        let salted = RedactionPolicy {
+            salt: "fleet-a".to_string(),
+            ..RedactionPolicy::all()
+        };
+        assert_ne!(
+            Redactor::new(&plain).token("ip", "10.0.0.1"),
+            Redactor::new(&salted).token("ip", "10.0.0.1")
+        );
+    }
+
+    fn marked_cron_report() -> Report {
+        let mut cron = Section::success(
+            "cron",
+            "Scheduled Jobs",
+            json!({
+                "system_crontab": [
+                    { "schedule": "0 3 * * *", "user": "root", "command": "backup --password=hunter2" },
+                    { "schedule": "@daily", "user": "root", "command": "ls" }
+                ]
+            }),
+        );
+        cron.mark_sensitive("/system_crontab/0/command", SensitiveCategory::Command);
+        cron.mark_sensitive("/system_crontab/1/command", SensitiveCategory::Command);
+        cron.mark_sensitive("/missing/pointer", SensitiveCategory::Command);
+        let mut table = vmic_sdk::Table::new(["Command"]);
+        table.push_row(["backup --password=hunter2"]);
+        cron.add_table(table);
+        Report::new(vec![cron])
+    }
+
+    #[test]
+    fn marked_values_are_replaced_exactly() {
+        let mut report = marked_cron_report();
+        report.redact(&RedactionPolicy {
+            commands: true,
+            ..RedactionPolicy::default()
+        });
+
+        let body = &report.sections[0].body;
+        let first = body["system_crontab"][0]["command"].as_str().unwrap();
+        assert!(first.starts_with("cmd-"), "{first}");
+        assert_ne!(body["system_crontab"][1]["command"], json!("ls"));
+        assert_eq!(body["system_crontab"][0]["user"], json!("root"));
+
+        let json = serde_json::to_string(&report.to_json_value()).unwrap();
+        assert!(!json.contains("hunter2"));
+        assert!(!json.contains("sensitive"));
+        assert!(!report.to_markdown().unwrap().contains("hunter2"));
+    }
+
+    #[test]
+    fn marked_values_follow_the_policy() {
+        let mut report = marked_cron_report();
+        report.redact(&RedactionPolicy {
+            usernames: true,
+            ..RedactionPolicy::default()
+        });
+        assert_eq!(
+            report.sections[0].body["system_crontab"][0]["command"],
+            json!("backup --password=hunter2")
+        );
+    }
+}
@@ -1,5 +1,9 @@
+use anyhow::{Result, anyhow};
+use jsonschema::JSONSchema;
 use once_cell::sync::Lazy;
 use serde_json::Value;
+use std::collections::HashMap;
+use vmic_sdk::Section;
 
 /// JSON Schema describing the machine-readable VMIC report format (Draft 2020-12).
 pub static REPORT_SCHEMA_JSON: &str = include_str!("../../schemas/vmic-report.schema.json");
@@ -14,3 +18,366 @@ pub static REPORT_SCHEMA_VALUE: Lazy<Value> = Lazy::new(|| {
 pub fn report_schema() -> &'static Value {
     &REPORT_SCHEMA_VALUE
 }
+
+static COMPILED_REPORT_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
+    JSONSchema::compile(report_schema()).expect("embedded VMIC report schema must compile")
+});
+
+/// Checks a report document against the published report schema; the error
+/// names the first violation and how many more were found.
+pub fn validate_report(document: &Value) -> Result<()> {
+    let errors: Vec<String> = match COMPILED_REPORT_SCHEMA.validate(document) {
+        Ok(()) => return Ok(()),
+        Err(errors) => errors.map(|error| describe(&error)).collect(),
+    };
+    let mut message = format!("report does not match the schema: {}", errors[0]);
+    if errors.len() > 1 {
+        message.push_str(&format!(" (and {} more)", errors.len() - 1));
+    }
+    Err(anyhow!(message))
+}
+
+/// One schema violation in a report document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON pointer to the offending value; empty for the document root.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{}: {}", pointer, self.message)
+    }
+}
+
+/// Lists every violation of the report schema and, for non-error sections with
+/// a published body schema, of that section schema and its subsection entries.
+/// Empty when the document is valid.
+pub fn report_violations(document: &Value) -> Vec<Violation> {
+    let mut violations: Vec<Violation> = match COMPILED_REPORT_SCHEMA.validate(document) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|error| Violation {
+                pointer: error.instance_path.to_string(),
+                message: error.to_string(),
+            })
+            .collect(),
+    };
+
+    let sections = document.get("sections").and_then(Value::as_array);
+    for (index, section) in sections.into_iter().flatten().enumerate() {
+        if section.get("status").and_then(Value::as_str) == Some("error") {
+            continue;
+        }
+        let Some(id) = section.get("id").and_then(Value::as_str) else {
+            continue;
+        };
+        if let (Some(compiled), Some(body)) =
+            (COMPILED_SECTION_SCHEMAS.get(id), section.get("body"))
+            && let Err(errors) = compiled.validate(body)
+        {
+            violations.extend(errors.map(|error| Violation {
+                pointer: format!("/sections/{}/body{}", index, error.instance_path),
+                message: error.to_string(),
+            }));
+        }
+        let subsections = section.get("subsections").and_then(Value::as_array);
+        for (position, subsection) in subsections.into_iter().flatten().enumerate() {
+            let Some(compiled) = subsection
+                .get("id")
+                .and_then(Value::as_str)
+                .and_then(|sub_id| COMPILED_SUBSECTION_SCHEMAS.get(&(id, sub_id)))
+            else {
+                continue;
+            };
+            let Some(body) = subsection.get("body") else {
+                continue;
+            };
+            if let Err(errors) = compiled.validate(body) {
+                violations.extend(errors.map(|error| Violation {
+                    pointer: format!(
+                        "/sections/{}/subsections/{}/body{}",
+                        index, position, error.instance_path
+                    ),
+                    message: error.to_string(),
+                }));
+            }
+        }
+    }
+    violations
+}
+
+fn describe(error: &jsonschema::ValidationError<'_>) -> String {
+    let path = error.instance_path.to_string();
+    if path.is_empty() {
+        error.to_string()
+    } else {
+        format!("{} at {}", error, path)
+    }
+}
+
+/// Embedded body schemas keyed by section id.
+static SECTION_SCHEMA_SOURCES: &[(&str, &str)] = &[
+    ("storage", include_str!("section_schemas/storage.json")),
+    ("proc", include_str!("section_schemas/proc.json")),
+    ("network", include_str!("section_schemas/network.json")),
+    ("docker", include_str!("section_schemas/docker.json")),
+    ("services", include_str!("section_schemas/services.json")),
+    ("users", include_str!("section_schemas/users.json")),
+    ("journal", include_str!("section_schemas/journal.json")),
+];
+
+static SECTION_SCHEMAS: Lazy<HashMap<&'static str, Value>> = Lazy::new(|| {
+    SECTION_SCHEMA_SOURCES
+        .iter()
+        .map(|(id, source)| {
+            let value = serde_json::from_str(source)
+                .unwrap_or_else(|err| panic!("embedded {id} section schema is invalid: {err}"));
+            (*id, value)
+        })
+        .collect()
+});
+
+static COMPILED_SECTION_SCHEMAS: Lazy<HashMap<&'static str, JSONSchema>> = Lazy::new(|| {
+    SECTION_SCHEMAS
+        .iter()
+        .map(|(id, schema)| {
+            let compiled = JSONSchema::compile(schema)
+                .unwrap_or_else(|err| panic!("embedded {id} section schema must compile: {err}"));
+            (*id, compiled)
+        })
+        .collect()
+});
+
+/// Body schemas of subsections, keyed by section and subsection id: the
+/// entries under a section schema's `subsections` key, compiled together with
+/// that schema's `$defs` so their references resolve.
+static COMPILED_SUBSECTION_SCHEMAS: Lazy<HashMap<(&'static str, &'static str), JSONSchema>> =
+    Lazy::new(|| {
+        let mut compiled = HashMap::new();
+        for (id, schema) in SECTION_SCHEMAS.iter() {
+            let Some(subsections) = schema.get("subsections").and_then(Value::as_object) else {
+                continue;
+            };
+            for (sub_id, sub_schema) in subsections {
+                let mut document = sub_schema.clone();
+                if let (Some(fields), Some(defs)) = (document.as_object_mut(), schema.get("$defs"))
+                {
+                    fields.insert("$defs".to_string(), defs.clone());
+                }
+                let schema = JSONSchema::compile(&document).unwrap_or_else(|err| {
+                    panic!("embedded {id}.{sub_id} subsection schema must compile: {err}")
+                });
+                compiled.insert((*id, sub_id.as_str()), schema);
+            }
+        }
+        compiled
+    });
+
+/// Returns the body schema for a built-in section id, if one is published.
+pub fn section_schema(id: &str) -> Option<&'static Value> {
+    SECTION_SCHEMAS.get(id)
+}
+
+/// Section ids with a published body schema, sorted.
+pub fn section_schema_ids() -> Vec<&'static str> {
+    let mut ids: Vec<&'static str> = SECTION_SCHEMA_SOURCES.iter().map(|(id, _)| *id).collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Validates a section body, and its subsection bodies, against the section's
+/// schema and returns a note describing the mismatch. Error sections and ids
+/// without a schema are skipped.
+pub(crate) fn validate_section_body(section: &Section) -> Option<String> {
+    if matches!(section.status, vmic_sdk::SectionStatus::Error) {
+        return None;
+    }
+    let mut errors: Vec<String> = Vec::new();
+    if let Some(compiled) = COMPILED_SECTION_SCHEMAS.get(section.id.as_ref())
+        && let Err(found) = compiled.validate(&section.body)
+    {
+        errors.extend(found.map(|error| describe(&error)));
+    }
+    for subsection in &section.subsections {
+        if let Some(compiled) =
+            COMPILED_SUBSECTION_SCHEMAS.get(&(section.id.as_ref(), subsection.id.as_str()))
+            && let Err(found) = compiled.validate(&subsection.body)
+        {
+            errors.extend(
+                found.map(|error| format!("{} in subsection {}", describe(&error), subsection.id)),
+            );
+        }
+    }
+    if errors.is_empty() {
+        return None;
+    }
+
+    let mut note = format!(
+        "body does not match the {} schema: {}",
+        section.id, errors[0]
+    );
+    if errors.len() > 1 {
+        note.push_str(&format!(" (and {} more)", errors.len() - 1));
+    }
+    Some(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn every_embedded_schema_compiles() {
+        for (id, _) in SECTION_SCHEMA_SOURCES {
+            assert!(section_schema(id).is_some());
+            assert!(COMPILED_SECTION_SCHEMAS.contains_key(id));
+        }
+        assert!(section_schema("unknown").is_none());
+
+        let ids = section_schema_ids();
+        assert_eq!(ids.len(), SECTION_SCHEMA_SOURCES.len());
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn misspelled_mount_key_is_reported() {
+        let section = Section::success(
+            "storage",
+            "Storage Overview",
+            json!({
+                "operating_mounts": [{
+                    "mount_point": "/",
+                    "source": "/dev/sda1",
+                    "fs_type": "ext4",
+                    "read_only": false,
+                    "category": "operating",
+                    "operational": true,
+                    "total_bytes": 100,
+                    "used_bytes": 40,
+                    "avaliable_bytes": 60,
+                    "usage_ratio": 0.4
+                }]
+            }),
+        );
+        let note = validate_section_body(&section).expect("schema mismatch");
+        assert!(note.starts_with("body does not match the storage schema: "));
+        assert!(note.contains("/operating_mounts/0"));
+    }
+
+    #[test]
+    fn docker_bodies_validate_for_engine_and_fallback_shapes() {
+        let live = Section::success(
+            "docker",
+            "Docker Containers",
+            json!({
+                "engine": { "version": "27.0.1", "api_version": "1.46" },
+                "containers": [{
+                    "id": "abc123",
+                    "names": ["web"],
+                    "image": "nginx:latest",
+                    "state": "running",
+                    "status": "Up 2 hours",
+                    "metrics": { "cpu_percent": 1.5, "memory_usage_bytes": 1024 },
+                    "health": null,
+                    "health_failing_streak": null,
+                    "restart_count": 3,
+                    "size_rw_bytes": null,
+                    "size_root_fs_bytes": null,
+                    "mounts": [{ "destination": "/data", "type": "volume", "rw": true }]
+                }],
+                "notes": [],
+                "storage": { "image_total_bytes": 10, "image_count": 1, "volume_total_bytes": null, "volume_count": 0 }
+            }),
+        );
+        assert_eq!(validate_section_body(&live), None);
+
+        let fallback = Section::degraded(
+            "docker",
+            "Docker Containers",
+            "daemon unavailable".to_string(),
+            json!({ "engine": { "status": "unavailable" }, "containers": [], "storage": null }),
+        );
+        assert_eq!(validate_section_body(&fallback), None);
+    }
+
+    #[test]
+    fn report_violations_cover_report_and_section_bodies() {
+        let report = crate::Report::new(vec![Section::success(
+            "storage",
+            "Storage Overview",
+            json!({ "operating_mounts": [{ "mount_point": 1 }] }),
+        )]);
+        let mut document = report.to_json_value();
+        document["metadata"]["generated_at_epoch"] = json!("soon");
+
+        let violations = report_violations(&document);
+        assert!(
+            violations
+                .iter()
+                .any(|violation| violation.pointer == "/metadata/generated_at_epoch")
+        );
+        assert!(violations.iter().any(|violation| {
+            violation
+                .pointer
+                .starts_with("/sections/0/body/operating_mounts/0")
+        }));
+
+        let valid = crate::Report::new(vec![Section::error(
+            "storage",
+            "Storage Overview",
+            "boom".to_string(),
+        )]);
+        assert!(report_violations(&valid.to_json_value()).is_empty());
+        assert_eq!(
+            report_violations(&json!([]))
+                .first()
+                .map(ToString::to_string),
+            Some("/: [] is not of type \"object\"".to_string())
+        );
+    }
+
+    #[test]
+    fn subsection_bodies_are_checked_against_the_section_schema() {
+        let mut storage = Section::success("storage", "Storage Overview", json!({}));
+        storage.add_subsection(vmic_sdk::SubSection::new(
+            "hotspots",
+            "Hotspots",
+            json!({ "directories": [{ "path": "/srv", "size_bytes": 1 }], "logs": [] }),
+        ));
+        storage.add_subsection(vmic_sdk::SubSection::new(
+            "extra",
+            "Extra",
+            json!({ "anything": true }),
+        ));
+        assert!(validate_section_body(&storage).is_none());
+
+        storage.subsections[0].body["directories"][0]["size_bytes"] = json!("big");
+        let note = validate_section_body(&storage).expect("schema mismatch");
+        assert!(note.starts_with("body does not match the storage schema: "));
+        assert!(note.ends_with("in subsection hotspots"));
+
+        let document = crate::Report::new(vec![storage]).to_json_value();
+        let violations = report_violations(&document);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].pointer,
+            "/sections/0/subsections/0/body/directories/0/size_bytes"
+        );
+    }
+
+    #[test]
+    fn error_sections_and_unknown_ids_are_skipped() {
+        let error = Section::error("storage", "Storage Overview", "boom".to_string());
+        assert!(validate_section_body(&error).is_none());
+        let custom = Section::success("custom", "Custom", json!({ "anything": true }));
+        assert!(validate_section_body(&custom).is_none());
+    }
+}
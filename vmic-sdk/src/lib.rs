@@ -1,11 +1,39 @@
-use anyhow::Result;
-use serde::Serialize;
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Collectors running longer than this are flagged unless the context overrides it.
+pub const DEFAULT_SLOW_COLLECTOR_THRESHOLD: Duration = Duration::from_secs(5);
 
 /// Data collection context; can be extended with environment parameters.
 #[derive(Debug, Default, Clone)]
 pub struct CollectionContext {
     since: Option<String>,
+    body_budget_bytes: Option<usize>,
+    locale: Locale,
+    detail_level: DetailLevel,
+    section_order: Vec<String>,
+    validate_schemas: bool,
+    fingerprint_salt: Option<String>,
+    slow_collector_threshold: Option<Duration>,
+    collector_timeout: Option<Duration>,
+    collector_timeouts: BTreeMap<String, Duration>,
+    max_jobs: Option<NonZeroUsize>,
+    skip_unavailable: bool,
+    settings: BTreeMap<String, BTreeMap<String, String>>,
+    root: Option<PathBuf>,
+    registry: Option<Arc<CollectorRegistry>>,
+    cancellation: CancellationToken,
+    #[cfg(feature = "async")]
+    runtime: Option<tokio::runtime::Handle>,
 }
 
 impl CollectionContext {
@@ -16,6 +44,7 @@ impl CollectionContext {
     pub fn with_since<S: Into<String>>(since: S) -> Self {
         Self {
             since: Some(since.into()),
+            ..Self::default()
         }
     }
 
@@ -26,18 +55,484 @@ impl CollectionContext {
     pub fn since(&self) -> Option<&str> {
         self.since.as_deref()
     }
+
+    /// Limits the serialized size of each section body; oversized bodies are trimmed
+    /// after the health digest has been computed.
+    pub fn set_body_budget_bytes(&mut self, budget: Option<usize>) {
+        self.body_budget_bytes = budget;
+    }
+
+    pub fn body_budget_bytes(&self) -> Option<usize> {
+        self.body_budget_bytes
+    }
+
+    /// Language used for section titles; JSON body keys are never localized.
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// How much work collectors should do; expensive probes are skipped at
+    /// [`DetailLevel::Minimal`] and limits are raised at [`DetailLevel::Full`].
+    pub fn set_detail_level(&mut self, level: DetailLevel) {
+        self.detail_level = level;
+    }
+
+    pub fn detail_level(&self) -> DetailLevel {
+        self.detail_level
+    }
+
+    /// Section ids to place first, in the given order; remaining sections keep the
+    /// canonical order.
+    pub fn set_section_order<I, S>(&mut self, order: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.section_order = order.into_iter().map(Into::into).collect();
+    }
+
+    pub fn section_order(&self) -> &[String] {
+        &self.section_order
+    }
+
+    /// Checks built-in section bodies against their published schemas and notes
+    /// any mismatch on the section; collection never fails because of it.
+    pub fn set_validate_schemas(&mut self, enabled: bool) {
+        self.validate_schemas = enabled;
+    }
+
+    pub fn validate_schemas(&self) -> bool {
+        self.validate_schemas
+    }
+
+    /// Salt for the report's host fingerprint; without one no fingerprint is emitted.
+    pub fn set_fingerprint_salt<S: Into<String>>(&mut self, salt: Option<S>) {
+        self.fingerprint_salt = salt.map(Into::into);
+    }
+
+    pub fn fingerprint_salt(&self) -> Option<&str> {
+        self.fingerprint_salt.as_deref()
+    }
+
+    /// Collectors that run longer than `threshold` get a note and are counted in
+    /// the report timing block.
+    pub fn set_slow_collector_threshold(&mut self, threshold: Duration) {
+        self.slow_collector_threshold = Some(threshold);
+    }
+
+    pub fn slow_collector_threshold(&self) -> Duration {
+        self.slow_collector_threshold
+            .unwrap_or(DEFAULT_SLOW_COLLECTOR_THRESHOLD)
+    }
+
+    /// Limit for every collector without its own override; `None` waits forever.
+    pub fn set_collector_timeout(&mut self, timeout: Option<Duration>) {
+        self.collector_timeout = timeout;
+    }
+
+    /// Limit for the collector with this id, taking precedence over the global one.
+    pub fn set_collector_timeout_for<S: Into<String>>(&mut self, id: S, timeout: Duration) {
+        self.collector_timeouts.insert(id.into(), timeout);
+    }
+
+    /// Effective limit for collector `id`.
+    pub fn collector_timeout(&self, id: &str) -> Option<Duration> {
+        self.collector_timeouts
+            .get(id)
+            .copied()
+            .or(self.collector_timeout)
+    }
+
+    /// Upper bound on collectors running at once; `None` lets the core pick
+    /// one from the available parallelism. One job runs collectors in sequence.
+    pub fn set_max_jobs(&mut self, jobs: Option<NonZeroUsize>) {
+        self.max_jobs = jobs;
+    }
+
+    pub fn max_jobs(&self) -> Option<NonZeroUsize> {
+        self.max_jobs
+    }
+
+    /// Probes every collector first and replaces those reporting
+    /// [`Availability::Unavailable`] with a degraded stub section instead of
+    /// running them.
+    pub fn set_skip_unavailable(&mut self, enabled: bool) {
+        self.skip_unavailable = enabled;
+    }
+
+    pub fn skip_unavailable(&self) -> bool {
+        self.skip_unavailable
+    }
+
+    /// Directory that stands in for `/` when collectors read host files, such
+    /// as an extracted sosreport-style tree; `None` reads the live system.
+    pub fn set_root<P: Into<PathBuf>>(&mut self, root: Option<P>) {
+        self.root = root.map(Into::into);
+    }
+
+    pub fn root(&self) -> &Path {
+        self.root.as_deref().unwrap_or(Path::new("/"))
+    }
+
+    /// Host path such as `/etc/passwd` resolved under [`root`](Self::root).
+    pub fn path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        let path = path.as_ref();
+        match &self.root {
+            Some(root) => root.join(path.strip_prefix("/").unwrap_or(path)),
+            None => path.to_path_buf(),
+        }
+    }
+
+    /// Collectors to run instead of the ones linked into the binary; `None`
+    /// collects from the compile-time registry.
+    pub fn set_registry(&mut self, registry: Option<CollectorRegistry>) {
+        self.registry = registry.map(Arc::new);
+    }
+
+    pub fn registry(&self) -> Option<&CollectorRegistry> {
+        self.registry.as_deref()
+    }
+
+    /// Token collectors poll in long loops; clones of the context share it.
+    /// The core gives each collector with a timeout a child token and cancels
+    /// it when the limit expires.
+    pub fn set_cancellation(&mut self, token: CancellationToken) {
+        self.cancellation = token;
+    }
+
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Runtime that [`block_on`](Self::block_on) drives futures on; the core
+    /// installs one shared runtime for every collector of a run.
+    #[cfg(feature = "async")]
+    pub fn set_runtime(&mut self, runtime: Option<tokio::runtime::Handle>) {
+        self.runtime = runtime;
+    }
+
+    #[cfg(feature = "async")]
+    pub fn runtime(&self) -> Option<&tokio::runtime::Handle> {
+        self.runtime.as_ref()
+    }
+
+    /// Runs `future` to completion on the shared runtime, or on a temporary
+    /// current-thread runtime when none is set (a synchronous caller using the
+    /// collector directly). Must not be called from inside an async task.
+    #[cfg(feature = "async")]
+    pub fn block_on<F: std::future::Future>(&self, future: F) -> Result<F::Output> {
+        match &self.runtime {
+            Some(handle) => Ok(handle.block_on(future)),
+            None => {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|err| anyhow!("failed to create Tokio runtime: {}", err))?;
+                Ok(runtime.block_on(future))
+            }
+        }
+    }
+
+    /// Free-form option `key` for collector `id`, as given by `--set id.key=value`.
+    /// Collectors interpret their own keys; a later value replaces an earlier one.
+    pub fn set_setting<I, K, V>(&mut self, id: I, key: K, value: V)
+    where
+        I: Into<String>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.settings
+            .entry(id.into())
+            .or_default()
+            .insert(key.into(), value.into());
+    }
+
+    /// Builder form of [`set_setting`](Self::set_setting) for embedding
+    /// applications.
+    pub fn with_setting<I, K, V>(mut self, id: I, key: K, value: V) -> Self
+    where
+        I: Into<String>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.set_setting(id, key, value);
+        self
+    }
+
+    pub fn setting(&self, id: &str, key: &str) -> Option<&str> {
+        self.settings.get(id)?.get(key).map(String::as_str)
+    }
+
+    /// All settings given for collector `id`, sorted by key.
+    pub fn settings(&self, id: &str) -> impl Iterator<Item = (&str, &str)> {
+        self.settings
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Setting `key` for collector `id` parsed as `T`; the error names the
+    /// setting and the rejected value.
+    pub fn setting_as<T>(&self, id: &str, key: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.setting(id, key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|err| anyhow!("invalid value '{}' for {}.{}: {}", value, id, key, err))
+            })
+            .transpose()
+    }
+}
+
+/// Cooperative stop signal. Clones share one flag; a [`child`](Self::child)
+/// is cancelled with its parent but can also be cancelled on its own.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    flag: Arc<AtomicBool>,
+    ancestors: Vec<Arc<AtomicBool>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token cancelled by itself, by `self`, or by any of `self`'s ancestors.
+    pub fn child(&self) -> Self {
+        let mut ancestors = self.ancestors.clone();
+        ancestors.push(Arc::clone(&self.flag));
+        Self {
+            flag: Arc::new(AtomicBool::new(false)),
+            ancestors,
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        std::iter::once(&self.flag)
+            .chain(&self.ancestors)
+            .any(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    /// `Err` with [`ErrorKind::Timeout`] once cancelled, for `?` in scan loops.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(CollectorError::new(ErrorKind::Timeout, "collection cancelled").into());
+        }
+        Ok(())
+    }
+}
+
+/// Report language for titles and render labels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ru => "ru",
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "en" => Ok(Locale::En),
+            "ru" => Ok(Locale::Ru),
+            other => Err(anyhow!(
+                "unsupported locale '{}' (expected en or ru)",
+                other
+            )),
+        }
+    }
 }
 
+/// Collection depth requested by the caller and recorded in the report metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DetailLevel {
+    /// Cheap snapshot: no per-container stats, no filesystem hotspot scan.
+    Minimal,
+    #[default]
+    Standard,
+    /// Deeper scans and longer log excerpts.
+    Full,
+}
+
+impl DetailLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetailLevel::Minimal => "minimal",
+            DetailLevel::Standard => "standard",
+            DetailLevel::Full => "full",
+        }
+    }
+}
+
+impl fmt::Display for DetailLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DetailLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "minimal" => Ok(DetailLevel::Minimal),
+            "standard" => Ok(DetailLevel::Standard),
+            "full" => Ok(DetailLevel::Full),
+            other => Err(anyhow!(
+                "unsupported detail level '{}' (expected minimal, standard or full)",
+                other
+            )),
+        }
+    }
+}
+
+/// Tag implied by [`CollectorMetadata::requires_root`].
+pub const REQUIRES_ROOT_TAG: &str = "requires-root";
+
 /// Collector metadata used for rendering and logging.
 #[derive(Debug, Clone, Copy)]
 pub struct CollectorMetadata {
     pub id: &'static str,
     pub title: &'static str,
     pub description: &'static str,
+    /// Groups such as `security` or `spawns-subprocess`, used for selection
+    /// and listings.
+    pub tags: &'static [&'static str],
+    /// Complete data needs root (or an equivalent group); otherwise the
+    /// section degrades.
+    pub requires_root: bool,
+    /// Position in the report and in the collection queue; lower values come
+    /// first and ties are broken by id.
+    pub order: i32,
+    /// Version of the implementing crate, normally [`collector_version!`].
+    pub version: &'static str,
+    /// Resources a run typically takes, for listings and `cost:` selectors.
+    pub cost: CostHint,
+}
+
+impl CollectorMetadata {
+    /// `tags`, plus [`REQUIRES_ROOT_TAG`] when `requires_root` is set.
+    pub fn all_tags(&self) -> Vec<&'static str> {
+        let mut tags = self.tags.to_vec();
+        if self.requires_root && !tags.contains(&REQUIRES_ROOT_TAG) {
+            tags.push(REQUIRES_ROOT_TAG);
+        }
+        tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.all_tags().contains(&tag)
+    }
+}
+
+/// Rough share of one resource a collector uses on a typical host.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostLevel {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+impl CostLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostLevel::Low => "low",
+            CostLevel::Medium => "medium",
+            CostLevel::High => "high",
+        }
+    }
+}
+
+impl fmt::Display for CostLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Resource cost a collector declares so operators on small hosts can leave
+/// heavy ones out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostHint {
+    pub cpu: CostLevel,
+    pub io: CostLevel,
+    /// Runs external commands such as `journalctl` or `systemctl`.
+    pub spawns_processes: bool,
+}
+
+impl CostHint {
+    /// Low CPU and I/O, no child processes.
+    pub const LOW: CostHint = CostHint {
+        cpu: CostLevel::Low,
+        io: CostLevel::Low,
+        spawns_processes: false,
+    };
+
+    /// Keys matched by `cost:<key>` selectors: `<level>-cpu`, `<level>-io`,
+    /// and `spawns-processes` when it applies.
+    pub fn selectors(&self) -> Vec<String> {
+        let mut keys = vec![format!("{}-cpu", self.cpu), format!("{}-io", self.io)];
+        if self.spawns_processes {
+            keys.push("spawns-processes".to_string());
+        }
+        keys
+    }
+
+    pub fn matches(&self, selector: &str) -> bool {
+        self.selectors().iter().any(|key| key == selector)
+    }
+}
+
+impl fmt::Display for CostHint {
+    /// `cpu=low io=high`, with ` spawns` appended when it spawns processes.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cpu={} io={}", self.cpu, self.io)?;
+        if self.spawns_processes {
+            f.write_str(" spawns")?;
+        }
+        Ok(())
+    }
 }
 
 /// Section status describing success or degraded collection.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SectionStatus {
     Success,
@@ -59,84 +554,620 @@ impl fmt::Display for SectionStatus {
 /// Result produced by a collector.
 #[derive(Debug, Serialize)]
 pub struct Section {
-    pub id: &'static str,
-    pub title: &'static str,
+    /// Borrowed for the usual one-section-per-collector case; owned when a
+    /// collector builds ids at run time (one section per disk or project) or a
+    /// report is read back from JSON.
+    pub id: Cow<'static, str>,
+    pub title: Cow<'static, str>,
+    /// Collector description, filled in by the core when the section is collected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'static str>,
+    /// Collector tags, filled in by the core like `description`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<&'static str>,
+    /// Version of the collector that produced the section, filled in by the
+    /// core like `description`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collector_version: Option<&'static str>,
+    /// Declared cost of the collector, filled in by the core like `description`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostHint>,
     pub status: SectionStatus,
     pub summary: Option<String>,
     pub body: serde_json::Value,
     pub notes: Vec<String>,
     pub duration_ms: Option<u64>,
+    /// Problems the collector detected itself; the core merges them into the
+    /// health digest.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<SectionFinding>,
+    /// Render-ready views of the body, in display order. Renderers prefer them
+    /// to reading the body; they are not serialized, so the JSON report only
+    /// carries `body`.
+    #[serde(skip)]
+    pub parts: Vec<SectionPart>,
+    /// Body values marked with [`Section::mark_sensitive`]; consumed by the
+    /// core redaction pass and never serialized.
+    #[serde(skip)]
+    pub sensitive: Vec<SensitiveField>,
+    /// Named groups rendered under their own sub-headers after the section's
+    /// own content, in order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subsections: Vec<SubSection>,
 }
 
 impl Section {
-    pub fn success(id: &'static str, title: &'static str, body: serde_json::Value) -> Self {
+    pub fn success<I, T>(id: I, title: T, body: serde_json::Value) -> Self
+    where
+        I: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
         Self {
-            id,
-            title,
+            id: id.into(),
+            title: title.into(),
+            description: None,
+            tags: Vec::new(),
+            collector_version: None,
+            cost: None,
             status: SectionStatus::Success,
             summary: None,
             body,
             notes: Vec::new(),
             duration_ms: None,
+            findings: Vec::new(),
+            parts: Vec::new(),
+            sensitive: Vec::new(),
+            subsections: Vec::new(),
         }
     }
 
-    pub fn degraded(
-        id: &'static str,
-        title: &'static str,
-        summary: String,
-        body: serde_json::Value,
-    ) -> Self {
+    /// [`success`](Self::success) for ids and titles built at run time.
+    pub fn success_owned(id: String, title: String, body: serde_json::Value) -> Self {
+        Self::success(id, title, body)
+    }
+
+    pub fn degraded<I, T>(id: I, title: T, summary: String, body: serde_json::Value) -> Self
+    where
+        I: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
         Self {
-            id,
-            title,
+            id: id.into(),
+            title: title.into(),
+            description: None,
+            tags: Vec::new(),
+            collector_version: None,
+            cost: None,
             status: SectionStatus::Degraded,
             summary: Some(summary),
             body,
             notes: Vec::new(),
             duration_ms: None,
+            findings: Vec::new(),
+            parts: Vec::new(),
+            sensitive: Vec::new(),
+            subsections: Vec::new(),
         }
     }
 
-    pub fn error(id: &'static str, title: &'static str, error: String) -> Self {
+    pub fn error<I, T>(id: I, title: T, error: String) -> Self
+    where
+        I: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
         Self {
-            id,
-            title,
+            id: id.into(),
+            title: title.into(),
+            description: None,
+            tags: Vec::new(),
+            collector_version: None,
+            cost: None,
             status: SectionStatus::Error,
             summary: Some(error.clone()),
             body: serde_json::json!({ "error": error }),
             notes: Vec::new(),
             duration_ms: None,
+            findings: Vec::new(),
+            parts: Vec::new(),
+            sensitive: Vec::new(),
+            subsections: Vec::new(),
         }
     }
 
+    /// Error section that keeps every layer of an `anyhow` error: the outermost
+    /// message becomes the summary, `body.error_chain` lists all causes,
+    /// outermost first, and `body.error_kind` holds its [`ErrorKind`].
+    pub fn from_error<I, T>(id: I, title: T, error: &anyhow::Error) -> Self
+    where
+        I: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
+        let chain: Vec<String> = error.chain().map(|cause| cause.to_string()).collect();
+        let mut section = Self::error(id, title, error.to_string());
+        section.body = serde_json::json!({
+            "error": error.to_string(),
+            "error_chain": chain,
+            "error_kind": ErrorKind::of(error),
+        });
+        section
+    }
+
+    /// Degraded section for a collector that failed but still reports an empty
+    /// `body`; the error becomes the summary and its kind `body.error_kind`.
+    pub fn degraded_from_error<I, T>(
+        id: I,
+        title: T,
+        error: &anyhow::Error,
+        mut body: serde_json::Value,
+    ) -> Self
+    where
+        I: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
+        if let Some(fields) = body.as_object_mut() {
+            fields.insert(
+                "error_kind".to_string(),
+                serde_json::json!(ErrorKind::of(error)),
+            );
+        }
+        Self::degraded(id, title, error.to_string(), body)
+    }
+
     pub fn has_notes(&self) -> bool {
         !self.notes.is_empty()
     }
+
+    /// Records a problem under a stable `code` such as `sshd.password_auth`.
+    pub fn add_finding<C, M>(&mut self, severity: FindingSeverity, code: C, message: M)
+    where
+        C: Into<String>,
+        M: Into<String>,
+    {
+        self.findings.push(SectionFinding {
+            severity,
+            code: code.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Marks the body value at `pointer` (RFC 6901, e.g. `/entries/3/message`)
+    /// for redaction under `category`. Arrays and objects cover every string
+    /// inside them.
+    pub fn mark_sensitive<P: Into<String>>(&mut self, pointer: P, category: SensitiveCategory) {
+        self.sensitive.push(SensitiveField {
+            pointer: pointer.into(),
+            category,
+        });
+    }
+
+    pub fn add_key_values(&mut self, set: KeyValueSet) {
+        self.parts.push(SectionPart::KeyValues(set));
+    }
+
+    pub fn add_table(&mut self, table: Table) {
+        self.parts.push(SectionPart::Table(table));
+    }
+
+    pub fn add_list(&mut self, list: ItemList) {
+        self.parts.push(SectionPart::List(list));
+    }
+
+    pub fn add_subsection(&mut self, subsection: SubSection) {
+        self.subsections.push(subsection);
+    }
+
+    pub fn subsection(&self, id: &str) -> Option<&SubSection> {
+        self.subsections
+            .iter()
+            .find(|subsection| subsection.id == id)
+    }
+}
+
+/// Group of related data inside a [`Section`], such as the Docker breakdown of
+/// the storage section. Renderers show it under its own sub-header, anchored
+/// as `<section id>-<subsection id>`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubSection {
+    /// Unique within the section.
+    pub id: String,
+    pub title: String,
+    pub body: serde_json::Value,
+    /// Render-ready views, like [`Section::parts`]; not serialized.
+    #[serde(skip)]
+    pub parts: Vec<SectionPart>,
+}
+
+impl SubSection {
+    pub fn new<I, T>(id: I, title: T, body: serde_json::Value) -> Self
+    where
+        I: Into<String>,
+        T: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            body,
+            parts: Vec::new(),
+        }
+    }
+
+    pub fn add_key_values(&mut self, set: KeyValueSet) {
+        self.parts.push(SectionPart::KeyValues(set));
+    }
+
+    pub fn add_table(&mut self, table: Table) {
+        self.parts.push(SectionPart::Table(table));
+    }
+
+    pub fn add_list(&mut self, list: ItemList) {
+        self.parts.push(SectionPart::List(list));
+    }
+}
+
+/// Severity of a [`SectionFinding`], matching the digest severities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// A problem reported by the collector itself rather than derived from its
+/// body by the core digest rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionFinding {
+    pub severity: FindingSeverity,
+    /// Stable identifier of the check, such as `network.wildcard_listener`.
+    pub code: String,
+    pub message: String,
+}
+
+/// Kind of data behind a [`SensitiveField`]; selects the redaction category
+/// that replaces it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensitiveCategory {
+    IpAddress,
+    Hostname,
+    Username,
+    ContainerName,
+    MountSource,
+    /// Command lines, which may carry credentials.
+    Command,
+    /// Free-form log message text.
+    LogMessage,
+}
+
+/// A body location recorded by [`Section::mark_sensitive`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SensitiveField {
+    pub pointer: String,
+    pub category: SensitiveCategory,
+}
+
+/// One structured piece of a section, attached with [`Section::add_table`] and
+/// friends. Titles, headers, and keys are English and localized by the renderer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionPart {
+    KeyValues(KeyValueSet),
+    Table(Table),
+    List(ItemList),
+}
+
+/// Labelled values shown as the section's key/value block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValueSet {
+    pub items: Vec<(String, String)>,
+}
+
+impl KeyValueSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.push(key, value);
+        self
+    }
+
+    pub fn push<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.items.push((key.into(), value.into()));
+    }
+}
+
+/// Rows of cells under fixed headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    pub title: Option<String>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// Appends the row count to the title, as in `Running Services (12 total)`.
+    pub show_total: bool,
+}
+
+impl Table {
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn titled<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_total(mut self) -> Self {
+        self.show_total = true;
+        self
+    }
+
+    /// Adds a row; it should have one cell per header.
+    pub fn push_row<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+}
+
+/// Bullet list of free-form items.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ItemList {
+    pub title: Option<String>,
+    pub items: Vec<String>,
+}
+
+impl ItemList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn titled<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn push<S: Into<String>>(&mut self, item: S) {
+        self.items.push(item.into());
+    }
+}
+
+/// Failure category recorded in the report so fleets can tell a missing tool
+/// from missing privileges or unreadable output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    NotInstalled,
+    PermissionDenied,
+    Timeout,
+    ParseError,
+    Io,
+    Other,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::NotInstalled => "not_installed",
+            ErrorKind::PermissionDenied => "permission_denied",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::ParseError => "parse_error",
+            ErrorKind::Io => "io",
+            ErrorKind::Other => "other",
+        }
+    }
+
+    /// Kind of the first [`CollectorError`] in the chain of `error`; without
+    /// one, I/O and JSON errors in the chain are classified by type.
+    pub fn of(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(error) = cause.downcast_ref::<CollectorError>() {
+                return error.kind;
+            }
+            if let Some(error) = cause.downcast_ref::<std::io::Error>() {
+                return match error.kind() {
+                    std::io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+                    std::io::ErrorKind::TimedOut => ErrorKind::Timeout,
+                    _ => ErrorKind::Io,
+                };
+            }
+            if cause.is::<serde_json::Error>() {
+                return ErrorKind::ParseError;
+            }
+        }
+        ErrorKind::Other
+    }
+
+    /// Kind for a command that exited unsuccessfully, judged from its stderr:
+    /// the usual permission messages map to [`ErrorKind::PermissionDenied`],
+    /// anything else to [`ErrorKind::Other`].
+    pub fn from_stderr(stderr: &str) -> Self {
+        let stderr = stderr.to_ascii_lowercase();
+        let denied = [
+            "permission denied",
+            "access denied",
+            "insufficient permissions",
+        ]
+        .iter()
+        .any(|phrase| stderr.contains(phrase));
+        if denied {
+            ErrorKind::PermissionDenied
+        } else {
+            ErrorKind::Other
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Classified collector failure. Collectors return it through `anyhow` (with
+/// `?` or `.into()`); [`ErrorKind::of`] finds it again anywhere in the chain.
+#[derive(Debug)]
+pub struct CollectorError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub source: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl CollectorError {
+    pub fn new<S: Into<String>>(kind: ErrorKind, message: S) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    pub fn with_source<E>(mut self, source: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Failure to start `command`: a missing executable is
+    /// [`ErrorKind::NotInstalled`], a refused one [`ErrorKind::PermissionDenied`].
+    pub fn spawn(command: &str, error: std::io::Error) -> Self {
+        let kind = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorKind::NotInstalled,
+            std::io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            _ => ErrorKind::Io,
+        };
+        Self::new(kind, format!("failed to execute {}", command)).with_source(error)
+    }
+}
+
+impl fmt::Display for CollectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CollectorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Whether a collector can gather its data on this host, as reported by
+/// [`Collector::probe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Availability {
+    Ready,
+    /// Collection runs, but part of the data will be missing.
+    Degraded(String),
+    /// Collection cannot gather anything useful.
+    Unavailable(String),
+}
+
+impl Availability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Availability::Ready => "ready",
+            Availability::Degraded(_) => "degraded",
+            Availability::Unavailable(_) => "unavailable",
+        }
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Availability::Ready => None,
+            Availability::Degraded(reason) | Availability::Unavailable(reason) => Some(reason),
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        *self == Availability::Ready
+    }
 }
 
 /// Common interface for data collection modules.
 pub trait Collector: Send + Sync + 'static {
     fn metadata(&self) -> CollectorMetadata;
     fn collect(&self, ctx: &CollectionContext) -> Result<Section>;
+
+    /// Checks what `collect` needs on this host (commands, sockets, readable
+    /// files) without collecting anything. The default needs nothing.
+    fn probe(&self, _ctx: &CollectionContext) -> Availability {
+        Availability::Ready
+    }
+}
+
+/// Full path of the executable `name` in `$PATH`, for probes of collectors
+/// that run external commands.
+pub fn find_command(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt as _;
+    path.metadata()
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &std::path::Path) -> bool {
+    path.is_file()
 }
 
 /// Descriptor of a compile-time registry entry.
 pub struct CollectorRegistration {
     pub constructor: fn() -> Box<dyn Collector>,
+    /// Same value as [`Collector::metadata`], readable without constructing
+    /// the collector so callers can filter by id or tag first.
+    pub metadata: fn() -> CollectorMetadata,
 }
 
 inventory::collect!(CollectorRegistration);
 
 pub use inventory;
 
+/// Version of the crate invoking the macro, for [`CollectorMetadata::version`].
+#[macro_export]
+macro_rules! collector_version {
+    () => {
+        env!("CARGO_PKG_VERSION")
+    };
+}
+
 /// Helper macro to register a collector inside a module.
+///
+/// `register_collector!(create, metadata)` also registers a function returning
+/// the collector's metadata; with only a constructor, reading the metadata
+/// constructs the collector.
 #[macro_export]
 macro_rules! register_collector {
+    ($ctor:expr, $metadata:expr) => {
+        ::vmic_sdk::inventory::submit! {
+            ::vmic_sdk::CollectorRegistration {
+                constructor: $ctor,
+                metadata: $metadata,
+            }
+        }
+    };
     ($ctor:expr) => {
         ::vmic_sdk::inventory::submit! {
             ::vmic_sdk::CollectorRegistration {
                 constructor: $ctor,
+                metadata: || ::vmic_sdk::Collector::metadata(&*($ctor)()),
             }
         }
     };
@@ -145,3 +1176,385 @@ macro_rules! register_collector {
 pub fn iter_registered_collectors() -> impl Iterator<Item = &'static CollectorRegistration> {
     inventory::iter::<CollectorRegistration>.into_iter()
 }
+
+type CollectorFactory = Arc<dyn Fn() -> Box<dyn Collector> + Send + Sync>;
+
+/// Fixed set of collectors, built at run time, that the core collects from in
+/// place of the compile-time registry; see [`CollectionContext::set_registry`].
+#[derive(Clone, Default)]
+pub struct CollectorRegistry {
+    entries: Vec<(CollectorMetadata, CollectorFactory)>,
+}
+
+impl CollectorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the collector built by `constructor`, which runs once now for the
+    /// metadata and again for every collection.
+    pub fn register<F, C>(&mut self, constructor: F)
+    where
+        F: Fn() -> C + Send + Sync + 'static,
+        C: Collector,
+    {
+        let metadata = constructor().metadata();
+        self.entries.push((
+            metadata,
+            Arc::new(move || Box::new(constructor()) as Box<dyn Collector>),
+        ));
+    }
+
+    pub fn with<F, C>(mut self, constructor: F) -> Self
+    where
+        F: Fn() -> C + Send + Sync + 'static,
+        C: Collector,
+    {
+        self.register(constructor);
+        self
+    }
+
+    /// Metadata of every collector, in registration order.
+    pub fn metadata(&self) -> impl Iterator<Item = CollectorMetadata> + '_ {
+        self.entries.iter().map(|(metadata, _)| *metadata)
+    }
+
+    /// Constructs the collectors whose metadata passes `include`.
+    pub fn collectors(
+        &self,
+        include: &dyn Fn(&CollectorMetadata) -> bool,
+    ) -> Vec<Box<dyn Collector>> {
+        self.entries
+            .iter()
+            .filter(|(metadata, _)| include(metadata))
+            .map(|(_, constructor)| constructor())
+            .collect()
+    }
+}
+
+impl fmt::Debug for CollectorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|(metadata, _)| metadata.id))
+            .finish()
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+// Lets the tests expand `register_collector!`, which names `::vmic_sdk`.
+#[cfg(test)]
+extern crate self as vmic_sdk;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Registered(&'static str);
+
+    impl Collector for Registered {
+        fn metadata(&self) -> CollectorMetadata {
+            registered_metadata(self.0)
+        }
+
+        fn collect(&self, _ctx: &CollectionContext) -> Result<Section> {
+            Ok(Section::success(
+                self.0,
+                "Registered",
+                serde_json::json!({}),
+            ))
+        }
+    }
+
+    fn registered_metadata(id: &'static str) -> CollectorMetadata {
+        CollectorMetadata {
+            id,
+            title: "Registered",
+            description: "Registry test collector",
+            tags: &[],
+            requires_root: false,
+            order: 0,
+            version: collector_version!(),
+            cost: CostHint::LOW,
+        }
+    }
+
+    fn unconstructible() -> Box<dyn Collector> {
+        panic!("metadata must not construct the collector")
+    }
+
+    register_collector!(unconstructible, || registered_metadata("static-metadata"));
+    register_collector!(|| Box::new(Registered("constructed-metadata")));
+
+    #[test]
+    fn registrations_expose_metadata() {
+        let ids: Vec<&str> = iter_registered_collectors()
+            .map(|entry| (entry.metadata)().id)
+            .collect();
+        assert!(ids.contains(&"static-metadata"));
+        assert!(ids.contains(&"constructed-metadata"));
+        let version = (iter_registered_collectors().next().unwrap().metadata)().version;
+        assert_eq!(version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn error_kind_is_found_anywhere_in_the_chain() {
+        let missing = std::io::Error::from(std::io::ErrorKind::NotFound);
+        let error = anyhow::Error::from(CollectorError::spawn("sar", missing))
+            .context("failed to collect sar");
+        assert_eq!(ErrorKind::of(&error), ErrorKind::NotInstalled);
+        assert_eq!(
+            error.chain().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "failed to collect sar",
+                "failed to execute sar",
+                "entity not found"
+            ]
+        );
+
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let error = anyhow::Error::from(denied).context("read /etc/sudoers");
+        assert_eq!(ErrorKind::of(&error), ErrorKind::PermissionDenied);
+
+        let parse = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let error = anyhow::Error::from(parse).context("bad line");
+        assert_eq!(ErrorKind::of(&error), ErrorKind::ParseError);
+
+        assert_eq!(ErrorKind::of(&anyhow!("exit status 1")), ErrorKind::Other);
+    }
+
+    #[test]
+    fn error_sections_record_the_kind() {
+        let error = anyhow::Error::from(CollectorError::new(ErrorKind::ParseError, "no Average"));
+        let section = Section::from_error("sar", "Sysstat Metrics", &error);
+        assert_eq!(section.body["error_kind"], "parse_error");
+
+        let section = Section::degraded_from_error(
+            "sar",
+            "Sysstat Metrics",
+            &error,
+            serde_json::json!({ "cpu": null }),
+        );
+        assert_eq!(section.status, SectionStatus::Degraded);
+        assert_eq!(section.summary.as_deref(), Some("no Average"));
+        assert_eq!(
+            section.body,
+            serde_json::json!({ "cpu": null, "error_kind": "parse_error" })
+        );
+    }
+
+    #[test]
+    fn subsections_serialize_without_parts_and_only_when_present() {
+        let mut section = Section::success("storage", "Storage Overview", serde_json::json!({}));
+        assert!(
+            serde_json::to_value(&section)
+                .unwrap()
+                .get("subsections")
+                .is_none()
+        );
+
+        let mut docker = SubSection::new(
+            "docker",
+            "Docker storage",
+            serde_json::json!({ "total_bytes": 10 }),
+        );
+        docker.add_key_values(KeyValueSet::new().with("Docker total", "10 B"));
+        section.add_subsection(docker);
+
+        assert_eq!(section.subsection("docker").unwrap().parts.len(), 1);
+        assert!(section.subsection("hotspots").is_none());
+        assert_eq!(
+            serde_json::to_value(&section).unwrap()["subsections"],
+            serde_json::json!([{
+                "id": "docker",
+                "title": "Docker storage",
+                "body": { "total_bytes": 10 }
+            }])
+        );
+    }
+
+    #[test]
+    fn settings_are_scoped_by_collector_and_later_values_win() {
+        let ctx = CollectionContext::new()
+            .with_setting("journal", "lines", "100")
+            .with_setting("journal", "lines", "200")
+            .with_setting("storage", "hotspot_depth", "4");
+
+        assert_eq!(ctx.setting("journal", "lines"), Some("200"));
+        assert_eq!(ctx.setting("storage", "lines"), None);
+        assert_eq!(ctx.setting("network", "socket_samples"), None);
+        assert_eq!(
+            ctx.settings("storage").collect::<Vec<_>>(),
+            [("hotspot_depth", "4")]
+        );
+        assert_eq!(ctx.settings("network").count(), 0);
+    }
+
+    #[test]
+    fn setting_as_parses_values_and_names_rejected_ones() {
+        let ctx = CollectionContext::new()
+            .with_setting("journal", "lines", "200")
+            .with_setting("network", "socket_samples", "0")
+            .with_setting("storage", "hotspot_depth", "deep");
+
+        assert_eq!(
+            ctx.setting_as::<u64>("journal", "lines").unwrap(),
+            Some(200)
+        );
+        assert_eq!(ctx.setting_as::<u64>("journal", "since").unwrap(), None);
+        assert_eq!(
+            ctx.setting_as::<u64>("network", "socket_samples").unwrap(),
+            Some(0)
+        );
+
+        let zero = ctx
+            .setting_as::<NonZeroUsize>("network", "socket_samples")
+            .unwrap_err();
+        assert!(
+            zero.to_string()
+                .starts_with("invalid value '0' for network.socket_samples: ")
+        );
+        let word = ctx
+            .setting_as::<u64>("storage", "hotspot_depth")
+            .unwrap_err();
+        assert_eq!(
+            word.to_string(),
+            "invalid value 'deep' for storage.hotspot_depth: invalid digit found in string"
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn block_on_uses_the_shared_runtime_when_set() {
+        let ctx = CollectionContext::new();
+        assert!(ctx.runtime().is_none());
+        assert_eq!(ctx.block_on(async { 7 }).unwrap(), 7);
+
+        let flavor = || tokio::runtime::Handle::current().runtime_flavor();
+        assert_eq!(
+            ctx.block_on(async { flavor() }).unwrap(),
+            tokio::runtime::RuntimeFlavor::CurrentThread
+        );
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .build()
+            .unwrap();
+        let mut ctx = CollectionContext::new();
+        ctx.set_runtime(Some(runtime.handle().clone()));
+        assert_eq!(
+            ctx.block_on(async { flavor() }).unwrap(),
+            tokio::runtime::RuntimeFlavor::MultiThread
+        );
+    }
+
+    #[test]
+    fn paths_resolve_under_the_configured_root() {
+        let mut ctx = CollectionContext::new();
+        assert_eq!(ctx.root(), Path::new("/"));
+        assert_eq!(ctx.path("/etc/passwd"), PathBuf::from("/etc/passwd"));
+
+        ctx.set_root(Some("/srv/snapshot"));
+        assert_eq!(ctx.root(), Path::new("/srv/snapshot"));
+        assert_eq!(
+            ctx.path("/etc/passwd"),
+            PathBuf::from("/srv/snapshot/etc/passwd")
+        );
+        assert_eq!(
+            ctx.path("proc/swaps"),
+            PathBuf::from("/srv/snapshot/proc/swaps")
+        );
+    }
+
+    #[test]
+    fn detail_levels_parse_and_default_to_standard() {
+        let mut ctx = CollectionContext::new();
+        assert_eq!(ctx.detail_level(), DetailLevel::Standard);
+        ctx.set_detail_level(" Full ".parse().unwrap());
+        assert_eq!(ctx.detail_level(), DetailLevel::Full);
+        assert!(DetailLevel::Minimal < DetailLevel::Standard);
+        assert_eq!(
+            serde_json::to_value(DetailLevel::Minimal).unwrap(),
+            serde_json::json!("minimal")
+        );
+        let err = "deep".parse::<DetailLevel>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported detail level 'deep' (expected minimal, standard or full)"
+        );
+    }
+
+    #[test]
+    fn cancellation_reaches_clones_and_children_but_not_parents() {
+        let mut ctx = CollectionContext::new();
+        assert!(!ctx.is_cancelled());
+        assert!(ctx.cancellation().check().is_ok());
+
+        let parent = CancellationToken::new();
+        let child = parent.child();
+        ctx.set_cancellation(child.clone());
+        let worker = ctx.clone();
+        child.cancel();
+        assert!(worker.is_cancelled());
+        assert!(!parent.is_cancelled());
+        let error = worker.cancellation().check().unwrap_err();
+        assert_eq!(ErrorKind::of(&error), ErrorKind::Timeout);
+
+        let grandchild = parent.child().child();
+        parent.cancel();
+        assert!(grandchild.is_cancelled());
+    }
+
+    #[test]
+    fn section_ids_may_be_static_or_built_at_run_time() {
+        let borrowed = Section::success("storage", "Storage Overview", serde_json::json!({}));
+        assert!(matches!(borrowed.id, Cow::Borrowed("storage")));
+
+        let disks: Vec<Section> = ["sda", "sdb"]
+            .iter()
+            .map(|disk| {
+                Section::success_owned(
+                    format!("disk-{disk}"),
+                    format!("Disk {disk}"),
+                    serde_json::json!({}),
+                )
+            })
+            .collect();
+        assert_eq!(disks[1].id, "disk-sdb");
+        assert_eq!(disks[1].title, "Disk sdb");
+        let degraded = Section::degraded(
+            disks[0].id.clone(),
+            "Disk",
+            "slow".into(),
+            serde_json::Value::Null,
+        );
+        assert_eq!(
+            serde_json::to_value(&degraded).unwrap()["id"],
+            serde_json::json!("disk-sda")
+        );
+    }
+
+    #[test]
+    fn cost_hints_expose_selector_keys() {
+        let docker = CostHint {
+            cpu: CostLevel::High,
+            io: CostLevel::Medium,
+            spawns_processes: false,
+        };
+        assert_eq!(docker.selectors(), ["high-cpu", "medium-io"]);
+        assert!(docker.matches("high-cpu"));
+        assert!(!docker.matches("high-io"));
+        assert_eq!(docker.to_string(), "cpu=high io=medium");
+
+        let sar = CostHint {
+            spawns_processes: true,
+            ..CostHint::LOW
+        };
+        assert!(sar.matches("spawns-processes"));
+        assert_eq!(
+            serde_json::to_value(sar).unwrap(),
+            serde_json::json!({ "cpu": "low", "io": "low", "spawns_processes": true })
+        );
+    }
+}
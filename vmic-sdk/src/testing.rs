@@ -0,0 +1,243 @@
+//! Helpers for tests that need collectors or sections without linking the
+//! real modules. Enabled by the `testing` feature; meant for dev-dependencies.
+//!
+//! A [`FakeCollector`] returns a fixed section. Set a
+//! [`CollectorRegistry`](crate::CollectorRegistry) of fakes on the context
+//! with [`CollectionContext::set_registry`] and the core collects from them
+//! alone.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+use crate::{
+    Availability, CollectionContext, Collector, CollectorMetadata, CostHint, Section, SectionStatus,
+};
+
+/// Collector returning a section with a fixed status and body.
+#[derive(Debug, Clone)]
+pub struct FakeCollector {
+    metadata: CollectorMetadata,
+    status: SectionStatus,
+    body: Value,
+    delay: Duration,
+    cancellable: bool,
+    failure: Option<String>,
+    availability: Availability,
+}
+
+impl FakeCollector {
+    /// Error statuses produce [`Section::error`] with the body replaced by
+    /// the error message, as a real failing collector would.
+    pub fn new(id: &'static str, status: SectionStatus, body: Value) -> Self {
+        Self {
+            metadata: CollectorMetadata {
+                id,
+                title: id,
+                description: "Fake collector for tests",
+                tags: &[],
+                requires_root: false,
+                order: 0,
+                version: "0.0.0-test",
+                cost: CostHint::LOW,
+            },
+            status,
+            body,
+            delay: Duration::ZERO,
+            cancellable: false,
+            failure: None,
+            availability: Availability::Ready,
+        }
+    }
+
+    pub fn titled(mut self, title: &'static str) -> Self {
+        self.metadata.title = title;
+        self
+    }
+
+    pub fn ordered(mut self, order: i32) -> Self {
+        self.metadata.order = order;
+        self
+    }
+
+    pub fn tagged(mut self, tags: &'static [&'static str]) -> Self {
+        self.metadata.tags = tags;
+        self
+    }
+
+    pub fn costed(mut self, cost: CostHint) -> Self {
+        self.metadata.cost = cost;
+        self
+    }
+
+    /// Sleeps for `delay` before answering, for timeout and parallelism tests.
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Cuts the delay short once the context is cancelled, the way a
+    /// cooperative collector returns what it has so far.
+    pub fn cancellable(mut self) -> Self {
+        self.cancellable = true;
+        self
+    }
+
+    /// Makes `collect` return an error with `message` instead of a section.
+    pub fn failing<M: Into<String>>(mut self, message: M) -> Self {
+        self.failure = Some(message.into());
+        self
+    }
+
+    /// Answer given by [`Collector::probe`].
+    pub fn probed(mut self, availability: Availability) -> Self {
+        self.availability = availability;
+        self
+    }
+}
+
+impl Collector for FakeCollector {
+    fn metadata(&self) -> CollectorMetadata {
+        self.metadata
+    }
+
+    fn collect(&self, ctx: &CollectionContext) -> Result<Section> {
+        if self.cancellable {
+            let deadline = Instant::now() + self.delay;
+            while !ctx.is_cancelled() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        } else {
+            std::thread::sleep(self.delay);
+        }
+        if let Some(message) = &self.failure {
+            return Err(anyhow!("{}", message));
+        }
+        let CollectorMetadata { id, title, .. } = self.metadata;
+        Ok(match self.status {
+            SectionStatus::Success => Section::success(id, title, self.body.clone()),
+            SectionStatus::Degraded => {
+                Section::degraded(id, title, format!("{} degraded", id), self.body.clone())
+            }
+            SectionStatus::Error => Section::error(id, title, format!("{} failed", id)),
+        })
+    }
+
+    fn probe(&self, _ctx: &CollectionContext) -> Availability {
+        self.availability.clone()
+    }
+}
+
+/// Panics, listing every violation, unless the section body validates
+/// against `schema` (a section body schema such as
+/// `vmic_core::schema::section_schema("storage")`).
+#[track_caller]
+pub fn assert_section_matches_schema(section: &Section, schema: &Value) {
+    let compiled = jsonschema::JSONSchema::compile(schema)
+        .unwrap_or_else(|err| panic!("schema for {} does not compile: {}", section.id, err));
+    if let Err(errors) = compiled.validate(&section.body) {
+        let violations: Vec<String> = errors
+            .map(|error| format!("{} at '{}'", error, error.instance_path))
+            .collect();
+        panic!(
+            "{} body does not match its schema:\n  {}",
+            section.id,
+            violations.join("\n  ")
+        );
+    }
+}
+
+/// Panics with both documents unless the section serializes to `expected`.
+/// `duration_ms` is dropped first, since it changes from run to run.
+#[track_caller]
+pub fn assert_section_golden(section: &Section, expected: &Value) {
+    let mut actual = serde_json::to_value(section).expect("section serializes to JSON");
+    if let Some(fields) = actual.as_object_mut() {
+        fields.remove("duration_ms");
+    }
+    if actual != *expected {
+        panic!(
+            "{} section differs from the golden value\nexpected: {}\n  actual: {}",
+            section.id,
+            serde_json::to_string_pretty(expected).unwrap_or_default(),
+            serde_json::to_string_pretty(&actual).unwrap_or_default()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CollectorRegistry;
+    use serde_json::json;
+
+    #[test]
+    fn fake_collectors_return_their_configured_section() {
+        let ctx = CollectionContext::new();
+        let degraded = FakeCollector::new("disk", SectionStatus::Degraded, json!({ "free": 1 }))
+            .titled("Disk")
+            .collect(&ctx)
+            .unwrap();
+        assert_section_golden(
+            &degraded,
+            &json!({
+                "id": "disk",
+                "title": "Disk",
+                "status": "degraded",
+                "summary": "disk degraded",
+                "body": { "free": 1 },
+                "notes": []
+            }),
+        );
+
+        let error = FakeCollector::new("disk", SectionStatus::Error, json!({}))
+            .collect(&ctx)
+            .unwrap();
+        assert_eq!(error.body, json!({ "error": "disk failed" }));
+
+        let failing = FakeCollector::new("disk", SectionStatus::Success, json!({})).failing("boom");
+        assert_eq!(failing.collect(&ctx).unwrap_err().to_string(), "boom");
+    }
+
+    #[test]
+    fn registries_filter_before_constructing() {
+        let registry = CollectorRegistry::new()
+            .with(|| FakeCollector::new("a", SectionStatus::Success, json!({})).ordered(2))
+            .with(|| FakeCollector::new("b", SectionStatus::Success, json!({})).ordered(1));
+
+        let orders: Vec<(&str, i32)> = registry
+            .metadata()
+            .map(|metadata| (metadata.id, metadata.order))
+            .collect();
+        assert_eq!(orders, [("a", 2), ("b", 1)]);
+        let collectors = registry.collectors(&|metadata| metadata.id == "b");
+        assert_eq!(collectors.len(), 1);
+        assert_eq!(collectors[0].metadata().id, "b");
+        assert_eq!(format!("{:?}", registry), r#"["a", "b"]"#);
+
+        let mut ctx = CollectionContext::new();
+        assert!(ctx.registry().is_none());
+        ctx.set_registry(Some(registry));
+        assert_eq!(ctx.clone().registry().unwrap().metadata().count(), 2);
+    }
+
+    #[test]
+    fn schema_assertion_accepts_matching_bodies() {
+        let schema = json!({
+            "type": "object",
+            "required": ["free"],
+            "properties": { "free": { "type": "integer" } }
+        });
+        let section = Section::success("disk", "Disk", json!({ "free": 1 }));
+        assert_section_matches_schema(&section, &schema);
+    }
+
+    #[test]
+    #[should_panic(expected = "disk body does not match its schema")]
+    fn schema_assertion_names_the_violations() {
+        let schema = json!({ "type": "object", "required": ["free"] });
+        let section = Section::success("disk", "Disk", json!({}));
+        assert_section_matches_schema(&section, &schema);
+    }
+}